@@ -9,6 +9,7 @@ use crate::avm1::{
 };
 use crate::backend::navigator::{NavigationMethod, RequestOptions};
 use crate::context::UpdateContext;
+use crate::debugger::{FrameVariables, PauseReason, ScopeVariables, Variable};
 use crate::display_object::{DisplayObject, MovieClip, TDisplayObject, TDisplayObjectContainer};
 use crate::ecma_conversions::f64_to_wrapping_u32;
 use crate::string::{AvmString, WStr, WString};
@@ -431,13 +432,104 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     pub fn run_actions(&mut self, code: SwfSlice) -> Result<ReturnType<'gc>, Error<'gc>> {
         let mut read = Reader::new(&code.movie.data()[code.start..], self.swf_version());
 
-        loop {
+        let is_debugging = self.context.debugger.is_attached();
+        if is_debugging {
+            self.context.debugger.enter_frame(self.id.to_string());
+        }
+
+        let result = loop {
             let result = self.do_action(&code, &mut read);
             match result {
                 Ok(FrameControl::Return(return_type)) => break Ok(return_type),
                 Ok(FrameControl::Continue) => {}
                 Err(e) => break Err(e),
             }
+        };
+
+        if is_debugging {
+            self.context.debugger.exit_frame();
+        }
+
+        result
+    }
+
+    /// Pause in the attached debugger until the client resumes execution.
+    fn debugger_pause(&mut self, reason: PauseReason) {
+        let this = self.this_cell();
+        let this = Some(self.debug_variable("this".to_string(), this));
+
+        let registers: Vec<_> = self
+            .local_registers
+            .map(|registers| {
+                let registers = registers.read();
+                (0..registers.len())
+                    .filter_map(|id| registers.get(id).copied())
+                    .collect()
+            })
+            .unwrap_or_default();
+        let locals = registers
+            .into_iter()
+            .enumerate()
+            .map(|(id, value)| self.debug_variable(format!("register{}", id), value))
+            .collect();
+
+        let mut scopes = vec![];
+        let mut scope = Some(self.scope_cell());
+        while let Some(cell) = scope {
+            let (class, object, parent) = {
+                let scope = cell.read();
+                (scope.class(), *scope.locals(), scope.parent_cell())
+            };
+            let mut variables = vec![];
+            for name in object.get_keys(self) {
+                if let Some(value) = object.get_local_stored(name, self) {
+                    variables.push(self.debug_variable(name.to_string(), value));
+                }
+            }
+            scopes.push(ScopeVariables {
+                name: format!("{:?}", class),
+                variables,
+            });
+            scope = parent;
+        }
+
+        self.context.debugger.pause(
+            reason,
+            FrameVariables {
+                this,
+                locals,
+                scopes,
+            },
+        );
+    }
+
+    /// Render a value for the debugger, without running any ActionScript.
+    pub(crate) fn debug_variable(&mut self, name: String, value: Value<'gc>) -> Variable {
+        let (value, type_name) = match value {
+            Value::Object(object) => {
+                let value = if let Some(display_object) = object.as_display_object() {
+                    display_object.path().to_string()
+                } else if object.type_of() == "function" {
+                    "[type Function]".to_string()
+                } else {
+                    "[object Object]".to_string()
+                };
+                (value, object.type_of().to_string())
+            }
+            Value::String(string) => (format!("{:?}", string.to_string()), "string".to_string()),
+            _ => (
+                value
+                    .coerce_to_string(self)
+                    .map(|string| string.to_string())
+                    .unwrap_or_default(),
+                value.type_of().to_string(),
+            ),
+        };
+
+        Variable {
+            name,
+            value,
+            type_name,
         }
     }
 
@@ -450,7 +542,11 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         self.actions_since_timeout_check += 1;
         if self.actions_since_timeout_check >= 2000 {
             self.actions_since_timeout_check = 0;
-            if self.context.update_start.elapsed() >= self.context.max_execution_duration {
+            let running_since = self
+                .context
+                .debugger
+                .running_since(self.context.update_start);
+            if running_since.elapsed() >= self.context.max_execution_duration {
                 return Err(Error::ExecutionTimeout);
             }
         }
@@ -459,6 +555,12 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
             //Executing beyond the end of a function constitutes an implicit return.
             Ok(FrameControl::Return(ReturnType::Implicit))
         } else {
+            if self.context.debugger.is_attached() {
+                if let Some(reason) = self.context.debugger.reached_action() {
+                    self.debugger_pause(reason);
+                }
+            }
+
             let action = reader.read_action()?;
             avm_debug!(
                 self.context.avm1,
@@ -29,6 +29,8 @@ pub(crate) mod display_object;
 pub mod drop_shadow_filter;
 pub(crate) mod error;
 mod external_interface;
+pub(crate) mod file_reference;
+mod file_reference_list;
 mod function;
 mod glow_filter;
 pub mod gradient_bevel_filter;
@@ -43,12 +45,14 @@ mod movie_clip_loader;
 pub(crate) mod number;
 mod object;
 mod point;
+mod print_job;
 mod rectangle;
 mod selection;
 pub(crate) mod shared_object;
 mod sound;
 mod stage;
 pub(crate) mod string;
+mod style_sheet;
 pub(crate) mod system;
 pub(crate) mod system_capabilities;
 pub(crate) mod system_ime;
@@ -574,6 +578,8 @@ pub struct SystemPrototypes<'gc> {
     pub bitmap_data_constructor: Object<'gc>,
     pub video: Object<'gc>,
     pub video_constructor: Object<'gc>,
+    pub file_reference: Object<'gc>,
+    pub file_reference_constructor: Object<'gc>,
 }
 
 /// Initialize default global scope and builtins for an AVM1 instance.
@@ -596,6 +602,7 @@ pub fn create_globals<'gc>(
     let sound_proto = sound::create_proto(gc_context, object_proto, function_proto);
 
     let text_field_proto = text_field::create_proto(gc_context, object_proto, function_proto);
+    let style_sheet_proto = style_sheet::create_proto(gc_context, object_proto, function_proto);
     let text_format_proto = text_format::create_proto(gc_context, object_proto, function_proto);
 
     let array_proto = array::create_proto(gc_context, object_proto, function_proto);
@@ -639,8 +646,39 @@ pub fn create_globals<'gc>(
         Some(function_proto),
         movie_clip_loader_proto,
     );
+    let file_reference_proto = file_reference::create_proto(
+        gc_context,
+        object_proto,
+        function_proto,
+        array_proto,
+        broadcaster_functions,
+    );
+    let file_reference = FunctionObject::constructor(
+        gc_context,
+        Executable::Native(file_reference::constructor),
+        constructor_to_fn!(file_reference::constructor),
+        Some(function_proto),
+        file_reference_proto,
+    );
+    let file_reference_list_proto = file_reference_list::create_proto(
+        gc_context,
+        object_proto,
+        function_proto,
+        array_proto,
+        broadcaster_functions,
+    );
+    let file_reference_list = FunctionObject::constructor(
+        gc_context,
+        Executable::Native(file_reference_list::constructor),
+        constructor_to_fn!(file_reference_list::constructor),
+        Some(function_proto),
+        file_reference_list_proto,
+    );
+
     let date_proto = date::create_proto(gc_context, object_proto, function_proto);
 
+    let print_job_proto = print_job::create_proto(gc_context, object_proto, function_proto);
+
     let video_proto = video::create_proto(gc_context, object_proto, function_proto);
 
     //TODO: These need to be constructors and should also set `.prototype` on each one
@@ -693,6 +731,13 @@ pub fn create_globals<'gc>(
         movie_clip_proto,
     );
 
+    let print_job = FunctionObject::constructor(
+        gc_context,
+        Executable::Native(print_job::constructor),
+        constructor_to_fn!(print_job::constructor),
+        Some(function_proto),
+        print_job_proto,
+    );
     let sound = FunctionObject::constructor(
         gc_context,
         Executable::Native(sound::constructor),
@@ -707,6 +752,19 @@ pub fn create_globals<'gc>(
         Some(function_proto),
         text_field_proto,
     );
+    let style_sheet = FunctionObject::constructor(
+        gc_context,
+        Executable::Native(style_sheet::constructor),
+        constructor_to_fn!(style_sheet::constructor),
+        Some(function_proto),
+        style_sheet_proto,
+    );
+    text_field.define_value(
+        gc_context,
+        "StyleSheet",
+        style_sheet.into(),
+        Attribute::DONT_ENUM,
+    );
     let text_format = FunctionObject::constructor(
         gc_context,
         Executable::Native(text_format::constructor),
@@ -735,6 +793,7 @@ pub fn create_globals<'gc>(
     let date = date::create_date_object(gc_context, date_proto, function_proto);
 
     let flash = ScriptObject::object(gc_context, Some(object_proto));
+    let net = ScriptObject::object(gc_context, Some(object_proto));
 
     let geom = ScriptObject::object(gc_context, Some(object_proto));
     let filters = ScriptObject::object(gc_context, Some(object_proto));
@@ -769,6 +828,7 @@ pub fn create_globals<'gc>(
     flash.define_value(gc_context, "geom", geom.into(), Attribute::empty());
     flash.define_value(gc_context, "filters", filters.into(), Attribute::empty());
     flash.define_value(gc_context, "display", display.into(), Attribute::empty());
+    flash.define_value(gc_context, "net", net.into(), Attribute::empty());
     geom.define_value(gc_context, "Matrix", matrix.into(), Attribute::empty());
     geom.define_value(gc_context, "Point", point.into(), Attribute::empty());
     geom.define_value(
@@ -962,6 +1022,19 @@ pub fn create_globals<'gc>(
         Attribute::empty(),
     );
 
+    net.define_value(
+        gc_context,
+        "FileReference",
+        file_reference.into(),
+        Attribute::empty(),
+    );
+    net.define_value(
+        gc_context,
+        "FileReferenceList",
+        file_reference_list.into(),
+        Attribute::empty(),
+    );
+
     let external = ScriptObject::object(gc_context, Some(object_proto));
     let external_interface = external_interface::create_external_interface_object(
         gc_context,
@@ -1014,6 +1087,12 @@ pub fn create_globals<'gc>(
         movie_clip_loader.into(),
         Attribute::DONT_ENUM,
     );
+    globals.define_value(
+        gc_context,
+        "PrintJob",
+        print_job.into(),
+        Attribute::DONT_ENUM,
+    );
     globals.define_value(gc_context, "Sound", sound.into(), Attribute::DONT_ENUM);
     globals.define_value(
         gc_context,
@@ -1212,6 +1291,8 @@ pub fn create_globals<'gc>(
             bitmap_data_constructor: bitmap_data,
             video: video_proto,
             video_constructor: video,
+            file_reference: file_reference_proto,
+            file_reference_constructor: file_reference,
         },
         globals.into(),
         broadcaster_functions,
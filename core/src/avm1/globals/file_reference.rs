@@ -0,0 +1,168 @@
+//! `flash.net.FileReference` impl
+
+use crate::avm1::activation::Activation;
+use crate::avm1::error::Error;
+use crate::avm1::globals::as_broadcaster::BroadcasterFunctions;
+use crate::avm1::object::script_object::ScriptObject;
+use crate::avm1::object::TObject;
+use crate::avm1::property::Attribute;
+use crate::avm1::property_decl::{define_properties_on, Declaration};
+use crate::avm1::{ArrayObject, Object, Value};
+use crate::avm_warn;
+use crate::backend::ui::{FileDialogResult, FileFilter};
+use crate::context::ActionType;
+use crate::string::AvmString;
+use gc_arena::MutationContext;
+
+const PROTO_DECLS: &[Declaration] = declare_properties! {
+    "browse" => method(browse; DONT_ENUM | DONT_DELETE);
+    "download" => method(download; DONT_ENUM | DONT_DELETE);
+    "upload" => method(upload; DONT_ENUM | DONT_DELETE);
+    "cancel" => method(cancel; DONT_ENUM | DONT_DELETE);
+};
+
+pub fn constructor<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let listeners = ArrayObject::empty(activation);
+    this.define_value(
+        activation.context.gc_context,
+        "_listeners",
+        Value::Object(listeners.into()),
+        Attribute::DONT_ENUM,
+    );
+    Ok(this.into())
+}
+
+pub fn create_proto<'gc>(
+    gc_context: MutationContext<'gc, '_>,
+    proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+    array_proto: Object<'gc>,
+    broadcaster_functions: BroadcasterFunctions<'gc>,
+) -> Object<'gc> {
+    let file_reference_proto = ScriptObject::object(gc_context, Some(proto));
+    broadcaster_functions.initialize(gc_context, file_reference_proto.into(), array_proto);
+    define_properties_on(PROTO_DECLS, gc_context, file_reference_proto, fn_proto);
+    file_reference_proto.into()
+}
+
+/// Reads the file filters from the `typelist` argument of `browse`.
+///
+/// Each entry of the array is an object with `description` and `extension`
+/// properties, such as `{description: "Images", extension: "*.jpg;*.png"}`.
+pub fn file_filters<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    type_list: Option<&Value<'gc>>,
+) -> Result<Vec<FileFilter>, Error<'gc>> {
+    let type_list = match type_list {
+        Some(Value::Object(type_list)) => *type_list,
+        _ => return Ok(vec![]),
+    };
+
+    let length = type_list.length(activation)?;
+    let mut filters = Vec::with_capacity(length.max(0) as usize);
+    for i in 0..length {
+        if let Value::Object(filter) = type_list.get_element(activation, i) {
+            let description = filter
+                .get("description", activation)?
+                .coerce_to_string(activation)?;
+            let extension = filter
+                .get("extension", activation)?
+                .coerce_to_string(activation)?;
+            filters.push(FileFilter::from_extension_list(
+                description.to_string(),
+                &extension.to_utf8_lossy(),
+            ));
+        }
+    }
+    Ok(filters)
+}
+
+/// Sets the properties describing a file chosen in a file dialog.
+pub fn set_file<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    file: &FileDialogResult,
+) -> Result<(), Error<'gc>> {
+    let name = AvmString::new_utf8(activation.context.gc_context, &file.file_name);
+    this.set("name", name.into(), activation)?;
+    this.set("size", file.contents.len().into(), activation)?;
+    let file_type = match file.file_type() {
+        Some(file_type) => AvmString::new_utf8(activation.context.gc_context, file_type).into(),
+        None => Value::Null,
+    };
+    this.set("type", file_type, activation)?;
+    this.set("creator", Value::Null, activation)?;
+    Ok(())
+}
+
+/// Queues a call to `broadcastMessage` on the given object.
+pub fn queue_broadcast<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: Vec<Value<'gc>>,
+) {
+    let root_clip = activation.context.stage.root_clip();
+    activation.context.action_queue.queue_actions(
+        root_clip,
+        ActionType::Method {
+            object: this,
+            name: "broadcastMessage",
+            args,
+        },
+        false,
+    );
+}
+
+fn browse<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let filters = file_filters(activation, args.get(0))?;
+    let file = activation
+        .context
+        .ui
+        .display_file_open_dialog(&filters, false)
+        .and_then(|files| files.into_iter().next());
+
+    match file {
+        Some(file) => {
+            set_file(activation, this, &file)?;
+            queue_broadcast(activation, this, vec!["onSelect".into(), this.into()]);
+        }
+        None => queue_broadcast(activation, this, vec!["onCancel".into(), this.into()]),
+    }
+
+    Ok(true.into())
+}
+
+fn download<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    avm_warn!(activation, "FileReference.download: Not yet implemented");
+    Ok(false.into())
+}
+
+fn upload<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    avm_warn!(activation, "FileReference.upload: Not yet implemented");
+    Ok(false.into())
+}
+
+fn cancel<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    // Nothing to cancel, as neither downloads nor uploads are implemented.
+    Ok(Value::Undefined)
+}
@@ -0,0 +1,85 @@
+//! `flash.net.FileReferenceList` impl
+
+use crate::avm1::activation::Activation;
+use crate::avm1::error::Error;
+use crate::avm1::globals::as_broadcaster::BroadcasterFunctions;
+use crate::avm1::globals::file_reference::{file_filters, queue_broadcast, set_file};
+use crate::avm1::object::script_object::ScriptObject;
+use crate::avm1::object::TObject;
+use crate::avm1::property::Attribute;
+use crate::avm1::property_decl::{define_properties_on, Declaration};
+use crate::avm1::{ArrayObject, Object, Value};
+use gc_arena::MutationContext;
+
+const PROTO_DECLS: &[Declaration] = declare_properties! {
+    "browse" => method(browse; DONT_ENUM | DONT_DELETE);
+};
+
+pub fn constructor<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let listeners = ArrayObject::empty(activation);
+    this.define_value(
+        activation.context.gc_context,
+        "_listeners",
+        Value::Object(listeners.into()),
+        Attribute::DONT_ENUM,
+    );
+    Ok(this.into())
+}
+
+pub fn create_proto<'gc>(
+    gc_context: MutationContext<'gc, '_>,
+    proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+    array_proto: Object<'gc>,
+    broadcaster_functions: BroadcasterFunctions<'gc>,
+) -> Object<'gc> {
+    let file_reference_list_proto = ScriptObject::object(gc_context, Some(proto));
+    broadcaster_functions.initialize(gc_context, file_reference_list_proto.into(), array_proto);
+    define_properties_on(PROTO_DECLS, gc_context, file_reference_list_proto, fn_proto);
+    file_reference_list_proto.into()
+}
+
+fn browse<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let filters = file_filters(activation, args.get(0))?;
+    let files = match activation
+        .context
+        .ui
+        .display_file_open_dialog(&filters, true)
+    {
+        Some(files) => files,
+        None => {
+            queue_broadcast(activation, this, vec!["onCancel".into(), this.into()]);
+            return Ok(true.into());
+        }
+    };
+
+    let constructor = activation
+        .context
+        .avm1
+        .prototypes()
+        .file_reference_constructor;
+    let mut file_list = Vec::with_capacity(files.len());
+    for file in &files {
+        if let Value::Object(file_reference) = constructor.construct(activation, &[])? {
+            set_file(activation, file_reference, file)?;
+            file_list.push(file_reference.into());
+        }
+    }
+    let file_list = ArrayObject::new(
+        activation.context.gc_context,
+        activation.context.avm1.prototypes().array,
+        file_list,
+    );
+    this.set("fileList", file_list.into(), activation)?;
+
+    queue_broadcast(activation, this, vec!["onSelect".into(), this.into()]);
+    Ok(true.into())
+}
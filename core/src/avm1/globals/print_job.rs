@@ -0,0 +1,186 @@
+//! AVM1 PrintJob object
+
+use crate::avm1::activation::Activation;
+use crate::avm1::error::Error;
+use crate::avm1::object::print_job_object::PrintJobObject;
+use crate::avm1::property_decl::{define_properties_on, Declaration};
+use crate::avm1::{Object, TObject, Value};
+use crate::avm_warn;
+use crate::prelude::*;
+use crate::printing::{PrintManager, PrintPageRequest};
+use gc_arena::MutationContext;
+
+const PROTO_DECLS: &[Declaration] = declare_properties! {
+    "start" => method(start; DONT_ENUM | DONT_DELETE);
+    "addPage" => method(add_page; DONT_ENUM | DONT_DELETE);
+    "send" => method(send; DONT_ENUM | DONT_DELETE);
+};
+
+/// Implements `PrintJob`
+pub fn constructor<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this.into())
+}
+
+pub fn create_proto<'gc>(
+    gc_context: MutationContext<'gc, '_>,
+    proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    let print_job = PrintJobObject::empty_object(gc_context, Some(proto));
+    let object = print_job.as_script_object().unwrap();
+    define_properties_on(PROTO_DECLS, gc_context, object, fn_proto);
+    print_job.into()
+}
+
+fn start<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = match this.as_print_job_object() {
+        Some(this) => this,
+        None => return Ok(false.into()),
+    };
+
+    let job_id = PrintManager::start_job(&mut activation.context);
+    this.set_job_id(activation.context.gc_context, job_id);
+
+    let settings = match job_id.and_then(|id| activation.context.print_manager.settings(id)) {
+        Some(settings) => settings.clone(),
+        None => return Ok(false.into()),
+    };
+
+    this.set("paperWidth", settings.paper_width.into(), activation)?;
+    this.set("paperHeight", settings.paper_height.into(), activation)?;
+    this.set("pageWidth", settings.page_width.into(), activation)?;
+    this.set("pageHeight", settings.page_height.into(), activation)?;
+    this.set(
+        "orientation",
+        settings.orientation.as_str().into(),
+        activation,
+    )?;
+
+    Ok(true.into())
+}
+
+fn add_page<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let job_id = match this.as_print_job_object().and_then(|o| o.job_id()) {
+        Some(job_id) => job_id,
+        None => return Ok(false.into()),
+    };
+
+    let target = match args.get(0).unwrap_or(&Value::Undefined) {
+        Value::Number(level_id) => {
+            // Levels are rounded down.
+            activation.context.stage.child_by_depth(*level_id as i32)
+        }
+        target => {
+            let start_clip = activation.target_clip_or_root()?;
+            activation.resolve_target_display_object(start_clip, *target, false)?
+        }
+    };
+    let target = match target {
+        Some(target) => target,
+        None => return Ok(false.into()),
+    };
+
+    let area = match args.get(1) {
+        Some(Value::Object(area)) => print_area(activation, *area)?,
+        _ => None,
+    }
+    .unwrap_or_else(|| {
+        // The full stage is printed when no area is given.
+        let (width, height) = activation.context.stage.stage_size();
+        BoundingBox {
+            x_min: Twips::ZERO,
+            y_min: Twips::ZERO,
+            x_max: Twips::from_pixels(width.into()),
+            y_max: Twips::from_pixels(height.into()),
+            valid: true,
+        }
+    });
+
+    let print_as_bitmap = match args.get(2) {
+        Some(Value::Object(options)) => options
+            .get("printAsBitmap", activation)?
+            .as_bool(activation.swf_version()),
+        _ => false,
+    };
+
+    // Printing another frame would mean seeking the target there and back
+    // without running its scripts, so the current frame is always printed.
+    if let Some(frame) = args
+        .get(3)
+        .filter(|frame| !matches!(frame, Value::Undefined))
+    {
+        let frame = frame.coerce_to_f64(activation)?;
+        let current_frame = target.as_movie_clip().map(|clip| clip.current_frame());
+        if current_frame.map(f64::from) != Some(frame) {
+            avm_warn!(
+                activation,
+                "PrintJob.addPage: frameNum {} is not yet implemented, printing the current frame instead",
+                frame
+            );
+        }
+    }
+
+    let added = PrintManager::add_page(
+        &mut activation.context,
+        job_id,
+        &PrintPageRequest {
+            target,
+            area,
+            print_as_bitmap,
+        },
+    );
+    Ok(added.into())
+}
+
+fn send<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this.as_print_job_object() {
+        if let Some(job_id) = this.job_id() {
+            PrintManager::send(&mut activation.context, job_id);
+            this.set_job_id(activation.context.gc_context, None);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Reads a print area from an object with `xMin`, `xMax`, `yMin` and `yMax`
+/// properties, in pixels.
+///
+/// Returns `None` if the area is empty or inverted.
+fn print_area<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    area: Object<'gc>,
+) -> Result<Option<BoundingBox>, Error<'gc>> {
+    let x_min = area.get("xMin", activation)?.coerce_to_f64(activation)?;
+    let x_max = area.get("xMax", activation)?.coerce_to_f64(activation)?;
+    let y_min = area.get("yMin", activation)?.coerce_to_f64(activation)?;
+    let y_max = area.get("yMax", activation)?.coerce_to_f64(activation)?;
+
+    if x_max > x_min && y_max > y_min {
+        Ok(Some(BoundingBox {
+            x_min: Twips::from_pixels(x_min),
+            y_min: Twips::from_pixels(y_min),
+            x_max: Twips::from_pixels(x_max),
+            y_max: Twips::from_pixels(y_max),
+            valid: true,
+        }))
+    } else {
+        Ok(None)
+    }
+}
@@ -0,0 +1,274 @@
+//! AVM1 TextField.StyleSheet object
+
+use crate::avm1::activation::Activation;
+use crate::avm1::error::Error;
+use crate::avm1::object::text_format_object::TextFormatObject;
+use crate::avm1::property::Attribute;
+use crate::avm1::property_decl::{define_properties_on, Declaration};
+use crate::avm1::{ArrayObject, Object, ScriptObject, TObject, Value};
+use crate::backend::navigator::RequestOptions;
+use crate::html::{css_to_text_format, parse_css, StyleSheet, TextFormat};
+use crate::string::AvmString;
+use gc_arena::MutationContext;
+
+const PROTO_DECLS: &[Declaration] = declare_properties! {
+    "clear" => method(clear; DONT_ENUM | DONT_DELETE);
+    "getStyle" => method(get_style; DONT_ENUM | DONT_DELETE);
+    "getStyleNames" => method(get_style_names; DONT_ENUM | DONT_DELETE);
+    "load" => method(load; DONT_ENUM | DONT_DELETE);
+    "onData" => method(on_data; DONT_ENUM | DONT_DELETE);
+    "parseCSS" => method(parse_css_method; DONT_ENUM | DONT_DELETE);
+    "setStyle" => method(set_style; DONT_ENUM | DONT_DELETE);
+    "transform" => method(transform; DONT_ENUM | DONT_DELETE);
+};
+
+/// Implements `TextField.StyleSheet`
+pub fn constructor<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    styles(activation, this)?;
+    Ok(this.into())
+}
+
+pub fn create_proto<'gc>(
+    gc_context: MutationContext<'gc, '_>,
+    proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    let object = ScriptObject::object(gc_context, Some(proto));
+    define_properties_on(PROTO_DECLS, gc_context, object, fn_proto);
+    object.into()
+}
+
+/// Retrieve the hidden object holding a style sheet's styles, creating it if
+/// it does not exist yet.
+fn styles<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+) -> Result<Object<'gc>, Error<'gc>> {
+    if let Value::Object(styles) = this.get("_styles", activation)? {
+        return Ok(styles);
+    }
+
+    let styles = ScriptObject::object(
+        activation.context.gc_context,
+        Some(activation.context.avm1.prototypes().object),
+    );
+    this.define_value(
+        activation.context.gc_context,
+        "_styles",
+        styles.into(),
+        Attribute::DONT_ENUM,
+    );
+    Ok(styles.into())
+}
+
+/// Make a shallow copy of a style object.
+fn copy_style<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    style: Object<'gc>,
+) -> Result<Object<'gc>, Error<'gc>> {
+    let copy = ScriptObject::object(
+        activation.context.gc_context,
+        Some(activation.context.avm1.prototypes().object),
+    );
+    for name in style.get_keys(activation) {
+        let value = style.get(name, activation)?;
+        copy.set(name, value, activation)?;
+    }
+
+    Ok(copy.into())
+}
+
+/// Convert a style object into a text format.
+fn style_to_text_format<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    style: Object<'gc>,
+) -> TextFormat {
+    css_to_text_format(|name| match style.get(name, activation) {
+        Ok(Value::Undefined) | Ok(Value::Null) | Err(_) => None,
+        Ok(value) => value
+            .coerce_to_string(activation)
+            .ok()
+            .map(|value| value.as_wstr().into()),
+    })
+}
+
+/// Build the style sheet applied to a text field from a `StyleSheet` object.
+pub fn to_style_sheet<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+) -> Result<StyleSheet, Error<'gc>> {
+    let styles = styles(activation, this)?;
+    let mut sheet = StyleSheet::new();
+    for name in styles.get_keys(activation) {
+        if let Value::Object(style) = styles.get(name, activation)? {
+            let format = style_to_text_format(activation, style);
+            sheet.set_style(&name, format);
+        }
+    }
+
+    Ok(sheet)
+}
+
+fn clear<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    this.delete(activation, "_styles".into());
+    styles(activation, this)?;
+    Ok(Value::Undefined)
+}
+
+fn get_style<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_string(activation)?;
+    let name = AvmString::new(activation.context.gc_context, name.to_ascii_lowercase());
+    let styles = styles(activation, this)?;
+    match styles.get(name, activation)? {
+        Value::Object(style) => Ok(copy_style(activation, style)?.into()),
+        _ => Ok(Value::Undefined),
+    }
+}
+
+fn get_style_names<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let styles = styles(activation, this)?;
+    let names = styles.get_keys(activation);
+    Ok(ArrayObject::new(
+        activation.context.gc_context,
+        activation.context.avm1.prototypes().array,
+        names.into_iter().map(Value::from),
+    )
+    .into())
+}
+
+fn load<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let url = match args.get(0) {
+        Some(val) => val.coerce_to_string(activation)?,
+        None => return Ok(false.into()),
+    };
+
+    // Like `LoadVars`, the loaded text is handed to `onData`.
+    let fetch = activation
+        .context
+        .navigator
+        .fetch(&url.to_utf8_lossy(), RequestOptions::get());
+    let process = activation.context.load_manager.load_form_into_load_vars(
+        activation.context.player.clone().unwrap(),
+        this,
+        fetch,
+    );
+    activation.context.navigator.spawn_future(process);
+
+    Ok(true.into())
+}
+
+fn on_data<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    // Default implementation parses the loaded CSS and forwards to onLoad.
+    let success = match args.get(0).unwrap_or(&Value::Undefined) {
+        Value::Undefined | Value::Null => false,
+        val => this
+            .call_method("parseCSS".into(), &[*val], activation)?
+            .as_bool(activation.swf_version()),
+    };
+
+    this.call_method("onLoad".into(), &[success.into()], activation)?;
+
+    Ok(Value::Undefined)
+}
+
+fn parse_css_method<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let css = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_string(activation)?;
+    let styles = styles(activation, this)?;
+    for (selector, declarations) in parse_css(&css) {
+        let selector = AvmString::new(activation.context.gc_context, selector);
+
+        // Declarations are merged into any existing style for the selector.
+        let style = match styles.get(selector, activation)? {
+            Value::Object(style) => style,
+            _ => {
+                let style: Object<'gc> = ScriptObject::object(
+                    activation.context.gc_context,
+                    Some(activation.context.avm1.prototypes().object),
+                )
+                .into();
+                styles.set(selector, style.into(), activation)?;
+                style
+            }
+        };
+
+        for (name, value) in declarations {
+            let name = AvmString::new(activation.context.gc_context, name);
+            let value = AvmString::new(activation.context.gc_context, value);
+            style.set(name, value.into(), activation)?;
+        }
+    }
+
+    Ok(true.into())
+}
+
+fn set_style<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_string(activation)?;
+    let name = AvmString::new(activation.context.gc_context, name.to_ascii_lowercase());
+    let styles = styles(activation, this)?;
+    match args.get(1) {
+        Some(Value::Object(style)) => {
+            let copy = copy_style(activation, *style)?;
+            styles.set(name, copy.into(), activation)?;
+        }
+        _ => {
+            styles.delete(activation, name);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+fn transform<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    match args.get(0) {
+        Some(Value::Object(style)) => {
+            let format = style_to_text_format(activation, *style);
+            Ok(TextFormatObject::new(activation, format).into())
+        }
+        _ => Ok(Value::Null),
+    }
+}
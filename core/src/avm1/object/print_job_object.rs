@@ -0,0 +1,54 @@
+use crate::avm1::{Object, ScriptObject, TObject};
+use crate::impl_custom_object;
+use gc_arena::{Collect, GcCell, MutationContext};
+use std::fmt;
+
+/// A PrintJob
+#[derive(Clone, Copy, Collect)]
+#[collect(no_drop)]
+pub struct PrintJobObject<'gc>(GcCell<'gc, PrintJobObjectData<'gc>>);
+
+#[derive(Clone, Collect)]
+#[collect(no_drop)]
+pub struct PrintJobObjectData<'gc> {
+    /// The underlying script object.
+    base: ScriptObject<'gc>,
+
+    /// The ID of the print job started by this object, if any.
+    job_id: Option<u32>,
+}
+
+impl fmt::Debug for PrintJobObject<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let this = self.0.read();
+        f.debug_struct("PrintJobObject")
+            .field("job_id", &this.job_id)
+            .finish()
+    }
+}
+
+impl<'gc> PrintJobObject<'gc> {
+    pub fn empty_object(gc_context: MutationContext<'gc, '_>, proto: Option<Object<'gc>>) -> Self {
+        PrintJobObject(GcCell::allocate(
+            gc_context,
+            PrintJobObjectData {
+                base: ScriptObject::object(gc_context, proto),
+                job_id: None,
+            },
+        ))
+    }
+
+    pub fn job_id(self) -> Option<u32> {
+        self.0.read().job_id
+    }
+
+    pub fn set_job_id(self, gc_context: MutationContext<'gc, '_>, job_id: Option<u32>) {
+        self.0.write(gc_context).job_id = job_id;
+    }
+}
+
+impl<'gc> TObject<'gc> for PrintJobObject<'gc> {
+    impl_custom_object!(base {
+        bare_object(as_print_job_object -> PrintJobObject::empty_object);
+    });
+}
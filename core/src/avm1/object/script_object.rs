@@ -547,19 +547,23 @@ mod tests {
     use crate::avm1::{Avm1, Timers};
     use crate::avm2::Avm2;
     use crate::backend::audio::{AudioManager, NullAudioBackend};
+    use crate::backend::font::NullFontBackend;
     use crate::backend::locale::NullLocaleBackend;
     use crate::backend::log::NullLogBackend;
     use crate::backend::navigator::NullNavigatorBackend;
-    use crate::backend::render::NullRenderer;
+    use crate::backend::print::NullPrintBackend;
+    use crate::backend::render::{NullRenderer, RenderBackend};
     use crate::backend::storage::MemoryStorageBackend;
     use crate::backend::ui::NullUiBackend;
     use crate::backend::video::NullVideoBackend;
     use crate::context::UpdateContext;
+    use crate::debugger::Debugger;
     use crate::display_object::{MovieClip, Stage};
     use crate::focus_tracker::FocusTracker;
     use crate::library::Library;
     use crate::loader::LoadManager;
     use crate::prelude::*;
+    use crate::printing::PrintManager;
     use crate::tag_utils::SwfMovie;
     use crate::vminterface::Instantiator;
     use gc_arena::rootless_arena;
@@ -598,10 +602,13 @@ mod tests {
                 ui: &mut NullUiBackend::new(),
                 library: &mut Library::empty(),
                 navigator: &mut NullNavigatorBackend::new(),
-                renderer: &mut NullRenderer::new(),
+                renderer: &mut (Box::new(NullRenderer::new()) as Box<dyn RenderBackend>),
                 locale: &mut NullLocaleBackend::new(),
                 log: &mut NullLogBackend::new(),
                 video: &mut NullVideoBackend::new(),
+                print: &mut NullPrintBackend::new(),
+                font: &mut NullFontBackend::new(),
+                print_manager: &mut PrintManager::new(),
                 mouse_over_object: None,
                 mouse_down_object: None,
                 input: &Default::default(),
@@ -626,6 +633,7 @@ mod tests {
                 times_get_time_called: 0,
                 time_offset: &mut 0,
                 frame_rate: &mut frame_rate,
+                debugger: &mut Debugger::default(),
             };
             context.stage.replace_at_depth(&mut context, root, 0);
 
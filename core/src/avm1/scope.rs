@@ -158,6 +158,11 @@ impl<'gc> Scope<'gc> {
         &mut self.values
     }
 
+    /// Returns what kind of scope this is.
+    pub fn class(&self) -> ScopeClass {
+        self.class
+    }
+
     /// Returns a reference to the parent scope object.
     pub fn parent(&self) -> Option<Ref<Scope<'gc>>> {
         match self.parent {
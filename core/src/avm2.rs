@@ -41,6 +41,7 @@ mod string;
 mod traits;
 mod value;
 mod vector;
+mod verify;
 mod vtable;
 
 pub use crate::avm2::activation::Activation;
@@ -12,15 +12,16 @@ use crate::avm2::object::{Object, TObject};
 use crate::avm2::scope::{Scope, ScopeChain, ScopeStack};
 use crate::avm2::script::Script;
 use crate::avm2::value::Value;
+use crate::avm2::verify::{verify_method, VerifiedMethod};
 use crate::avm2::{value, Avm2, Error};
 use crate::context::UpdateContext;
+use crate::debugger::{FrameVariables, PauseReason, ScopeVariables, Variable};
 use crate::string::{AvmString, WStr, WString};
-use crate::swf::extensions::ReadSwfExt;
 use gc_arena::{Gc, GcCell, MutationContext};
 use smallvec::SmallVec;
 use std::borrow::Cow;
 use std::cmp::{min, Ordering};
-use swf::avm2::read::Reader;
+use std::rc::Rc;
 use swf::avm2::types::{
     Class as AbcClass, Index, Method as AbcMethod, Multiname as AbcMultiname,
     Namespace as AbcNamespace, Op,
@@ -741,37 +742,182 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         method.translation_unit().load_class(index.0, self)
     }
 
+    /// Get the verified body of a method, verifying it if it hasn't been run
+    /// before.
+    fn verified_body(
+        &mut self,
+        method: Gc<'gc, BytecodeMethod<'gc>>,
+    ) -> Result<Rc<VerifiedMethod>, Error> {
+        // Properties of `this` can only be resolved ahead of time for a
+        // receiver whose vtable is fully known. The result is then only valid
+        // for that exact vtable, since subclasses may add or override traits.
+        let receiver = match (method.is_function, self.this, self.subclass_object) {
+            (false, Some(this), Some(class)) => {
+                let is_sealed = this
+                    .instance_of()
+                    .map(|c| c.inner_class_definition().read().is_sealed())
+                    .unwrap_or(false);
+                if Object::ptr_eq(this, class.into()) || is_sealed {
+                    this.vtable()
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        }
+        .filter(|receiver| method.can_cache_for_receiver(*receiver));
+
+        if let Some(verified) = method.verified(receiver) {
+            return Ok(verified);
+        }
+
+        let verified = Rc::new(verify_method(self, method, receiver)?);
+        method.set_verified(self.context.gc_context, receiver, verified.clone());
+
+        Ok(verified)
+    }
+
     pub fn run_actions(
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
     ) -> Result<Value<'gc>, Error> {
-        let body: Result<_, Error> = method
-            .body()
-            .ok_or_else(|| "Cannot execute non-native method without body".into());
-        let body = body?;
-        let mut reader = Reader::new(&body.code);
+        let verified = self.verified_body(method)?;
+        let ops = verified.ops();
+        let mut ip = 0;
+
+        let is_debugging = self.context.debugger.is_attached();
+        if is_debugging {
+            self.context.debugger.enter_frame(method.method_name());
+        }
 
-        loop {
-            let result = self.do_next_opcode(method, &mut reader, &body.code);
+        let result = loop {
+            let result = self.do_next_opcode(method, ops, &mut ip);
             match result {
                 Ok(FrameControl::Return(value)) => break Ok(value),
                 Ok(FrameControl::Continue) => {}
                 Err(e) => break Err(e),
             }
+        };
+
+        if is_debugging {
+            self.context.debugger.exit_frame();
         }
+
+        result
     }
 
-    /// Run a single action from a given action reader.
-    fn do_next_opcode<'b>(
+    /// Pause in the attached debugger until the client resumes execution.
+    fn debugger_pause(&mut self, reason: PauseReason) -> Result<(), Error> {
+        let this = match self.this {
+            Some(this) => Some(self.debug_variable("this".to_string(), this.into())?),
+            None => None,
+        };
+
+        let registers: Vec<_> = self
+            .local_registers
+            .read()
+            .0
+            .iter()
+            .skip(1)
+            .copied()
+            .collect();
+        let mut locals = vec![];
+        for (register, value) in (1..).zip(registers) {
+            let name = match self.context.debugger.register_name(register) {
+                Some(name) => name.to_string(),
+                None => format!("register{}", register),
+            };
+            locals.push(self.debug_variable(name, value)?);
+        }
+
+        let mut scope_objects: Vec<_> = self
+            .scope_stack
+            .scopes()
+            .iter()
+            .rev()
+            .map(|scope| scope.values())
+            .collect();
+        let mut depth = 0;
+        while let Some(scope) = self.outer.get(depth) {
+            scope_objects.push(scope.values());
+            depth += 1;
+        }
+
+        let mut scopes = vec![];
+        for object in scope_objects {
+            let name = self.debug_class_name(object);
+            let mut variables = vec![];
+            for (name, value) in object.base().debug_properties() {
+                variables.push(self.debug_variable(name.to_string(), value)?);
+            }
+            scopes.push(ScopeVariables { name, variables });
+        }
+
+        self.context.debugger.pause(
+            reason,
+            FrameVariables {
+                this,
+                locals,
+                scopes,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Render a value for the debugger, without running any ActionScript.
+    pub(crate) fn debug_variable(
+        &mut self,
+        name: String,
+        value: Value<'gc>,
+    ) -> Result<Variable, Error> {
+        let (value, type_name) = match value {
+            Value::Object(object) => {
+                let class_name = self.debug_class_name(object);
+                (format!("[object {}]", class_name), class_name)
+            }
+            Value::String(string) => (format!("{:?}", string.to_string()), "String".to_string()),
+            Value::Undefined => ("undefined".to_string(), "void".to_string()),
+            Value::Null => ("null".to_string(), "null".to_string()),
+            Value::Bool(b) => (b.to_string(), "Boolean".to_string()),
+            Value::Number(_) => (
+                value.coerce_to_string(self)?.to_string(),
+                "Number".to_string(),
+            ),
+            Value::Unsigned(u) => (u.to_string(), "uint".to_string()),
+            Value::Integer(i) => (i.to_string(), "int".to_string()),
+        };
+
+        Ok(Variable {
+            name,
+            value,
+            type_name,
+        })
+    }
+
+    fn debug_class_name(&self, object: Object<'gc>) -> String {
+        object
+            .instance_of_class_definition()
+            .map(|class| class.read().name().local_name().to_string())
+            .unwrap_or_else(|| "Object".to_string())
+    }
+
+    /// Run a single op from a verified op stream, and advance the
+    /// instruction pointer past it.
+    fn do_next_opcode(
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
-        reader: &mut Reader<'b>,
-        full_data: &'b [u8],
+        ops: &[Op],
+        ip: &mut usize,
     ) -> Result<FrameControl<'gc>, Error> {
         self.actions_since_timeout_check += 1;
         if self.actions_since_timeout_check >= 2000 {
             self.actions_since_timeout_check = 0;
-            if self.context.update_start.elapsed() >= self.context.max_execution_duration {
+            let running_since = self
+                .context
+                .debugger
+                .running_since(self.context.update_start);
+            if running_since.elapsed() >= self.context.max_execution_duration {
                 return Err(
                     "A script in this movie has taken too long to execute and has been terminated."
                         .into(),
@@ -779,207 +925,193 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
             }
         }
 
-        let instruction_start = reader.pos(full_data);
-        let op = reader.read_op();
-        if let Ok(op) = op {
-            avm_debug!(self.avm2(), "Opcode: {:?}", op);
-
-            let result = match op {
-                Op::PushByte { value } => self.op_push_byte(value),
-                Op::PushDouble { value } => self.op_push_double(method, value),
-                Op::PushFalse => self.op_push_false(),
-                Op::PushInt { value } => self.op_push_int(method, value),
-                Op::PushNamespace { value } => self.op_push_namespace(method, value),
-                Op::PushNaN => self.op_push_nan(),
-                Op::PushNull => self.op_push_null(),
-                Op::PushShort { value } => self.op_push_short(value),
-                Op::PushString { value } => self.op_push_string(method, value),
-                Op::PushTrue => self.op_push_true(),
-                Op::PushUint { value } => self.op_push_uint(method, value),
-                Op::PushUndefined => self.op_push_undefined(),
-                Op::Pop => self.op_pop(),
-                Op::Dup => self.op_dup(),
-                Op::GetLocal { index } => self.op_get_local(index),
-                Op::SetLocal { index } => self.op_set_local(index),
-                Op::Kill { index } => self.op_kill(index),
-                Op::Call { num_args } => self.op_call(num_args),
-                Op::CallMethod { index, num_args } => self.op_call_method(index, num_args),
-                Op::CallProperty { index, num_args } => {
-                    self.op_call_property(method, index, num_args)
-                }
-                Op::CallPropLex { index, num_args } => {
-                    self.op_call_prop_lex(method, index, num_args)
-                }
-                Op::CallPropVoid { index, num_args } => {
-                    self.op_call_prop_void(method, index, num_args)
-                }
-                Op::CallStatic { index, num_args } => self.op_call_static(method, index, num_args),
-                Op::CallSuper { index, num_args } => self.op_call_super(method, index, num_args),
-                Op::CallSuperVoid { index, num_args } => {
-                    self.op_call_super_void(method, index, num_args)
-                }
-                Op::ReturnValue => self.op_return_value(),
-                Op::ReturnVoid => self.op_return_void(),
-                Op::GetProperty { index } => self.op_get_property(method, index),
-                Op::SetProperty { index } => self.op_set_property(method, index),
-                Op::InitProperty { index } => self.op_init_property(method, index),
-                Op::DeleteProperty { index } => self.op_delete_property(method, index),
-                Op::GetSuper { index } => self.op_get_super(method, index),
-                Op::SetSuper { index } => self.op_set_super(method, index),
-                Op::In => self.op_in(),
-                Op::PushScope => self.op_push_scope(),
-                Op::PushWith => self.op_push_with(),
-                Op::PopScope => self.op_pop_scope(),
-                Op::GetOuterScope { index } => self.op_get_outer_scope(index),
-                Op::GetScopeObject { index } => self.op_get_scope_object(index),
-                Op::GetGlobalScope => self.op_get_global_scope(),
-                Op::FindProperty { index } => self.op_find_property(method, index),
-                Op::FindPropStrict { index } => self.op_find_prop_strict(method, index),
-                Op::GetLex { index } => self.op_get_lex(method, index),
-                Op::GetSlot { index } => self.op_get_slot(index),
-                Op::SetSlot { index } => self.op_set_slot(index),
-                Op::GetGlobalSlot { index } => self.op_get_global_slot(index),
-                Op::SetGlobalSlot { index } => self.op_set_global_slot(index),
-                Op::Construct { num_args } => self.op_construct(num_args),
-                Op::ConstructProp { index, num_args } => {
-                    self.op_construct_prop(method, index, num_args)
-                }
-                Op::ConstructSuper { num_args } => self.op_construct_super(num_args),
-                Op::NewActivation => self.op_new_activation(),
-                Op::NewObject { num_args } => self.op_new_object(num_args),
-                Op::NewFunction { index } => self.op_new_function(method, index),
-                Op::NewClass { index } => self.op_new_class(method, index),
-                Op::ApplyType { num_types } => self.op_apply_type(num_types),
-                Op::NewArray { num_args } => self.op_new_array(num_args),
-                Op::CoerceA => self.op_coerce_a(),
-                Op::CoerceB => self.op_coerce_b(),
-                Op::CoerceD => self.op_coerce_d(),
-                Op::CoerceI => self.op_coerce_i(),
-                Op::CoerceO => self.op_coerce_o(),
-                Op::CoerceS => self.op_coerce_s(),
-                Op::CoerceU => self.op_coerce_u(),
-                Op::ConvertB => self.op_convert_b(),
-                Op::ConvertI => self.op_convert_i(),
-                Op::ConvertD => self.op_convert_d(),
-                Op::ConvertO => self.op_convert_o(),
-                Op::ConvertU => self.op_convert_u(),
-                Op::ConvertS => self.op_convert_s(),
-                Op::Add => self.op_add(),
-                Op::AddI => self.op_add_i(),
-                Op::BitAnd => self.op_bitand(),
-                Op::BitNot => self.op_bitnot(),
-                Op::BitOr => self.op_bitor(),
-                Op::BitXor => self.op_bitxor(),
-                Op::DecLocal { index } => self.op_declocal(index),
-                Op::DecLocalI { index } => self.op_declocal_i(index),
-                Op::Decrement => self.op_decrement(),
-                Op::DecrementI => self.op_decrement_i(),
-                Op::Divide => self.op_divide(),
-                Op::IncLocal { index } => self.op_inclocal(index),
-                Op::IncLocalI { index } => self.op_inclocal_i(index),
-                Op::Increment => self.op_increment(),
-                Op::IncrementI => self.op_increment_i(),
-                Op::LShift => self.op_lshift(),
-                Op::Modulo => self.op_modulo(),
-                Op::Multiply => self.op_multiply(),
-                Op::MultiplyI => self.op_multiply_i(),
-                Op::Negate => self.op_negate(),
-                Op::NegateI => self.op_negate_i(),
-                Op::RShift => self.op_rshift(),
-                Op::Subtract => self.op_subtract(),
-                Op::SubtractI => self.op_subtract_i(),
-                Op::Swap => self.op_swap(),
-                Op::URShift => self.op_urshift(),
-                Op::Jump { offset } => self.op_jump(offset, reader, full_data),
-                Op::IfTrue { offset } => self.op_if_true(offset, reader, full_data),
-                Op::IfFalse { offset } => self.op_if_false(offset, reader, full_data),
-                Op::IfStrictEq { offset } => self.op_if_strict_eq(offset, reader, full_data),
-                Op::IfStrictNe { offset } => self.op_if_strict_ne(offset, reader, full_data),
-                Op::IfEq { offset } => self.op_if_eq(offset, reader, full_data),
-                Op::IfNe { offset } => self.op_if_ne(offset, reader, full_data),
-                Op::IfGe { offset } => self.op_if_ge(offset, reader, full_data),
-                Op::IfGt { offset } => self.op_if_gt(offset, reader, full_data),
-                Op::IfLe { offset } => self.op_if_le(offset, reader, full_data),
-                Op::IfLt { offset } => self.op_if_lt(offset, reader, full_data),
-                Op::IfNge { offset } => self.op_if_nge(offset, reader, full_data),
-                Op::IfNgt { offset } => self.op_if_ngt(offset, reader, full_data),
-                Op::IfNle { offset } => self.op_if_nle(offset, reader, full_data),
-                Op::IfNlt { offset } => self.op_if_nlt(offset, reader, full_data),
-                Op::StrictEquals => self.op_strict_equals(),
-                Op::Equals => self.op_equals(),
-                Op::GreaterEquals => self.op_greater_equals(),
-                Op::GreaterThan => self.op_greater_than(),
-                Op::LessEquals => self.op_less_equals(),
-                Op::LessThan => self.op_less_than(),
-                Op::Nop => self.op_nop(),
-                Op::Not => self.op_not(),
-                Op::HasNext => self.op_has_next(),
-                Op::HasNext2 {
-                    object_register,
-                    index_register,
-                } => self.op_has_next_2(object_register, index_register),
-                Op::NextName => self.op_next_name(),
-                Op::NextValue => self.op_next_value(),
-                Op::IsType { index } => self.op_is_type(method, index),
-                Op::IsTypeLate => self.op_is_type_late(),
-                Op::AsType { type_name } => self.op_as_type(method, type_name),
-                Op::AsTypeLate => self.op_as_type_late(),
-                Op::InstanceOf => self.op_instance_of(),
-                Op::Label => Ok(FrameControl::Continue),
-                Op::Debug {
-                    is_local_register,
-                    register_name,
-                    register,
-                } => self.op_debug(method, is_local_register, register_name, register),
-                Op::DebugFile { file_name } => self.op_debug_file(method, file_name),
-                Op::DebugLine { line_num } => self.op_debug_line(line_num),
-                Op::Bkpt => self.op_bkpt(),
-                Op::BkptLine { line_num } => self.op_bkpt_line(line_num),
-                Op::Timestamp => self.op_timestamp(),
-                Op::TypeOf => self.op_type_of(),
-                Op::EscXAttr => self.op_esc_xattr(),
-                Op::EscXElem => self.op_esc_elem(),
-                Op::LookupSwitch {
-                    default_offset,
-                    case_offsets,
-                } => self.op_lookup_switch(
-                    default_offset,
-                    &case_offsets,
-                    instruction_start,
-                    reader,
-                    full_data,
-                ),
-                Op::Coerce { index } => self.op_coerce(method, index),
-                Op::Si8 => self.op_si8(),
-                Op::Si16 => self.op_si16(),
-                Op::Si32 => self.op_si32(),
-                Op::Sf32 => self.op_sf32(),
-                Op::Sf64 => self.op_sf64(),
-                Op::Li8 => self.op_li8(),
-                Op::Li16 => self.op_li16(),
-                Op::Li32 => self.op_li32(),
-                Op::Lf32 => self.op_lf32(),
-                Op::Lf64 => self.op_lf64(),
-                Op::Sxi1 => self.op_sxi1(),
-                Op::Sxi8 => self.op_sxi8(),
-                Op::Sxi16 => self.op_sxi16(),
-                _ => self.unknown_op(op),
-            };
-
-            if let Err(e) = result {
-                log::error!("AVM2 error: {}", e);
-                return Err(e);
+        let op = ops.get(*ip).ok_or_else(|| {
+            Error::from("VerifyError: Error #1020: Code cannot fall off the end of a method.")
+        })?;
+        *ip += 1;
+
+        avm_debug!(self.avm2(), "Opcode: {:?}", op);
+
+        let result = match *op {
+            Op::PushByte { value } => self.op_push_byte(value),
+            Op::PushDouble { value } => self.op_push_double(method, value),
+            Op::PushFalse => self.op_push_false(),
+            Op::PushInt { value } => self.op_push_int(method, value),
+            Op::PushNamespace { value } => self.op_push_namespace(method, value),
+            Op::PushNaN => self.op_push_nan(),
+            Op::PushNull => self.op_push_null(),
+            Op::PushShort { value } => self.op_push_short(value),
+            Op::PushString { value } => self.op_push_string(method, value),
+            Op::PushTrue => self.op_push_true(),
+            Op::PushUint { value } => self.op_push_uint(method, value),
+            Op::PushUndefined => self.op_push_undefined(),
+            Op::Pop => self.op_pop(),
+            Op::Dup => self.op_dup(),
+            Op::GetLocal { index } => self.op_get_local(index),
+            Op::SetLocal { index } => self.op_set_local(index),
+            Op::Kill { index } => self.op_kill(index),
+            Op::Call { num_args } => self.op_call(num_args),
+            Op::CallMethod { index, num_args } => self.op_call_method(index, num_args),
+            Op::CallProperty { index, num_args } => self.op_call_property(method, index, num_args),
+            Op::CallPropLex { index, num_args } => self.op_call_prop_lex(method, index, num_args),
+            Op::CallPropVoid { index, num_args } => self.op_call_prop_void(method, index, num_args),
+            Op::CallStatic { index, num_args } => self.op_call_static(method, index, num_args),
+            Op::CallSuper { index, num_args } => self.op_call_super(method, index, num_args),
+            Op::CallSuperVoid { index, num_args } => {
+                self.op_call_super_void(method, index, num_args)
             }
-            result
-        } else if let Err(e) = op {
-            log::error!("Parse error: {:?}", e);
-            Err(e.into())
-        } else {
-            unreachable!();
+            Op::ReturnValue => self.op_return_value(),
+            Op::ReturnVoid => self.op_return_void(),
+            Op::GetProperty { index } => self.op_get_property(method, index),
+            Op::SetProperty { index } => self.op_set_property(method, index),
+            Op::InitProperty { index } => self.op_init_property(method, index),
+            Op::DeleteProperty { index } => self.op_delete_property(method, index),
+            Op::GetDescendants { index } => self.op_get_descendants(method, index),
+            Op::GetSuper { index } => self.op_get_super(method, index),
+            Op::SetSuper { index } => self.op_set_super(method, index),
+            Op::In => self.op_in(),
+            Op::PushScope => self.op_push_scope(),
+            Op::PushWith => self.op_push_with(),
+            Op::PopScope => self.op_pop_scope(),
+            Op::GetOuterScope { index } => self.op_get_outer_scope(index),
+            Op::GetScopeObject { index } => self.op_get_scope_object(index),
+            Op::GetGlobalScope => self.op_get_global_scope(),
+            Op::FindProperty { index } => self.op_find_property(method, index),
+            Op::FindPropStrict { index } => self.op_find_prop_strict(method, index),
+            Op::FindDef { index } => self.op_find_def(method, index),
+            Op::GetLex { index } => self.op_get_lex(method, index),
+            Op::GetSlot { index } => self.op_get_slot(index),
+            Op::SetSlot { index } => self.op_set_slot(index),
+            Op::GetGlobalSlot { index } => self.op_get_global_slot(index),
+            Op::SetGlobalSlot { index } => self.op_set_global_slot(index),
+            Op::Construct { num_args } => self.op_construct(num_args),
+            Op::ConstructProp { index, num_args } => {
+                self.op_construct_prop(method, index, num_args)
+            }
+            Op::ConstructSuper { num_args } => self.op_construct_super(num_args),
+            Op::NewActivation => self.op_new_activation(),
+            Op::NewObject { num_args } => self.op_new_object(num_args),
+            Op::NewFunction { index } => self.op_new_function(method, index),
+            Op::NewClass { index } => self.op_new_class(method, index),
+            Op::ApplyType { num_types } => self.op_apply_type(num_types),
+            Op::NewArray { num_args } => self.op_new_array(num_args),
+            Op::CoerceA => self.op_coerce_a(),
+            Op::CoerceB => self.op_coerce_b(),
+            Op::CoerceD => self.op_coerce_d(),
+            Op::CoerceI => self.op_coerce_i(),
+            Op::CoerceO => self.op_coerce_o(),
+            Op::CoerceS => self.op_coerce_s(),
+            Op::CoerceU => self.op_coerce_u(),
+            Op::ConvertB => self.op_convert_b(),
+            Op::ConvertI => self.op_convert_i(),
+            Op::ConvertD => self.op_convert_d(),
+            Op::ConvertO => self.op_convert_o(),
+            Op::ConvertU => self.op_convert_u(),
+            Op::ConvertS => self.op_convert_s(),
+            Op::Add => self.op_add(),
+            Op::AddI => self.op_add_i(),
+            Op::BitAnd => self.op_bitand(),
+            Op::BitNot => self.op_bitnot(),
+            Op::BitOr => self.op_bitor(),
+            Op::BitXor => self.op_bitxor(),
+            Op::DecLocal { index } => self.op_declocal(index),
+            Op::DecLocalI { index } => self.op_declocal_i(index),
+            Op::Decrement => self.op_decrement(),
+            Op::DecrementI => self.op_decrement_i(),
+            Op::Divide => self.op_divide(),
+            Op::IncLocal { index } => self.op_inclocal(index),
+            Op::IncLocalI { index } => self.op_inclocal_i(index),
+            Op::Increment => self.op_increment(),
+            Op::IncrementI => self.op_increment_i(),
+            Op::LShift => self.op_lshift(),
+            Op::Modulo => self.op_modulo(),
+            Op::Multiply => self.op_multiply(),
+            Op::MultiplyI => self.op_multiply_i(),
+            Op::Negate => self.op_negate(),
+            Op::NegateI => self.op_negate_i(),
+            Op::RShift => self.op_rshift(),
+            Op::Subtract => self.op_subtract(),
+            Op::SubtractI => self.op_subtract_i(),
+            Op::Swap => self.op_swap(),
+            Op::URShift => self.op_urshift(),
+            Op::Jump { offset } => self.op_jump(offset, ip),
+            Op::IfTrue { offset } => self.op_if_true(offset, ip),
+            Op::IfFalse { offset } => self.op_if_false(offset, ip),
+            Op::IfStrictEq { offset } => self.op_if_strict_eq(offset, ip),
+            Op::IfStrictNe { offset } => self.op_if_strict_ne(offset, ip),
+            Op::IfEq { offset } => self.op_if_eq(offset, ip),
+            Op::IfNe { offset } => self.op_if_ne(offset, ip),
+            Op::IfGe { offset } => self.op_if_ge(offset, ip),
+            Op::IfGt { offset } => self.op_if_gt(offset, ip),
+            Op::IfLe { offset } => self.op_if_le(offset, ip),
+            Op::IfLt { offset } => self.op_if_lt(offset, ip),
+            Op::IfNge { offset } => self.op_if_nge(offset, ip),
+            Op::IfNgt { offset } => self.op_if_ngt(offset, ip),
+            Op::IfNle { offset } => self.op_if_nle(offset, ip),
+            Op::IfNlt { offset } => self.op_if_nlt(offset, ip),
+            Op::StrictEquals => self.op_strict_equals(),
+            Op::Equals => self.op_equals(),
+            Op::GreaterEquals => self.op_greater_equals(),
+            Op::GreaterThan => self.op_greater_than(),
+            Op::LessEquals => self.op_less_equals(),
+            Op::LessThan => self.op_less_than(),
+            Op::Nop => self.op_nop(),
+            Op::Not => self.op_not(),
+            Op::HasNext => self.op_has_next(),
+            Op::HasNext2 {
+                object_register,
+                index_register,
+            } => self.op_has_next_2(object_register, index_register),
+            Op::NextName => self.op_next_name(),
+            Op::NextValue => self.op_next_value(),
+            Op::IsType { index } => self.op_is_type(method, index),
+            Op::IsTypeLate => self.op_is_type_late(),
+            Op::AsType { type_name } => self.op_as_type(method, type_name),
+            Op::AsTypeLate => self.op_as_type_late(),
+            Op::InstanceOf => self.op_instance_of(),
+            Op::Label => Ok(FrameControl::Continue),
+            Op::Debug {
+                is_local_register,
+                register_name,
+                register,
+            } => self.op_debug(method, is_local_register, register_name, register),
+            Op::DebugFile { file_name } => self.op_debug_file(method, file_name),
+            Op::DebugLine { line_num } => self.op_debug_line(line_num),
+            Op::Bkpt => self.op_bkpt(),
+            Op::BkptLine { line_num } => self.op_bkpt_line(line_num),
+            Op::Timestamp => self.op_timestamp(),
+            Op::TypeOf => self.op_type_of(),
+            Op::EscXAttr => self.op_esc_xattr(),
+            Op::EscXElem => self.op_esc_elem(),
+            Op::LookupSwitch {
+                default_offset,
+                ref case_offsets,
+            } => self.op_lookup_switch(default_offset, case_offsets, ip),
+            Op::Coerce { index } => self.op_coerce(method, index),
+            Op::Si8 => self.op_si8(),
+            Op::Si16 => self.op_si16(),
+            Op::Si32 => self.op_si32(),
+            Op::Sf32 => self.op_sf32(),
+            Op::Sf64 => self.op_sf64(),
+            Op::Li8 => self.op_li8(),
+            Op::Li16 => self.op_li16(),
+            Op::Li32 => self.op_li32(),
+            Op::Lf32 => self.op_lf32(),
+            Op::Lf64 => self.op_lf64(),
+            Op::Sxi1 => self.op_sxi1(),
+            Op::Sxi8 => self.op_sxi8(),
+            Op::Sxi16 => self.op_sxi16(),
+            _ => self.unknown_op(op),
+        };
+
+        if let Err(e) = result {
+            log::error!("AVM2 error: {}", e);
+            return Err(e);
         }
+        result
     }
 
-    fn unknown_op(&mut self, op: swf::avm2::types::Op) -> Result<FrameControl<'gc>, Error> {
+    fn unknown_op(&mut self, op: &Op) -> Result<FrameControl<'gc>, Error> {
         log::error!("Unknown AVM2 opcode: {:?}", op);
         Err("Unknown op".into())
     }
@@ -1124,25 +1256,33 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         index: Index<AbcMethod>,
         arg_count: u32,
     ) -> Result<FrameControl<'gc>, Error> {
-        // The entire implementation of VTable assumes that
-        // call_method is never encountered. (see the long comment there)
-        // This was also the conlusion from analysing avmplus behavior - they
-        // unconditionally VerifyError upon noticing it.
-        // If we ever reach here, let's immediately panic instead of erroring,
-        // so we get an issue report ASAP.
-        panic!("Call_method is not supported");
-
-        #[allow(unreachable_code)]
-        {
-            let args = self.context.avm2.pop_args(arg_count);
-            let receiver = self.context.avm2.pop().coerce_to_object(self)?;
+        // Bytecode containing `callmethod` is rejected by the verifier, so
+        // this only runs for calls on `this` that it resolved to a disp_id.
+        // Anything else means the op stream was run with the wrong receiver.
+        let args = self.context.avm2.pop_args(arg_count);
+        let receiver = self.context.avm2.pop().coerce_to_object(self)?;
 
-            let value = receiver.call_method(index.0, &args, self)?;
+        let is_this = self
+            .this
+            .map(|this| Object::ptr_eq(this, receiver))
+            .unwrap_or(false);
+        let has_method = receiver
+            .vtable()
+            .and_then(|vtable| vtable.get_method(index.0))
+            .is_some();
+        if !is_this || !has_method {
+            return Err(format!(
+                "VerifyError: Error #1051: Illegal early binding access to method id {}.",
+                index.0
+            )
+            .into());
+        }
 
-            self.context.avm2.push(value);
+        let value = receiver.call_method(index.0, &args, self)?;
 
-            Ok(FrameControl::Continue)
-        }
+        self.context.avm2.push(value);
+
+        Ok(FrameControl::Continue)
     }
 
     fn op_call_property(
@@ -1421,6 +1561,21 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
+    /// Implements `Op::GetDescendants`
+    fn op_get_descendants(
+        &mut self,
+        method: Gc<'gc, BytecodeMethod<'gc>>,
+        index: Index<AbcMultiname>,
+    ) -> Result<FrameControl<'gc>, Error> {
+        let multiname = self.pool_multiname(method, index)?;
+        let object = self.context.avm2.pop().coerce_to_object(self)?;
+
+        let value = object.get_descendants(&multiname, self)?;
+        self.context.avm2.push(value);
+
+        Ok(FrameControl::Continue)
+    }
+
     fn op_get_super(
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
@@ -1573,6 +1728,30 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
+    /// Implements `Op::FindDef`
+    fn op_find_def(
+        &mut self,
+        method: Gc<'gc, BytecodeMethod<'gc>>,
+        index: Index<AbcMultiname>,
+    ) -> Result<FrameControl<'gc>, Error> {
+        let multiname = self.pool_multiname_static(method, index)?;
+        avm_debug!(self.avm2(), "Resolving {:?}", multiname);
+        let (_qname, mut script) =
+            self.domain()
+                .get_defining_script(&multiname)?
+                .ok_or_else(|| {
+                    format!(
+                        "ReferenceError: Error #1065: Variable {} is not defined.",
+                        multiname.local_name().unwrap_or_else(|| "*".into())
+                    )
+                })?;
+        let globals = script.globals(&mut self.context)?;
+
+        self.context.avm2.push(globals);
+
+        Ok(FrameControl::Continue)
+    }
+
     fn op_get_lex(
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
@@ -2117,234 +2296,159 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_jump<'b>(
-        &mut self,
-        offset: i32,
-        reader: &mut Reader<'b>,
-        full_data: &'b [u8],
-    ) -> Result<FrameControl<'gc>, Error> {
-        reader.seek(full_data, offset);
+    fn op_jump(&mut self, offset: i32, ip: &mut usize) -> Result<FrameControl<'gc>, Error> {
+        *ip = offset as usize;
 
         Ok(FrameControl::Continue)
     }
 
-    fn op_if_true<'b>(
-        &mut self,
-        offset: i32,
-        reader: &mut Reader<'b>,
-        full_data: &'b [u8],
-    ) -> Result<FrameControl<'gc>, Error> {
+    fn op_if_true(&mut self, offset: i32, ip: &mut usize) -> Result<FrameControl<'gc>, Error> {
         let value = self.context.avm2.pop().coerce_to_boolean();
 
         if value {
-            reader.seek(full_data, offset);
+            *ip = offset as usize;
         }
 
         Ok(FrameControl::Continue)
     }
 
-    fn op_if_false<'b>(
-        &mut self,
-        offset: i32,
-        reader: &mut Reader<'b>,
-        full_data: &'b [u8],
-    ) -> Result<FrameControl<'gc>, Error> {
+    fn op_if_false(&mut self, offset: i32, ip: &mut usize) -> Result<FrameControl<'gc>, Error> {
         let value = self.context.avm2.pop().coerce_to_boolean();
 
         if !value {
-            reader.seek(full_data, offset);
+            *ip = offset as usize;
         }
 
         Ok(FrameControl::Continue)
     }
 
-    fn op_if_strict_eq<'b>(
-        &mut self,
-        offset: i32,
-        reader: &mut Reader<'b>,
-        full_data: &'b [u8],
-    ) -> Result<FrameControl<'gc>, Error> {
+    fn op_if_strict_eq(&mut self, offset: i32, ip: &mut usize) -> Result<FrameControl<'gc>, Error> {
         let value2 = self.context.avm2.pop();
         let value1 = self.context.avm2.pop();
 
         if value1 == value2 {
-            reader.seek(full_data, offset);
+            *ip = offset as usize;
         }
 
         Ok(FrameControl::Continue)
     }
 
-    fn op_if_strict_ne<'b>(
-        &mut self,
-        offset: i32,
-        reader: &mut Reader<'b>,
-        full_data: &'b [u8],
-    ) -> Result<FrameControl<'gc>, Error> {
+    fn op_if_strict_ne(&mut self, offset: i32, ip: &mut usize) -> Result<FrameControl<'gc>, Error> {
         let value2 = self.context.avm2.pop();
         let value1 = self.context.avm2.pop();
 
         if value1 != value2 {
-            reader.seek(full_data, offset);
+            *ip = offset as usize;
         }
 
         Ok(FrameControl::Continue)
     }
 
-    fn op_if_eq<'b>(
-        &mut self,
-        offset: i32,
-        reader: &mut Reader<'b>,
-        full_data: &'b [u8],
-    ) -> Result<FrameControl<'gc>, Error> {
+    fn op_if_eq(&mut self, offset: i32, ip: &mut usize) -> Result<FrameControl<'gc>, Error> {
         let value2 = self.context.avm2.pop();
         let value1 = self.context.avm2.pop();
 
         if value1.abstract_eq(&value2, self)? {
-            reader.seek(full_data, offset);
+            *ip = offset as usize;
         }
 
         Ok(FrameControl::Continue)
     }
 
-    fn op_if_ne<'b>(
-        &mut self,
-        offset: i32,
-        reader: &mut Reader<'b>,
-        full_data: &'b [u8],
-    ) -> Result<FrameControl<'gc>, Error> {
+    fn op_if_ne(&mut self, offset: i32, ip: &mut usize) -> Result<FrameControl<'gc>, Error> {
         let value2 = self.context.avm2.pop();
         let value1 = self.context.avm2.pop();
 
         if !value1.abstract_eq(&value2, self)? {
-            reader.seek(full_data, offset);
+            *ip = offset as usize;
         }
 
         Ok(FrameControl::Continue)
     }
 
-    fn op_if_ge<'b>(
-        &mut self,
-        offset: i32,
-        reader: &mut Reader<'b>,
-        full_data: &'b [u8],
-    ) -> Result<FrameControl<'gc>, Error> {
+    fn op_if_ge(&mut self, offset: i32, ip: &mut usize) -> Result<FrameControl<'gc>, Error> {
         let value2 = self.context.avm2.pop();
         let value1 = self.context.avm2.pop();
 
         if value1.abstract_lt(&value2, self)? == Some(false) {
-            reader.seek(full_data, offset);
+            *ip = offset as usize;
         }
 
         Ok(FrameControl::Continue)
     }
 
-    fn op_if_gt<'b>(
-        &mut self,
-        offset: i32,
-        reader: &mut Reader<'b>,
-        full_data: &'b [u8],
-    ) -> Result<FrameControl<'gc>, Error> {
+    fn op_if_gt(&mut self, offset: i32, ip: &mut usize) -> Result<FrameControl<'gc>, Error> {
         let value2 = self.context.avm2.pop();
         let value1 = self.context.avm2.pop();
 
         if value2.abstract_lt(&value1, self)? == Some(true) {
-            reader.seek(full_data, offset);
+            *ip = offset as usize;
         }
 
         Ok(FrameControl::Continue)
     }
 
-    fn op_if_le<'b>(
-        &mut self,
-        offset: i32,
-        reader: &mut Reader<'b>,
-        full_data: &'b [u8],
-    ) -> Result<FrameControl<'gc>, Error> {
+    fn op_if_le(&mut self, offset: i32, ip: &mut usize) -> Result<FrameControl<'gc>, Error> {
         let value2 = self.context.avm2.pop();
         let value1 = self.context.avm2.pop();
 
         if value2.abstract_lt(&value1, self)? == Some(false) {
-            reader.seek(full_data, offset);
+            *ip = offset as usize;
         }
 
         Ok(FrameControl::Continue)
     }
 
-    fn op_if_lt<'b>(
-        &mut self,
-        offset: i32,
-        reader: &mut Reader<'b>,
-        full_data: &'b [u8],
-    ) -> Result<FrameControl<'gc>, Error> {
+    fn op_if_lt(&mut self, offset: i32, ip: &mut usize) -> Result<FrameControl<'gc>, Error> {
         let value2 = self.context.avm2.pop();
         let value1 = self.context.avm2.pop();
 
         if value1.abstract_lt(&value2, self)? == Some(true) {
-            reader.seek(full_data, offset);
+            *ip = offset as usize;
         }
 
         Ok(FrameControl::Continue)
     }
 
-    fn op_if_nge<'b>(
-        &mut self,
-        offset: i32,
-        reader: &mut Reader<'b>,
-        full_data: &'b [u8],
-    ) -> Result<FrameControl<'gc>, Error> {
+    fn op_if_nge(&mut self, offset: i32, ip: &mut usize) -> Result<FrameControl<'gc>, Error> {
         let value2 = self.context.avm2.pop();
         let value1 = self.context.avm2.pop();
 
         if value1.abstract_lt(&value2, self)?.unwrap_or(true) {
-            reader.seek(full_data, offset);
+            *ip = offset as usize;
         }
 
         Ok(FrameControl::Continue)
     }
 
-    fn op_if_ngt<'b>(
-        &mut self,
-        offset: i32,
-        reader: &mut Reader<'b>,
-        full_data: &'b [u8],
-    ) -> Result<FrameControl<'gc>, Error> {
+    fn op_if_ngt(&mut self, offset: i32, ip: &mut usize) -> Result<FrameControl<'gc>, Error> {
         let value2 = self.context.avm2.pop();
         let value1 = self.context.avm2.pop();
 
         if !value2.abstract_lt(&value1, self)?.unwrap_or(false) {
-            reader.seek(full_data, offset);
+            *ip = offset as usize;
         }
 
         Ok(FrameControl::Continue)
     }
 
-    fn op_if_nle<'b>(
-        &mut self,
-        offset: i32,
-        reader: &mut Reader<'b>,
-        full_data: &'b [u8],
-    ) -> Result<FrameControl<'gc>, Error> {
+    fn op_if_nle(&mut self, offset: i32, ip: &mut usize) -> Result<FrameControl<'gc>, Error> {
         let value2 = self.context.avm2.pop();
         let value1 = self.context.avm2.pop();
 
         if value2.abstract_lt(&value1, self)?.unwrap_or(true) {
-            reader.seek(full_data, offset);
+            *ip = offset as usize;
         }
 
         Ok(FrameControl::Continue)
     }
 
-    fn op_if_nlt<'b>(
-        &mut self,
-        offset: i32,
-        reader: &mut Reader<'b>,
-        full_data: &'b [u8],
-    ) -> Result<FrameControl<'gc>, Error> {
+    fn op_if_nlt(&mut self, offset: i32, ip: &mut usize) -> Result<FrameControl<'gc>, Error> {
         let value2 = self.context.avm2.pop();
         let value1 = self.context.avm2.pop();
 
         if !value1.abstract_lt(&value2, self)?.unwrap_or(false) {
-            reader.seek(full_data, offset);
+            *ip = offset as usize;
         }
 
         Ok(FrameControl::Continue)
@@ -2706,24 +2810,21 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     }
 
     /// Implements `Op::LookupSwitch`
-    fn op_lookup_switch<'b>(
+    fn op_lookup_switch(
         &mut self,
         default_offset: i32,
         case_offsets: &[i32],
-        instruction_start: usize,
-        reader: &mut Reader<'b>,
-        full_data: &'b [u8],
+        ip: &mut usize,
     ) -> Result<FrameControl<'gc>, Error> {
         let index = self.context.avm2.pop().coerce_to_i32(self)?;
 
-        let offset = case_offsets
-            .get(index as usize)
+        let offset = usize::try_from(index)
+            .ok()
+            .and_then(|index| case_offsets.get(index))
             .copied()
-            .unwrap_or(default_offset)
-            + instruction_start as i32
-            - reader.pos(full_data) as i32;
+            .unwrap_or(default_offset);
 
-        reader.seek(full_data, offset);
+        *ip = offset as usize;
         Ok(FrameControl::Continue)
     }
 
@@ -2755,19 +2856,42 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         self.outer.domain().domain_memory()
     }
 
-    /// Implements `Op::Si8`
-    fn op_si8(&mut self) -> Result<FrameControl<'gc>, Error> {
-        let address = self.context.avm2.pop().coerce_to_i32(self)?;
-        let val = self.context.avm2.pop().coerce_to_i32(self)?;
+    /// Read `N` bytes of domain memory at an address.
+    fn domain_memory_load<const N: usize>(&mut self, address: i32) -> Result<[u8; N], Error> {
+        let dm = self.domain_memory();
+        let dm = dm
+            .as_bytearray()
+            .ok_or_else(|| "Unable to get bytearray storage".to_string())?;
+
+        usize::try_from(address)
+            .ok()
+            .and_then(|address| dm.bytes().get(address..)?.get(..N))
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or_else(|| "RangeError: Error #1506: The specified range is invalid.".into())
+    }
 
+    /// Write bytes to domain memory at an address.
+    fn domain_memory_store(&mut self, address: i32, bytes: &[u8]) -> Result<(), Error> {
         let dm = self.domain_memory();
         let mut dm = dm
             .as_bytearray_mut(self.context.gc_context)
             .ok_or_else(|| "Unable to get bytearray storage".to_string())?;
 
-        let address =
-            usize::try_from(address).map_err(|_| "RangeError: The specified range is invalid")?;
-        dm.write_at_nongrowing(&val.to_le_bytes(), address)?;
+        usize::try_from(address)
+            .ok()
+            .and_then(|address| dm.bytes_mut().get_mut(address..)?.get_mut(..bytes.len()))
+            .ok_or_else(|| Error::from("RangeError: Error #1506: The specified range is invalid."))?
+            .copy_from_slice(bytes);
+
+        Ok(())
+    }
+
+    /// Implements `Op::Si8`
+    fn op_si8(&mut self) -> Result<FrameControl<'gc>, Error> {
+        let address = self.context.avm2.pop().coerce_to_i32(self)?;
+        let val = self.context.avm2.pop().coerce_to_i32(self)?;
+
+        self.domain_memory_store(address, &(val as u8).to_le_bytes())?;
 
         Ok(FrameControl::Continue)
     }
@@ -2777,14 +2901,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         let address = self.context.avm2.pop().coerce_to_i32(self)?;
         let val = self.context.avm2.pop().coerce_to_i32(self)?;
 
-        let dm = self.domain_memory();
-        let mut dm = dm
-            .as_bytearray_mut(self.context.gc_context)
-            .ok_or_else(|| "Unable to get bytearray storage".to_string())?;
-
-        let address =
-            usize::try_from(address).map_err(|_| "RangeError: The specified range is invalid")?;
-        dm.write_at_nongrowing(&val.to_le_bytes(), address)?;
+        self.domain_memory_store(address, &(val as u16).to_le_bytes())?;
 
         Ok(FrameControl::Continue)
     }
@@ -2794,14 +2911,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         let address = self.context.avm2.pop().coerce_to_i32(self)?;
         let val = self.context.avm2.pop().coerce_to_i32(self)?;
 
-        let dm = self.domain_memory();
-        let mut dm = dm
-            .as_bytearray_mut(self.context.gc_context)
-            .ok_or_else(|| "Unable to get bytearray storage".to_string())?;
-
-        let address =
-            usize::try_from(address).map_err(|_| "RangeError: The specified range is invalid")?;
-        dm.write_at_nongrowing(&val.to_le_bytes(), address)?;
+        self.domain_memory_store(address, &val.to_le_bytes())?;
 
         Ok(FrameControl::Continue)
     }
@@ -2811,14 +2921,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         let address = self.context.avm2.pop().coerce_to_i32(self)?;
         let val = self.context.avm2.pop().coerce_to_number(self)? as f32;
 
-        let dm = self.domain_memory();
-        let mut dm = dm
-            .as_bytearray_mut(self.context.gc_context)
-            .ok_or_else(|| "Unable to get bytearray storage".to_string())?;
-
-        let address =
-            usize::try_from(address).map_err(|_| "RangeError: The specified range is invalid")?;
-        dm.write_at_nongrowing(&val.to_le_bytes(), address)?;
+        self.domain_memory_store(address, &val.to_le_bytes())?;
 
         Ok(FrameControl::Continue)
     }
@@ -2828,96 +2931,58 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         let address = self.context.avm2.pop().coerce_to_i32(self)?;
         let val = self.context.avm2.pop().coerce_to_number(self)?;
 
-        let dm = self.domain_memory();
-        let mut dm = dm
-            .as_bytearray_mut(self.context.gc_context)
-            .ok_or_else(|| "Unable to get bytearray storage".to_string())?;
-
-        let address =
-            usize::try_from(address).map_err(|_| "RangeError: The specified range is invalid")?;
-        dm.write_at_nongrowing(&val.to_le_bytes(), address)?;
+        self.domain_memory_store(address, &val.to_le_bytes())?;
 
         Ok(FrameControl::Continue)
     }
 
     /// Implements `Op::Li8`
     fn op_li8(&mut self) -> Result<FrameControl<'gc>, Error> {
-        let address = self.context.avm2.pop().coerce_to_u32(self)? as usize;
-
-        let dm = self.domain_memory();
-        let dm = dm
-            .as_bytearray()
-            .ok_or_else(|| "Unable to get bytearray storage".to_string())?;
-        let val = dm.get(address);
+        let address = self.context.avm2.pop().coerce_to_i32(self)?;
 
-        if let Some(val) = val {
-            self.context.avm2.push(val);
-        } else {
-            return Err("RangeError: The specified range is invalid".into());
-        }
+        let val = u8::from_le_bytes(self.domain_memory_load(address)?);
+        self.context.avm2.push(Value::Integer(val.into()));
 
         Ok(FrameControl::Continue)
     }
 
     /// Implements `Op::Li16`
     fn op_li16(&mut self) -> Result<FrameControl<'gc>, Error> {
-        let address = self.context.avm2.pop().coerce_to_u32(self)? as usize;
+        let address = self.context.avm2.pop().coerce_to_i32(self)?;
 
-        let dm = self.domain_memory();
-        let dm = dm
-            .as_bytearray()
-            .ok_or_else(|| "Unable to get bytearray storage".to_string())?;
-        let val = dm.read_at(2, address)?;
-        self.context
-            .avm2
-            .push(u16::from_le_bytes(val.try_into().unwrap()));
+        let val = u16::from_le_bytes(self.domain_memory_load(address)?);
+        self.context.avm2.push(Value::Integer(val.into()));
 
         Ok(FrameControl::Continue)
     }
 
     /// Implements `Op::Li32`
     fn op_li32(&mut self) -> Result<FrameControl<'gc>, Error> {
-        let address = self.context.avm2.pop().coerce_to_u32(self)? as usize;
+        let address = self.context.avm2.pop().coerce_to_i32(self)?;
+
+        let val = i32::from_le_bytes(self.domain_memory_load(address)?);
+        self.context.avm2.push(Value::Integer(val));
 
-        let dm = self.domain_memory();
-        let dm = dm
-            .as_bytearray()
-            .ok_or_else(|| "Unable to get bytearray storage".to_string())?;
-        let val = dm.read_at(4, address)?;
-        self.context
-            .avm2
-            .push(i32::from_le_bytes(val.try_into().unwrap()));
         Ok(FrameControl::Continue)
     }
 
     /// Implements `Op::Lf32`
     fn op_lf32(&mut self) -> Result<FrameControl<'gc>, Error> {
-        let address = self.context.avm2.pop().coerce_to_u32(self)? as usize;
+        let address = self.context.avm2.pop().coerce_to_i32(self)?;
 
-        let dm = self.domain_memory();
-        let dm = dm
-            .as_bytearray()
-            .ok_or_else(|| "Unable to get bytearray storage".to_string())?;
-        let val = dm.read_at(4, address)?;
-        self.context
-            .avm2
-            .push(f32::from_le_bytes(val.try_into().unwrap()));
+        let val = f32::from_le_bytes(self.domain_memory_load(address)?);
+        self.context.avm2.push(Value::Number(val.into()));
 
         Ok(FrameControl::Continue)
     }
 
     /// Implements `Op::Lf64`
     fn op_lf64(&mut self) -> Result<FrameControl<'gc>, Error> {
-        let address = self.context.avm2.pop().coerce_to_u32(self)? as usize;
+        let address = self.context.avm2.pop().coerce_to_i32(self)?;
+
+        let val = f64::from_le_bytes(self.domain_memory_load(address)?);
+        self.context.avm2.push(Value::Number(val));
 
-        let dm = self.domain_memory();
-        let dm = dm
-            .as_bytearray()
-            .ok_or_else(|| "Unable to get bytearray storage".to_string())?;
-        let val = dm.read_at(8, address)?;
-        self.context
-            .avm2
-            .push(f64::from_le_bytes(val.try_into().unwrap()));
         Ok(FrameControl::Continue)
     }
 
@@ -2936,7 +3001,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     fn op_sxi8(&mut self) -> Result<FrameControl<'gc>, Error> {
         let val = self.context.avm2.pop().coerce_to_i32(self)?;
 
-        let val = (val.wrapping_shl(23).wrapping_shr(23) & 0xFF) as i8 as i32;
+        let val = val as i8 as i32;
 
         self.context.avm2.push(Value::Integer(val));
 
@@ -2947,14 +3012,13 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     fn op_sxi16(&mut self) -> Result<FrameControl<'gc>, Error> {
         let val = self.context.avm2.pop().coerce_to_i32(self)?;
 
-        let val = (val.wrapping_shl(15).wrapping_shr(15) & 0xFFFF) as i16 as i32;
+        let val = val as i16 as i32;
 
         self.context.avm2.push(Value::Integer(val));
 
         Ok(FrameControl::Continue)
     }
 
-    #[cfg(avm_debug)]
     fn op_debug(
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
@@ -2962,11 +3026,18 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         register_name: Index<String>,
         register: u8,
     ) -> Result<FrameControl<'gc>, Error> {
+        if !cfg!(avm_debug) && !self.context.debugger.is_attached() {
+            return Ok(FrameControl::Continue);
+        }
+
         if is_local_register {
-            let register_name = self.pool_string(method, register_name)?;
+            let register_name = self.pool_string(&method, register_name)?;
             let value = self.local_register(register as u32)?;
 
             avm_debug!(self.avm2(), "Debug: {} = {:?}", register_name, value);
+            self.context
+                .debugger
+                .set_register_name(register as u32, register_name.to_string());
         } else {
             avm_debug!(self.avm2(), "Unknown debugging mode!");
         }
@@ -2974,47 +3045,41 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    #[cfg(not(avm_debug))]
-    fn op_debug(
-        &mut self,
-        _method: Gc<'gc, BytecodeMethod<'gc>>,
-        _is_local_register: bool,
-        _register_name: Index<String>,
-        _register: u8,
-    ) -> Result<FrameControl<'gc>, Error> {
-        Ok(FrameControl::Continue)
-    }
-
-    #[cfg(avm_debug)]
     fn op_debug_file(
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
         file_name: Index<String>,
     ) -> Result<FrameControl<'gc>, Error> {
-        let file_name = self.pool_string(method, file_name)?;
+        if !cfg!(avm_debug) && !self.context.debugger.is_attached() {
+            return Ok(FrameControl::Continue);
+        }
 
-        avm_debug!(self.avm2(), "File: {}", file_name);
+        let file_name = self.pool_string(&method, file_name)?;
 
-        Ok(FrameControl::Continue)
-    }
+        avm_debug!(self.avm2(), "File: {}", file_name);
+        self.context.debugger.set_file(file_name.to_string());
 
-    #[cfg(not(avm_debug))]
-    fn op_debug_file(
-        &mut self,
-        _method: Gc<'gc, BytecodeMethod<'gc>>,
-        _file_name: Index<String>,
-    ) -> Result<FrameControl<'gc>, Error> {
         Ok(FrameControl::Continue)
     }
 
     fn op_debug_line(&mut self, line_num: u32) -> Result<FrameControl<'gc>, Error> {
         avm_debug!(self.avm2(), "Line: {}", line_num);
 
+        if self.context.debugger.is_attached() {
+            if let Some(reason) = self.context.debugger.reached_line(line_num) {
+                self.debugger_pause(reason)?;
+            }
+        }
+
         Ok(FrameControl::Continue)
     }
 
     fn op_bkpt(&mut self) -> Result<FrameControl<'gc>, Error> {
         // while a debugger is not attached, this is a no-op
+        if self.context.debugger.is_attached() {
+            self.debugger_pause(PauseReason::Breakpoint)?;
+        }
+
         Ok(FrameControl::Continue)
     }
 
@@ -101,6 +101,9 @@ pub enum EventData<'gc> {
         button_down: bool,
         delta: i32,
     },
+    Text {
+        text: AvmString<'gc>,
+    },
 }
 
 impl<'gc> EventData<'gc> {
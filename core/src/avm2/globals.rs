@@ -70,6 +70,10 @@ pub struct SystemPrototypes<'gc> {
     pub rectangle: Object<'gc>,
     pub textfield: Object<'gc>,
     pub textformat: Object<'gc>,
+    pub textlinemetrics: Object<'gc>,
+    pub font: Object<'gc>,
+    pub fontdescription: Object<'gc>,
+    pub textline: Object<'gc>,
     pub graphics: Object<'gc>,
     pub loaderinfo: Object<'gc>,
     pub bytearray: Object<'gc>,
@@ -88,6 +92,8 @@ pub struct SystemPrototypes<'gc> {
     pub nativemenu: Object<'gc>,
     pub contextmenu: Object<'gc>,
     pub mouseevent: Object<'gc>,
+    pub textevent: Object<'gc>,
+    pub filereference: Object<'gc>,
 }
 
 impl<'gc> SystemPrototypes<'gc> {
@@ -132,6 +138,10 @@ impl<'gc> SystemPrototypes<'gc> {
             rectangle: empty,
             textfield: empty,
             textformat: empty,
+            textlinemetrics: empty,
+            font: empty,
+            fontdescription: empty,
+            textline: empty,
             graphics: empty,
             loaderinfo: empty,
             bytearray: empty,
@@ -150,6 +160,8 @@ impl<'gc> SystemPrototypes<'gc> {
             nativemenu: empty,
             contextmenu: empty,
             mouseevent: empty,
+            textevent: empty,
+            filereference: empty,
         }
     }
 }
@@ -184,6 +196,10 @@ pub struct SystemClasses<'gc> {
     pub rectangle: ClassObject<'gc>,
     pub textfield: ClassObject<'gc>,
     pub textformat: ClassObject<'gc>,
+    pub textlinemetrics: ClassObject<'gc>,
+    pub font: ClassObject<'gc>,
+    pub fontdescription: ClassObject<'gc>,
+    pub textline: ClassObject<'gc>,
     pub graphics: ClassObject<'gc>,
     pub loaderinfo: ClassObject<'gc>,
     pub bytearray: ClassObject<'gc>,
@@ -202,6 +218,8 @@ pub struct SystemClasses<'gc> {
     pub nativemenu: ClassObject<'gc>,
     pub contextmenu: ClassObject<'gc>,
     pub mouseevent: ClassObject<'gc>,
+    pub textevent: ClassObject<'gc>,
+    pub filereference: ClassObject<'gc>,
 }
 
 impl<'gc> SystemClasses<'gc> {
@@ -246,6 +264,10 @@ impl<'gc> SystemClasses<'gc> {
             rectangle: object,
             textfield: object,
             textformat: object,
+            textlinemetrics: object,
+            font: object,
+            fontdescription: object,
+            textline: object,
             graphics: object,
             loaderinfo: object,
             bytearray: object,
@@ -264,6 +286,8 @@ impl<'gc> SystemClasses<'gc> {
             nativemenu: object,
             contextmenu: object,
             mouseevent: object,
+            textevent: object,
+            filereference: object,
         }
     }
 }
@@ -594,6 +618,12 @@ pub fn load_player_globals<'gc>(
         flash::events::fullscreenevent::create_class(mc),
         script
     );
+    avm2_system_class!(
+        textevent,
+        activation,
+        flash::events::textevent::create_class(mc),
+        script
+    );
     class(
         activation,
         flash::events::eventphase::create_class(mc),
@@ -872,6 +902,52 @@ pub fn load_player_globals<'gc>(
         flash::net::object_encoding::create_class(mc),
         script,
     )?;
+    class(activation, flash::net::filefilter::create_class(mc), script)?;
+    avm2_system_class!(
+        filereference,
+        activation,
+        flash::net::filereference::create_class(mc),
+        script
+    );
+    class(
+        activation,
+        flash::net::filereferencelist::create_class(mc),
+        script,
+    )?;
+
+    // package `flash.desktop`
+    class(
+        activation,
+        flash::desktop::clipboard::create_class(mc),
+        script,
+    )?;
+    class(
+        activation,
+        flash::desktop::clipboardformats::create_class(mc),
+        script,
+    )?;
+    class(
+        activation,
+        flash::desktop::clipboardtransfermode::create_class(mc),
+        script,
+    )?;
+
+    // package `flash.printing`
+    class(
+        activation,
+        flash::printing::printjob::create_class(mc),
+        script,
+    )?;
+    class(
+        activation,
+        flash::printing::printjoboptions::create_class(mc),
+        script,
+    )?;
+    class(
+        activation,
+        flash::printing::printjoborientation::create_class(mc),
+        script,
+    )?;
 
     // package `flash.text`
     avm2_system_class!(
@@ -886,6 +962,12 @@ pub fn load_player_globals<'gc>(
         flash::text::textformat::create_class(mc),
         script
     );
+    avm2_system_class!(
+        textlinemetrics,
+        activation,
+        flash::text::textlinemetrics::create_class(mc),
+        script
+    );
     class(
         activation,
         flash::text::textfieldautosize::create_class(mc),
@@ -901,7 +983,91 @@ pub fn load_player_globals<'gc>(
         flash::text::textfieldtype::create_class(mc),
         script,
     )?;
-    class(activation, flash::text::font::create_class(mc), script)?;
+    avm2_system_class!(
+        font,
+        activation,
+        flash::text::font::create_class(mc),
+        script
+    );
+    class(
+        activation,
+        flash::text::stylesheet::create_class(mc),
+        script,
+    )?;
+    class(
+        activation,
+        flash::text::antialiastype::create_class(mc),
+        script,
+    )?;
+    class(
+        activation,
+        flash::text::csmsettings::create_class(mc),
+        script,
+    )?;
+    class(
+        activation,
+        flash::text::gridfittype::create_class(mc),
+        script,
+    )?;
+    class(
+        activation,
+        flash::text::textcolortype::create_class(mc),
+        script,
+    )?;
+    class(
+        activation,
+        flash::text::textrenderer::create_class(mc),
+        script,
+    )?;
+
+    // package `flash.text.engine`
+    class(
+        activation,
+        flash::text::engine::contentelement::create_class(mc),
+        script,
+    )?;
+    class(
+        activation,
+        flash::text::engine::elementformat::create_class(mc),
+        script,
+    )?;
+    avm2_system_class!(
+        fontdescription,
+        activation,
+        flash::text::engine::fontdescription::create_class(mc),
+        script
+    );
+    class(
+        activation,
+        flash::text::engine::fontlookup::create_class(mc),
+        script,
+    )?;
+    class(
+        activation,
+        flash::text::engine::fontposture::create_class(mc),
+        script,
+    )?;
+    class(
+        activation,
+        flash::text::engine::fontweight::create_class(mc),
+        script,
+    )?;
+    class(
+        activation,
+        flash::text::engine::textblock::create_class(mc),
+        script,
+    )?;
+    class(
+        activation,
+        flash::text::engine::textelement::create_class(mc),
+        script,
+    )?;
+    avm2_system_class!(
+        textline,
+        activation,
+        flash::text::engine::textline::create_class(mc),
+        script
+    );
 
     // package `flash.crypto`
     function(
@@ -1,11 +1,13 @@
 //! `flash` namespace
 
 pub mod crypto;
+pub mod desktop;
 pub mod display;
 pub mod events;
 pub mod geom;
 pub mod media;
 pub mod net;
+pub mod printing;
 pub mod system;
 pub mod text;
 pub mod ui;
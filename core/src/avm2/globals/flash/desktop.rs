@@ -0,0 +1,5 @@
+//! `flash.desktop` namespace
+
+pub mod clipboard;
+pub mod clipboardformats;
+pub mod clipboardtransfermode;
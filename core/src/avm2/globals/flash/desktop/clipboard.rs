@@ -0,0 +1,238 @@
+//! `flash.desktop.Clipboard` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::globals::NS_RUFFLE_INTERNAL;
+use crate::avm2::method::{Method, NativeMethodImpl};
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{ArrayObject, Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::string::AvmString;
+use gc_arena::{GcCell, MutationContext};
+
+/// The only clipboard format that can be read and written.
+const TEXT_FORMAT: &str = "air:text";
+
+/// Implements `flash.desktop.Clipboard`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        activation.super_init(this, &[])?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.desktop.Clipboard`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Returns whether the `format` argument of a method names the text format.
+fn is_text_format<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    format: Option<&Value<'gc>>,
+) -> Result<bool, Error> {
+    let format = format
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)?;
+    if &format == TEXT_FORMAT.as_bytes() {
+        Ok(true)
+    } else {
+        log::warn!("Clipboard: Format {} is not yet supported", format);
+        Ok(false)
+    }
+}
+
+/// Returns the text on the system clipboard, if there is any.
+///
+/// `clear` leaves the clipboard empty rather than removing it, so empty text
+/// counts as no data.
+fn text_content(activation: &mut Activation<'_, '_, '_>) -> Option<String> {
+    activation
+        .context
+        .ui
+        .clipboard_content()
+        .filter(|content| !content.is_empty())
+}
+
+/// Implements `Clipboard.generalClipboard`.
+///
+/// Every `Clipboard` is backed by the system clipboard, so this is simply an
+/// instance that is created the first time it's needed.
+pub fn general_clipboard<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let name = QName::new(
+            Namespace::Private(NS_RUFFLE_INTERNAL.into()),
+            "generalClipboard",
+        );
+        let clipboard = this.get_property(&name.into(), activation)?;
+        if let Value::Object(_) = clipboard {
+            return Ok(clipboard);
+        }
+
+        let clipboard: Value<'gc> = this.construct(activation, &[])?.into();
+        this.set_property(&name.into(), clipboard, activation)?;
+        return Ok(clipboard);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Clipboard.formats`.
+pub fn formats<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let mut formats = Vec::new();
+    if text_content(activation).is_some() {
+        formats.push(Some(TEXT_FORMAT.into()));
+    }
+
+    Ok(ArrayObject::from_storage(activation, ArrayStorage::from_storage(formats))?.into())
+}
+
+/// Implements `Clipboard.clear`.
+pub fn clear<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    activation.context.ui.set_clipboard_content("".to_string());
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Clipboard.clearData`.
+pub fn clear_data<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if is_text_format(activation, args.get(0))? {
+        activation.context.ui.set_clipboard_content("".to_string());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Clipboard.getData`.
+pub fn get_data<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if is_text_format(activation, args.get(0))? {
+        if let Some(content) = text_content(activation) {
+            return Ok(AvmString::new_utf8(activation.context.gc_context, content).into());
+        }
+    }
+
+    Ok(Value::Null)
+}
+
+/// Implements `Clipboard.hasFormat`.
+pub fn has_format<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if is_text_format(activation, args.get(0))? {
+        return Ok(text_content(activation).is_some().into());
+    }
+
+    Ok(false.into())
+}
+
+/// Implements `Clipboard.setData`.
+pub fn set_data<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if is_text_format(activation, args.get(0))? {
+        let content = args
+            .get(1)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?
+            .to_string();
+        activation.context.ui.set_clipboard_content(content);
+        return Ok(true.into());
+    }
+
+    Ok(false.into())
+}
+
+/// Implements `Clipboard.setDataHandler`.
+pub fn set_data_handler<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    log::warn!("Clipboard.setDataHandler: Not yet implemented");
+    Ok(false.into())
+}
+
+/// Construct `Clipboard`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.desktop"), "Clipboard"),
+        Some(QName::new(Namespace::public(), "Object").into()),
+        Method::from_builtin(instance_init, "<Clipboard instance initializer>", mc),
+        Method::from_builtin(class_init, "<Clipboard class initializer>", mc),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED);
+
+    const PUBLIC_INSTANCE_METHODS: &[(&str, NativeMethodImpl)] = &[
+        ("clear", clear),
+        ("clearData", clear_data),
+        ("getData", get_data),
+        ("hasFormat", has_format),
+        ("setData", set_data),
+        ("setDataHandler", set_data_handler),
+    ];
+    write.define_public_builtin_instance_methods(mc, PUBLIC_INSTANCE_METHODS);
+
+    const PUBLIC_INSTANCE_PROPERTIES: &[(
+        &str,
+        Option<NativeMethodImpl>,
+        Option<NativeMethodImpl>,
+    )] = &[("formats", Some(formats), None)];
+    write.define_public_builtin_instance_properties(mc, PUBLIC_INSTANCE_PROPERTIES);
+
+    const PUBLIC_CLASS_PROPERTIES: &[(&str, Option<NativeMethodImpl>, Option<NativeMethodImpl>)] =
+        &[("generalClipboard", Some(general_clipboard), None)];
+    write.define_public_builtin_class_properties(mc, PUBLIC_CLASS_PROPERTIES);
+
+    write.define_class_trait(Trait::from_slot(
+        QName::new(
+            Namespace::Private(NS_RUFFLE_INTERNAL.into()),
+            "generalClipboard",
+        ),
+        QName::new(Namespace::package("flash.desktop"), "Clipboard").into(),
+        None,
+    ));
+
+    class
+}
@@ -9,3 +9,4 @@ pub mod ieventdispatcher;
 pub mod keyboardevent;
 pub mod mouseevent;
 pub mod progressevent;
+pub mod textevent;
@@ -1,4 +1,7 @@
 //! `flash.net` namespace
 
+pub mod filefilter;
+pub mod filereference;
+pub mod filereferencelist;
 pub mod object_encoding;
 pub mod sharedobject;
@@ -0,0 +1,381 @@
+//! `flash.net.FileReference` builtin/prototype
+
+use crate::avm2::bytearray::ByteArrayStorage;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::globals::NS_RUFFLE_INTERNAL;
+use crate::avm2::method::{Method, NativeMethodImpl};
+use crate::avm2::object::{ByteArrayObject, TObject};
+use crate::avm2::{Activation, Error, Event, EventData, Namespace, Object, QName, Value};
+use crate::backend::ui::{FileDialogResult, FileFilter};
+use crate::context::ActionType;
+use crate::string::AvmString;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.net.FileReference`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        activation.super_init(this, &[])?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.net.FileReference`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Reads one of the private slots of a `FileReference`.
+fn get_internal<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    name: &'static str,
+) -> Result<Value<'gc>, Error> {
+    this.get_property(
+        &QName::new(Namespace::Private(NS_RUFFLE_INTERNAL.into()), name).into(),
+        activation,
+    )
+}
+
+/// Writes one of the private slots of a `FileReference`.
+fn set_internal<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut this: Object<'gc>,
+    name: &'static str,
+    value: Value<'gc>,
+) -> Result<(), Error> {
+    this.set_property(
+        &QName::new(Namespace::Private(NS_RUFFLE_INTERNAL.into()), name).into(),
+        value,
+        activation,
+    )
+}
+
+/// Reads the file filters from the `typeFilter` argument of `browse`.
+///
+/// Each entry of the array should be a `FileFilter`, though any object with
+/// `description` and `extension` properties is accepted.
+pub fn file_filters<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    type_filter: Option<&Value<'gc>>,
+) -> Result<Vec<FileFilter>, Error> {
+    let type_filter = match type_filter {
+        Some(Value::Object(type_filter)) => *type_filter,
+        _ => return Ok(vec![]),
+    };
+
+    let entries: Vec<Value<'gc>> = match type_filter.as_array_storage() {
+        Some(array) => array.iter().flatten().collect(),
+        None => return Ok(vec![]),
+    };
+
+    let mut filters = Vec::with_capacity(entries.len());
+    for entry in entries {
+        if let Value::Object(entry) = entry {
+            let description = entry
+                .get_property(
+                    &QName::new(Namespace::public(), "description").into(),
+                    activation,
+                )?
+                .coerce_to_string(activation)?;
+            let extension = entry
+                .get_property(
+                    &QName::new(Namespace::public(), "extension").into(),
+                    activation,
+                )?
+                .coerce_to_string(activation)?;
+            filters.push(FileFilter::from_extension_list(
+                description.to_string(),
+                &extension.to_utf8_lossy(),
+            ));
+        }
+    }
+    Ok(filters)
+}
+
+/// Stores a file chosen in a file dialog on a `FileReference`.
+///
+/// The file's contents are kept aside until `load` is called.
+pub fn set_file<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    file: FileDialogResult,
+) -> Result<(), Error> {
+    let name = AvmString::new_utf8(activation.context.gc_context, &file.file_name);
+    let file_type = match file.file_type() {
+        Some(file_type) => AvmString::new_utf8(activation.context.gc_context, file_type).into(),
+        None => Value::Null,
+    };
+    let size = file.contents.len() as f64;
+    let contents =
+        ByteArrayObject::from_storage(activation, ByteArrayStorage::from_vec(file.contents))?;
+
+    set_internal(activation, this, "name", name.into())?;
+    set_internal(activation, this, "type", file_type)?;
+    set_internal(activation, this, "size", size.into())?;
+    set_internal(activation, this, "contents", contents.into())?;
+    set_internal(activation, this, "data", Value::Null)?;
+    Ok(())
+}
+
+/// Queues an event to be dispatched on the given object once the current
+/// script has finished running.
+pub fn queue_event<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    target: Object<'gc>,
+    event_type: &'static str,
+) {
+    let root_clip = activation.context.stage.root_clip();
+    activation.context.action_queue.queue_actions(
+        root_clip,
+        ActionType::Event2 {
+            event: Event::new(event_type, EventData::Empty),
+            target,
+        },
+        false,
+    );
+}
+
+/// Implements `FileReference.browse`.
+pub fn browse<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let filters = file_filters(activation, args.get(0))?;
+        let file = activation
+            .context
+            .ui
+            .display_file_open_dialog(&filters, false)
+            .and_then(|files| files.into_iter().next());
+
+        match file {
+            Some(file) => {
+                set_file(activation, this, file)?;
+                queue_event(activation, this, "select");
+            }
+            None => queue_event(activation, this, "cancel"),
+        }
+
+        return Ok(true.into());
+    }
+
+    Ok(false.into())
+}
+
+/// Implements `FileReference.load`.
+pub fn load<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let contents = get_internal(activation, this, "contents")?;
+        if matches!(contents, Value::Null | Value::Undefined) {
+            return Err("Error #2037: Functions called in incorrect sequence, or earlier call was unsuccessful.".into());
+        }
+
+        set_internal(activation, this, "data", contents)?;
+        queue_event(activation, this, "complete");
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `FileReference.save`.
+pub fn save<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let data = match args.get(0).cloned().unwrap_or(Value::Undefined) {
+            Value::Null | Value::Undefined => {
+                return Err("ArgumentError: Error #2007: Parameter data must be non-null.".into())
+            }
+            Value::Object(object) if object.as_bytearray().is_some() => {
+                object.as_bytearray().unwrap().bytes().to_vec()
+            }
+            // Strings are written as UTF-8, and everything else (including XML)
+            // is converted to a string first.
+            value => value.coerce_to_string(activation)?.to_string().into_bytes(),
+        };
+
+        let default_file_name = match args.get(1).cloned().unwrap_or(Value::Undefined) {
+            Value::Null | Value::Undefined => "".to_string(),
+            value => value.coerce_to_string(activation)?.to_string(),
+        };
+
+        let saved = activation
+            .context
+            .ui
+            .display_file_save_dialog(&default_file_name, &data);
+
+        match saved {
+            Ok(Some(file_name)) => {
+                let name = AvmString::new_utf8(activation.context.gc_context, file_name);
+                set_internal(activation, this, "name", name.into())?;
+                set_internal(activation, this, "size", (data.len() as f64).into())?;
+                queue_event(activation, this, "select");
+                queue_event(activation, this, "complete");
+            }
+            Ok(None) => queue_event(activation, this, "cancel"),
+            Err(e) => {
+                log::error!("FileReference.save: Unable to save file: {}", e);
+                queue_event(activation, this, "ioError");
+            }
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `FileReference.download`.
+pub fn download<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    log::warn!("FileReference.download: Not yet implemented");
+    Ok(Value::Undefined)
+}
+
+/// Implements `FileReference.upload`.
+pub fn upload<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    log::warn!("FileReference.upload: Not yet implemented");
+    Ok(Value::Undefined)
+}
+
+/// Implements `FileReference.cancel`.
+pub fn cancel<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    // Nothing to cancel, as neither downloads nor uploads are implemented.
+    Ok(Value::Undefined)
+}
+
+/// Implements `FileReference.data`.
+pub fn data<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        return get_internal(activation, this, "data");
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `FileReference.name`.
+pub fn name<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        return get_internal(activation, this, "name");
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `FileReference.size`.
+pub fn size<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        return get_internal(activation, this, "size");
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `FileReference.type`.
+pub fn file_type<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        return get_internal(activation, this, "type");
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `FileReference.creator`.
+pub fn creator<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    // Only ever set on Mac OS.
+    Ok(Value::Null)
+}
+
+/// Construct `FileReference`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.net"), "FileReference"),
+        Some(QName::new(Namespace::package("flash.events"), "EventDispatcher").into()),
+        Method::from_builtin(instance_init, "<FileReference instance initializer>", mc),
+        Method::from_builtin(class_init, "<FileReference class initializer>", mc),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED);
+
+    const PUBLIC_INSTANCE_METHODS: &[(&str, NativeMethodImpl)] = &[
+        ("browse", browse),
+        ("load", load),
+        ("save", save),
+        ("download", download),
+        ("upload", upload),
+        ("cancel", cancel),
+    ];
+    write.define_public_builtin_instance_methods(mc, PUBLIC_INSTANCE_METHODS);
+
+    const PUBLIC_INSTANCE_PROPERTIES: &[(
+        &str,
+        Option<NativeMethodImpl>,
+        Option<NativeMethodImpl>,
+    )] = &[
+        ("data", Some(data), None),
+        ("name", Some(name), None),
+        ("size", Some(size), None),
+        ("type", Some(file_type), None),
+        ("creator", Some(creator), None),
+    ];
+    write.define_public_builtin_instance_properties(mc, PUBLIC_INSTANCE_PROPERTIES);
+
+    const PRIVATE_INSTANCE_SLOTS: &[(&str, &str, &str, &str)] = &[
+        (NS_RUFFLE_INTERNAL, "name", "", "String"),
+        (NS_RUFFLE_INTERNAL, "type", "", "String"),
+        (NS_RUFFLE_INTERNAL, "size", "", "Number"),
+        (NS_RUFFLE_INTERNAL, "contents", "flash.utils", "ByteArray"),
+        (NS_RUFFLE_INTERNAL, "data", "flash.utils", "ByteArray"),
+    ];
+    write.define_private_slot_instance_traits(PRIVATE_INSTANCE_SLOTS);
+
+    class
+}
@@ -0,0 +1,104 @@
+//! `flash.net.FileReferenceList` builtin/prototype
+
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::globals::flash::net::filereference::{file_filters, queue_event, set_file};
+use crate::avm2::method::{Method, NativeMethodImpl};
+use crate::avm2::object::{ArrayObject, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::{Activation, Error, Namespace, Object, QName, Value};
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.net.FileReferenceList`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        activation.super_init(this, &[])?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.net.FileReferenceList`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `FileReferenceList.browse`.
+pub fn browse<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let filters = file_filters(activation, args.get(0))?;
+        let files = match activation
+            .context
+            .ui
+            .display_file_open_dialog(&filters, true)
+        {
+            Some(files) => files,
+            None => {
+                queue_event(activation, this, "cancel");
+                return Ok(true.into());
+            }
+        };
+
+        let class = activation.context.avm2.classes().filereference;
+        let mut file_list = Vec::with_capacity(files.len());
+        for file in files {
+            let file_reference = class.construct(activation, &[])?;
+            set_file(activation, file_reference, file)?;
+            file_list.push(Some(file_reference.into()));
+        }
+        let file_list =
+            ArrayObject::from_storage(activation, ArrayStorage::from_storage(file_list))?;
+        this.set_property(
+            &QName::new(Namespace::public(), "fileList").into(),
+            file_list.into(),
+            activation,
+        )?;
+
+        queue_event(activation, this, "select");
+        return Ok(true.into());
+    }
+
+    Ok(false.into())
+}
+
+/// Construct `FileReferenceList`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.net"), "FileReferenceList"),
+        Some(QName::new(Namespace::package("flash.events"), "EventDispatcher").into()),
+        Method::from_builtin(
+            instance_init,
+            "<FileReferenceList instance initializer>",
+            mc,
+        ),
+        Method::from_builtin(class_init, "<FileReferenceList class initializer>", mc),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED);
+
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::public(), "fileList"),
+        QName::new(Namespace::public(), "Array").into(),
+        None,
+    ));
+
+    const PUBLIC_INSTANCE_METHODS: &[(&str, NativeMethodImpl)] = &[("browse", browse)];
+    write.define_public_builtin_instance_methods(mc, PUBLIC_INSTANCE_METHODS);
+
+    class
+}
@@ -0,0 +1,323 @@
+//! `flash.printing.PrintJob` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::globals::NS_RUFFLE_INTERNAL;
+use crate::avm2::method::{Method, NativeMethodImpl};
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::prelude::*;
+use crate::printing::{PrintManager, PrintPageRequest};
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.printing.PrintJob`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        activation.super_init(this, &[])?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.printing.PrintJob`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Reads one of the private slots of a `PrintJob`.
+fn get_internal<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    name: &'static str,
+) -> Result<Value<'gc>, Error> {
+    this.get_property(
+        &QName::new(Namespace::Private(NS_RUFFLE_INTERNAL.into()), name).into(),
+        activation,
+    )
+}
+
+/// Writes one of the private slots of a `PrintJob`.
+fn set_internal<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut this: Object<'gc>,
+    name: &'static str,
+    value: Value<'gc>,
+) -> Result<(), Error> {
+    this.set_property(
+        &QName::new(Namespace::Private(NS_RUFFLE_INTERNAL.into()), name).into(),
+        value,
+        activation,
+    )
+}
+
+/// Implements `PrintJob.start`.
+pub fn start<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let job_id = PrintManager::start_job(&mut activation.context);
+        let settings = match job_id.and_then(|id| activation.context.print_manager.settings(id)) {
+            Some(settings) => settings.clone(),
+            None => return Ok(false.into()),
+        };
+
+        set_internal(activation, this, "jobId", job_id.unwrap_or(0).into())?;
+        set_internal(activation, this, "paperWidth", settings.paper_width.into())?;
+        set_internal(
+            activation,
+            this,
+            "paperHeight",
+            settings.paper_height.into(),
+        )?;
+        set_internal(activation, this, "pageWidth", settings.page_width.into())?;
+        set_internal(activation, this, "pageHeight", settings.page_height.into())?;
+        set_internal(
+            activation,
+            this,
+            "orientation",
+            settings.orientation.as_str().into(),
+        )?;
+
+        return Ok(true.into());
+    }
+
+    Ok(false.into())
+}
+
+/// Implements `PrintJob.addPage`.
+pub fn add_page<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let job_id = get_internal(activation, this, "jobId")?.coerce_to_u32(activation)?;
+
+        let target = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_object(activation)?
+            .as_display_object()
+            .ok_or("PrintJob.addPage: sprite must be a display object")?;
+
+        let area = match args.get(1) {
+            Some(Value::Object(rect)) => {
+                let x = rect
+                    .get_property(&QName::new(Namespace::public(), "x").into(), activation)?
+                    .coerce_to_number(activation)?;
+                let y = rect
+                    .get_property(&QName::new(Namespace::public(), "y").into(), activation)?
+                    .coerce_to_number(activation)?;
+                let width = rect
+                    .get_property(&QName::new(Namespace::public(), "width").into(), activation)?
+                    .coerce_to_number(activation)?;
+                let height = rect
+                    .get_property(
+                        &QName::new(Namespace::public(), "height").into(),
+                        activation,
+                    )?
+                    .coerce_to_number(activation)?;
+                BoundingBox {
+                    x_min: Twips::from_pixels(x),
+                    y_min: Twips::from_pixels(y),
+                    x_max: Twips::from_pixels(x + width),
+                    y_max: Twips::from_pixels(y + height),
+                    valid: true,
+                }
+            }
+            // The whole sprite is printed when no area is given.
+            _ => target.bounds(),
+        };
+
+        let print_as_bitmap = match args.get(2) {
+            Some(Value::Object(options)) => options
+                .get_property(
+                    &QName::new(Namespace::public(), "printAsBitmap").into(),
+                    activation,
+                )?
+                .coerce_to_boolean(),
+            _ => false,
+        };
+
+        let frame_num = args
+            .get(3)
+            .cloned()
+            .unwrap_or_else(|| 0.into())
+            .coerce_to_i32(activation)?;
+        // Printing another frame would mean seeking the target there and
+        // back without running its scripts, so the current frame is always
+        // printed.
+        let current_frame = target.as_movie_clip().map(|clip| clip.current_frame());
+        if frame_num != 0 && current_frame.map(i32::from) != Some(frame_num) {
+            log::warn!(
+                "PrintJob.addPage: frameNum {} is not yet implemented, printing the current frame instead",
+                frame_num
+            );
+        }
+
+        if !PrintManager::add_page(
+            &mut activation.context,
+            job_id,
+            &PrintPageRequest {
+                target,
+                area,
+                print_as_bitmap,
+            },
+        ) {
+            return Err("Error #2057: The page could not be added to the print job.".into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `PrintJob.send`.
+pub fn send<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let job_id = get_internal(activation, this, "jobId")?.coerce_to_u32(activation)?;
+        if PrintManager::send(&mut activation.context, job_id) {
+            set_internal(activation, this, "jobId", 0.into())?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `PrintJob.orientation`.
+pub fn orientation<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        return get_internal(activation, this, "orientation");
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `PrintJob.pageHeight`.
+pub fn page_height<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        return get_internal(activation, this, "pageHeight");
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `PrintJob.pageWidth`.
+pub fn page_width<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        return get_internal(activation, this, "pageWidth");
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `PrintJob.paperHeight`.
+pub fn paper_height<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        return get_internal(activation, this, "paperHeight");
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `PrintJob.paperWidth`.
+pub fn paper_width<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        return get_internal(activation, this, "paperWidth");
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `PrintJob.isSupported`.
+pub fn is_supported<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(true.into())
+}
+
+/// Construct `PrintJob`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.printing"), "PrintJob"),
+        Some(QName::new(Namespace::package("flash.events"), "EventDispatcher").into()),
+        Method::from_builtin(instance_init, "<PrintJob instance initializer>", mc),
+        Method::from_builtin(class_init, "<PrintJob class initializer>", mc),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED);
+
+    const PUBLIC_INSTANCE_METHODS: &[(&str, NativeMethodImpl)] =
+        &[("start", start), ("addPage", add_page), ("send", send)];
+    write.define_public_builtin_instance_methods(mc, PUBLIC_INSTANCE_METHODS);
+
+    const PUBLIC_INSTANCE_PROPERTIES: &[(
+        &str,
+        Option<NativeMethodImpl>,
+        Option<NativeMethodImpl>,
+    )] = &[
+        ("orientation", Some(orientation), None),
+        ("pageHeight", Some(page_height), None),
+        ("pageWidth", Some(page_width), None),
+        ("paperHeight", Some(paper_height), None),
+        ("paperWidth", Some(paper_width), None),
+    ];
+    write.define_public_builtin_instance_properties(mc, PUBLIC_INSTANCE_PROPERTIES);
+
+    const PUBLIC_CLASS_PROPERTIES: &[(&str, Option<NativeMethodImpl>, Option<NativeMethodImpl>)] =
+        &[("isSupported", Some(is_supported), None)];
+    write.define_public_builtin_class_properties(mc, PUBLIC_CLASS_PROPERTIES);
+
+    const PRIVATE_INSTANCE_SLOTS: &[(&str, &str, &str, &str)] = &[
+        (NS_RUFFLE_INTERNAL, "jobId", "", "uint"),
+        (NS_RUFFLE_INTERNAL, "orientation", "", "String"),
+        (NS_RUFFLE_INTERNAL, "pageHeight", "", "int"),
+        (NS_RUFFLE_INTERNAL, "pageWidth", "", "int"),
+        (NS_RUFFLE_INTERNAL, "paperHeight", "", "int"),
+        (NS_RUFFLE_INTERNAL, "paperWidth", "", "int"),
+    ];
+    write.define_private_slot_instance_traits(PRIVATE_INSTANCE_SLOTS);
+
+    class
+}
@@ -103,6 +103,9 @@ pub fn has_definition<'gc>(
     Ok(Value::Undefined)
 }
 
+/// The smallest `ByteArray` that can be used as domain memory.
+const MIN_DOMAIN_MEMORY_LENGTH: usize = 1024;
+
 /// `domainMemory` property setter
 pub fn set_domain_memory<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
@@ -111,6 +114,11 @@ pub fn set_domain_memory<'gc>(
 ) -> Result<Value<'gc>, Error> {
     if let Some(Value::Object(arg)) = args.get(0) {
         if let Some(bytearray_obj) = arg.as_bytearray_object() {
+            let len = arg.as_bytearray().map(|bytes| bytes.len()).unwrap_or(0);
+            if len < MIN_DOMAIN_MEMORY_LENGTH {
+                return Err("RangeError: Error #1506: The specified range is invalid.".into());
+            }
+
             if let Some(appdomain) = this.and_then(|this| this.as_application_domain()) {
                 appdomain.set_domain_memory(activation.context.gc_context, bytearray_obj);
             }
@@ -36,6 +36,24 @@ pub fn gc<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implements `System.setClipboard`
+pub fn set_clipboard<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let new_content = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)?
+        .to_string();
+
+    activation.context.ui.set_clipboard_content(new_content);
+
+    Ok(Value::Undefined)
+}
+
 /// Construct `System`'s class.
 pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
     let class = Class::new(
@@ -48,7 +66,8 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
 
     let mut write = class.write(mc);
 
-    const PUBLIC_CLASS_METHODS: &[(&str, NativeMethodImpl)] = &[("gc", gc)];
+    const PUBLIC_CLASS_METHODS: &[(&str, NativeMethodImpl)] =
+        &[("gc", gc), ("setClipboard", set_clipboard)];
     write.define_public_builtin_class_methods(mc, PUBLIC_CLASS_METHODS);
 
     class
@@ -1,8 +1,16 @@
 //! `flash.text` namespace
 
+pub mod antialiastype;
+pub mod csmsettings;
+pub mod engine;
 pub mod font;
+pub mod gridfittype;
+pub mod stylesheet;
+pub mod textcolortype;
 pub mod textfield;
 pub mod textfieldautosize;
 pub mod textfieldtype;
 pub mod textformat;
 pub mod textformatalign;
+pub mod textlinemetrics;
+pub mod textrenderer;
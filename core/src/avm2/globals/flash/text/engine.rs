@@ -0,0 +1,11 @@
+//! `flash.text.engine` namespace
+
+pub mod contentelement;
+pub mod elementformat;
+pub mod fontdescription;
+pub mod fontlookup;
+pub mod fontposture;
+pub mod fontweight;
+pub mod textblock;
+pub mod textelement;
+pub mod textline;
@@ -0,0 +1,131 @@
+//! `flash.text.engine.ElementFormat` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// The numeric properties of an `ElementFormat`, along with their constructor
+/// argument positions and default values.
+const NUMBER_PROPERTIES: [(&str, usize, f64); 5] = [
+    ("fontSize", 1, 12.0),
+    ("alpha", 3, 1.0),
+    ("baselineShift", 7, 0.0),
+    ("trackingRight", 9, 0.0),
+    ("trackingLeft", 10, 0.0),
+];
+
+/// Implements `flash.text.engine.ElementFormat`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        activation.super_init(this, &[])?;
+
+        let font_description = match args.get(0) {
+            Some(Value::Object(font_description)) => *font_description,
+            _ => activation
+                .context
+                .avm2
+                .classes()
+                .fontdescription
+                .construct(activation, &[])?,
+        };
+        this.set_property(
+            &QName::new(Namespace::public(), "fontDescription").into(),
+            font_description.into(),
+            activation,
+        )?;
+
+        for (name, index, default) in NUMBER_PROPERTIES {
+            let value = match args.get(index) {
+                Some(Value::Undefined) | None => default,
+                Some(value) => value.coerce_to_number(activation)?,
+            };
+            this.set_property(
+                &QName::new(Namespace::public(), name).into(),
+                value.into(),
+                activation,
+            )?;
+        }
+
+        let color = args
+            .get(2)
+            .cloned()
+            .unwrap_or_else(|| 0.into())
+            .coerce_to_u32(activation)?;
+        this.set_property(
+            &QName::new(Namespace::public(), "color").into(),
+            color.into(),
+            activation,
+        )?;
+
+        let kerning = match args.get(8) {
+            Some(Value::Undefined) | None => "on".into(),
+            Some(value) => value.coerce_to_string(activation)?,
+        };
+        this.set_property(
+            &QName::new(Namespace::public(), "kerning").into(),
+            kerning.into(),
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.text.engine.ElementFormat`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Construct `ElementFormat`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.text.engine"), "ElementFormat"),
+        Some(QName::new(Namespace::public(), "Object").into()),
+        Method::from_builtin(instance_init, "<ElementFormat instance initializer>", mc),
+        Method::from_builtin(class_init, "<ElementFormat class initializer>", mc),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::FINAL | ClassAttributes::SEALED);
+
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::public(), "fontDescription"),
+        QName::new(Namespace::package("flash.text.engine"), "FontDescription").into(),
+        None,
+    ));
+    for (name, _, _) in NUMBER_PROPERTIES {
+        write.define_instance_trait(Trait::from_slot(
+            QName::new(Namespace::public(), name),
+            QName::new(Namespace::public(), "Number").into(),
+            None,
+        ));
+    }
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::public(), "color"),
+        QName::new(Namespace::public(), "uint").into(),
+        None,
+    ));
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::public(), "kerning"),
+        QName::new(Namespace::public(), "String").into(),
+        None,
+    ));
+
+    class
+}
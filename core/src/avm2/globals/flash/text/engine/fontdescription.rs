@@ -0,0 +1,81 @@
+//! `flash.text.engine.FontDescription` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// The properties of a `FontDescription` and their default values, in
+/// constructor argument order.
+const PROPERTIES: [(&str, &str); 6] = [
+    ("fontName", "_serif"),
+    ("fontWeight", "normal"),
+    ("fontPosture", "normal"),
+    ("fontLookup", "device"),
+    ("renderingMode", "cff"),
+    ("cffHinting", "horizontalStem"),
+];
+
+/// Implements `flash.text.engine.FontDescription`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        activation.super_init(this, &[])?;
+
+        for (i, (name, default)) in PROPERTIES.iter().enumerate() {
+            let value = match args.get(i) {
+                Some(Value::Undefined) | None => (*default).into(),
+                Some(value) => value.coerce_to_string(activation)?,
+            };
+            this.set_property(
+                &QName::new(Namespace::public(), *name).into(),
+                value.into(),
+                activation,
+            )?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.text.engine.FontDescription`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Construct `FontDescription`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.text.engine"), "FontDescription"),
+        Some(QName::new(Namespace::public(), "Object").into()),
+        Method::from_builtin(instance_init, "<FontDescription instance initializer>", mc),
+        Method::from_builtin(class_init, "<FontDescription class initializer>", mc),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::FINAL | ClassAttributes::SEALED);
+
+    for (name, _) in PROPERTIES {
+        write.define_instance_trait(Trait::from_slot(
+            QName::new(Namespace::public(), name),
+            QName::new(Namespace::public(), "String").into(),
+            None,
+        ));
+    }
+
+    class
+}
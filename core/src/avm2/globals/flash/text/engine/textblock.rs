@@ -0,0 +1,351 @@
+//! `flash.text.engine.TextBlock` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::method::{Method, NativeMethodImpl};
+use crate::avm2::names::{Multiname, Namespace, QName};
+use crate::avm2::object::{Object, StageObject, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::display_object::{AutoSizeMode, DisplayObject, EditText, TDisplayObject};
+use crate::font::FontType;
+use crate::html::TextFormat;
+use crate::string::WStr;
+use gc_arena::{GcCell, MutationContext};
+
+/// The widest line that may be requested from `createTextLine`.
+const MAX_LINE_WIDTH: f64 = 1000000.0;
+
+/// Implements `flash.text.engine.TextBlock`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        activation.super_init(this, &[])?;
+
+        let content = args.get(0).cloned().unwrap_or(Value::Null);
+        this.set_property(
+            &QName::new(Namespace::public(), "content").into(),
+            content,
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.text.engine.TextBlock`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Read a public property of an object, if the object is not null.
+fn property<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    object: Option<Object<'gc>>,
+    name: &'static str,
+) -> Result<Value<'gc>, Error> {
+    match object {
+        Some(object) => {
+            object.get_property(&QName::new(Namespace::public(), name).into(), activation)
+        }
+        None => Ok(Value::Undefined),
+    }
+}
+
+/// Convert an `ElementFormat` into the text format and font type used to lay
+/// out a text line.
+fn element_format_to_text_format<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    element_format: Option<Object<'gc>>,
+) -> Result<(TextFormat, FontType), Error> {
+    let font_description = property(activation, element_format, "fontDescription")?;
+    let font_description = font_description.as_object();
+
+    let mut format = TextFormat::default();
+    let mut font_type = FontType::Device;
+
+    if let Value::String(font_name) = property(activation, font_description, "fontName")? {
+        format.font = Some(font_name.as_wstr().into());
+    }
+    if let Value::String(weight) = property(activation, font_description, "fontWeight")? {
+        format.bold = Some(&*weight == WStr::from_units(b"bold"));
+    }
+    if let Value::String(posture) = property(activation, font_description, "fontPosture")? {
+        format.italic = Some(&*posture == WStr::from_units(b"italic"));
+    }
+    if let Value::String(lookup) = property(activation, font_description, "fontLookup")? {
+        if &*lookup == WStr::from_units(b"embeddedCFF") {
+            font_type = FontType::EmbeddedCFF;
+        }
+    }
+
+    if element_format.is_some() {
+        format.size =
+            Some(property(activation, element_format, "fontSize")?.coerce_to_number(activation)?);
+        format.color = Some(swf::Color::from_rgb(
+            property(activation, element_format, "color")?.coerce_to_u32(activation)?,
+            255,
+        ));
+        format.letter_spacing = Some(
+            property(activation, element_format, "trackingRight")?.coerce_to_number(activation)?,
+        );
+        let kerning =
+            property(activation, element_format, "kerning")?.coerce_to_string(activation)?;
+        format.kerning = Some(&*kerning != WStr::from_units(b"off"));
+    }
+
+    Ok((format, font_type))
+}
+
+/// Implements `TextBlock.createTextLine`.
+///
+/// Each line is laid out as a single-line text field. Only the text of
+/// `TextElement` content is supported.
+pub fn create_text_line<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let mut this = match this {
+        Some(this) => this,
+        None => return Ok(Value::Undefined),
+    };
+
+    let content = this
+        .get_property(
+            &QName::new(Namespace::public(), "content").into(),
+            activation,
+        )?
+        .as_object();
+    let text = match property(activation, content, "text")? {
+        Value::Undefined | Value::Null => "".into(),
+        text => text.coerce_to_string(activation)?,
+    };
+
+    let previous_line = args.get(0).and_then(|line| line.as_object());
+    let begin = if previous_line.is_some() {
+        let begin_index = property(activation, previous_line, "textBlockBeginIndex")?
+            .coerce_to_u32(activation)?;
+        let length =
+            property(activation, previous_line, "rawTextLength")?.coerce_to_u32(activation)?;
+        (begin_index + length) as usize
+    } else {
+        0
+    };
+
+    let result_name = QName::new(Namespace::public(), "textLineCreationResult").into();
+    if begin >= text.len() {
+        this.set_property(&result_name, "complete".into(), activation)?;
+        return Ok(Value::Null);
+    }
+
+    let width = args
+        .get(1)
+        .cloned()
+        .unwrap_or_else(|| MAX_LINE_WIDTH.into())
+        .coerce_to_number(activation)?;
+    if !(0.0..=MAX_LINE_WIDTH).contains(&width) {
+        return Err("ArgumentError: Error #2004: One of the parameters is invalid.".into());
+    }
+
+    let element_format = property(activation, content, "elementFormat")?.as_object();
+    let (format, font_type) = element_format_to_text_format(activation, element_format)?;
+
+    // Lay out the remaining text with word wrapping to find where this line
+    // ends, then shrink the field down to just that line.
+    let movie = activation.context.swf.clone();
+    let edit_text = EditText::new(&mut activation.context, movie, 0.0, 0.0, width + 4.0, 100.0);
+    let format = format.mix_with(edit_text.new_text_format());
+    edit_text.set_new_text_format(format, &mut activation.context);
+    edit_text.set_font_type(&mut activation.context, font_type);
+    edit_text.set_selectable(false, &mut activation.context);
+    edit_text.set_word_wrap(true, &mut activation.context);
+    edit_text.set_text(&text[begin..], &mut activation.context)?;
+
+    let length = edit_text
+        .line_length(0)
+        .unwrap_or(text.len() - begin)
+        .max(1);
+    edit_text.set_word_wrap(false, &mut activation.context);
+    edit_text.set_autosize(AutoSizeMode::Left, &mut activation.context);
+    edit_text.set_text(&text[begin..begin + length], &mut activation.context)?;
+    edit_text.set_origin_to_baseline(&mut activation.context);
+
+    let alpha = property(activation, element_format, "alpha")?;
+    if !matches!(alpha, Value::Undefined) {
+        let alpha = alpha.coerce_to_number(activation)?;
+        edit_text.set_alpha(activation.context.gc_context, alpha);
+    }
+
+    let mut display_object: DisplayObject<'gc> = edit_text.into();
+    let class = activation.context.avm2.classes().textline;
+    let mut line: Object<'gc> =
+        StageObject::for_display_object_childless(activation, display_object, class)?.into();
+    display_object.set_object2(activation.context.gc_context, line);
+
+    let line_properties: [(&str, Value<'gc>); 5] = [
+        ("textBlock", this.into()),
+        (
+            "previousLine",
+            previous_line.map_or(Value::Null, Value::from),
+        ),
+        ("textBlockBeginIndex", begin.into()),
+        ("rawTextLength", length.into()),
+        ("specifiedWidth", width.into()),
+    ];
+    for (name, value) in line_properties {
+        line.set_property(
+            &QName::new(Namespace::public(), name).into(),
+            value,
+            activation,
+        )?;
+    }
+
+    if let Some(mut previous_line) = previous_line {
+        previous_line.set_property(
+            &QName::new(Namespace::public(), "nextLine").into(),
+            line.into(),
+            activation,
+        )?;
+    } else {
+        this.set_property(
+            &QName::new(Namespace::public(), "firstLine").into(),
+            line.into(),
+            activation,
+        )?;
+    }
+    this.set_property(
+        &QName::new(Namespace::public(), "lastLine").into(),
+        line.into(),
+        activation,
+    )?;
+    this.set_property(&result_name, "success".into(), activation)?;
+
+    Ok(line.into())
+}
+
+/// Implements `TextBlock.releaseLines`.
+pub fn release_lines<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let mut this = match this {
+        Some(this) => this,
+        None => return Ok(Value::Undefined),
+    };
+
+    let first_line = args.get(0).and_then(|line| line.as_object());
+    let last_line = args.get(1).and_then(|line| line.as_object());
+    let (first_line, last_line) = match (first_line, last_line) {
+        (Some(first_line), Some(last_line)) => (first_line, last_line),
+        _ => return Ok(Value::Undefined),
+    };
+
+    let previous_line = property(activation, Some(first_line), "previousLine")?;
+    let next_line = property(activation, Some(last_line), "nextLine")?;
+
+    // Detach every line in the range from this block.
+    let mut line = Some(first_line);
+    while let Some(mut current) = line {
+        line = if Object::ptr_eq(current, last_line) {
+            None
+        } else {
+            property(activation, Some(current), "nextLine")?.as_object()
+        };
+        for name in ["textBlock", "previousLine", "nextLine"] {
+            current.set_property(
+                &QName::new(Namespace::public(), name).into(),
+                Value::Null,
+                activation,
+            )?;
+        }
+        current.set_property(
+            &QName::new(Namespace::public(), "validity").into(),
+            "invalid".into(),
+            activation,
+        )?;
+    }
+
+    match previous_line.as_object() {
+        Some(mut previous_line) => previous_line.set_property(
+            &QName::new(Namespace::public(), "nextLine").into(),
+            next_line,
+            activation,
+        )?,
+        None => this.set_property(
+            &QName::new(Namespace::public(), "firstLine").into(),
+            next_line,
+            activation,
+        )?,
+    }
+    match next_line.as_object() {
+        Some(mut next_line) => next_line.set_property(
+            &QName::new(Namespace::public(), "previousLine").into(),
+            previous_line,
+            activation,
+        )?,
+        None => this.set_property(
+            &QName::new(Namespace::public(), "lastLine").into(),
+            previous_line,
+            activation,
+        )?,
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `TextBlock`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.text.engine"), "TextBlock"),
+        Some(QName::new(Namespace::public(), "Object").into()),
+        Method::from_builtin(instance_init, "<TextBlock instance initializer>", mc),
+        Method::from_builtin(class_init, "<TextBlock class initializer>", mc),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::FINAL | ClassAttributes::SEALED);
+
+    const PUBLIC_INSTANCE_METHODS: &[(&str, NativeMethodImpl)] = &[
+        ("createTextLine", create_text_line),
+        ("releaseLines", release_lines),
+    ];
+    write.define_public_builtin_instance_methods(mc, PUBLIC_INSTANCE_METHODS);
+
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::public(), "content"),
+        QName::new(Namespace::package("flash.text.engine"), "ContentElement").into(),
+        None,
+    ));
+    const TEXT_LINE_SLOTS: &[&str] = &["firstLine", "lastLine"];
+    for name in TEXT_LINE_SLOTS {
+        write.define_instance_trait(Trait::from_slot(
+            QName::new(Namespace::public(), *name),
+            QName::new(Namespace::package("flash.text.engine"), "TextLine").into(),
+            None,
+        ));
+    }
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::public(), "textLineCreationResult"),
+        QName::new(Namespace::public(), "String").into(),
+        None,
+    ));
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::public(), "userData"),
+        Multiname::any(),
+        None,
+    ));
+
+    class
+}
@@ -0,0 +1,179 @@
+//! `flash.text.engine.TextLine` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::method::{Method, NativeMethodImpl};
+use crate::avm2::names::{Multiname, Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::display_object::{LineMetrics, TDisplayObject};
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.text.engine.TextLine`'s instance constructor.
+pub fn instance_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Err("ArgumentError: Error #2012: TextLine class cannot be instantiated.".into())
+}
+
+/// Implements `flash.text.engine.TextLine`'s native instance constructor.
+pub fn native_instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        activation.super_init(this, &[])?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.text.engine.TextLine`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Retrieve the metrics of a text line.
+fn line_metrics(this: Option<Object<'_>>) -> Option<LineMetrics> {
+    this.and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+        .and_then(|this| this.line_metrics(0))
+}
+
+/// Implements `TextLine.ascent`.
+pub fn ascent<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(line_metrics(this)
+        .map_or(0.0, |metrics| metrics.ascent.to_pixels())
+        .into())
+}
+
+/// Implements `TextLine.descent`.
+pub fn descent<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(line_metrics(this)
+        .map_or(0.0, |metrics| metrics.descent.to_pixels())
+        .into())
+}
+
+/// Implements `TextLine.textWidth` and `TextLine.unjustifiedTextWidth`.
+pub fn text_width<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(line_metrics(this)
+        .map_or(0.0, |metrics| metrics.width.to_pixels())
+        .into())
+}
+
+/// Implements `TextLine.textHeight`.
+pub fn text_height<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(line_metrics(this)
+        .map_or(0.0, |metrics| {
+            (metrics.ascent + metrics.descent).to_pixels()
+        })
+        .into())
+}
+
+/// Construct `TextLine`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.text.engine"), "TextLine"),
+        Some(
+            QName::new(
+                Namespace::package("flash.display"),
+                "DisplayObjectContainer",
+            )
+            .into(),
+        ),
+        Method::from_builtin(instance_init, "<TextLine instance initializer>", mc),
+        Method::from_builtin(class_init, "<TextLine class initializer>", mc),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::FINAL | ClassAttributes::SEALED);
+    write.set_native_instance_init(Method::from_builtin(
+        native_instance_init,
+        "<TextLine native instance initializer>",
+        mc,
+    ));
+
+    const PUBLIC_INSTANCE_PROPERTIES: &[(
+        &str,
+        Option<NativeMethodImpl>,
+        Option<NativeMethodImpl>,
+    )] = &[
+        ("ascent", Some(ascent), None),
+        ("descent", Some(descent), None),
+        ("textHeight", Some(text_height), None),
+        ("textWidth", Some(text_width), None),
+        ("unjustifiedTextWidth", Some(text_width), None),
+    ];
+    write.define_public_builtin_instance_properties(mc, PUBLIC_INSTANCE_PROPERTIES);
+
+    const TEXT_LINE_SLOTS: &[&str] = &["nextLine", "previousLine"];
+    for name in TEXT_LINE_SLOTS {
+        write.define_instance_trait(Trait::from_slot(
+            QName::new(Namespace::public(), *name),
+            QName::new(Namespace::package("flash.text.engine"), "TextLine").into(),
+            None,
+        ));
+    }
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::public(), "textBlock"),
+        QName::new(Namespace::package("flash.text.engine"), "TextBlock").into(),
+        None,
+    ));
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::public(), "textBlockBeginIndex"),
+        QName::new(Namespace::public(), "int").into(),
+        None,
+    ));
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::public(), "rawTextLength"),
+        QName::new(Namespace::public(), "int").into(),
+        None,
+    ));
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::public(), "specifiedWidth"),
+        QName::new(Namespace::public(), "Number").into(),
+        None,
+    ));
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::public(), "validity"),
+        QName::new(Namespace::public(), "String").into(),
+        Some("valid".into()),
+    ));
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::public(), "userData"),
+        Multiname::any(),
+        None,
+    ));
+
+    const CONSTANTS: &[(&str, i32)] = &[("MAX_LINE_WIDTH", 1000000)];
+    write.define_public_constant_int_class_traits(CONSTANTS);
+
+    class
+}
@@ -1,10 +1,13 @@
 //! `flash.text.Font` builtin/prototype
 
 use crate::avm2::activation::Activation;
+use crate::avm2::array::ArrayStorage;
 use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::globals::NS_RUFFLE_INTERNAL;
 use crate::avm2::method::{Method, NativeMethodImpl};
 use crate::avm2::names::{Namespace, QName};
-use crate::avm2::object::{Object, TObject};
+use crate::avm2::object::{ArrayObject, Object, TObject};
+use crate::avm2::traits::Trait;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
 use crate::character::Character;
@@ -33,6 +36,24 @@ pub fn class_init<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Get a property of a device font returned by `Font.enumerateFonts`.
+///
+/// Device fonts are not linked to a font symbol, so their name and style are
+/// kept in private slots instead.
+fn device_font_property<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    name: &'static str,
+) -> Result<Value<'gc>, Error> {
+    match this {
+        Some(this) => this.get_property(
+            &QName::new(Namespace::private(NS_RUFFLE_INTERNAL), name).into(),
+            activation,
+        ),
+        None => Ok(Value::Undefined),
+    }
+}
+
 /// Implements `Font.fontName`
 pub fn font_name<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
@@ -60,7 +81,7 @@ pub fn font_name<'gc>(
         }
     }
 
-    Ok(Value::Undefined)
+    device_font_property(activation, this, "deviceName")
 }
 
 /// Implements `Font.fontStyle`
@@ -91,7 +112,7 @@ pub fn font_style<'gc>(
         }
     }
 
-    Ok(Value::Undefined)
+    device_font_property(activation, this, "deviceStyle")
 }
 
 /// Implements `Font.fontType`
@@ -107,18 +128,23 @@ pub fn font_type<'gc>(
             .avm2_class_registry()
             .class_symbol(this)
     }) {
-        if let Some(Character::Font(_)) = activation
+        if let Some(Character::Font(font)) = activation
             .context
             .library
             .library_for_movie_mut(movie)
             .character_by_id(character_id)
         {
-            //TODO: How do we distinguish between CFF and non-CFF embedded fonts?
+            if font.is_cff() {
+                return Ok("embeddedCFF".into());
+            }
             return Ok("embedded".into());
         }
     }
 
-    Ok(Value::Undefined)
+    match device_font_property(activation, this, "deviceName")? {
+        Value::Undefined | Value::Null => Ok(Value::Undefined),
+        _ => Ok("device".into()),
+    }
 }
 
 /// Implements `Font.hasGlyphs`
@@ -150,16 +176,68 @@ pub fn has_glyphs<'gc>(
         }
     }
 
+    if let Value::String(name) = device_font_property(activation, this, "deviceName")? {
+        let style =
+            device_font_property(activation, this, "deviceStyle")?.coerce_to_string(activation)?;
+        let is_bold = &style == b"bold" || &style == b"boldItalic";
+        let is_italic = &style == b"italic" || &style == b"boldItalic";
+        let my_str = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+
+        let context = &mut activation.context;
+        if let Some(font) = context.library.get_or_load_device_font(
+            context.gc_context,
+            context.renderer,
+            context.font,
+            &name.to_utf8_lossy(),
+            is_bold,
+            is_italic,
+        ) {
+            return Ok(font.has_glyphs_for_str(&my_str).into());
+        }
+    }
+
     Ok(Value::Undefined)
 }
 
-/// Stub `Font.enumerateFonts`
+/// Implements `Font.enumerateFonts`
+///
+/// TODO: List fonts registered with `Font.registerFont`.
 pub fn enumerate_fonts<'gc>(
-    _activation: &mut Activation<'_, 'gc, '_>,
+    activation: &mut Activation<'_, 'gc, '_>,
     _this: Option<Object<'gc>>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error> {
-    Err("Font.enumerateFonts is a stub".into())
+    let enumerate_device_fonts = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_boolean();
+
+    let mut fonts = Vec::new();
+    if enumerate_device_fonts {
+        let font_class = activation.avm2().classes().font;
+        for name in activation.context.font.font_names() {
+            let name = AvmString::new_utf8(activation.context.gc_context, name);
+            let mut font = font_class.construct(activation, &[])?;
+            font.set_property(
+                &QName::new(Namespace::private(NS_RUFFLE_INTERNAL), "deviceName").into(),
+                name.into(),
+                activation,
+            )?;
+            font.set_property(
+                &QName::new(Namespace::private(NS_RUFFLE_INTERNAL), "deviceStyle").into(),
+                "regular".into(),
+                activation,
+            )?;
+            fonts.push(font);
+        }
+    }
+
+    Ok(ArrayObject::from_storage(activation, fonts.into_iter().collect::<ArrayStorage>())?.into())
 }
 
 /// Stub `Font.registerFont`
@@ -205,5 +283,16 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
     ];
     write.define_public_builtin_class_methods(mc, PUBLIC_CLASS_METHODS);
 
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::private(NS_RUFFLE_INTERNAL), "deviceName"),
+        QName::new(Namespace::public(), "String").into(),
+        Some(Value::Null),
+    ));
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::private(NS_RUFFLE_INTERNAL), "deviceStyle"),
+        QName::new(Namespace::public(), "String").into(),
+        Some(Value::Null),
+    ));
+
     class
 }
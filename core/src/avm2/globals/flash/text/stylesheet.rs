@@ -0,0 +1,322 @@
+//! `flash.text.StyleSheet` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::class::Class;
+use crate::avm2::globals::NS_RUFFLE_INTERNAL;
+use crate::avm2::method::{Method, NativeMethodImpl};
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{ArrayObject, Object, ScriptObject, TObject, TextFormatObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::html::{css_to_text_format, parse_css, StyleSheet};
+use crate::string::AvmString;
+use gc_arena::{GcCell, MutationContext};
+
+/// Retrieve the hidden object holding a style sheet's styles, creating it if
+/// it does not exist yet.
+fn styles<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut this: Object<'gc>,
+) -> Result<Object<'gc>, Error> {
+    let name = QName::new(Namespace::private(NS_RUFFLE_INTERNAL), "styles").into();
+    match this.get_property(&name, activation)? {
+        Value::Object(styles) => Ok(styles),
+        _ => {
+            let styles = ScriptObject::object(
+                activation.context.gc_context,
+                activation.avm2().prototypes().object,
+            );
+            this.set_property(&name, styles.into(), activation)?;
+            Ok(styles)
+        }
+    }
+}
+
+/// List the names of the dynamic properties of an object.
+fn property_names<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    object: Object<'gc>,
+) -> Result<Vec<AvmString<'gc>>, Error> {
+    let mut names = Vec::new();
+    let mut index = 0;
+    while let Some(next) = object.get_next_enumerant(index, activation)? {
+        let name = object.get_enumerant_name(next, activation)?;
+        names.push(name.coerce_to_string(activation)?);
+        index = next;
+    }
+
+    Ok(names)
+}
+
+/// Make a shallow copy of a style object.
+fn copy_style<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    style: Object<'gc>,
+) -> Result<Object<'gc>, Error> {
+    let copy = ScriptObject::object(
+        activation.context.gc_context,
+        activation.avm2().prototypes().object,
+    );
+    for name in property_names(activation, style)? {
+        let name = QName::new(Namespace::public(), name).into();
+        let value = style.get_property(&name, activation)?;
+        copy.set_property_local(&name, value, activation)?;
+    }
+
+    Ok(copy)
+}
+
+/// Convert a style object into a text format.
+fn style_to_text_format<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    style: Object<'gc>,
+) -> crate::html::TextFormat {
+    css_to_text_format(|name| {
+        match style.get_property(&QName::new(Namespace::public(), name).into(), activation) {
+            Ok(Value::Undefined) | Ok(Value::Null) | Err(_) => None,
+            Ok(value) => value
+                .coerce_to_string(activation)
+                .ok()
+                .map(|value| value.as_wstr().into()),
+        }
+    })
+}
+
+/// Build the style sheet applied to a text field from a `StyleSheet` object.
+pub fn to_style_sheet<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+) -> Result<StyleSheet, Error> {
+    let styles = styles(activation, this)?;
+    let mut sheet = StyleSheet::new();
+    for name in property_names(activation, styles)? {
+        if let Value::Object(style) =
+            styles.get_property(&QName::new(Namespace::public(), name).into(), activation)?
+        {
+            let format = style_to_text_format(activation, style);
+            sheet.set_style(&name, format);
+        }
+    }
+
+    Ok(sheet)
+}
+
+/// Implements `flash.text.StyleSheet`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        activation.super_init(this, &[])?;
+        styles(activation, this)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.text.StyleSheet`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `StyleSheet.styleNames`.
+pub fn style_names<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let styles = styles(activation, this)?;
+        let names = property_names(activation, styles)?;
+        let storage = ArrayStorage::from_iter(names.into_iter().map(Value::from));
+        return Ok(ArrayObject::from_storage(activation, storage)?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `StyleSheet.clear`.
+pub fn clear<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        this.set_property(
+            &QName::new(Namespace::private(NS_RUFFLE_INTERNAL), "styles").into(),
+            Value::Null,
+            activation,
+        )?;
+        styles(activation, this)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `StyleSheet.getStyle`.
+pub fn get_style<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let name = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+        let name = AvmString::new(
+            activation.context.gc_context,
+            name.as_wstr().to_ascii_lowercase(),
+        );
+        let styles = styles(activation, this)?;
+        let style =
+            styles.get_property(&QName::new(Namespace::public(), name).into(), activation)?;
+        let copy = match style {
+            Value::Object(style) => copy_style(activation, style)?,
+            _ => ScriptObject::object(
+                activation.context.gc_context,
+                activation.avm2().prototypes().object,
+            ),
+        };
+        return Ok(copy.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `StyleSheet.setStyle`.
+pub fn set_style<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let name = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+        let name = AvmString::new(
+            activation.context.gc_context,
+            name.as_wstr().to_ascii_lowercase(),
+        );
+        let name = QName::new(Namespace::public(), name).into();
+        let styles = styles(activation, this)?;
+        match args.get(1) {
+            Some(Value::Object(style)) => {
+                let copy = copy_style(activation, *style)?;
+                styles.set_property_local(&name, copy.into(), activation)?;
+            }
+            _ => {
+                styles.delete_property_local(activation, &name)?;
+            }
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `StyleSheet.parseCSS`.
+pub fn parse_css_method<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let css = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+        let styles = styles(activation, this)?;
+        for (selector, declarations) in parse_css(&css) {
+            let selector = AvmString::new(activation.context.gc_context, selector);
+            let selector = QName::new(Namespace::public(), selector).into();
+
+            // Declarations are merged into any existing style for the selector.
+            let style = match styles.get_property(&selector, activation)? {
+                Value::Object(style) => style,
+                _ => {
+                    let style = ScriptObject::object(
+                        activation.context.gc_context,
+                        activation.avm2().prototypes().object,
+                    );
+                    styles.set_property_local(&selector, style.into(), activation)?;
+                    style
+                }
+            };
+
+            for (name, value) in declarations {
+                let name = AvmString::new(activation.context.gc_context, name);
+                let value = AvmString::new(activation.context.gc_context, value);
+                style.set_property_local(
+                    &QName::new(Namespace::public(), name).into(),
+                    value.into(),
+                    activation,
+                )?;
+            }
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `StyleSheet.transform`.
+pub fn transform<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    match args.get(0) {
+        Some(Value::Object(style)) => {
+            let format = style_to_text_format(activation, *style);
+            Ok(TextFormatObject::from_text_format(activation, format)?.into())
+        }
+        _ => Ok(Value::Null),
+    }
+}
+
+/// Construct `StyleSheet`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.text"), "StyleSheet"),
+        Some(QName::new(Namespace::package("flash.events"), "EventDispatcher").into()),
+        Method::from_builtin(instance_init, "<StyleSheet instance initializer>", mc),
+        Method::from_builtin(class_init, "<StyleSheet class initializer>", mc),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    const PUBLIC_INSTANCE_PROPERTIES: &[(
+        &str,
+        Option<NativeMethodImpl>,
+        Option<NativeMethodImpl>,
+    )] = &[("styleNames", Some(style_names), None)];
+    write.define_public_builtin_instance_properties(mc, PUBLIC_INSTANCE_PROPERTIES);
+
+    const PUBLIC_INSTANCE_METHODS: &[(&str, NativeMethodImpl)] = &[
+        ("clear", clear),
+        ("getStyle", get_style),
+        ("parseCSS", parse_css_method),
+        ("setStyle", set_style),
+        ("transform", transform),
+    ];
+    write.define_public_builtin_instance_methods(mc, PUBLIC_INSTANCE_METHODS);
+
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::private(NS_RUFFLE_INTERNAL), "styles"),
+        QName::new(Namespace::public(), "Object").into(),
+        None,
+    ));
+
+    class
+}
@@ -2,18 +2,22 @@
 
 use crate::avm2::activation::Activation;
 use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::globals::flash::geom::rectangle::create_rectangle;
+use crate::avm2::globals::flash::text::stylesheet::to_style_sheet;
 use crate::avm2::method::{Method, NativeMethodImpl};
 use crate::avm2::names::{Namespace, QName};
 use crate::avm2::object::{Object, TObject, TextFormatObject};
 use crate::avm2::value::Value;
 use crate::avm2::Error;
 use crate::display_object::{AutoSizeMode, EditText, TDisplayObject, TextSelection};
+use crate::font::{AntiAliasType, TextGridFit};
 use crate::html::TextFormat;
 use crate::string::AvmString;
 use crate::tag_utils::SwfMovie;
-use crate::vminterface::AvmType;
+use crate::vminterface::{AvmObject, AvmType};
 use gc_arena::{GcCell, MutationContext};
 use std::sync::Arc;
+use swf::Twips;
 
 /// Implements `flash.text.TextField`'s instance constructor.
 pub fn instance_init<'gc>(
@@ -101,6 +105,56 @@ pub fn set_autosize<'gc>(
     Ok(Value::Undefined)
 }
 
+pub fn anti_alias_type<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        return Ok(match this.render_settings().anti_alias_type {
+            AntiAliasType::Normal => "normal".into(),
+            AntiAliasType::Advanced => "advanced".into(),
+        });
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn set_anti_alias_type<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        let value = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+        let anti_alias_type = if &value == b"normal" {
+            AntiAliasType::Normal
+        } else if &value == b"advanced" {
+            AntiAliasType::Advanced
+        } else {
+            return Err(
+                "ArgumentError: Parameter antiAliasType must be one of the accepted values.".into(),
+            );
+        };
+
+        let mut settings = this.render_settings();
+        settings.anti_alias_type = anti_alias_type;
+        this.set_render_settings(activation.context.gc_context, settings);
+    }
+
+    Ok(Value::Undefined)
+}
+
 pub fn background_color<'gc>(
     _activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
@@ -314,6 +368,59 @@ pub fn set_embed_fonts<'gc>(
     Ok(Value::Undefined)
 }
 
+pub fn grid_fit_type<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        return Ok(match this.render_settings().grid_fit {
+            TextGridFit::None => "none".into(),
+            TextGridFit::Pixel => "pixel".into(),
+            TextGridFit::SubPixel => "subpixel".into(),
+        });
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn set_grid_fit_type<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        let value = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+        let grid_fit = if &value == b"none" {
+            TextGridFit::None
+        } else if &value == b"pixel" {
+            TextGridFit::Pixel
+        } else if &value == b"subpixel" {
+            TextGridFit::SubPixel
+        } else {
+            return Err(
+                "ArgumentError: Parameter gridFitType must be one of the accepted values.".into(),
+            );
+        };
+
+        let mut settings = this.render_settings();
+        settings.grid_fit = grid_fit;
+        this.set_render_settings(activation.context.gc_context, settings);
+    }
+
+    Ok(Value::Undefined)
+}
+
 pub fn html_text<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
@@ -350,6 +457,73 @@ pub fn set_html_text<'gc>(
     Ok(Value::Undefined)
 }
 
+pub fn style_sheet<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        return Ok(this
+            .style_sheet()
+            .and_then(|style_sheet| style_sheet.as_avm2_object().ok())
+            .map(Value::from)
+            .unwrap_or(Value::Null));
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn set_style_sheet<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        let style_sheet = match args.get(0).cloned().unwrap_or(Value::Undefined) {
+            Value::Undefined | Value::Null => None,
+            value => {
+                let object = value.coerce_to_object(activation)?;
+                let sheet = to_style_sheet(activation, object)?;
+                Some((AvmObject::Avm2(object), sheet))
+            }
+        };
+
+        this.set_style_sheet(style_sheet, &mut activation.context)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_image_reference<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        let id = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+
+        return Ok(this
+            .image_by_id(&id)
+            .map(|image| image.object2())
+            .unwrap_or(Value::Null));
+    }
+
+    Ok(Value::Undefined)
+}
+
 pub fn length<'gc>(
     _activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
@@ -401,6 +575,80 @@ pub fn set_multiline<'gc>(
     Ok(Value::Undefined)
 }
 
+pub fn max_chars<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        return Ok(this.max_chars().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn set_max_chars<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        let max_chars = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_i32(activation)?;
+
+        this.set_max_chars(max_chars, &mut activation.context);
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn restrict<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        return match this.restrict() {
+            Some(restrict) => Ok(AvmString::new(activation.context.gc_context, restrict).into()),
+            None => Ok(Value::Null),
+        };
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn set_restrict<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        let restrict = match args.get(0).cloned().unwrap_or(Value::Undefined) {
+            Value::Undefined | Value::Null => None,
+            value => Some(value.coerce_to_string(activation)?),
+        };
+
+        this.set_restrict(restrict.as_deref(), &mut activation.context);
+    }
+
+    Ok(Value::Undefined)
+}
+
 pub fn selectable<'gc>(
     _activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
@@ -437,6 +685,44 @@ pub fn set_selectable<'gc>(
     Ok(Value::Undefined)
 }
 
+pub fn sharpness<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        return Ok(this.render_settings().sharpness.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn set_sharpness<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        let value = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_number(activation)?;
+
+        let mut settings = this.render_settings();
+        settings.sharpness = (value as f32).clamp(-400.0, 400.0);
+        this.set_render_settings(activation.context.gc_context, settings);
+    }
+
+    Ok(Value::Undefined)
+}
+
 pub fn text<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
@@ -555,6 +841,44 @@ pub fn text_width<'gc>(
     Ok(Value::Undefined)
 }
 
+pub fn thickness<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        return Ok(this.render_settings().thickness.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn set_thickness<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        let value = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_number(activation)?;
+
+        let mut settings = this.render_settings();
+        settings.thickness = (value as f32).clamp(-200.0, 200.0);
+        this.set_render_settings(activation.context.gc_context, settings);
+    }
+
+    Ok(Value::Undefined)
+}
+
 pub fn get_type<'gc>(
     _activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
@@ -846,6 +1170,283 @@ pub fn set_text_format<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Coerce a line or character index argument, rejecting negative values.
+fn index_arg<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+) -> Result<Option<usize>, Error> {
+    let index = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_i32(activation)?;
+    Ok(usize::try_from(index).ok())
+}
+
+/// Coerce a pair of `x` and `y` arguments into a local position.
+fn point_args<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+) -> Result<(Twips, Twips), Error> {
+    let x = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_number(activation)?;
+    let y = args
+        .get(1)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_number(activation)?;
+    Ok((Twips::from_pixels(x), Twips::from_pixels(y)))
+}
+
+/// Convert an optional index into the `-1`-for-none convention of the
+/// `TextField` API.
+fn index_value<'gc>(index: Option<usize>) -> Value<'gc> {
+    index.map_or(-1, |index| index as i32).into()
+}
+
+pub fn num_lines<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        return Ok((this.num_lines() as i32).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn scroll_v<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        return Ok((this.scroll() as i32).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn set_scroll_v<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        let scroll = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_number(activation)?;
+
+        this.set_scroll(scroll, &mut activation.context);
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn max_scroll_v<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        return Ok((this.maxscroll() as i32).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn bottom_scroll_v<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        return Ok((this.bottom_scroll() as i32).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_char_boundaries<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        let bounds = index_arg(activation, args)?.and_then(|index| this.char_bounds(index));
+        if let Some(bounds) = bounds {
+            return create_rectangle(
+                activation,
+                (
+                    bounds.offset_x().to_pixels(),
+                    bounds.offset_y().to_pixels(),
+                    bounds.width().to_pixels(),
+                    bounds.height().to_pixels(),
+                ),
+            );
+        }
+
+        return Ok(Value::Null);
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_char_index_at_point<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        let position = point_args(activation, args)?;
+        return Ok(index_value(this.char_index_at_point(position)));
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_line_index_at_point<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        let position = point_args(activation, args)?;
+        return Ok(index_value(this.line_index_at_point(position)));
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_line_index_of_char<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        let line = index_arg(activation, args)?.and_then(|index| this.line_index_of_char(index));
+        return Ok(index_value(line));
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_line_length<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        return match index_arg(activation, args)?.and_then(|line| this.line_length(line)) {
+            Some(length) => Ok((length as i32).into()),
+            None => Err("RangeError: The supplied index is out of bounds".into()),
+        };
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_line_metrics<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        let metrics = match index_arg(activation, args)?.and_then(|line| this.line_metrics(line)) {
+            Some(metrics) => metrics,
+            None => return Err("RangeError: The supplied index is out of bounds".into()),
+        };
+
+        let args = [
+            metrics.x.to_pixels().into(),
+            metrics.width.to_pixels().into(),
+            metrics.height.to_pixels().into(),
+            metrics.ascent.to_pixels().into(),
+            metrics.descent.to_pixels().into(),
+            metrics.leading.to_pixels().into(),
+        ];
+        let class = activation.context.avm2.classes().textlinemetrics;
+        return Ok(class.construct(activation, &args)?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_line_offset<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        return match index_arg(activation, args)?.and_then(|line| this.line_offset(line)) {
+            Some(offset) => Ok((offset as i32).into()),
+            None => Err("RangeError: The supplied index is out of bounds".into()),
+        };
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_line_text<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        return match index_arg(activation, args)?.and_then(|line| this.line_text(line)) {
+            Some(text) => Ok(AvmString::new(activation.context.gc_context, text).into()),
+            None => Err("RangeError: The supplied index is out of bounds".into()),
+        };
+    }
+
+    Ok(Value::Undefined)
+}
+
 /// Construct `TextField`'s class.
 pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
     let class = Class::new(
@@ -865,6 +1466,11 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
         Option<NativeMethodImpl>,
         Option<NativeMethodImpl>,
     )] = &[
+        (
+            "antiAliasType",
+            Some(anti_alias_type),
+            Some(set_anti_alias_type),
+        ),
         ("autoSize", Some(autosize), Some(set_autosize)),
         (
             "backgroundColor",
@@ -873,6 +1479,7 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
         ),
         ("border", Some(border), Some(set_border)),
         ("borderColor", Some(border_color), Some(set_border_color)),
+        ("bottomScrollV", Some(bottom_scroll_v), None),
         (
             "defaultTextFormat",
             Some(default_text_format),
@@ -884,14 +1491,23 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
             Some(set_display_as_password),
         ),
         ("embedFonts", Some(embed_fonts), Some(set_embed_fonts)),
+        ("gridFitType", Some(grid_fit_type), Some(set_grid_fit_type)),
         ("htmlText", Some(html_text), Some(set_html_text)),
+        ("styleSheet", Some(style_sheet), Some(set_style_sheet)),
         ("length", Some(length), None),
+        ("maxChars", Some(max_chars), Some(set_max_chars)),
+        ("maxScrollV", Some(max_scroll_v), None),
         ("multiline", Some(multiline), Some(set_multiline)),
+        ("numLines", Some(num_lines), None),
+        ("restrict", Some(restrict), Some(set_restrict)),
+        ("scrollV", Some(scroll_v), Some(set_scroll_v)),
         ("selectable", Some(selectable), Some(set_selectable)),
+        ("sharpness", Some(sharpness), Some(set_sharpness)),
         ("text", Some(text), Some(set_text)),
         ("textColor", Some(text_color), Some(set_text_color)),
         ("textHeight", Some(text_height), None),
         ("textWidth", Some(text_width), None),
+        ("thickness", Some(thickness), Some(set_thickness)),
         ("type", Some(get_type), Some(set_type)),
         ("wordWrap", Some(word_wrap), Some(set_word_wrap)),
     ];
@@ -899,6 +1515,15 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
 
     const PUBLIC_INSTANCE_METHODS: &[(&str, NativeMethodImpl)] = &[
         ("appendText", append_text),
+        ("getCharBoundaries", get_char_boundaries),
+        ("getCharIndexAtPoint", get_char_index_at_point),
+        ("getImageReference", get_image_reference),
+        ("getLineIndexAtPoint", get_line_index_at_point),
+        ("getLineIndexOfChar", get_line_index_of_char),
+        ("getLineLength", get_line_length),
+        ("getLineMetrics", get_line_metrics),
+        ("getLineOffset", get_line_offset),
+        ("getLineText", get_line_text),
         ("getTextFormat", get_text_format),
         ("replaceSelectedText", replace_selected_text),
         ("replaceText", replace_text),
@@ -0,0 +1,147 @@
+//! `flash.text.TextRenderer` builtin
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::method::{Method, NativeMethodImpl};
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::font::{CsmSettings, FontDescriptor};
+use gc_arena::{GcCell, MutationContext};
+
+fn instance_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Err("The TextRenderer class cannot be constructed.".into())
+}
+
+fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `TextRenderer.setAdvancedAntiAliasingTable`.
+fn set_advanced_anti_aliasing_table<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let font_name = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)?;
+    let font_style = args
+        .get(1)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)?;
+    let color_type = args
+        .get(2)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)?;
+    let table = args
+        .get(3)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_object(activation)?;
+
+    let (is_bold, is_italic) = if &font_style == b"regular" {
+        (false, false)
+    } else if &font_style == b"bold" {
+        (true, false)
+    } else if &font_style == b"italic" {
+        (false, true)
+    } else if &font_style == b"boldItalic" {
+        (true, true)
+    } else {
+        return Err(
+            "ArgumentError: Parameter fontStyle must be one of the accepted values.".into(),
+        );
+    };
+
+    let is_dark = if &color_type == b"dark" {
+        true
+    } else if &color_type == b"light" {
+        false
+    } else {
+        return Err(
+            "ArgumentError: Parameter colorType must be one of the accepted values.".into(),
+        );
+    };
+
+    let entries: Vec<Value<'gc>> = match table.as_array_storage() {
+        Some(storage) => storage
+            .iter()
+            .map(|v| v.unwrap_or(Value::Undefined))
+            .collect(),
+        None => return Err("TypeError: advancedAntiAliasingTable must be an Array.".into()),
+    };
+
+    let mut settings = Vec::with_capacity(entries.len());
+    for entry in entries {
+        let entry = entry.coerce_to_object(activation)?;
+        let mut number = |name| -> Result<f32, Error> {
+            Ok(entry
+                .get_property(&QName::new(Namespace::public(), name).into(), activation)?
+                .coerce_to_number(activation)? as f32)
+        };
+        settings.push(CsmSettings {
+            font_size: number("fontSize")?,
+            inside_cutoff: number("insideCutoff")?,
+            outside_cutoff: number("outsideCutoff")?,
+        });
+    }
+
+    let descriptor = FontDescriptor::from_parts(&font_name.to_utf8_lossy(), is_bold, is_italic);
+    activation
+        .context
+        .library
+        .set_anti_aliasing_table(descriptor, is_dark, settings);
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `TextRenderer`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.text"), "TextRenderer"),
+        Some(QName::new(Namespace::public(), "Object").into()),
+        Method::from_builtin(instance_init, "<TextRenderer instance initializer>", mc),
+        Method::from_builtin(class_init, "<TextRenderer class initializer>", mc),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED | ClassAttributes::FINAL);
+
+    // Ruffle always renders with the same settings, but these are accepted
+    // for compatibility.
+    write.define_class_trait(Trait::from_slot(
+        QName::new(Namespace::public(), "displayMode"),
+        QName::new(Namespace::public(), "String").into(),
+        Some("default".into()),
+    ));
+    write.define_class_trait(Trait::from_slot(
+        QName::new(Namespace::public(), "maxLevel"),
+        QName::new(Namespace::public(), "int").into(),
+        Some(4.into()),
+    ));
+
+    const PUBLIC_CLASS_METHODS: &[(&str, NativeMethodImpl)] = &[(
+        "setAdvancedAntiAliasingTable",
+        set_advanced_anti_aliasing_table,
+    )];
+    write.define_public_builtin_class_methods(mc, PUBLIC_CLASS_METHODS);
+
+    class
+}
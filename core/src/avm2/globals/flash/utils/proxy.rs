@@ -3,7 +3,7 @@ use crate::avm2::class::{Class, ClassAttributes};
 use crate::avm2::globals::flash::utils::NS_FLASH_PROXY;
 use crate::avm2::method::{Method, NativeMethodImpl};
 use crate::avm2::names::{Namespace, QName};
-use crate::avm2::object::{proxy_allocator, Object};
+use crate::avm2::object::{proxy_allocator, Object, TObject};
 use crate::avm2::value::Value;
 use crate::avm2::Error;
 use gc_arena::{GcCell, MutationContext};
@@ -72,12 +72,23 @@ pub fn has_property<'gc>(
 }
 
 /// Implements `Proxy.isAttribute`
+///
+/// Unlike the other methods in `flash_proxy`, this is not a trap: it reports
+/// whether a name passed to one was written as an attribute, e.g. `p.@name`.
 pub fn is_attribute<'gc>(
     _activation: &mut Activation<'_, 'gc, '_>,
     _this: Option<Object<'gc>>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error> {
-    Err("isattribute is not implemented for this Proxy".into())
+    let is_attribute = match args.get(0) {
+        Some(Value::Object(name)) => name
+            .as_qname_object()
+            .map(|qname| qname.is_attribute())
+            .unwrap_or(false),
+        _ => false,
+    };
+
+    Ok(is_attribute.into())
 }
 
 /// Implements `Proxy.getDescendants`
@@ -5,9 +5,12 @@ use crate::avm2::names::Multiname;
 use crate::avm2::object::Object;
 use crate::avm2::script::TranslationUnit;
 use crate::avm2::value::{abc_default_value, Value};
+use crate::avm2::verify::VerifiedMethod;
+use crate::avm2::vtable::VTable;
 use crate::avm2::Error;
 use crate::string::AvmString;
-use gc_arena::{Collect, CollectionContext, Gc, MutationContext};
+use gc_arena::{Collect, CollectionContext, Gc, GcCell, MutationContext};
+use std::cell::RefCell;
 use std::fmt;
 use std::rc::Rc;
 use swf::avm2::types::{
@@ -130,6 +133,16 @@ pub struct BytecodeMethod<'gc> {
     /// A free-standing function corresponds to the `Function` trait type, and
     /// is instantiated with the `newfunction` opcode.
     pub is_function: bool,
+
+    /// The verified and optimized body of this method, with no properties of
+    /// `this` resolved ahead of time.
+    #[collect(require_static)]
+    verified: RefCell<Option<Rc<VerifiedMethod>>>,
+
+    /// The verified and optimized body of this method, with properties of
+    /// `this` resolved against the vtable of the first receiver that allowed
+    /// it.
+    verified_for_receiver: GcCell<'gc, Option<(VTable<'gc>, Rc<VerifiedMethod>)>>,
 }
 
 impl<'gc> BytecodeMethod<'gc> {
@@ -171,6 +184,11 @@ impl<'gc> BytecodeMethod<'gc> {
                             signature,
                             return_type,
                             is_function,
+                            verified: RefCell::new(None),
+                            verified_for_receiver: GcCell::allocate(
+                                activation.context.gc_context,
+                                None,
+                            ),
                         },
                     ));
                 }
@@ -187,6 +205,8 @@ impl<'gc> BytecodeMethod<'gc> {
                 signature,
                 return_type: Multiname::any(),
                 is_function,
+                verified: RefCell::new(None),
+                verified_for_receiver: GcCell::allocate(activation.context.gc_context, None),
             },
         ))
     }
@@ -196,6 +216,49 @@ impl<'gc> BytecodeMethod<'gc> {
         self.txunit.abc()
     }
 
+    /// Get the verified body of this method, if it has been run before.
+    ///
+    /// `receiver` is the vtable properties of `this` were resolved against,
+    /// if any.
+    pub fn verified(&self, receiver: Option<VTable<'gc>>) -> Option<Rc<VerifiedMethod>> {
+        match receiver {
+            Some(receiver) => match &*self.verified_for_receiver.read() {
+                Some((vtable, verified)) if VTable::ptr_eq(*vtable, receiver) => {
+                    Some(verified.clone())
+                }
+                _ => None,
+            },
+            None => self.verified.borrow().clone(),
+        }
+    }
+
+    /// Check if a body resolved against this receiver's vtable can be cached.
+    ///
+    /// Only the body for the first such receiver is kept; calls with any
+    /// other receiver run the receiver-independent body.
+    pub fn can_cache_for_receiver(&self, receiver: VTable<'gc>) -> bool {
+        match &*self.verified_for_receiver.read() {
+            Some((vtable, _)) => VTable::ptr_eq(*vtable, receiver),
+            None => true,
+        }
+    }
+
+    /// Cache the verified body of this method.
+    ///
+    /// `receiver` is the vtable properties of `this` were resolved against,
+    /// if any.
+    pub fn set_verified(
+        &self,
+        mc: MutationContext<'gc, '_>,
+        receiver: Option<VTable<'gc>>,
+        verified: Rc<VerifiedMethod>,
+    ) {
+        match receiver {
+            Some(receiver) => *self.verified_for_receiver.write(mc) = Some((receiver, verified)),
+            None => *self.verified.borrow_mut() = Some(verified),
+        }
+    }
+
     /// Get the underlying translation unit this method was defined in.
     pub fn translation_unit(&self) -> TranslationUnit<'gc> {
         self.txunit
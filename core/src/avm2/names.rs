@@ -284,6 +284,9 @@ pub struct Multiname<'gc> {
     /// The type parameters required to satisfy this multiname. If empty, then
     /// this multiname is satisfied by any type parameters in any amount.
     params: Vec<Multiname<'gc>>,
+
+    /// Whether this multiname names an XML attribute, such as `@name`.
+    is_attribute: bool,
 }
 
 impl<'gc> Multiname<'gc> {
@@ -337,6 +340,7 @@ impl<'gc> Multiname<'gc> {
                 )?,
                 name: Some(name.coerce_to_string(activation)?),
                 params: Vec::new(),
+                is_attribute: matches!(abc_multiname, AbcMultiname::MultinameLA { .. }),
             }),
             _ => Err("Cannot assemble early-bound multinames using from_multiname_late".into()),
         }
@@ -355,7 +359,7 @@ impl<'gc> Multiname<'gc> {
         abc_multiname: &AbcMultiname,
         activation: &mut Activation<'_, 'gc, '_>,
     ) -> Result<Self, Error> {
-        Ok(match abc_multiname {
+        let mut multiname = match abc_multiname {
             AbcMultiname::QName { namespace, name } | AbcMultiname::QNameA { namespace, name } => {
                 Self {
                     ns: vec![Namespace::from_abc_namespace(
@@ -366,6 +370,7 @@ impl<'gc> Multiname<'gc> {
                     name: translation_unit
                         .pool_string_option(name.0, activation.context.gc_context)?,
                     params: Vec::new(),
+                    is_attribute: false,
                 }
             }
             AbcMultiname::RTQName { name } | AbcMultiname::RTQNameA { name } => {
@@ -376,6 +381,7 @@ impl<'gc> Multiname<'gc> {
                     name: translation_unit
                         .pool_string_option(name.0, activation.context.gc_context)?,
                     params: Vec::new(),
+                    is_attribute: false,
                 }
             }
             AbcMultiname::RTQNameL | AbcMultiname::RTQNameLA => {
@@ -386,6 +392,7 @@ impl<'gc> Multiname<'gc> {
                     ns: vec![*ns],
                     name: Some(name),
                     params: Vec::new(),
+                    is_attribute: false,
                 }
             }
             AbcMultiname::Multiname {
@@ -403,6 +410,7 @@ impl<'gc> Multiname<'gc> {
                 )?,
                 name: translation_unit.pool_string_option(name.0, activation.context.gc_context)?,
                 params: Vec::new(),
+                is_attribute: false,
             },
             AbcMultiname::MultinameL { .. } | AbcMultiname::MultinameLA { .. } => {
                 let name = activation.avm2().pop();
@@ -411,7 +419,10 @@ impl<'gc> Multiname<'gc> {
             AbcMultiname::TypeName { .. } => {
                 return Err("Recursive TypeNames are not supported!".into())
             }
-        })
+        };
+        multiname.is_attribute = is_abc_attribute(abc_multiname);
+
+        Ok(multiname)
     }
 
     /// Retrieve a given multiname index from the ABC file, yielding an error
@@ -499,7 +510,8 @@ impl<'gc> Multiname<'gc> {
             .get(actual_index)
             .ok_or_else(|| format!("Unknown multiname constant {}", multiname_index.0).into());
 
-        Ok(match abc_multiname? {
+        let abc_multiname = abc_multiname?;
+        let mut multiname = match abc_multiname {
             AbcMultiname::QName { namespace, name } | AbcMultiname::QNameA { namespace, name } => {
                 Self {
                     ns: vec![Namespace::from_abc_namespace(
@@ -509,6 +521,7 @@ impl<'gc> Multiname<'gc> {
                     )?],
                     name: translation_unit.pool_string_option(name.0, mc)?,
                     params: Vec::new(),
+                    is_attribute: false,
                 }
             }
             AbcMultiname::Multiname {
@@ -522,6 +535,7 @@ impl<'gc> Multiname<'gc> {
                 ns: Self::abc_namespace_set(translation_unit, *namespace_set, mc)?,
                 name: translation_unit.pool_string_option(name.0, mc)?,
                 params: Vec::new(),
+                is_attribute: false,
             },
             AbcMultiname::TypeName {
                 base_type,
@@ -550,7 +564,10 @@ impl<'gc> Multiname<'gc> {
                 base
             }
             _ => return Err(format!("Multiname {} is not static", multiname_index.0).into()),
-        })
+        };
+        multiname.is_attribute = is_abc_attribute(abc_multiname);
+
+        Ok(multiname)
     }
 
     /// Indicates the any type (any name in any namespace).
@@ -559,6 +576,7 @@ impl<'gc> Multiname<'gc> {
             ns: vec![Namespace::Any],
             name: None,
             params: Vec::new(),
+            is_attribute: false,
         }
     }
 
@@ -567,6 +585,7 @@ impl<'gc> Multiname<'gc> {
             ns: vec![Namespace::public()],
             name: Some(name.into()),
             params: Vec::new(),
+            is_attribute: false,
         }
     }
 
@@ -598,18 +617,36 @@ impl<'gc> Multiname<'gc> {
         ns_match && name_match
     }
 
+    /// Indicates if this multiname names an XML attribute.
+    pub fn is_attribute(&self) -> bool {
+        self.is_attribute
+    }
+
     /// List the parameters that the selected class must match.
     pub fn params(&self) -> &[Multiname<'gc>] {
         &self.params[..]
     }
 }
 
+/// Determine if an ABC multiname is one of the attribute forms.
+fn is_abc_attribute(abc_multiname: &AbcMultiname) -> bool {
+    matches!(
+        abc_multiname,
+        AbcMultiname::QNameA { .. }
+            | AbcMultiname::RTQNameA { .. }
+            | AbcMultiname::RTQNameLA
+            | AbcMultiname::MultinameA { .. }
+            | AbcMultiname::MultinameLA { .. }
+    )
+}
+
 impl<'gc> From<QName<'gc>> for Multiname<'gc> {
     fn from(q: QName<'gc>) -> Self {
         Self {
             ns: vec![q.ns],
             name: Some(q.name),
             params: Vec::new(),
+            is_attribute: false,
         }
     }
 }
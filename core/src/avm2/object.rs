@@ -19,7 +19,7 @@ use crate::bitmap::bitmap_data::BitmapData;
 use crate::display_object::DisplayObject;
 use crate::html::TextFormat;
 use crate::string::AvmString;
-use gc_arena::{Collect, GcCell, MutationContext};
+use gc_arena::{Collect, GcCell, GcWeakCell, MutationContext};
 use ruffle_macros::enum_trait_object;
 use std::cell::{Ref, RefMut};
 use std::fmt::Debug;
@@ -409,6 +409,26 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         Ok(self.has_property(name))
     }
 
+    /// Implements the `getdescendants` opcode and the `..` operator.
+    ///
+    /// Only XML-like objects support descendants; everything else errors.
+    fn get_descendants(
+        self,
+        _multiname: &Multiname<'gc>,
+        _activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<Value<'gc>, Error> {
+        let class_name = self
+            .instance_of_class_definition()
+            .map(|c| c.read().name().local_name())
+            .unwrap_or_else(|| "Object".into());
+
+        Err(format!(
+            "TypeError: Error #1016: Descendants operator (..) not supported on type {}",
+            class_name
+        )
+        .into())
+    }
+
     /// Indicates whether or not a property exists on an object.
     fn has_property(self, name: &Multiname<'gc>) -> bool {
         if self.has_own_property(name) {
@@ -1048,3 +1068,68 @@ impl<'gc> Hash for Object<'gc> {
         self.as_ptr().hash(state);
     }
 }
+
+/// Declares `WeakObject`, with a variant for every kind of `Object`.
+macro_rules! weak_object {
+    ($($variant:ident($data:ty)),* $(,)?) => {
+        /// A weak reference to an object.
+        ///
+        /// It doesn't keep the object alive: once nothing else refers to the
+        /// object, the garbage collector may drop it, after which the weak
+        /// reference can no longer be upgraded.
+        #[derive(Clone, Collect, Copy)]
+        #[collect(no_drop)]
+        pub enum WeakObject<'gc> {
+            $($variant(GcWeakCell<'gc, $data>)),*
+        }
+
+        impl<'gc> Object<'gc> {
+            pub fn downgrade(self) -> WeakObject<'gc> {
+                match self {
+                    $(Self::$variant(object) => WeakObject::$variant(GcCell::downgrade(object.0))),*
+                }
+            }
+        }
+
+        impl<'gc> WeakObject<'gc> {
+            /// Get the object back, unless it has been collected.
+            pub fn upgrade(self, mc: MutationContext<'gc, '_>) -> Option<Object<'gc>> {
+                match self {
+                    $(Self::$variant(weak) => weak.upgrade(mc).map(|cell| $variant(cell).into())),*
+                }
+            }
+        }
+    };
+}
+
+weak_object! {
+    ScriptObject(ScriptObjectData<'gc>),
+    FunctionObject(function_object::FunctionObjectData<'gc>),
+    PrimitiveObject(primitive_object::PrimitiveObjectData<'gc>),
+    NamespaceObject(namespace_object::NamespaceObjectData<'gc>),
+    ArrayObject(array_object::ArrayObjectData<'gc>),
+    StageObject(stage_object::StageObjectData<'gc>),
+    DomainObject(domain_object::DomainObjectData<'gc>),
+    EventObject(event_object::EventObjectData<'gc>),
+    DispatchObject(dispatch_object::DispatchObjectData<'gc>),
+    XmlObject(xml_object::XmlObjectData<'gc>),
+    RegExpObject(regexp_object::RegExpObjectData<'gc>),
+    ByteArrayObject(bytearray_object::ByteArrayObjectData<'gc>),
+    LoaderInfoObject(loaderinfo_object::LoaderInfoObjectData<'gc>),
+    ClassObject(class_object::ClassObjectData<'gc>),
+    VectorObject(vector_object::VectorObjectData<'gc>),
+    SoundObject(sound_object::SoundObjectData<'gc>),
+    SoundChannelObject(soundchannel_object::SoundChannelObjectData<'gc>),
+    BitmapDataObject(bitmapdata_object::BitmapDataObjectData<'gc>),
+    DateObject(date_object::DateObjectData<'gc>),
+    DictionaryObject(dictionary_object::DictionaryObjectData<'gc>),
+    QNameObject(qname_object::QNameObjectData<'gc>),
+    TextFormatObject(textformat_object::TextFormatObjectData<'gc>),
+    ProxyObject(proxy_object::ProxyObjectData<'gc>),
+}
+
+impl<'gc> Debug for WeakObject<'gc> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WeakObject").finish()
+    }
+}
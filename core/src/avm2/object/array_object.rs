@@ -32,7 +32,7 @@ pub fn array_allocator<'gc>(
 /// An Object which stores numerical properties in an array.
 #[derive(Collect, Debug, Clone, Copy)]
 #[collect(no_drop)]
-pub struct ArrayObject<'gc>(GcCell<'gc, ArrayObjectData<'gc>>);
+pub struct ArrayObject<'gc>(pub(super) GcCell<'gc, ArrayObjectData<'gc>>);
 
 #[derive(Collect, Debug, Clone)]
 #[collect(no_drop)]
@@ -29,7 +29,7 @@ pub fn bitmapdata_allocator<'gc>(
 
 #[derive(Clone, Collect, Debug, Copy)]
 #[collect(no_drop)]
-pub struct BitmapDataObject<'gc>(GcCell<'gc, BitmapDataObjectData<'gc>>);
+pub struct BitmapDataObject<'gc>(pub(super) GcCell<'gc, BitmapDataObjectData<'gc>>);
 
 #[derive(Clone, Collect, Debug)]
 #[collect(no_drop)]
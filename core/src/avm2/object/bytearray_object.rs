@@ -28,7 +28,7 @@ pub fn bytearray_allocator<'gc>(
 
 #[derive(Clone, Collect, Debug, Copy)]
 #[collect(no_drop)]
-pub struct ByteArrayObject<'gc>(GcCell<'gc, ByteArrayObjectData<'gc>>);
+pub struct ByteArrayObject<'gc>(pub(super) GcCell<'gc, ByteArrayObjectData<'gc>>);
 
 #[derive(Clone, Collect, Debug)]
 #[collect(no_drop)]
@@ -22,7 +22,7 @@ use std::hash::{Hash, Hasher};
 /// An Object which can be called to execute its function code.
 #[derive(Collect, Debug, Clone, Copy)]
 #[collect(no_drop)]
-pub struct ClassObject<'gc>(GcCell<'gc, ClassObjectData<'gc>>);
+pub struct ClassObject<'gc>(pub(super) GcCell<'gc, ClassObjectData<'gc>>);
 
 #[derive(Collect, Debug, Clone)]
 #[collect(no_drop)]
@@ -26,7 +26,7 @@ pub fn date_allocator<'gc>(
 }
 #[derive(Clone, Collect, Debug, Copy)]
 #[collect(no_drop)]
-pub struct DateObject<'gc>(GcCell<'gc, DateObjectData<'gc>>);
+pub struct DateObject<'gc>(pub(super) GcCell<'gc, DateObjectData<'gc>>);
 
 impl<'gc> DateObject<'gc> {
     pub fn date_time(self) -> Option<DateTime<Utc>> {
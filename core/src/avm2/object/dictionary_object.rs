@@ -1,8 +1,9 @@
 //! Object representation for `flash.utils.Dictionary`
 
 use crate::avm2::activation::Activation;
+use crate::avm2::names::QName;
 use crate::avm2::object::script_object::ScriptObjectData;
-use crate::avm2::object::{ClassObject, Object, ObjectPtr, TObject};
+use crate::avm2::object::{ClassObject, Object, ObjectPtr, TObject, WeakObject};
 use crate::avm2::value::Value;
 use crate::avm2::Error;
 use fnv::FnvHashMap;
@@ -22,6 +23,8 @@ pub fn dictionary_allocator<'gc>(
         DictionaryObjectData {
             base,
             object_space: Default::default(),
+            slots: Vec::new(),
+            weak_keys: false,
         },
     ))
     .into())
@@ -34,7 +37,7 @@ pub fn dictionary_allocator<'gc>(
 /// keys are objects instead of strings.
 #[derive(Clone, Collect, Debug, Copy)]
 #[collect(no_drop)]
-pub struct DictionaryObject<'gc>(GcCell<'gc, DictionaryObjectData<'gc>>);
+pub struct DictionaryObject<'gc>(pub(super) GcCell<'gc, DictionaryObjectData<'gc>>);
 
 #[derive(Clone, Collect, Debug)]
 #[collect(no_drop)]
@@ -42,18 +45,71 @@ pub struct DictionaryObjectData<'gc> {
     /// Base script object
     base: ScriptObjectData<'gc>,
 
-    /// Object key storage
-    object_space: FnvHashMap<Object<'gc>, Value<'gc>>,
+    /// Maps the addresses of object keys to their slot in `slots`.
+    ///
+    /// Keys are identified by address so that weak keys are not kept alive
+    /// by this map. The address of a collected weak key is not reused for as
+    /// long as its weak reference is held in `slots`.
+    #[collect(require_static)]
+    object_space: FnvHashMap<*const ObjectPtr, usize>,
+
+    /// Object key storage, in insertion order.
+    ///
+    /// The enumerant index of a key is derived from its slot. Deleted entries
+    /// leave an empty slot behind, so that deleting keys while enumerating the
+    /// dictionary doesn't skip or repeat any other key. Empty slots are
+    /// reclaimed when the storage would otherwise have to grow, once they
+    /// make up more than half of it.
+    slots: Vec<Option<DictionaryEntry<'gc>>>,
+
+    /// Whether this dictionary was constructed with `weakKeys`.
+    weak_keys: bool,
+}
+
+/// A key in a dictionary's object space, and its value.
+#[derive(Clone, Collect, Debug, Copy)]
+#[collect(no_drop)]
+struct DictionaryEntry<'gc> {
+    key: DictionaryKey<'gc>,
+
+    /// The address of the key, which still identifies it in `object_space`
+    /// once a weak key has been collected.
+    #[collect(require_static)]
+    ptr: *const ObjectPtr,
+
+    value: Value<'gc>,
+}
+
+#[derive(Clone, Collect, Debug, Copy)]
+#[collect(no_drop)]
+enum DictionaryKey<'gc> {
+    Strong(Object<'gc>),
+
+    /// A key of a `weakKeys` dictionary, which the dictionary doesn't keep
+    /// alive. Its entry is removed once the key has been collected. A value
+    /// that refers to its own key still keeps the key alive.
+    Weak(WeakObject<'gc>),
+}
+
+impl<'gc> DictionaryKey<'gc> {
+    /// The key object, unless it is a weak key that has been collected.
+    fn get(self, mc: MutationContext<'gc, '_>) -> Option<Object<'gc>> {
+        match self {
+            Self::Strong(object) => Some(object),
+            Self::Weak(weak) => weak.upgrade(mc),
+        }
+    }
 }
 
 impl<'gc> DictionaryObject<'gc> {
     /// Retrieve a value in the dictionary's object space.
     pub fn get_property_by_object(self, name: Object<'gc>) -> Value<'gc> {
-        self.0
-            .read()
-            .object_space
-            .get(&name)
-            .cloned()
+        let read = self.0.read();
+
+        read.object_space
+            .get(&name.as_ptr())
+            .and_then(|slot| read.slots[*slot])
+            .map(|entry| entry.value)
             .unwrap_or(Value::Undefined)
     }
 
@@ -64,16 +120,121 @@ impl<'gc> DictionaryObject<'gc> {
         value: Value<'gc>,
         mc: MutationContext<'gc, '_>,
     ) {
-        self.0.write(mc).object_space.insert(name, value);
+        let mut write = self.0.write(mc);
+        let ptr = name.as_ptr();
+
+        if let Some(slot) = write.object_space.get(&ptr).copied() {
+            if let Some(entry) = &mut write.slots[slot] {
+                entry.value = value;
+            }
+            return;
+        }
+
+        if write.slots.len() == write.slots.capacity() {
+            write.remove_collected_keys(mc);
+            if write.object_space.len() < write.slots.len() / 2 {
+                write.compact();
+            }
+        }
+
+        let key = if write.weak_keys {
+            DictionaryKey::Weak(name.downgrade())
+        } else {
+            DictionaryKey::Strong(name)
+        };
+        let slot = write.slots.len();
+        write.slots.push(Some(DictionaryEntry { key, ptr, value }));
+        write.object_space.insert(ptr, slot);
     }
 
     /// Delete a value from the dictionary's object space.
     pub fn delete_property_by_object(self, name: Object<'gc>, mc: MutationContext<'gc, '_>) {
-        self.0.write(mc).object_space.remove(&name);
+        let mut write = self.0.write(mc);
+
+        if let Some(slot) = write.object_space.get(&name.as_ptr()).copied() {
+            write.remove_slot(slot);
+        }
     }
 
     pub fn has_property_by_object(self, name: Object<'gc>) -> bool {
-        self.0.read().object_space.get(&name).is_some()
+        self.0.read().object_space.contains_key(&name.as_ptr())
+    }
+
+    pub fn set_weak_keys(self, weak_keys: bool, mc: MutationContext<'gc, '_>) {
+        self.0.write(mc).weak_keys = weak_keys;
+    }
+
+    /// Find the first slot at or after `first_slot` whose key is still alive.
+    ///
+    /// Entries whose weak key has been collected are removed along the way.
+    fn next_live_slot(self, first_slot: usize, mc: MutationContext<'gc, '_>) -> Option<usize> {
+        let mut write = self.0.write(mc);
+
+        let mut slot = first_slot;
+        while let Some(entry) = write.slots.get(slot).copied() {
+            match entry {
+                Some(entry) if entry.key.get(mc).is_some() => return Some(slot),
+                Some(_) => write.remove_slot(slot),
+                None => {}
+            }
+            slot += 1;
+        }
+
+        None
+    }
+
+    /// Get the object key and value at an enumerant index.
+    ///
+    /// Object keys are enumerated after the dictionary's dynamic properties.
+    fn object_enumerant(
+        self,
+        index: u32,
+        mc: MutationContext<'gc, '_>,
+    ) -> Option<(Object<'gc>, Value<'gc>)> {
+        let read = self.0.read();
+        let slot = index.checked_sub(read.base.get_last_enumerant() + 1)?;
+        let entry = read.slots.get(slot as usize).copied().flatten()?;
+
+        Some((entry.key.get(mc)?, entry.value))
+    }
+}
+
+impl<'gc> DictionaryObjectData<'gc> {
+    /// Empty a slot, and forget its key.
+    fn remove_slot(&mut self, slot: usize) {
+        if let Some(entry) = self.slots[slot].take() {
+            self.object_space.remove(&entry.ptr);
+        }
+
+        // With no keys left, no enumerant index can refer to a live key.
+        if self.object_space.is_empty() {
+            self.slots.clear();
+        }
+    }
+
+    /// Remove the entries whose weak key has been collected.
+    fn remove_collected_keys(&mut self, mc: MutationContext<'gc, '_>) {
+        if !self.weak_keys {
+            return;
+        }
+
+        for slot in 0..self.slots.len() {
+            if matches!(self.slots.get(slot), Some(Some(entry)) if entry.key.get(mc).is_none()) {
+                self.remove_slot(slot);
+            }
+        }
+    }
+
+    /// Remove the empty slots left behind by deleted keys.
+    ///
+    /// This renumbers the enumerant indices of the remaining keys.
+    fn compact(&mut self) {
+        self.slots.retain(Option::is_some);
+        for (slot, entry) in self.slots.iter().enumerate() {
+            if let Some(entry) = entry {
+                self.object_space.insert(entry.ptr, slot);
+            }
+        }
     }
 }
 
@@ -101,42 +262,139 @@ impl<'gc> TObject<'gc> for DictionaryObject<'gc> {
     fn get_next_enumerant(
         self,
         last_index: u32,
-        _activation: &mut Activation<'_, 'gc, '_>,
+        activation: &mut Activation<'_, 'gc, '_>,
     ) -> Result<Option<u32>, Error> {
-        let read = self.0.read();
-        let last_enumerant = read.base.get_last_enumerant();
-        let object_space_length = read.object_space.keys().len() as u32;
+        let last_enumerant = self.0.read().base.get_last_enumerant();
 
-        if last_index < last_enumerant + object_space_length {
-            Ok(Some(last_index.saturating_add(1)))
-        } else {
-            Ok(None)
+        if last_index < last_enumerant {
+            return Ok(Some(last_index + 1));
         }
+
+        // Skip the slots of deleted and collected keys.
+        let first_slot = (last_index - last_enumerant) as usize;
+        Ok(self
+            .next_live_slot(first_slot, activation.context.gc_context)
+            .map(|slot| last_enumerant + slot as u32 + 1))
     }
 
     fn get_enumerant_name(
         self,
         index: u32,
-        _activation: &mut Activation<'_, 'gc, '_>,
+        activation: &mut Activation<'_, 'gc, '_>,
     ) -> Result<Value<'gc>, Error> {
-        let read = self.0.read();
-        let last_enumerant = read.base.get_last_enumerant();
+        if let Some((key, _)) = self.object_enumerant(index, activation.context.gc_context) {
+            return Ok(key.into());
+        }
 
-        if index < last_enumerant {
-            Ok(read
-                .base
-                .get_enumerant_name(index)
-                .unwrap_or(Value::Undefined))
-        } else {
-            let object_space_index = index.saturating_sub(last_enumerant);
-
-            Ok(read
-                .object_space
-                .keys()
-                .nth(object_space_index as usize)
-                .cloned()
-                .map(|v| v.into())
-                .unwrap_or(Value::Undefined))
+        Ok(self
+            .0
+            .read()
+            .base
+            .get_enumerant_name(index)
+            .unwrap_or(Value::Undefined))
+    }
+
+    fn get_enumerant_value(
+        self,
+        index: u32,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<Value<'gc>, Error> {
+        if let Some((_, value)) = self.object_enumerant(index, activation.context.gc_context) {
+            return Ok(value);
         }
+
+        let name = self
+            .get_enumerant_name(index, activation)?
+            .coerce_to_string(activation)?;
+        self.get_property(&QName::dynamic_name(name).into(), activation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::object::ScriptObject;
+    use gc_arena::{make_arena, ArenaParameters};
+
+    #[derive(Collect)]
+    #[collect(no_drop)]
+    struct TestRoot<'gc> {
+        dictionary: DictionaryObject<'gc>,
+        key: GcCell<'gc, Option<Object<'gc>>>,
+    }
+
+    make_arena!(TestArena, TestRoot);
+
+    fn dictionary<'gc>(mc: MutationContext<'gc, '_>, weak_keys: bool) -> DictionaryObject<'gc> {
+        DictionaryObject(GcCell::allocate(
+            mc,
+            DictionaryObjectData {
+                base: ScriptObjectData::base_new(None, None),
+                object_space: Default::default(),
+                slots: Vec::new(),
+                weak_keys,
+            },
+        ))
+    }
+
+    #[test]
+    fn dropped_weak_key_is_removed() {
+        let mut arena = TestArena::new(ArenaParameters::default(), |mc| TestRoot {
+            dictionary: dictionary(mc, true),
+            key: GcCell::allocate(mc, None),
+        });
+
+        arena.mutate(|mc, root| {
+            let key = ScriptObject::bare_object(mc);
+            root.dictionary
+                .set_property_by_object(key, Value::Number(1.0), mc);
+            *root.key.write(mc) = Some(key);
+        });
+        arena.collect_all();
+
+        arena.mutate(|mc, root| {
+            let key = root.key.read().unwrap();
+            assert!(root.dictionary.has_property_by_object(key));
+            assert_eq!(root.dictionary.next_live_slot(0, mc), Some(0));
+            *root.key.write(mc) = None;
+        });
+        arena.collect_all();
+
+        arena.mutate(|mc, root| {
+            assert_eq!(root.dictionary.next_live_slot(0, mc), None);
+            assert!(root.dictionary.0.read().object_space.is_empty());
+        });
+    }
+
+    #[test]
+    fn compacts_deleted_slots() {
+        let mut arena = TestArena::new(ArenaParameters::default(), |mc| TestRoot {
+            dictionary: dictionary(mc, false),
+            key: GcCell::allocate(mc, None),
+        });
+
+        arena.mutate(|mc, root| {
+            let keys: Vec<_> = (0..8).map(|_| ScriptObject::bare_object(mc)).collect();
+            for key in &keys {
+                root.dictionary
+                    .set_property_by_object(*key, Value::Undefined, mc);
+            }
+            for key in &keys[..6] {
+                root.dictionary.delete_property_by_object(*key, mc);
+            }
+
+            // Eight slots fill the storage, so growing it reclaims the
+            // slots of the deleted keys first.
+            assert_eq!(root.dictionary.0.read().slots.capacity(), 8);
+            let key = ScriptObject::bare_object(mc);
+            root.dictionary
+                .set_property_by_object(key, Value::Undefined, mc);
+
+            let read = root.dictionary.0.read();
+            assert_eq!(read.slots.len(), 3);
+            assert_eq!(read.object_space.get(&keys[6].as_ptr()), Some(&0));
+            assert_eq!(read.object_space.get(&keys[7].as_ptr()), Some(&1));
+            assert_eq!(read.object_space.get(&key.as_ptr()), Some(&2));
+        });
     }
 }
@@ -36,7 +36,7 @@ use std::cell::{Ref, RefMut};
 ///    `StageObject`.
 #[derive(Clone, Collect, Debug, Copy)]
 #[collect(no_drop)]
-pub struct DispatchObject<'gc>(GcCell<'gc, DispatchObjectData<'gc>>);
+pub struct DispatchObject<'gc>(pub(super) GcCell<'gc, DispatchObjectData<'gc>>);
 
 #[derive(Clone, Collect, Debug)]
 #[collect(no_drop)]
@@ -27,7 +27,7 @@ pub fn appdomain_allocator<'gc>(
 
 #[derive(Clone, Collect, Debug, Copy)]
 #[collect(no_drop)]
-pub struct DomainObject<'gc>(GcCell<'gc, DomainObjectData<'gc>>);
+pub struct DomainObject<'gc>(pub(super) GcCell<'gc, DomainObjectData<'gc>>);
 
 #[derive(Clone, Collect, Debug)]
 #[collect(no_drop)]
@@ -29,7 +29,7 @@ pub fn event_allocator<'gc>(
 
 #[derive(Clone, Collect, Debug, Copy)]
 #[collect(no_drop)]
-pub struct EventObject<'gc>(GcCell<'gc, EventObjectData<'gc>>);
+pub struct EventObject<'gc>(pub(super) GcCell<'gc, EventObjectData<'gc>>);
 
 #[derive(Clone, Collect, Debug)]
 #[collect(no_drop)]
@@ -54,6 +54,7 @@ impl<'gc> EventObject<'gc> {
             EventData::Empty => activation.avm2().classes().event,
             EventData::FullScreen { .. } => activation.avm2().classes().fullscreenevent,
             EventData::Mouse { .. } => activation.avm2().classes().mouseevent,
+            EventData::Text { .. } => activation.avm2().classes().textevent,
         };
 
         let proto = class.prototype();
@@ -14,7 +14,7 @@ use std::cell::{Ref, RefMut};
 /// An Object which can be called to execute its function code.
 #[derive(Collect, Debug, Clone, Copy)]
 #[collect(no_drop)]
-pub struct FunctionObject<'gc>(GcCell<'gc, FunctionObjectData<'gc>>);
+pub struct FunctionObject<'gc>(pub(super) GcCell<'gc, FunctionObjectData<'gc>>);
 
 #[derive(Collect, Debug, Clone)]
 #[collect(no_drop)]
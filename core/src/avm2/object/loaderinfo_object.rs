@@ -51,7 +51,7 @@ pub enum LoaderStream<'gc> {
 /// resource.
 #[derive(Collect, Debug, Clone, Copy)]
 #[collect(no_drop)]
-pub struct LoaderInfoObject<'gc>(GcCell<'gc, LoaderInfoObjectData<'gc>>);
+pub struct LoaderInfoObject<'gc>(pub(super) GcCell<'gc, LoaderInfoObjectData<'gc>>);
 
 #[derive(Collect, Debug, Clone)]
 #[collect(no_drop)]
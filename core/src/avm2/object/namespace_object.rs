@@ -30,7 +30,7 @@ pub fn namespace_allocator<'gc>(
 /// An Object which represents a boxed namespace name.
 #[derive(Collect, Debug, Clone, Copy)]
 #[collect(no_drop)]
-pub struct NamespaceObject<'gc>(GcCell<'gc, NamespaceObjectData<'gc>>);
+pub struct NamespaceObject<'gc>(pub(super) GcCell<'gc, NamespaceObjectData<'gc>>);
 
 #[derive(Collect, Debug, Clone)]
 #[collect(no_drop)]
@@ -31,7 +31,7 @@ pub fn primitive_allocator<'gc>(
 /// An Object which represents a primitive value of some other kind.
 #[derive(Collect, Debug, Clone, Copy)]
 #[collect(no_drop)]
-pub struct PrimitiveObject<'gc>(GcCell<'gc, PrimitiveObjectData<'gc>>);
+pub struct PrimitiveObject<'gc>(pub(super) GcCell<'gc, PrimitiveObjectData<'gc>>);
 
 #[derive(Collect, Debug, Clone)]
 #[collect(no_drop)]
@@ -27,7 +27,7 @@ pub fn proxy_allocator<'gc>(
 
 #[derive(Clone, Collect, Debug, Copy)]
 #[collect(no_drop)]
-pub struct ProxyObject<'gc>(GcCell<'gc, ProxyObjectData<'gc>>);
+pub struct ProxyObject<'gc>(pub(super) GcCell<'gc, ProxyObjectData<'gc>>);
 
 #[derive(Clone, Collect, Debug)]
 #[collect(no_drop)]
@@ -36,6 +36,40 @@ pub struct ProxyObjectData<'gc> {
     base: ScriptObjectData<'gc>,
 }
 
+/// Box the name of a property into the `QName` passed to a `Proxy` trap.
+///
+/// Returns `None` if the multiname can't be expressed as a `QName` for the
+/// proxy, in which case the trap is not called.
+fn trap_name<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    multiname: &Multiname<'gc>,
+) -> Result<Option<Object<'gc>>, Error> {
+    // NOTE: This is incorrect behavior.
+    // `QName` should instead store the whole multiname's namespace set,
+    // so that it can be used to index other objects using the same
+    // namespace set.
+    if let Some(local_name) = multiname.local_name() {
+        for namespace in multiname.namespace_set() {
+            if namespace.is_any() || namespace.is_public() || namespace.is_namespace() {
+                let qname =
+                    QNameObject::from_qname(activation, QName::new(*namespace, local_name))?;
+                if let Some(qname) = qname.as_qname_object() {
+                    qname.set_is_attribute(activation.context.gc_context, multiname.is_attribute());
+                }
+
+                return Ok(Some(qname));
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+/// The name of a `flash_proxy` trap method.
+fn trap<'gc>(name: &'static str) -> Multiname<'gc> {
+    QName::new(Namespace::Namespace(NS_FLASH_PROXY.into()), name).into()
+}
+
 impl<'gc> TObject<'gc> for ProxyObject<'gc> {
     fn base(&self) -> Ref<ScriptObjectData<'gc>> {
         Ref::map(self.0.read(), |read| &read.base)
@@ -58,24 +92,8 @@ impl<'gc> TObject<'gc> for ProxyObject<'gc> {
         multiname: &Multiname<'gc>,
         activation: &mut Activation<'_, 'gc, '_>,
     ) -> Result<Value<'gc>, Error> {
-        // NOTE: This is incorrect behavior.
-        // `QName` should instead store the whole multiname's namespace set,
-        // so that it can be used to index other objects using the same
-        // namespace set.
-        if let Some(local_name) = multiname.local_name() {
-            for namespace in multiname.namespace_set() {
-                if namespace.is_any() || namespace.is_public() || namespace.is_namespace() {
-                    let qname =
-                        QNameObject::from_qname(activation, QName::new(*namespace, local_name))?;
-
-                    return self.call_property(
-                        &QName::new(Namespace::Namespace(NS_FLASH_PROXY.into()), "getProperty")
-                            .into(),
-                        &[qname.into()],
-                        activation,
-                    );
-                }
-            }
+        if let Some(qname) = trap_name(activation, multiname)? {
+            return self.call_property(&trap("getProperty"), &[qname.into()], activation);
         }
 
         if !self
@@ -95,26 +113,10 @@ impl<'gc> TObject<'gc> for ProxyObject<'gc> {
         value: Value<'gc>,
         activation: &mut Activation<'_, 'gc, '_>,
     ) -> Result<(), Error> {
-        // NOTE: This is incorrect behavior.
-        // `QName` should instead store the whole multiname's namespace set,
-        // so that it can be used to index other objects using the same
-        // namespace set.
-        if let Some(local_name) = multiname.local_name() {
-            for namespace in multiname.namespace_set() {
-                if namespace.is_any() || namespace.is_public() || namespace.is_namespace() {
-                    let qname =
-                        QNameObject::from_qname(activation, QName::new(*namespace, local_name))?;
-
-                    self.call_property(
-                        &QName::new(Namespace::Namespace(NS_FLASH_PROXY.into()), "setProperty")
-                            .into(),
-                        &[qname.into(), value],
-                        activation,
-                    )?;
+        if let Some(qname) = trap_name(activation, multiname)? {
+            self.call_property(&trap("setProperty"), &[qname.into(), value], activation)?;
 
-                    return Ok(());
-                }
-            }
+            return Ok(());
         }
 
         if !self
@@ -138,27 +140,11 @@ impl<'gc> TObject<'gc> for ProxyObject<'gc> {
         arguments: &[Value<'gc>],
         activation: &mut Activation<'_, 'gc, '_>,
     ) -> Result<Value<'gc>, Error> {
-        // NOTE: This is incorrect behavior.
-        // `QName` should instead store the whole multiname's namespace set,
-        // so that it can be used to index other objects using the same
-        // namespace set.
-        if let Some(local_name) = multiname.local_name() {
-            for namespace in multiname.namespace_set() {
-                if namespace.is_any() || namespace.is_public() || namespace.is_namespace() {
-                    let qname =
-                        QNameObject::from_qname(activation, QName::new(*namespace, local_name))?;
+        if let Some(qname) = trap_name(activation, multiname)? {
+            let mut args = vec![qname.into()];
+            args.extend_from_slice(arguments);
 
-                    let mut args = vec![qname.into()];
-                    args.extend_from_slice(arguments);
-
-                    return self.call_property(
-                        &QName::new(Namespace::Namespace(NS_FLASH_PROXY.into()), "callProperty")
-                            .into(),
-                        &args[..],
-                        activation,
-                    );
-                }
-            }
+            return self.call_property(&trap("callProperty"), &args[..], activation);
         }
 
         Err(format!(
@@ -173,29 +159,10 @@ impl<'gc> TObject<'gc> for ProxyObject<'gc> {
         activation: &mut Activation<'_, 'gc, '_>,
         multiname: &Multiname<'gc>,
     ) -> Result<bool, Error> {
-        // NOTE: This is incorrect behavior.
-        // `QName` should instead store the whole multiname's namespace set,
-        // so that it can be used to index other objects using the same
-        // namespace set.
-        if let Some(local_name) = multiname.local_name() {
-            for namespace in multiname.namespace_set() {
-                if namespace.is_any() || namespace.is_public() || namespace.is_namespace() {
-                    let qname =
-                        QNameObject::from_qname(activation, QName::new(*namespace, local_name))?;
-
-                    return Ok(self
-                        .call_property(
-                            &QName::new(
-                                Namespace::Namespace(NS_FLASH_PROXY.into()),
-                                "deleteProperty",
-                            )
-                            .into(),
-                            &[qname.into()],
-                            activation,
-                        )?
-                        .coerce_to_boolean());
-                }
-            }
+        if let Some(qname) = trap_name(activation, multiname)? {
+            return Ok(self
+                .call_property(&trap("deleteProperty"), &[qname.into()], activation)?
+                .coerce_to_boolean());
         }
 
         // Unknown properties on a dynamic class delete successfully.
@@ -212,7 +179,7 @@ impl<'gc> TObject<'gc> for ProxyObject<'gc> {
     ) -> Result<bool, Error> {
         Ok(self
             .call_property(
-                &QName::new(Namespace::Namespace(NS_FLASH_PROXY.into()), "hasProperty").into(),
+                &trap("hasProperty"),
                 // this should probably pass the multiname as-is? See above
                 &[name.local_name().unwrap().into()],
                 activation,
@@ -220,18 +187,27 @@ impl<'gc> TObject<'gc> for ProxyObject<'gc> {
             .coerce_to_boolean())
     }
 
+    fn get_descendants(
+        self,
+        multiname: &Multiname<'gc>,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<Value<'gc>, Error> {
+        let name = match trap_name(activation, multiname)? {
+            Some(qname) => qname.into(),
+            None => Value::Undefined,
+        };
+
+        self.call_property(&trap("getDescendants"), &[name], activation)
+    }
+
     fn get_next_enumerant(
         self,
         last_index: u32,
         activation: &mut Activation<'_, 'gc, '_>,
     ) -> Result<Option<u32>, Error> {
         Ok(Some(
-            self.call_property(
-                &QName::new(Namespace::Namespace(NS_FLASH_PROXY.into()), "nextNameIndex").into(),
-                &[last_index.into()],
-                activation,
-            )?
-            .coerce_to_u32(activation)?,
+            self.call_property(&trap("nextNameIndex"), &[last_index.into()], activation)?
+                .coerce_to_u32(activation)?,
         ))
     }
 
@@ -240,11 +216,7 @@ impl<'gc> TObject<'gc> for ProxyObject<'gc> {
         index: u32,
         activation: &mut Activation<'_, 'gc, '_>,
     ) -> Result<Value<'gc>, Error> {
-        self.call_property(
-            &QName::new(Namespace::Namespace(NS_FLASH_PROXY.into()), "nextName").into(),
-            &[index.into()],
-            activation,
-        )
+        self.call_property(&trap("nextName"), &[index.into()], activation)
     }
 
     fn get_enumerant_value(
@@ -252,10 +224,6 @@ impl<'gc> TObject<'gc> for ProxyObject<'gc> {
         index: u32,
         activation: &mut Activation<'_, 'gc, '_>,
     ) -> Result<Value<'gc>, Error> {
-        self.call_property(
-            &QName::new(Namespace::Namespace(NS_FLASH_PROXY.into()), "nextValue").into(),
-            &[index.into()],
-            activation,
-        )
+        self.call_property(&trap("nextValue"), &[index.into()], activation)
     }
 }
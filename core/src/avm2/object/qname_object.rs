@@ -19,7 +19,11 @@ pub fn qname_allocator<'gc>(
 
     Ok(QNameObject(GcCell::allocate(
         activation.context.gc_context,
-        QNameObjectData { base, qname: None },
+        QNameObjectData {
+            base,
+            qname: None,
+            is_attribute: false,
+        },
     ))
     .into())
 }
@@ -27,7 +31,7 @@ pub fn qname_allocator<'gc>(
 /// An Object which represents a boxed QName.
 #[derive(Collect, Debug, Clone, Copy)]
 #[collect(no_drop)]
-pub struct QNameObject<'gc>(GcCell<'gc, QNameObjectData<'gc>>);
+pub struct QNameObject<'gc>(pub(super) GcCell<'gc, QNameObjectData<'gc>>);
 
 #[derive(Collect, Debug, Clone)]
 #[collect(no_drop)]
@@ -37,6 +41,9 @@ pub struct QNameObjectData<'gc> {
 
     /// The QName name this object is associated with.
     qname: Option<QName<'gc>>,
+
+    /// Whether this QName names an XML attribute.
+    is_attribute: bool,
 }
 
 impl<'gc> QNameObject<'gc> {
@@ -54,6 +61,7 @@ impl<'gc> QNameObject<'gc> {
             QNameObjectData {
                 base,
                 qname: Some(qname),
+                is_attribute: false,
             },
         ))
         .into();
@@ -74,6 +82,14 @@ impl<'gc> QNameObject<'gc> {
     pub fn init_qname(self, mc: MutationContext<'gc, '_>, qname: QName<'gc>) {
         self.0.write(mc).qname = Some(qname);
     }
+
+    pub fn is_attribute(&self) -> bool {
+        self.0.read().is_attribute
+    }
+
+    pub fn set_is_attribute(self, mc: MutationContext<'gc, '_>, is_attribute: bool) {
+        self.0.write(mc).is_attribute = is_attribute;
+    }
 }
 
 impl<'gc> TObject<'gc> for QNameObject<'gc> {
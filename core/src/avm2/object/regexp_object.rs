@@ -30,7 +30,7 @@ pub fn regexp_allocator<'gc>(
 
 #[derive(Clone, Collect, Debug, Copy)]
 #[collect(no_drop)]
-pub struct RegExpObject<'gc>(GcCell<'gc, RegExpObjectData<'gc>>);
+pub struct RegExpObject<'gc>(pub(super) GcCell<'gc, RegExpObjectData<'gc>>);
 
 #[derive(Clone, Collect, Debug)]
 #[collect(no_drop)]
@@ -27,7 +27,7 @@ pub fn scriptobject_allocator<'gc>(
 /// Default implementation of `avm2::Object`.
 #[derive(Clone, Collect, Debug, Copy)]
 #[collect(no_drop)]
-pub struct ScriptObject<'gc>(GcCell<'gc, ScriptObjectData<'gc>>);
+pub struct ScriptObject<'gc>(pub(super) GcCell<'gc, ScriptObjectData<'gc>>);
 
 /// Base data common to all `TObject` implementations.
 ///
@@ -229,6 +229,21 @@ impl<'gc> ScriptObjectData<'gc> {
         }
     }
 
+    /// List the slot traits and dynamic properties of this object, without
+    /// running any getters.
+    pub fn debug_properties(&self) -> Vec<(AvmString<'gc>, Value<'gc>)> {
+        let mut properties: Vec<_> = self
+            .vtable
+            .map(|vtable| vtable.slot_names())
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|(name, slot_id)| Some((name, *self.slots.get(slot_id as usize)?)))
+            .collect();
+        properties.extend(self.values.iter().map(|(name, value)| (*name, *value)));
+
+        properties
+    }
+
     pub fn get_slot(&self, id: u32) -> Result<Value<'gc>, Error> {
         self.slots
             .get(id as usize)
@@ -26,7 +26,7 @@ pub fn sound_allocator<'gc>(
 
 #[derive(Clone, Collect, Debug, Copy)]
 #[collect(no_drop)]
-pub struct SoundObject<'gc>(GcCell<'gc, SoundObjectData<'gc>>);
+pub struct SoundObject<'gc>(pub(super) GcCell<'gc, SoundObjectData<'gc>>);
 
 #[derive(Clone, Collect, Debug)]
 #[collect(no_drop)]
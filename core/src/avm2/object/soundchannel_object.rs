@@ -30,7 +30,7 @@ pub fn soundchannel_allocator<'gc>(
 
 #[derive(Clone, Collect, Debug, Copy)]
 #[collect(no_drop)]
-pub struct SoundChannelObject<'gc>(GcCell<'gc, SoundChannelObjectData<'gc>>);
+pub struct SoundChannelObject<'gc>(pub(super) GcCell<'gc, SoundChannelObjectData<'gc>>);
 
 #[derive(Clone, Collect, Debug)]
 #[collect(no_drop)]
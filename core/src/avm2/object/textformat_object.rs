@@ -29,7 +29,7 @@ pub fn textformat_allocator<'gc>(
 
 #[derive(Clone, Collect, Debug, Copy)]
 #[collect(no_drop)]
-pub struct TextFormatObject<'gc>(GcCell<'gc, TextFormatObjectData<'gc>>);
+pub struct TextFormatObject<'gc>(pub(super) GcCell<'gc, TextFormatObjectData<'gc>>);
 
 #[derive(Clone, Collect, Debug)]
 #[collect(no_drop)]
@@ -40,7 +40,7 @@ pub fn vector_allocator<'gc>(
 /// An Object which stores typed properties in vector storage
 #[derive(Collect, Debug, Clone, Copy)]
 #[collect(no_drop)]
-pub struct VectorObject<'gc>(GcCell<'gc, VectorObjectData<'gc>>);
+pub struct VectorObject<'gc>(pub(super) GcCell<'gc, VectorObjectData<'gc>>);
 
 #[derive(Collect, Debug, Clone)]
 #[collect(no_drop)]
@@ -0,0 +1,976 @@
+//! AVM2 bytecode verification and optimization
+//!
+//! Before a method is first run, its body is decoded and checked for
+//! malformed bytecode, such as branches into the middle of an instruction or
+//! stack and scope depths that disagree between control flow paths. The
+//! decoded ops are then rewritten into a cheaper form that the interpreter
+//! runs in place of the original bytecode.
+
+use crate::avm2::activation::Activation;
+use crate::avm2::method::BytecodeMethod;
+use crate::avm2::names::Multiname;
+use crate::avm2::property::Property;
+use crate::avm2::vtable::VTable;
+use crate::avm2::Error;
+use crate::swf::extensions::ReadSwfExt;
+use gc_arena::{Collect, Gc};
+use std::collections::BTreeMap;
+use swf::avm2::read::Reader;
+use swf::avm2::types::{AbcFile, Index, MethodBody, Multiname as AbcMultiname, Op};
+
+/// The maximum number of jumps followed when threading a jump to another
+/// jump.
+const MAX_JUMP_THREADING: usize = 16;
+
+/// A verified method body, ready for execution.
+///
+/// Unlike ops read from ABC bytecode, the branch offsets of `Jump`, the `If*`
+/// family and `LookupSwitch` hold the index of their target op in `ops`.
+///
+/// The op stream may also contain `CallMethod`, which is never accepted from
+/// bytecode. It is produced when a property of `this` resolves to a method,
+/// getter or setter in the class's vtable, and holds its disp_id.
+#[derive(Collect, Debug)]
+#[collect(require_static)]
+pub struct VerifiedMethod {
+    ops: Vec<Op>,
+}
+
+impl VerifiedMethod {
+    pub fn ops(&self) -> &[Op] {
+        &self.ops
+    }
+}
+
+/// The abstract state of the operand stack before an op executes.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Frame {
+    /// One entry per stack value, set if that value is known to be `this`.
+    stack: Vec<bool>,
+
+    /// The number of scopes pushed by this method.
+    scope_depth: u32,
+}
+
+/// Verify a method's body and build its optimized op stream.
+///
+/// `receiver` is the vtable of the `this` this method is being called with,
+/// if known. Properties of `this` are then resolved ahead of time, and the
+/// result must only be run with a receiver of that exact vtable.
+pub fn verify_method<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    method: Gc<'gc, BytecodeMethod<'gc>>,
+    receiver: Option<VTable<'gc>>,
+) -> Result<VerifiedMethod, Error> {
+    let body = method
+        .body()
+        .ok_or_else(|| Error::from("Cannot verify non-native method without body"))?;
+    let abc = method.abc();
+
+    let CheckedBody {
+        ops,
+        is_target,
+        frames,
+        tracks_this,
+    } = check_body(body, &abc, receiver.is_some())?;
+
+    // Each original op is replaced by zero or more ops.
+    let mut groups: Vec<Vec<Op>> = ops
+        .iter()
+        .zip(&frames)
+        .map(|(op, frame)| match frame {
+            // Unreachable code is dropped.
+            None => vec![],
+            Some(_) => vec![op.clone()],
+        })
+        .collect();
+
+    if let Some(receiver) = receiver.filter(|_| tracks_this) {
+        for (i, op) in ops.iter().enumerate() {
+            if let Some(frame) = &frames[i] {
+                if let Some(resolved) =
+                    resolve_this_property(activation, method, receiver, op, frame)?
+                {
+                    groups[i] = resolved;
+                }
+            }
+        }
+    }
+
+    for i in 0..ops.len() {
+        optimize_at(&mut groups, &is_target, &abc, i);
+    }
+
+    // Remap branch targets from original op indices to output indices.
+    let mut new_index = Vec::with_capacity(groups.len() + 1);
+    let mut next = 0;
+    for group in &groups {
+        new_index.push(next);
+        next += group.len();
+    }
+    new_index.push(next);
+
+    let mut optimized: Vec<Op> = groups.into_iter().flatten().collect();
+    for op in &mut optimized {
+        for offset in branch_offsets_mut(op) {
+            *offset = new_index[*offset as usize] as i32;
+        }
+    }
+    thread_jumps(&mut optimized);
+
+    Ok(VerifiedMethod { ops: optimized })
+}
+
+/// A method body that passed verification.
+struct CheckedBody {
+    /// The decoded ops, with branch offsets resolved to op indices.
+    ops: Vec<Op>,
+
+    /// Whether each op is the target of a branch or exception handler.
+    is_target: Vec<bool>,
+
+    /// The state before each op, or `None` if it is unreachable.
+    frames: Vec<Option<Frame>>,
+
+    /// Whether values on the stack are tracked as being `this`.
+    tracks_this: bool,
+}
+
+/// Decode a method body and check it for malformed bytecode.
+///
+/// `has_receiver` is set if `this` will be resolved ahead of time, in which
+/// case values known to be `this` are tracked through the stack.
+fn check_body(body: &MethodBody, abc: &AbcFile, has_receiver: bool) -> Result<CheckedBody, Error> {
+    let handler_offsets = body
+        .exceptions
+        .iter()
+        .map(|exception| exception.target_offset as usize);
+    let (ops, offsets) = decode(&body.code, handler_offsets)?;
+
+    let mut is_target = vec![false; ops.len()];
+    for op in &ops {
+        for target in branch_targets(op) {
+            is_target[target] = true;
+        }
+    }
+
+    let mut handlers = Vec::with_capacity(body.exceptions.len());
+    for exception in &body.exceptions {
+        let from = exception.from_offset as usize;
+        let to = exception.to_offset as usize;
+        match offsets.binary_search(&(exception.target_offset as usize)) {
+            Ok(target) if from <= to && to <= body.code.len() => {
+                is_target[target] = true;
+                handlers.push(target);
+            }
+            _ => return Err(
+                "VerifyError: Error #1054: Illegal range or target offsets in exception handler."
+                    .into(),
+            ),
+        }
+    }
+
+    let tracks_this = has_receiver && !ops.iter().any(writes_this_register);
+    let frames = analyze(&ops, &handlers, body, abc, tracks_this)?;
+
+    Ok(CheckedBody {
+        ops,
+        is_target,
+        frames,
+        tracks_this,
+    })
+}
+
+/// Decode the ops of a method body that control flow can reach.
+///
+/// Decoding starts at the entry point and at each exception handler, and
+/// follows branches from there, so bytes that no path reaches are never read.
+/// Returns the ops in code order, with branch offsets resolved to op indices,
+/// and the byte offset each op starts at.
+fn decode(
+    code: &[u8],
+    handler_offsets: impl Iterator<Item = usize>,
+) -> Result<(Vec<Op>, Vec<usize>), Error> {
+    // Decoded ops by start offset, along with their end offset.
+    let mut decoded = BTreeMap::new();
+    decode_from(code, 0, &mut decoded)?;
+    for offset in handler_offsets {
+        // Handlers that don't start on an instruction are rejected by the
+        // caller.
+        if offset < code.len() && !splits_op(&decoded, offset) {
+            decode_from(code, offset, &mut decoded)?;
+        }
+    }
+
+    let offsets: Vec<usize> = decoded.keys().copied().collect();
+    let mut ops = Vec::with_capacity(decoded.len());
+    for (start, (mut op, end)) in decoded {
+        let base = branch_base(&op, start, end);
+        for offset in branch_offsets_mut(&mut op) {
+            let target = (base as i64 + *offset as i64) as usize;
+            *offset = offsets.binary_search(&target).unwrap() as i32;
+        }
+        ops.push(op);
+    }
+
+    Ok((ops, offsets))
+}
+
+/// Decode every op reachable from `entry` that hasn't been decoded yet.
+fn decode_from(
+    code: &[u8],
+    entry: usize,
+    decoded: &mut BTreeMap<usize, (Op, usize)>,
+) -> Result<(), Error> {
+    let mut worklist = vec![entry];
+
+    while let Some(offset) = worklist.pop() {
+        // Falling off the end of the code is reported by `analyze`.
+        if offset == code.len() || decoded.contains_key(&offset) {
+            continue;
+        }
+
+        let rest = &code[offset..];
+        let mut reader = Reader::new(rest);
+        let mut op = reader.read_op().map_err(|e| {
+            format!(
+                "VerifyError: Error #1011: Method contained illegal opcode at offset {}: {}",
+                offset, e
+            )
+        })?;
+        let end = offset + reader.pos(rest);
+
+        // Reachable ops must not overlap.
+        if splits_op(decoded, offset) || decoded.range(offset + 1..end).next().is_some() {
+            return Err(branch_target_error());
+        }
+
+        let base = branch_base(&op, offset, end);
+        for target in branch_offsets_mut(&mut op) {
+            let target = base as i64 + *target as i64;
+            if !(0..code.len() as i64).contains(&target) {
+                return Err(branch_target_error());
+            }
+            worklist.push(target as usize);
+        }
+        if falls_through(&op) {
+            worklist.push(end);
+        }
+
+        decoded.insert(offset, (op, end));
+    }
+
+    Ok(())
+}
+
+/// Whether an offset lies inside an op that has already been decoded.
+fn splits_op(decoded: &BTreeMap<usize, (Op, usize)>, offset: usize) -> bool {
+    decoded
+        .range(..offset)
+        .next_back()
+        .map_or(false, |(_, (_, end))| offset < *end)
+}
+
+/// The byte offset that a branch op's offsets are relative to.
+///
+/// `LookupSwitch` offsets are relative to the start of the instruction, all
+/// others to its end.
+fn branch_base(op: &Op, start: usize, end: usize) -> usize {
+    if matches!(op, Op::LookupSwitch { .. }) {
+        start
+    } else {
+        end
+    }
+}
+
+fn branch_target_error() -> Error {
+    "VerifyError: Error #1021: At least one branch target was not on a valid instruction in the method."
+        .into()
+}
+
+fn branch_offsets_mut(op: &mut Op) -> Vec<&mut i32> {
+    match op {
+        Op::Jump { offset }
+        | Op::IfTrue { offset }
+        | Op::IfFalse { offset }
+        | Op::IfStrictEq { offset }
+        | Op::IfStrictNe { offset }
+        | Op::IfEq { offset }
+        | Op::IfNe { offset }
+        | Op::IfGe { offset }
+        | Op::IfGt { offset }
+        | Op::IfLe { offset }
+        | Op::IfLt { offset }
+        | Op::IfNge { offset }
+        | Op::IfNgt { offset }
+        | Op::IfNle { offset }
+        | Op::IfNlt { offset } => vec![offset],
+        Op::LookupSwitch {
+            default_offset,
+            case_offsets,
+        } => std::iter::once(default_offset)
+            .chain(case_offsets.iter_mut())
+            .collect(),
+        _ => vec![],
+    }
+}
+
+/// The op indices a branch op may jump to, once targets are resolved.
+fn branch_targets(op: &Op) -> Vec<usize> {
+    let mut op = op.clone();
+    branch_offsets_mut(&mut op)
+        .into_iter()
+        .map(|target| *target as usize)
+        .collect()
+}
+
+/// Whether an op can overwrite register 0, which holds `this`.
+fn writes_this_register(op: &Op) -> bool {
+    match op {
+        Op::SetLocal { index }
+        | Op::Kill { index }
+        | Op::IncLocal { index }
+        | Op::IncLocalI { index }
+        | Op::DecLocal { index }
+        | Op::DecLocalI { index } => *index == 0,
+        Op::HasNext2 {
+            object_register,
+            index_register,
+        } => *object_register == 0 || *index_register == 0,
+        _ => false,
+    }
+}
+
+/// Look up a multiname in the constant pool.
+fn pool_multiname<'a>(
+    abc: &'a AbcFile,
+    index: Index<AbcMultiname>,
+) -> Result<&'a AbcMultiname, Error> {
+    (index.0 as usize)
+        .checked_sub(1)
+        .and_then(|i| abc.constant_pool.multinames.get(i))
+        .ok_or_else(|| {
+            format!(
+                "VerifyError: Error #1032: Cpool index {} is out of range {}.",
+                index.0,
+                abc.constant_pool.multinames.len() + 1
+            )
+            .into()
+        })
+}
+
+/// The number of stack values holding the runtime parts of a multiname.
+fn runtime_name_args(abc: &AbcFile, index: Index<AbcMultiname>) -> Result<u32, Error> {
+    Ok(match pool_multiname(abc, index)? {
+        AbcMultiname::RTQName { .. } | AbcMultiname::RTQNameA { .. } => 1,
+        AbcMultiname::RTQNameL | AbcMultiname::RTQNameLA => 2,
+        AbcMultiname::MultinameL { .. } | AbcMultiname::MultinameLA { .. } => 1,
+        _ => 0,
+    })
+}
+
+/// Check that a constant pool index is in range.
+///
+/// Index zero is allowed, and refers to a default value.
+fn check_pool_index<T>(index: Index<T>, len: usize) -> Result<(), Error> {
+    if index.0 as usize > len {
+        return Err(format!(
+            "VerifyError: Error #1032: Cpool index {} is out of range {}.",
+            index.0,
+            len + 1
+        )
+        .into());
+    }
+
+    Ok(())
+}
+
+/// The number of values an op pops from and pushes onto the stack.
+fn stack_effect(op: &Op, abc: &AbcFile) -> Result<(u32, u32), Error> {
+    let pool = &abc.constant_pool;
+    Ok(match op {
+        Op::Add
+        | Op::AddI
+        | Op::BitAnd
+        | Op::BitOr
+        | Op::BitXor
+        | Op::Divide
+        | Op::Equals
+        | Op::GreaterEquals
+        | Op::GreaterThan
+        | Op::In
+        | Op::InstanceOf
+        | Op::IsTypeLate
+        | Op::AsTypeLate
+        | Op::LessEquals
+        | Op::LessThan
+        | Op::LShift
+        | Op::Modulo
+        | Op::Multiply
+        | Op::MultiplyI
+        | Op::RShift
+        | Op::StrictEquals
+        | Op::Subtract
+        | Op::SubtractI
+        | Op::URShift
+        | Op::HasNext
+        | Op::NextName
+        | Op::NextValue => (2, 1),
+        Op::BitNot
+        | Op::CheckFilter
+        | Op::CoerceA
+        | Op::CoerceB
+        | Op::CoerceD
+        | Op::CoerceI
+        | Op::CoerceO
+        | Op::CoerceS
+        | Op::CoerceU
+        | Op::ConvertB
+        | Op::ConvertD
+        | Op::ConvertI
+        | Op::ConvertO
+        | Op::ConvertS
+        | Op::ConvertU
+        | Op::Decrement
+        | Op::DecrementI
+        | Op::EscXAttr
+        | Op::EscXElem
+        | Op::Increment
+        | Op::IncrementI
+        | Op::Negate
+        | Op::NegateI
+        | Op::Not
+        | Op::TypeOf
+        | Op::Sxi1
+        | Op::Sxi8
+        | Op::Sxi16
+        | Op::Li8
+        | Op::Li16
+        | Op::Li32
+        | Op::Lf32
+        | Op::Lf64
+        | Op::GetSlot { .. }
+        | Op::NewClass { .. } => (1, 1),
+        Op::AsType { type_name: index } | Op::Coerce { index } | Op::IsType { index } => {
+            if index.0 != 0 {
+                pool_multiname(abc, *index)?;
+            }
+            (1, 1)
+        }
+        Op::Bkpt
+        | Op::BkptLine { .. }
+        | Op::Debug { .. }
+        | Op::DebugFile { .. }
+        | Op::DebugLine { .. }
+        | Op::DecLocal { .. }
+        | Op::DecLocalI { .. }
+        | Op::IncLocal { .. }
+        | Op::IncLocalI { .. }
+        | Op::Kill { .. }
+        | Op::Dxns { .. }
+        | Op::Jump { .. }
+        | Op::Label
+        | Op::Nop
+        | Op::PopScope
+        | Op::ReturnVoid
+        | Op::Timestamp => (0, 0),
+        Op::PushByte { .. }
+        | Op::PushFalse
+        | Op::PushNaN
+        | Op::PushNull
+        | Op::PushShort { .. }
+        | Op::PushTrue
+        | Op::PushUndefined
+        | Op::GetGlobalScope
+        | Op::GetGlobalSlot { .. }
+        | Op::GetLocal { .. }
+        | Op::GetOuterScope { .. }
+        | Op::GetScopeObject { .. }
+        | Op::HasNext2 { .. }
+        | Op::NewActivation
+        | Op::NewCatch { .. }
+        | Op::NewFunction { .. } => (0, 1),
+        Op::PushDouble { value } => {
+            check_pool_index(*value, pool.doubles.len())?;
+            (0, 1)
+        }
+        Op::PushInt { value } => {
+            check_pool_index(*value, pool.ints.len())?;
+            (0, 1)
+        }
+        Op::PushUint { value } => {
+            check_pool_index(*value, pool.uints.len())?;
+            (0, 1)
+        }
+        Op::PushString { value } => {
+            check_pool_index(*value, pool.strings.len())?;
+            (0, 1)
+        }
+        Op::PushNamespace { value } => {
+            check_pool_index(*value, pool.namespaces.len())?;
+            (0, 1)
+        }
+        Op::DxnsLate
+        | Op::IfTrue { .. }
+        | Op::IfFalse { .. }
+        | Op::LookupSwitch { .. }
+        | Op::Pop
+        | Op::PushScope
+        | Op::PushWith
+        | Op::ReturnValue
+        | Op::SetGlobalSlot { .. }
+        | Op::SetLocal { .. }
+        | Op::Throw => (1, 0),
+        Op::IfEq { .. }
+        | Op::IfNe { .. }
+        | Op::IfGe { .. }
+        | Op::IfGt { .. }
+        | Op::IfLe { .. }
+        | Op::IfLt { .. }
+        | Op::IfNge { .. }
+        | Op::IfNgt { .. }
+        | Op::IfNle { .. }
+        | Op::IfNlt { .. }
+        | Op::IfStrictEq { .. }
+        | Op::IfStrictNe { .. }
+        | Op::SetSlot { .. }
+        | Op::Si8
+        | Op::Si16
+        | Op::Si32
+        | Op::Sf32
+        | Op::Sf64 => (2, 0),
+        Op::Dup => (1, 2),
+        Op::Swap => (2, 2),
+        Op::ApplyType { num_types } => (num_types + 1, 1),
+        Op::Call { num_args } => (num_args + 2, 1),
+        Op::CallStatic { num_args, .. } | Op::Construct { num_args } => (num_args + 1, 1),
+        Op::ConstructSuper { num_args } => (num_args + 1, 0),
+        Op::NewArray { num_args } => (*num_args, 1),
+        Op::NewObject { num_args } => (num_args * 2, 1),
+        Op::PushConstant { .. } => {
+            return Err(
+                "VerifyError: Error #1011: The pushconstant opcode is not supported.".into(),
+            );
+        }
+        Op::CallMethod { .. } => {
+            return Err("VerifyError: The callmethod opcode is not supported.".into());
+        }
+        Op::CallProperty { index, num_args }
+        | Op::CallPropLex { index, num_args }
+        | Op::CallSuper { index, num_args }
+        | Op::ConstructProp { index, num_args } => {
+            (num_args + 1 + runtime_name_args(abc, *index)?, 1)
+        }
+        Op::CallPropVoid { index, num_args } | Op::CallSuperVoid { index, num_args } => {
+            (num_args + 1 + runtime_name_args(abc, *index)?, 0)
+        }
+        Op::DeleteProperty { index }
+        | Op::GetDescendants { index }
+        | Op::GetProperty { index }
+        | Op::GetSuper { index } => (1 + runtime_name_args(abc, *index)?, 1),
+        Op::InitProperty { index } | Op::SetProperty { index } | Op::SetSuper { index } => {
+            (2 + runtime_name_args(abc, *index)?, 0)
+        }
+        Op::FindProperty { index } | Op::FindPropStrict { index } => {
+            (runtime_name_args(abc, *index)?, 1)
+        }
+        Op::FindDef { index } | Op::GetLex { index } => {
+            if runtime_name_args(abc, *index)? != 0 {
+                return Err(
+                    "VerifyError: Error #1078: Illegal opcode/multiname combination.".into(),
+                );
+            }
+            (0, 1)
+        }
+    })
+}
+
+/// Check that an op only accesses registers that exist.
+fn check_registers(op: &Op, num_locals: u32) -> Result<(), Error> {
+    let check = |index: u32| {
+        if index >= num_locals {
+            Err(Error::from(format!(
+                "VerifyError: Error #1025: An invalid register {} was accessed.",
+                index
+            )))
+        } else {
+            Ok(())
+        }
+    };
+
+    match op {
+        Op::GetLocal { index }
+        | Op::SetLocal { index }
+        | Op::Kill { index }
+        | Op::IncLocal { index }
+        | Op::IncLocalI { index }
+        | Op::DecLocal { index }
+        | Op::DecLocalI { index } => check(*index),
+        Op::HasNext2 {
+            object_register,
+            index_register,
+        } => {
+            check(*object_register)?;
+            check(*index_register)
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Whether the op after an op may run after it.
+fn falls_through(op: &Op) -> bool {
+    !matches!(
+        op,
+        Op::Jump { .. } | Op::LookupSwitch { .. } | Op::ReturnValue | Op::ReturnVoid | Op::Throw
+    )
+}
+
+/// The ops that may run after an op.
+fn successors(op: &Op, index: usize) -> Vec<usize> {
+    let mut successors = branch_targets(op);
+    if falls_through(op) {
+        successors.push(index + 1);
+    }
+    successors
+}
+
+/// Compute the stack and scope state before each reachable op, checking
+/// that both stay within the method's declared limits and agree wherever
+/// control flow merges.
+fn analyze(
+    ops: &[Op],
+    handlers: &[usize],
+    body: &MethodBody,
+    abc: &AbcFile,
+    tracks_this: bool,
+) -> Result<Vec<Option<Frame>>, Error> {
+    let max_scope_depth = body.max_scope_depth.saturating_sub(body.init_scope_depth);
+    let mut frames: Vec<Option<Frame>> = vec![None; ops.len()];
+    let mut worklist = vec![];
+
+    if ops.is_empty() {
+        return Err("VerifyError: Error #1020: Code cannot fall off the end of a method.".into());
+    }
+
+    merge(&mut frames, &mut worklist, 0, Frame::default())?;
+    for &handler in handlers {
+        // Handlers start with the caught exception on the stack.
+        let frame = Frame {
+            stack: vec![false],
+            scope_depth: 0,
+        };
+        merge(&mut frames, &mut worklist, handler, frame)?;
+    }
+
+    while let Some(i) = worklist.pop() {
+        let op = &ops[i];
+        let mut frame = frames[i].clone().unwrap();
+
+        check_registers(op, body.num_locals)?;
+        let (pops, pushes) = stack_effect(op, abc)?;
+        if pops as usize > frame.stack.len() {
+            return Err("VerifyError: Error #1024: Stack underflow occurred.".into());
+        }
+
+        let popped = frame.stack.split_off(frame.stack.len() - pops as usize);
+        match op {
+            Op::GetLocal { index: 0 } => frame.stack.push(tracks_this),
+            Op::Dup => frame.stack.extend([popped[0], popped[0]]),
+            Op::Swap => frame.stack.extend([popped[1], popped[0]]),
+            _ => frame
+                .stack
+                .extend(std::iter::repeat(false).take(pushes as usize)),
+        }
+        if frame.stack.len() > body.max_stack as usize {
+            return Err("VerifyError: Error #1023: Stack overflow occurred.".into());
+        }
+
+        match op {
+            Op::PushScope | Op::PushWith => {
+                frame.scope_depth += 1;
+                if frame.scope_depth > max_scope_depth {
+                    return Err("VerifyError: Error #1017: Scope stack overflow occurred.".into());
+                }
+            }
+            Op::PopScope => {
+                frame.scope_depth = frame.scope_depth.checked_sub(1).ok_or_else(|| {
+                    Error::from("VerifyError: Error #1018: Scope stack underflow occurred.")
+                })?;
+            }
+            _ => {}
+        }
+
+        for successor in successors(op, i) {
+            if successor >= ops.len() {
+                return Err(
+                    "VerifyError: Error #1020: Code cannot fall off the end of a method.".into(),
+                );
+            }
+            merge(&mut frames, &mut worklist, successor, frame.clone())?;
+        }
+    }
+
+    Ok(frames)
+}
+
+/// Merge the state flowing into an op with its known state.
+fn merge(
+    frames: &mut [Option<Frame>],
+    worklist: &mut Vec<usize>,
+    index: usize,
+    frame: Frame,
+) -> Result<(), Error> {
+    match &mut frames[index] {
+        Some(existing) => {
+            if existing.stack.len() != frame.stack.len() {
+                return Err(format!(
+                    "VerifyError: Error #1030: Stack depth is unbalanced. {} != {}.",
+                    existing.stack.len(),
+                    frame.stack.len()
+                )
+                .into());
+            }
+            if existing.scope_depth != frame.scope_depth {
+                return Err(format!(
+                    "VerifyError: Error #1031: Scope depth is unbalanced. {} != {}.",
+                    existing.scope_depth, frame.scope_depth
+                )
+                .into());
+            }
+
+            // A value is only known to be `this` if it is on every path.
+            let mut changed = false;
+            for (existing, incoming) in existing.stack.iter_mut().zip(frame.stack) {
+                if *existing && !incoming {
+                    *existing = false;
+                    changed = true;
+                }
+            }
+            if changed {
+                worklist.push(index);
+            }
+        }
+        slot @ None => {
+            *slot = Some(frame);
+            worklist.push(index);
+        }
+    }
+
+    Ok(())
+}
+
+/// Resolve an access to a property of `this` to a slot or method of its
+/// class.
+fn resolve_this_property<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    method: Gc<'gc, BytecodeMethod<'gc>>,
+    receiver: VTable<'gc>,
+    op: &Op,
+    frame: &Frame,
+) -> Result<Option<Vec<Op>>, Error> {
+    let (index, receiver_depth) = match op {
+        Op::GetProperty { index } => (*index, 0),
+        Op::SetProperty { index } => (*index, 1),
+        Op::CallProperty { index, num_args } | Op::CallPropVoid { index, num_args } => {
+            (*index, *num_args as usize)
+        }
+        _ => return Ok(None),
+    };
+
+    // Only names known ahead of time can be resolved.
+    let abc = method.abc();
+    if !matches!(
+        pool_multiname(&abc, index)?,
+        AbcMultiname::QName { .. } | AbcMultiname::Multiname { .. }
+    ) {
+        return Ok(None);
+    }
+
+    let is_this = frame
+        .stack
+        .len()
+        .checked_sub(receiver_depth + 1)
+        .map(|i| frame.stack[i])
+        .unwrap_or(false);
+    if !is_this {
+        return Ok(None);
+    }
+
+    let name = Multiname::from_abc_multiname_static(
+        method.translation_unit(),
+        index,
+        activation.context.gc_context,
+    )?;
+    let property = match receiver.get_trait(&name) {
+        Some(property) => property,
+        None => return Ok(None),
+    };
+
+    Ok(match (op, property) {
+        (Op::GetProperty { .. }, Property::Slot { slot_id } | Property::ConstSlot { slot_id }) => {
+            Some(vec![Op::GetSlot { index: slot_id }])
+        }
+        (Op::GetProperty { .. }, Property::Virtual { get: Some(get), .. }) => {
+            Some(vec![Op::CallMethod {
+                index: Index::new(get),
+                num_args: 0,
+            }])
+        }
+        (Op::SetProperty { .. }, Property::Slot { slot_id }) => {
+            Some(vec![Op::SetSlot { index: slot_id }])
+        }
+        (Op::SetProperty { .. }, Property::Virtual { set: Some(set), .. }) => Some(vec![
+            Op::CallMethod {
+                index: Index::new(set),
+                num_args: 1,
+            },
+            Op::Pop,
+        ]),
+        (Op::CallProperty { num_args, .. }, Property::Method { disp_id }) => {
+            Some(vec![Op::CallMethod {
+                index: Index::new(disp_id),
+                num_args: *num_args,
+            }])
+        }
+        (Op::CallPropVoid { num_args, .. }, Property::Method { disp_id }) => Some(vec![
+            Op::CallMethod {
+                index: Index::new(disp_id),
+                num_args: *num_args,
+            },
+            Op::Pop,
+        ]),
+        _ => None,
+    })
+}
+
+/// The value pushed by an integer constant op.
+fn int_constant(op: &Op, abc: &AbcFile) -> Option<i32> {
+    match op {
+        Op::PushByte { value } => Some(*value as i8 as i32),
+        Op::PushShort { value } => Some(i32::from(*value)),
+        Op::PushInt { value } => {
+            let index = (value.0 as usize).checked_sub(1)?;
+            abc.constant_pool.ints.get(index).copied()
+        }
+        _ => None,
+    }
+}
+
+/// Get the single op at an index, if it hasn't been rewritten or removed.
+fn single(groups: &[Vec<Op>], index: usize) -> Option<&Op> {
+    match groups.get(index).map(|group| group.as_slice()) {
+        Some([op]) => Some(op),
+        _ => None,
+    }
+}
+
+/// Apply peephole optimizations to the ops starting at `i`.
+///
+/// Only the first op of a pattern may be a branch target.
+fn optimize_at(groups: &mut [Vec<Op>], is_target: &[bool], abc: &AbcFile, i: usize) {
+    let op = match single(groups, i) {
+        Some(op) => op.clone(),
+        None => return,
+    };
+    let next = single(groups, i + 1).filter(|_| !is_target[i + 1]).cloned();
+    let after = single(groups, i + 2).filter(|_| !is_target[i + 2]).cloned();
+
+    match (&op, &next, &after) {
+        (Op::Nop | Op::Label, _, _) => groups[i].clear(),
+
+        // Fold arithmetic on integer constants.
+        (_, Some(second), Some(operation)) if int_constant(&op, abc).is_some() => {
+            let value1 = int_constant(&op, abc).unwrap();
+            let value2 = match int_constant(second, abc) {
+                Some(value2) => value2,
+                None => return optimize_push_int(groups, abc, &op, i),
+            };
+            let result = match operation {
+                Op::AddI => value1.wrapping_add(value2),
+                Op::SubtractI => value1.wrapping_sub(value2),
+                Op::MultiplyI => value1.wrapping_mul(value2),
+                Op::BitAnd => value1 & value2,
+                Op::BitOr => value1 | value2,
+                Op::BitXor => value1 ^ value2,
+                Op::LShift => value1 << (value2 & 0x1F),
+                Op::RShift => value1 >> (value2 & 0x1F),
+                _ => return optimize_push_int(groups, abc, &op, i),
+            };
+            match i16::try_from(result) {
+                Ok(value) => {
+                    groups[i] = vec![Op::PushShort { value }];
+                    groups[i + 1].clear();
+                    groups[i + 2].clear();
+                }
+                Err(_) => optimize_push_int(groups, abc, &op, i),
+            }
+        }
+        (Op::PushInt { .. }, _, _) => optimize_push_int(groups, abc, &op, i),
+
+        // Resolve branches on constant booleans.
+        (Op::PushTrue | Op::PushFalse, Some(Op::IfTrue { offset } | Op::IfFalse { offset }), _) => {
+            let taken = matches!(
+                (&op, &next),
+                (Op::PushTrue, Some(Op::IfTrue { .. })) | (Op::PushFalse, Some(Op::IfFalse { .. }))
+            );
+            groups[i].clear();
+            groups[i + 1] = if taken {
+                vec![Op::Jump { offset: *offset }]
+            } else {
+                vec![]
+            };
+        }
+
+        // Fuse increments and decrements of a register.
+        (Op::GetLocal { index }, Some(operation), Some(Op::SetLocal { index: set_index }))
+            if index == set_index =>
+        {
+            let index = *index;
+            let fused = match operation {
+                Op::Increment => Op::IncLocal { index },
+                Op::IncrementI => Op::IncLocalI { index },
+                Op::Decrement => Op::DecLocal { index },
+                Op::DecrementI => Op::DecLocalI { index },
+                _ => return,
+            };
+            groups[i] = vec![fused];
+            groups[i + 1].clear();
+            groups[i + 2].clear();
+        }
+        _ => {}
+    }
+}
+
+/// Replace a `PushInt` of a small value with a `PushShort`, which doesn't
+/// need a constant pool lookup.
+fn optimize_push_int(groups: &mut [Vec<Op>], abc: &AbcFile, op: &Op, i: usize) {
+    if let Op::PushInt { .. } = op {
+        if let Some(Ok(value)) = int_constant(op, abc).map(i16::try_from) {
+            groups[i] = vec![Op::PushShort { value }];
+        }
+    }
+}
+
+/// Retarget jumps that land on another jump to that jump's target.
+fn thread_jumps(ops: &mut [Op]) {
+    for i in 0..ops.len() {
+        let mut target = match &ops[i] {
+            Op::Jump { offset } => *offset as usize,
+            _ => continue,
+        };
+        for _ in 0..MAX_JUMP_THREADING {
+            match ops.get(target) {
+                Some(Op::Jump { offset }) if *offset as usize != target => {
+                    target = *offset as usize
+                }
+                _ => break,
+            }
+        }
+        ops[i] = Op::Jump {
+            offset: target as i32,
+        };
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,172 @@
+//! Tests for AVM2 bytecode verification
+
+use crate::avm2::verify::check_body;
+use swf::avm2::types::{AbcFile, ConstantPool, Exception, Index, MethodBody};
+
+fn abc() -> AbcFile {
+    AbcFile {
+        major_version: 46,
+        minor_version: 16,
+        constant_pool: ConstantPool {
+            ints: vec![],
+            uints: vec![],
+            doubles: vec![],
+            strings: vec![],
+            namespaces: vec![],
+            namespace_sets: vec![],
+            multinames: vec![],
+        },
+        methods: vec![],
+        metadata: vec![],
+        instances: vec![],
+        classes: vec![],
+        scripts: vec![],
+        method_bodies: vec![],
+    }
+}
+
+fn body(max_stack: u32, code: &[u8], exceptions: Vec<Exception>) -> MethodBody {
+    MethodBody {
+        method: Index::new(0),
+        max_stack,
+        num_locals: 1,
+        init_scope_depth: 0,
+        max_scope_depth: 1,
+        code: code.to_vec(),
+        exceptions,
+        traits: vec![],
+    }
+}
+
+fn exception(from_offset: u32, to_offset: u32, target_offset: u32) -> Exception {
+    Exception {
+        from_offset,
+        to_offset,
+        target_offset,
+        variable_name: Index::new(0),
+        type_name: Index::new(0),
+    }
+}
+
+fn verify_error(body: &MethodBody) -> String {
+    match check_body(body, &abc(), false) {
+        Ok(_) => panic!("Expected {:?} to fail verification", body.code),
+        Err(e) => e.to_string(),
+    }
+}
+
+#[test]
+fn valid_body() {
+    // pushbyte 1; pop; returnvoid; pop; returnvoid
+    let code = [0x24, 0x01, 0x29, 0x47, 0x29, 0x47];
+    let body = body(1, &code, vec![exception(0, 3, 4)]);
+    let checked = check_body(&body, &abc(), false).unwrap();
+
+    assert_eq!(checked.ops.len(), 5);
+    assert!(checked.frames.iter().all(|frame| frame.is_some()));
+    assert!(checked.is_target[3]);
+}
+
+#[test]
+fn unreachable_ops_are_not_decoded() {
+    // returnvoid; pushbyte 1; pop; returnvoid
+    let code = [0x47, 0x24, 0x01, 0x29, 0x47];
+    let checked = check_body(&body(1, &code, vec![]), &abc(), false).unwrap();
+
+    assert_eq!(checked.ops.len(), 1);
+    assert!(checked.frames[0].is_some());
+}
+
+#[test]
+fn garbage_after_jump() {
+    // jump +2; <0xff 0xff, not an opcode>; returnvoid
+    let code = [0x10, 0x02, 0x00, 0x00, 0xff, 0xff, 0x47];
+    let checked = check_body(&body(1, &code, vec![]), &abc(), false).unwrap();
+
+    assert_eq!(checked.ops.len(), 2);
+    assert!(checked.is_target[1]);
+    assert!(checked.frames.iter().all(|frame| frame.is_some()));
+}
+
+#[test]
+fn garbage_reached_by_fall_through() {
+    // nop; <0xff, not an opcode>
+    let code = [0x02, 0xff];
+    assert!(verify_error(&body(1, &code, vec![])).contains("Error #1011"));
+}
+
+#[test]
+fn jump_into_instruction() {
+    // pushtrue; iftrue +1 (into the middle of pushbyte); pushbyte 5; pop;
+    // returnvoid
+    let code = [0x26, 0x11, 0x01, 0x00, 0x00, 0x24, 0x05, 0x29, 0x47];
+    assert!(verify_error(&body(1, &code, vec![])).contains("Error #1021"));
+}
+
+#[test]
+fn jump_past_end() {
+    // jump +2 (past the end of the code); returnvoid
+    let code = [0x10, 0x02, 0x00, 0x00, 0x47];
+    assert!(verify_error(&body(1, &code, vec![])).contains("Error #1021"));
+}
+
+#[test]
+fn fall_off_end() {
+    // nop
+    assert!(verify_error(&body(1, &[0x02], vec![])).contains("Error #1020"));
+}
+
+#[test]
+fn stack_overflow() {
+    // pushbyte 1; pushbyte 2; pop; pop; returnvoid
+    let code = [0x24, 0x01, 0x24, 0x02, 0x29, 0x29, 0x47];
+    assert!(verify_error(&body(1, &code, vec![])).contains("Error #1023"));
+    assert!(check_body(&body(2, &code, vec![]), &abc(), false).is_ok());
+}
+
+#[test]
+fn stack_underflow() {
+    // pushbyte 1; pop; pop; returnvoid
+    let code = [0x24, 0x01, 0x29, 0x29, 0x47];
+    assert!(verify_error(&body(1, &code, vec![])).contains("Error #1024"));
+}
+
+#[test]
+fn unbalanced_stack() {
+    // pushtrue; iftrue +2; pushbyte 1; returnvoid
+    let code = [0x26, 0x11, 0x02, 0x00, 0x00, 0x24, 0x01, 0x47];
+    assert!(verify_error(&body(1, &code, vec![])).contains("Error #1030"));
+}
+
+#[test]
+fn exception_range_reversed() {
+    // pushbyte 1; pop; returnvoid
+    let code = [0x24, 0x01, 0x29, 0x47];
+    let body = body(1, &code, vec![exception(3, 0, 3)]);
+    assert!(verify_error(&body).contains("Error #1054"));
+}
+
+#[test]
+fn exception_target_inside_instruction() {
+    // pushbyte 1; pop; returnvoid
+    let code = [0x24, 0x01, 0x29, 0x47];
+    let body = body(1, &code, vec![exception(0, 3, 1)]);
+    assert!(verify_error(&body).contains("Error #1054"));
+}
+
+#[test]
+fn exception_range_past_end() {
+    // pushbyte 1; pop; returnvoid
+    let code = [0x24, 0x01, 0x29, 0x47];
+    let body = body(1, &code, vec![exception(0, 5, 3)]);
+    assert!(verify_error(&body).contains("Error #1054"));
+}
+
+#[test]
+fn push_constant_rejected() {
+    // pushconstant 0; returnvalue
+    let code = [0x22, 0x00, 0x48];
+    let error = verify_error(&body(1, &code, vec![]));
+    assert!(error.contains("Error #1011"));
+    assert!(error.contains("pushconstant"));
+}
@@ -47,6 +47,10 @@ impl<'gc> VTable<'gc> {
         ))
     }
 
+    pub fn ptr_eq(a: Self, b: Self) -> bool {
+        GcCell::ptr_eq(a.0, b.0)
+    }
+
     pub fn duplicate(self, mc: MutationContext<'gc, '_>) -> Self {
         VTable(GcCell::allocate(mc, self.0.read().clone()))
     }
@@ -84,6 +88,21 @@ impl<'gc> VTable<'gc> {
         Ref::map(self.0.read(), |v| &v.default_slots)
     }
 
+    /// List the names of all slot traits, along with their slot IDs.
+    pub fn slot_names(self) -> Vec<(AvmString<'gc>, u32)> {
+        self.0
+            .read()
+            .resolved_traits
+            .iter()
+            .filter_map(|(name, _ns, prop)| match prop {
+                Property::Slot { slot_id } | Property::ConstSlot { slot_id } => {
+                    Some((name, *slot_id))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
     /// Calculate the flattened list of instance traits that this class
     /// maintains.
     ///
@@ -1,7 +1,9 @@
 pub mod audio;
+pub mod font;
 pub mod locale;
 pub mod log;
 pub mod navigator;
+pub mod print;
 pub mod render;
 pub mod storage;
 pub mod ui;
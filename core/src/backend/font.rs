@@ -0,0 +1,107 @@
+//! Device font-related platform functions
+
+pub use crate::font::FontDescriptor;
+
+pub type Error = Box<dyn std::error::Error>;
+
+/// Provides the fonts installed on the system, used to render text fields
+/// that use device fonts.
+pub trait FontBackend {
+    /// The family names of all available fonts.
+    fn font_names(&self) -> Vec<String>;
+
+    /// Load the TrueType or OpenType font file of the given family that best
+    /// matches the style of `descriptor`.
+    ///
+    /// Returns `None` if no font of that family is available.
+    fn load_font(&mut self, descriptor: &FontDescriptor) -> Option<Vec<u8>>;
+}
+
+/// Pick the font of the same family as `descriptor` that best matches its
+/// style.
+///
+/// Family names are compared case-insensitively. A font of the requested
+/// family is always returned if one exists, even if its style differs.
+pub fn best_match<'a, T>(
+    fonts: impl IntoIterator<Item = (&'a FontDescriptor, T)>,
+    descriptor: &FontDescriptor,
+) -> Option<T> {
+    fonts
+        .into_iter()
+        .filter(|(font, _)| font.class().eq_ignore_ascii_case(descriptor.class()))
+        .max_by_key(|(font, _)| {
+            (
+                font.bold() == descriptor.bold(),
+                font.italic() == descriptor.italic(),
+            )
+        })
+        .map(|(_, font)| font)
+}
+
+/// Font backend without any fonts.
+///
+/// Device text is always rendered with the player's embedded fallback font.
+pub struct NullFontBackend {}
+
+impl NullFontBackend {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl FontBackend for NullFontBackend {
+    fn font_names(&self) -> Vec<String> {
+        vec![]
+    }
+
+    fn load_font(&mut self, _descriptor: &FontDescriptor) -> Option<Vec<u8>> {
+        None
+    }
+}
+
+impl Default for NullFontBackend {
+    fn default() -> Self {
+        NullFontBackend::new()
+    }
+}
+
+/// Font backend that provides fonts held in memory, such as fonts downloaded
+/// by the web frontend.
+#[derive(Default)]
+pub struct MemoryFontBackend {
+    fonts: Vec<(FontDescriptor, Vec<u8>)>,
+}
+
+impl MemoryFontBackend {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Add a TrueType or OpenType font file.
+    pub fn add_font(&mut self, data: Vec<u8>) -> Result<(), Error> {
+        let descriptor = FontDescriptor::from_font_file(&data)?;
+        self.fonts.push((descriptor, data));
+        Ok(())
+    }
+}
+
+impl FontBackend for MemoryFontBackend {
+    fn font_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .fonts
+            .iter()
+            .map(|(font, _)| font.class().to_string())
+            .collect();
+        names.sort_unstable();
+        names.dedup();
+        names
+    }
+
+    fn load_font(&mut self, descriptor: &FontDescriptor) -> Option<Vec<u8>> {
+        best_match(
+            self.fonts.iter().map(|(font, data)| (font, data)),
+            descriptor,
+        )
+        .cloned()
+    }
+}
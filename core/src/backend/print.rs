@@ -0,0 +1,124 @@
+//! Printing-related platform functions
+
+use crate::backend::render::{CommandList, RenderBackend};
+
+pub type Error = Box<dyn std::error::Error>;
+
+/// The orientation of the paper used by a print job.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PrintOrientation {
+    Portrait,
+    Landscape,
+}
+
+impl PrintOrientation {
+    /// The name of this orientation, as used by ActionScript.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PrintOrientation::Portrait => "portrait",
+            PrintOrientation::Landscape => "landscape",
+        }
+    }
+}
+
+/// The settings chosen by the user when a print job was started.
+///
+/// All dimensions are in points (1/72 of an inch). One point is rendered as
+/// one pixel of the movie.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PrintJobSettings {
+    /// The size of the paper.
+    pub paper_width: u32,
+    pub paper_height: u32,
+
+    /// The size of the printable area of the paper.
+    pub page_width: u32,
+    pub page_height: u32,
+
+    pub orientation: PrintOrientation,
+}
+
+impl Default for PrintJobSettings {
+    /// US Letter paper in portrait orientation, with quarter-inch margins.
+    fn default() -> Self {
+        Self {
+            paper_width: 612,
+            paper_height: 792,
+            page_width: 576,
+            page_height: 756,
+            orientation: PrintOrientation::Portrait,
+        }
+    }
+}
+
+/// A single page of a print job.
+#[derive(Clone, Debug)]
+pub struct PrintPage {
+    /// The size of the page, in points.
+    pub width: u32,
+    pub height: u32,
+
+    /// The drawing operations that make up this page.
+    ///
+    /// These reference shapes and bitmaps registered with the player's
+    /// renderer, which is passed alongside the page.
+    pub commands: CommandList,
+
+    /// Whether the content requested to be printed as a bitmap rather than
+    /// as vectors (`printAsBitmap`).
+    pub print_as_bitmap: bool,
+}
+
+pub trait PrintBackend {
+    /// Begins a new print job, usually by showing a print dialog to the user.
+    ///
+    /// Returns `None` if printing is unavailable or the user cancelled.
+    fn start_job(&mut self) -> Option<PrintJobSettings>;
+
+    /// Adds a page to the current print job.
+    ///
+    /// This is called as soon as the movie adds the page. The page's commands
+    /// may be replayed on `renderer`, for example with
+    /// `RenderBackend::render_offscreen` to rasterize the page. This should
+    /// happen right away, as the shapes they reference may change once the
+    /// movie continues.
+    fn add_page(&mut self, renderer: &mut dyn RenderBackend, page: PrintPage) -> Result<(), Error>;
+
+    /// Finishes the current print job and sends it to the printer.
+    fn send(&mut self) -> Result<(), Error>;
+}
+
+/// Print backend that does nothing.
+///
+/// Print jobs can never be started, as if no printer were installed.
+pub struct NullPrintBackend {}
+
+impl NullPrintBackend {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl PrintBackend for NullPrintBackend {
+    fn start_job(&mut self) -> Option<PrintJobSettings> {
+        None
+    }
+
+    fn add_page(
+        &mut self,
+        _renderer: &mut dyn RenderBackend,
+        _page: PrintPage,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn send(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+impl Default for NullPrintBackend {
+    fn default() -> Self {
+        NullPrintBackend::new()
+    }
+}
@@ -60,6 +60,20 @@ pub trait RenderBackend: Downcast {
         height: u32,
         rgba: Vec<u8>,
     ) -> Result<BitmapHandle, Error>;
+
+    /// Renders a list of commands into a new bitmap of the given size,
+    /// without affecting the frame currently being drawn.
+    ///
+    /// Returns `None` if this backend is unable to render offscreen.
+    fn render_offscreen(
+        &mut self,
+        _commands: &CommandList,
+        _width: u32,
+        _height: u32,
+        _clear: Color,
+    ) -> Option<Bitmap> {
+        None
+    }
 }
 impl_downcast!(RenderBackend);
 
@@ -200,6 +214,226 @@ impl RenderBackend for NullRenderer {
     }
 }
 
+/// A single drawing operation, as recorded by a `CommandRecorder`.
+#[derive(Clone, Debug)]
+pub enum Command {
+    RenderBitmap {
+        bitmap: BitmapHandle,
+        transform: Transform,
+        smoothing: bool,
+    },
+    RenderShape {
+        shape: ShapeHandle,
+        transform: Transform,
+    },
+    DrawRect {
+        color: Color,
+        matrix: Matrix,
+    },
+    PushMask,
+    ActivateMask,
+    DeactivateMask,
+    PopMask,
+}
+
+/// A list of drawing operations that can be replayed onto a `RenderBackend`.
+///
+/// Any handles referenced by the commands belong to the renderer that was
+/// used to record them, and are only valid on that renderer.
+#[derive(Clone, Debug, Default)]
+pub struct CommandList(pub Vec<Command>);
+
+impl CommandList {
+    pub fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Replays all commands onto the given renderer.
+    ///
+    /// The caller is responsible for beginning and ending a frame.
+    pub fn execute(&self, renderer: &mut dyn RenderBackend) {
+        for command in &self.0 {
+            match command {
+                Command::RenderBitmap {
+                    bitmap,
+                    transform,
+                    smoothing,
+                } => renderer.render_bitmap(*bitmap, transform, *smoothing),
+                Command::RenderShape { shape, transform } => {
+                    renderer.render_shape(*shape, transform)
+                }
+                Command::DrawRect { color, matrix } => renderer.draw_rect(color.clone(), matrix),
+                Command::PushMask => renderer.push_mask(),
+                Command::ActivateMask => renderer.activate_mask(),
+                Command::DeactivateMask => renderer.deactivate_mask(),
+                Command::PopMask => renderer.pop_mask(),
+            }
+        }
+    }
+}
+
+/// A renderer that records drawing operations into a `CommandList` instead
+/// of drawing them.
+///
+/// Shapes and bitmaps are still registered with the wrapped renderer, so the
+/// recorded commands can be replayed onto it afterwards.
+pub struct CommandRecorder {
+    renderer: Box<dyn RenderBackend>,
+    commands: CommandList,
+}
+
+impl CommandRecorder {
+    pub fn new(renderer: Box<dyn RenderBackend>) -> Self {
+        Self {
+            renderer,
+            commands: CommandList::new(),
+        }
+    }
+
+    /// Stops recording, returning the wrapped renderer and the recorded commands.
+    pub fn finish(self) -> (Box<dyn RenderBackend>, CommandList) {
+        (self.renderer, self.commands)
+    }
+}
+
+impl RenderBackend for CommandRecorder {
+    // The wrapped renderer keeps its own viewport while recording.
+    fn set_viewport_dimensions(&mut self, _width: u32, _height: u32) {}
+
+    fn register_shape(
+        &mut self,
+        shape: DistilledShape,
+        bitmap_source: &dyn BitmapSource,
+    ) -> ShapeHandle {
+        self.renderer.register_shape(shape, bitmap_source)
+    }
+
+    fn replace_shape(
+        &mut self,
+        shape: DistilledShape,
+        bitmap_source: &dyn BitmapSource,
+        handle: ShapeHandle,
+    ) {
+        self.renderer.replace_shape(shape, bitmap_source, handle)
+    }
+
+    fn register_glyph_shape(&mut self, shape: &swf::Glyph) -> ShapeHandle {
+        self.renderer.register_glyph_shape(shape)
+    }
+
+    fn register_bitmap_jpeg(
+        &mut self,
+        data: &[u8],
+        jpeg_tables: Option<&[u8]>,
+    ) -> Result<BitmapInfo, Error> {
+        self.renderer.register_bitmap_jpeg(data, jpeg_tables)
+    }
+
+    fn register_bitmap_jpeg_2(&mut self, data: &[u8]) -> Result<BitmapInfo, Error> {
+        self.renderer.register_bitmap_jpeg_2(data)
+    }
+
+    fn register_bitmap_jpeg_3_or_4(
+        &mut self,
+        jpeg_data: &[u8],
+        alpha_data: &[u8],
+    ) -> Result<BitmapInfo, Error> {
+        self.renderer
+            .register_bitmap_jpeg_3_or_4(jpeg_data, alpha_data)
+    }
+
+    fn register_bitmap_png(
+        &mut self,
+        swf_tag: &swf::DefineBitsLossless,
+    ) -> Result<BitmapInfo, Error> {
+        self.renderer.register_bitmap_png(swf_tag)
+    }
+
+    fn begin_frame(&mut self, _clear: Color) {}
+
+    fn render_bitmap(&mut self, bitmap: BitmapHandle, transform: &Transform, smoothing: bool) {
+        self.commands.0.push(Command::RenderBitmap {
+            bitmap,
+            transform: transform.clone(),
+            smoothing,
+        });
+    }
+
+    fn render_shape(&mut self, shape: ShapeHandle, transform: &Transform) {
+        self.commands.0.push(Command::RenderShape {
+            shape,
+            transform: transform.clone(),
+        });
+    }
+
+    fn draw_rect(&mut self, color: Color, matrix: &Matrix) {
+        self.commands.0.push(Command::DrawRect {
+            color,
+            matrix: *matrix,
+        });
+    }
+
+    fn end_frame(&mut self) {}
+
+    fn push_mask(&mut self) {
+        self.commands.0.push(Command::PushMask);
+    }
+
+    fn activate_mask(&mut self) {
+        self.commands.0.push(Command::ActivateMask);
+    }
+
+    fn deactivate_mask(&mut self) {
+        self.commands.0.push(Command::DeactivateMask);
+    }
+
+    fn pop_mask(&mut self) {
+        self.commands.0.push(Command::PopMask);
+    }
+
+    fn get_bitmap_pixels(&mut self, bitmap: BitmapHandle) -> Option<Bitmap> {
+        self.renderer.get_bitmap_pixels(bitmap)
+    }
+
+    fn register_bitmap_raw(
+        &mut self,
+        width: u32,
+        height: u32,
+        rgba: Vec<u8>,
+    ) -> Result<BitmapHandle, Error> {
+        self.renderer.register_bitmap_raw(width, height, rgba)
+    }
+
+    fn update_texture(
+        &mut self,
+        bitmap: BitmapHandle,
+        width: u32,
+        height: u32,
+        rgba: Vec<u8>,
+    ) -> Result<BitmapHandle, Error> {
+        self.renderer.update_texture(bitmap, width, height, rgba)
+    }
+
+    fn render_offscreen(
+        &mut self,
+        commands: &CommandList,
+        width: u32,
+        height: u32,
+        clear: Color,
+    ) -> Option<Bitmap> {
+        self.renderer
+            .render_offscreen(commands, width, height, clear)
+    }
+}
+
 /// The format of image data in a DefineBitsJpeg2/3 tag.
 /// Generally this will be JPEG, but according to SWF19, these tags can also contain PNG and GIF data.
 /// SWF19 pp.138-139
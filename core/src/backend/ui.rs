@@ -14,6 +14,9 @@ pub trait UiBackend {
     /// Sets the clipboard to the given content.
     fn set_clipboard_content(&mut self, content: String);
 
+    /// Returns the text content of the clipboard, if any.
+    fn clipboard_content(&mut self) -> Option<String>;
+
     fn set_fullscreen(&mut self, is_full: bool) -> Result<(), Error>;
 
     /// Displays a warning about unsupported content in Ruffle.
@@ -27,6 +30,77 @@ pub trait UiBackend {
 
     // Unused, but kept in case we need it later.
     fn message(&self, message: &str);
+
+    /// Displays a dialog for the user to choose one or more files to open.
+    ///
+    /// Returns `None` if the user cancelled the dialog, or if file dialogs
+    /// are unavailable.
+    fn display_file_open_dialog(
+        &mut self,
+        filters: &[FileFilter],
+        multiple: bool,
+    ) -> Option<Vec<FileDialogResult>>;
+
+    /// Displays a dialog for the user to choose where to save the given data.
+    ///
+    /// Returns the name of the saved file, or `None` if the user cancelled
+    /// the dialog.
+    fn display_file_save_dialog(
+        &mut self,
+        default_file_name: &str,
+        data: &[u8],
+    ) -> Result<Option<String>, Error>;
+}
+
+/// A filter restricting the files that can be chosen in a file dialog.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FileFilter {
+    /// The description of this filter, as shown to the user.
+    pub description: String,
+
+    /// The file extensions allowed by this filter, without the leading `*.`.
+    pub extensions: Vec<String>,
+}
+
+impl FileFilter {
+    /// Creates a filter from an ActionScript extension list, such as `"*.jpg;*.png"`.
+    pub fn from_extension_list(description: String, extension_list: &str) -> Self {
+        let extensions = extension_list
+            .split(';')
+            .map(|extension| {
+                extension
+                    .trim()
+                    .trim_start_matches('*')
+                    .trim_start_matches('.')
+            })
+            .filter(|extension| !extension.is_empty())
+            .map(|extension| extension.to_string())
+            .collect();
+        Self {
+            description,
+            extensions,
+        }
+    }
+}
+
+/// A file chosen by the user in a file dialog.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FileDialogResult {
+    /// The name of the file, without any directories.
+    pub file_name: String,
+
+    /// The contents of the file.
+    pub contents: Vec<u8>,
+}
+
+impl FileDialogResult {
+    /// The type of this file, as reported by `FileReference.type`.
+    ///
+    /// This is the file extension including the leading dot, like on Windows.
+    pub fn file_type(&self) -> Option<String> {
+        let (_, extension) = self.file_name.rsplit_once('.')?;
+        Some(format!(".{}", extension))
+    }
 }
 
 /// A mouse cursor icon displayed by the Flash Player.
@@ -138,6 +212,10 @@ impl UiBackend for NullUiBackend {
 
     fn set_clipboard_content(&mut self, _content: String) {}
 
+    fn clipboard_content(&mut self) -> Option<String> {
+        None
+    }
+
     fn set_fullscreen(&mut self, _is_full: bool) -> Result<(), Error> {
         Ok(())
     }
@@ -147,6 +225,22 @@ impl UiBackend for NullUiBackend {
     fn display_root_movie_download_failed_message(&self) {}
 
     fn message(&self, _message: &str) {}
+
+    fn display_file_open_dialog(
+        &mut self,
+        _filters: &[FileFilter],
+        _multiple: bool,
+    ) -> Option<Vec<FileDialogResult>> {
+        None
+    }
+
+    fn display_file_save_dialog(
+        &mut self,
+        _default_file_name: &str,
+        _data: &[u8],
+    ) -> Result<Option<String>, Error> {
+        Ok(None)
+    }
 }
 
 impl Default for NullUiBackend {
@@ -7,15 +7,18 @@ use crate::avm2::{
 };
 use crate::backend::{
     audio::{AudioBackend, AudioManager, SoundHandle, SoundInstanceHandle},
+    font::FontBackend,
     locale::LocaleBackend,
     log::LogBackend,
     navigator::NavigatorBackend,
+    print::PrintBackend,
     render::RenderBackend,
     storage::StorageBackend,
     ui::{InputManager, UiBackend},
     video::VideoBackend,
 };
 use crate::context_menu::ContextMenuState;
+use crate::debugger::Debugger;
 use crate::display_object::{EditText, InteractiveObject, MovieClip, SoundTransform, Stage};
 use crate::external::ExternalInterface;
 use crate::focus_tracker::FocusTracker;
@@ -23,6 +26,7 @@ use crate::library::Library;
 use crate::loader::LoadManager;
 use crate::player::Player;
 use crate::prelude::*;
+use crate::printing::PrintManager;
 use crate::tag_utils::{SwfMovie, SwfSlice};
 use crate::transform::TransformStack;
 use crate::vminterface::AvmType;
@@ -72,7 +76,10 @@ pub struct UpdateContext<'a, 'gc, 'gc_context> {
     pub navigator: &'a mut (dyn NavigatorBackend + 'a),
 
     /// The renderer, used by the display objects to draw themselves.
-    pub renderer: &'a mut dyn RenderBackend,
+    ///
+    /// This is boxed so that it can be temporarily wrapped, such as when
+    /// recording the pages of a print job.
+    pub renderer: &'a mut Box<dyn RenderBackend>,
 
     /// The UI backend, used to detect user interactions.
     pub ui: &'a mut dyn UiBackend,
@@ -91,6 +98,15 @@ pub struct UpdateContext<'a, 'gc, 'gc_context> {
     /// The video backend, used for video decoding
     pub video: &'a mut dyn VideoBackend,
 
+    /// The print backend, used to print pages added to a `PrintJob`.
+    pub print: &'a mut dyn PrintBackend,
+
+    /// Print jobs started by `PrintJob`.
+    pub print_manager: &'a mut PrintManager,
+
+    /// The font backend, used to load device fonts.
+    pub font: &'a mut dyn FontBackend,
+
     /// The RNG, used by the AVM `RandomNumber` opcode,  `Math.random(),` and `random()`.
     pub rng: &'a mut SmallRng,
 
@@ -168,6 +184,9 @@ pub struct UpdateContext<'a, 'gc, 'gc_context> {
 
     /// The current stage frame rate.
     pub frame_rate: &'a mut f64,
+
+    /// The ActionScript debugger.
+    pub debugger: &'a mut Debugger,
 }
 
 /// Convenience methods for controlling audio.
@@ -301,6 +320,9 @@ impl<'a, 'gc, 'gc_context> UpdateContext<'a, 'gc, 'gc_context> {
             log: self.log,
             ui: self.ui,
             video: self.video,
+            print: self.print,
+            print_manager: self.print_manager,
+            font: self.font,
             storage: self.storage,
             rng: self.rng,
             stage: self.stage,
@@ -326,6 +348,7 @@ impl<'a, 'gc, 'gc_context> UpdateContext<'a, 'gc, 'gc_context> {
             times_get_time_called: self.times_get_time_called,
             time_offset: self.time_offset,
             frame_rate: self.frame_rate,
+            debugger: self.debugger,
         }
     }
 
@@ -437,6 +460,9 @@ pub struct RenderContext<'a, 'gc> {
     /// Whether to allow pushing a new mask. A masker-inside-a-masker does not work in Flash, instead
     /// causing the inner mask to be included as part of the outer mask. Maskee-inside-a-maskee works as one expects.
     pub allow_mask: bool,
+
+    /// The bounds to highlight over the stage, in stage coordinates.
+    pub highlight_bounds: Option<BoundingBox>,
 }
 
 /// The type of action being run.
@@ -0,0 +1,513 @@
+//! ActionScript debugger
+//!
+//! The debugger follows each running activation, along with the source file
+//! and line reported by the AVM2 `debugfile` and `debugline` ops, and pauses
+//! execution when a breakpoint is hit or a step completes.
+//!
+//! A pause requested by the client between ticks is cooperative: the player
+//! is suspended, and each tick returns without running anything until the
+//! client resumes it. The host keeps running its event loop and rendering in
+//! the meantime.
+//!
+//! Breakpoints and steps are reached in the middle of a script. The
+//! interpreter keeps its activations on the native stack, so such a pause
+//! blocks inside the activation that paused until the attached
+//! [`DebuggerBackend`] resumes it. The backend is responsible for talking to
+//! the actual debugger client.
+//!
+//! AVM1 bytecode carries no line information, so AVM1 code can only be
+//! paused on request and stepped one action at a time.
+
+use instant::Instant;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+
+/// A command sent from the debugger client.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DebugCommand {
+    /// Replace all breakpoints in a source file.
+    SetBreakpoints { file: String, lines: Vec<u32> },
+
+    /// Pause as soon as possible.
+    ///
+    /// Between ticks, this suspends the player until it is resumed.
+    Pause,
+
+    /// Resume execution.
+    Continue,
+
+    /// Resume execution until the next line, entering any calls.
+    StepIn,
+
+    /// Resume execution until the next line in the current function or its
+    /// callers.
+    StepOver,
+
+    /// Resume execution until the current function returns.
+    StepOut,
+
+    /// The client went away; clear all breakpoints and resume execution.
+    Disconnect,
+}
+
+/// Why execution paused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PauseReason {
+    Breakpoint,
+    Step,
+    Pause,
+}
+
+/// A function in the call stack of a paused player.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StackFrame {
+    pub name: String,
+    pub file: Option<String>,
+    pub line: Option<u32>,
+}
+
+/// A variable, rendered for display.
+///
+/// Values are rendered without calling into ActionScript, so objects are
+/// shown by their class name rather than their `toString`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Variable {
+    pub name: String,
+    pub value: String,
+    pub type_name: String,
+}
+
+/// An object on the scope chain and its properties.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScopeVariables {
+    pub name: String,
+    pub variables: Vec<Variable>,
+}
+
+/// The variables visible from the innermost stack frame.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FrameVariables {
+    pub this: Option<Variable>,
+    pub locals: Vec<Variable>,
+
+    /// The scope chain, from the innermost scope outwards.
+    pub scopes: Vec<ScopeVariables>,
+}
+
+/// The state of a paused player.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PausedState {
+    pub reason: PauseReason,
+
+    /// The call stack, from the innermost frame outwards.
+    pub frames: Vec<StackFrame>,
+    pub variables: FrameVariables,
+}
+
+/// The connection to a debugger client.
+pub trait DebuggerBackend {
+    /// Collect the commands that arrived since the last call, without
+    /// blocking.
+    fn poll(&mut self) -> Vec<DebugCommand>;
+
+    /// Notify the client that the player has paused.
+    fn paused(&mut self, state: &PausedState);
+
+    /// Notify the client that the player has resumed.
+    fn resumed(&mut self);
+
+    /// Block until the client sends a command.
+    ///
+    /// Returning `None` means the client is gone.
+    fn wait(&mut self) -> Option<DebugCommand>;
+}
+
+/// How far to run before pausing again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Step {
+    In,
+    Over { depth: usize },
+    Out { depth: usize },
+}
+
+#[derive(Debug, Clone, Default)]
+struct Frame {
+    name: String,
+    file: Option<String>,
+    line: Option<u32>,
+
+    /// Register names declared by `debug` ops.
+    register_names: HashMap<u32, String>,
+}
+
+/// The debugger state of a player.
+#[derive(Default)]
+pub struct Debugger {
+    backend: Option<Box<dyn DebuggerBackend>>,
+    breakpoints: HashMap<String, HashSet<u32>>,
+    frames: Vec<Frame>,
+    step: Option<Step>,
+    pause_requested: bool,
+
+    /// Whether the player is suspended between ticks, waiting for the client
+    /// to resume it.
+    suspended: bool,
+
+    /// When the player last resumed from a pause.
+    resumed_at: Option<Instant>,
+}
+
+impl Debugger {
+    pub fn new(backend: Box<dyn DebuggerBackend>) -> Self {
+        Self {
+            backend: Some(backend),
+            ..Default::default()
+        }
+    }
+
+    /// Whether a debugger client is attached.
+    ///
+    /// Activations skip all debugger bookkeeping when this is false.
+    pub fn is_attached(&self) -> bool {
+        self.backend.is_some()
+    }
+
+    /// Whether the player is suspended, and should not run anything until
+    /// the client resumes it.
+    pub fn is_suspended(&self) -> bool {
+        self.suspended
+    }
+
+    /// Handle commands sent since the last tick.
+    ///
+    /// This must only be called between ticks, as a `Pause` suspends the
+    /// player instead of pausing a script.
+    pub fn poll(&mut self) {
+        let commands = match &mut self.backend {
+            Some(backend) => backend.poll(),
+            None => return,
+        };
+        for command in commands {
+            let resumes = resumes(&command);
+            if command == DebugCommand::Pause {
+                self.suspend();
+            } else {
+                self.run_command(command);
+                if self.suspended && resumes {
+                    self.resume();
+                }
+            }
+        }
+    }
+
+    /// Suspend the player between ticks.
+    fn suspend(&mut self) {
+        if self.suspended {
+            return;
+        }
+        let state = PausedState {
+            reason: PauseReason::Pause,
+            frames: vec![],
+            variables: FrameVariables::default(),
+        };
+        self.step = None;
+        self.pause_requested = false;
+        if let Some(backend) = &mut self.backend {
+            backend.paused(&state);
+            self.suspended = true;
+        }
+    }
+
+    /// Resume a player suspended between ticks.
+    fn resume(&mut self) {
+        self.suspended = false;
+        if let Some(backend) = &mut self.backend {
+            backend.resumed();
+        }
+        self.resumed_at = Some(Instant::now());
+    }
+
+    /// Note that an activation started running.
+    pub fn enter_frame(&mut self, name: impl Into<String>) {
+        self.frames.push(Frame {
+            name: name.into(),
+            ..Default::default()
+        });
+    }
+
+    /// Note that the innermost activation finished running.
+    pub fn exit_frame(&mut self) {
+        self.frames.pop();
+    }
+
+    /// Set the source file of the innermost activation.
+    pub fn set_file(&mut self, file: impl Into<String>) {
+        if let Some(frame) = self.frames.last_mut() {
+            frame.file = Some(file.into());
+        }
+    }
+
+    /// Name a register of the innermost activation.
+    pub fn set_register_name(&mut self, register: u32, name: impl Into<String>) {
+        if let Some(frame) = self.frames.last_mut() {
+            frame.register_names.insert(register, name.into());
+        }
+    }
+
+    /// Get the declared name of a register of the innermost activation.
+    pub fn register_name(&self, register: u32) -> Option<&str> {
+        self.frames
+            .last()?
+            .register_names
+            .get(&register)
+            .map(|name| name.as_str())
+    }
+
+    /// Move the innermost activation to a new line, and determine if it
+    /// should pause there.
+    pub fn reached_line(&mut self, line: u32) -> Option<PauseReason> {
+        let frame = self.frames.last_mut()?;
+        frame.line = Some(line);
+
+        let is_breakpoint = frame
+            .file
+            .as_ref()
+            .map(|file| {
+                self.breakpoints
+                    .iter()
+                    .any(|(path, lines)| lines.contains(&line) && is_same_source(file, path))
+            })
+            .unwrap_or(false);
+        if is_breakpoint {
+            Some(PauseReason::Breakpoint)
+        } else {
+            self.should_step()
+        }
+    }
+
+    /// Determine if an activation without line information should pause
+    /// before its next action.
+    pub fn reached_action(&mut self) -> Option<PauseReason> {
+        self.frames.last()?;
+        self.should_step()
+    }
+
+    fn should_step(&self) -> Option<PauseReason> {
+        let depth = self.frames.len();
+        let step_done = match self.step {
+            None => false,
+            Some(Step::In) => true,
+            Some(Step::Over { depth: from }) => depth <= from,
+            Some(Step::Out { depth: from }) => depth < from,
+        };
+
+        if self.pause_requested {
+            Some(PauseReason::Pause)
+        } else if step_done {
+            Some(PauseReason::Step)
+        } else {
+            None
+        }
+    }
+
+    /// Pause the running script, and block until the client resumes it.
+    ///
+    /// `variables` describes the innermost activation.
+    pub fn pause(&mut self, reason: PauseReason, variables: FrameVariables) {
+        let state = PausedState {
+            reason,
+            frames: self
+                .frames
+                .iter()
+                .rev()
+                .map(|frame| StackFrame {
+                    name: frame.name.clone(),
+                    file: frame.file.clone(),
+                    line: frame.line,
+                })
+                .collect(),
+            variables,
+        };
+
+        self.step = None;
+        self.pause_requested = false;
+
+        let mut backend = match self.backend.take() {
+            Some(backend) => backend,
+            None => return,
+        };
+        backend.paused(&state);
+        let mut connected = true;
+        loop {
+            let command = match backend.wait() {
+                Some(command) => command,
+                None => {
+                    connected = false;
+                    DebugCommand::Disconnect
+                }
+            };
+            let resumes = resumes(&command);
+            self.run_command(command);
+            if resumes {
+                break;
+            }
+        }
+        if connected {
+            backend.resumed();
+            self.backend = Some(backend);
+        }
+        self.resumed_at = Some(Instant::now());
+    }
+
+    /// Get the time from which the running script should be timed, so that
+    /// time spent paused does not count towards the script timeout.
+    pub fn running_since(&self, update_start: Instant) -> Instant {
+        match self.resumed_at {
+            Some(resumed_at) if resumed_at > update_start => resumed_at,
+            _ => update_start,
+        }
+    }
+
+    fn run_command(&mut self, command: DebugCommand) {
+        let depth = self.frames.len();
+        match command {
+            DebugCommand::SetBreakpoints { file, lines } => {
+                if lines.is_empty() {
+                    self.breakpoints.remove(&file);
+                } else {
+                    self.breakpoints.insert(file, lines.into_iter().collect());
+                }
+            }
+            DebugCommand::Pause => self.pause_requested = true,
+            DebugCommand::Continue => self.step = None,
+            DebugCommand::StepIn => self.step = Some(Step::In),
+            DebugCommand::StepOver => self.step = Some(Step::Over { depth }),
+            DebugCommand::StepOut => self.step = Some(Step::Out { depth }),
+            DebugCommand::Disconnect => {
+                self.breakpoints.clear();
+                self.step = None;
+                self.pause_requested = false;
+            }
+        }
+    }
+}
+
+/// Determine if a command resumes a paused player.
+fn resumes(command: &DebugCommand) -> bool {
+    !matches!(
+        command,
+        DebugCommand::SetBreakpoints { .. } | DebugCommand::Pause
+    )
+}
+
+/// Determine if the source file named by a `debugfile` op is the file at
+/// `path`.
+///
+/// Compilers write the file as `source root;package;File.as`. As the client
+/// may have the sources elsewhere, only the package and file name are
+/// compared with the end of `path`.
+fn is_same_source(debug_file: &str, path: &str) -> bool {
+    let components = |path: &str| -> Vec<String> {
+        path.split(|c| c == '/' || c == '\\')
+            .filter(|component| !component.is_empty())
+            .map(|component| component.to_string())
+            .collect()
+    };
+    let relative_file = match debug_file.split_once(';') {
+        Some((_root, relative_file)) => relative_file.replace(';', "/"),
+        None => debug_file.to_string(),
+    };
+    let debug_file = components(&relative_file);
+    let path = components(path);
+
+    !debug_file.is_empty() && path.ends_with(&debug_file)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    /// A backend that replays queued commands, and records what it was told.
+    #[derive(Clone, Default)]
+    struct TestBackend {
+        commands: Rc<RefCell<Vec<DebugCommand>>>,
+        events: Rc<RefCell<Vec<&'static str>>>,
+    }
+
+    impl DebuggerBackend for TestBackend {
+        fn poll(&mut self) -> Vec<DebugCommand> {
+            std::mem::take(&mut *self.commands.borrow_mut())
+        }
+
+        fn paused(&mut self, _state: &PausedState) {
+            self.events.borrow_mut().push("paused");
+        }
+
+        fn resumed(&mut self) {
+            self.events.borrow_mut().push("resumed");
+        }
+
+        fn wait(&mut self) -> Option<DebugCommand> {
+            panic!("a suspended player must not block");
+        }
+    }
+
+    #[test]
+    fn pause_suspends_between_ticks() {
+        let backend = TestBackend::default();
+        let mut debugger = Debugger::new(Box::new(backend.clone()));
+
+        backend.commands.borrow_mut().push(DebugCommand::Pause);
+        debugger.poll();
+        assert!(debugger.is_suspended());
+
+        backend
+            .commands
+            .borrow_mut()
+            .push(DebugCommand::SetBreakpoints {
+                file: "Main.as".to_string(),
+                lines: vec![1],
+            });
+        debugger.poll();
+        assert!(debugger.is_suspended());
+
+        backend.commands.borrow_mut().push(DebugCommand::StepIn);
+        debugger.poll();
+        assert!(!debugger.is_suspended());
+        assert_eq!(*backend.events.borrow(), ["paused", "resumed"]);
+
+        debugger.enter_frame("main");
+        assert_eq!(debugger.reached_line(2), Some(PauseReason::Step));
+    }
+
+    #[test]
+    fn step_over_skips_calls() {
+        let mut debugger = Debugger::default();
+        debugger.enter_frame("outer");
+        debugger.run_command(DebugCommand::StepOver);
+
+        debugger.enter_frame("inner");
+        assert_eq!(debugger.reached_line(1), None);
+        debugger.exit_frame();
+
+        assert_eq!(debugger.reached_line(2), Some(PauseReason::Step));
+    }
+
+    #[test]
+    fn breakpoints_match_file_and_line() {
+        let mut debugger = Debugger::default();
+        debugger.run_command(DebugCommand::SetBreakpoints {
+            file: "/home/user/project/src/com/example/Main.as".to_string(),
+            lines: vec![10],
+        });
+        debugger.enter_frame("main");
+        assert_eq!(debugger.reached_line(10), None);
+
+        debugger.set_file("C:\\project\\src;com\\example;Main.as");
+        assert_eq!(debugger.reached_line(9), None);
+        assert_eq!(debugger.reached_line(10), Some(PauseReason::Breakpoint));
+    }
+}
@@ -43,10 +43,10 @@ pub use crate::display_object::container::{
 pub use avm1_button::{Avm1Button, ButtonState, ButtonTracking};
 pub use avm2_button::Avm2Button;
 pub use bitmap::Bitmap;
-pub use edit_text::{AutoSizeMode, EditText, TextSelection};
+pub use edit_text::{AutoSizeMode, EditText, LineMetrics, TextSelection};
 pub use graphic::Graphic;
 pub use interactive::{InteractiveObject, TInteractiveObject};
-pub use morph_shape::{MorphShape, MorphShapeStatic};
+pub use morph_shape::{interpolate_morph_shape, MorphShape, MorphShapeStatic};
 pub use movie_clip::{MovieClip, Scene};
 pub use stage::{Stage, StageAlign, StageDisplayState, StageQuality, StageScaleMode};
 pub use text::Text;
@@ -6,18 +6,20 @@ use crate::avm1::{
     Value as Avm1Value,
 };
 use crate::avm2::{
-    Activation as Avm2Activation, Object as Avm2Object, StageObject as Avm2StageObject,
+    Activation as Avm2Activation, Avm2, Event as Avm2Event, EventData as Avm2EventData,
+    Object as Avm2Object, StageObject as Avm2StageObject,
 };
+use crate::backend::navigator::RequestOptions;
 use crate::backend::ui::MouseCursor;
 use crate::context::{RenderContext, UpdateContext};
 use crate::display_object::interactive::{
     InteractiveObject, InteractiveObjectBase, TInteractiveObject,
 };
-use crate::display_object::{DisplayObjectBase, DisplayObjectPtr, TDisplayObject};
+use crate::display_object::{DisplayObjectBase, DisplayObjectPtr, MovieClip, TDisplayObject};
 use crate::drawing::Drawing;
-use crate::events::{ButtonKeyCode, ClipEvent, ClipEventResult, KeyCode};
-use crate::font::{round_down_to_pixel, Glyph, TextRenderSettings};
-use crate::html::{BoxBounds, FormatSpans, LayoutBox, LayoutContent, TextFormat};
+use crate::events::{ButtonKeyCode, ClipEvent, ClipEventResult, KeyCode, TextControlCode};
+use crate::font::{round_down_to_pixel, FontType, Glyph, TextRenderSettings};
+use crate::html::{BoxBounds, FormatSpans, LayoutBox, LayoutContent, Size, StyleSheet, TextFormat};
 use crate::prelude::*;
 use crate::shape_utils::DrawCommand;
 use crate::string::{utils as string_utils, AvmString, WStr, WString};
@@ -101,8 +103,8 @@ pub struct EditTextData<'gc> {
     /// The color of the border.
     border_color: u32,
 
-    /// If the text field is required to use device fonts only.
-    is_device_font: bool,
+    /// The kind of fonts the text field uses.
+    font_type: FontType,
 
     /// If the text field renders as HTML.
     is_html: bool,
@@ -153,52 +155,45 @@ pub struct EditTextData<'gc> {
 
     /// How many lines down the text is offset by. 1-based index.
     scroll: usize,
-}
-
-// TODO: would be nicer to compute (and return) this during layout, instead of afterwards
-/// Compute line (index, offset, extent) from the layout data.
-fn get_line_data(layout: &[LayoutBox]) -> Vec<LineData> {
-    // if there are no boxes, there are no lines
-    if layout.is_empty() {
-        return Vec::new();
-    }
 
-    let first_box = &layout[0];
+    /// The maximum number of characters the user may enter, or 0 for no limit.
+    max_chars: i32,
 
-    let mut index = 1;
-    let mut offset = first_box.bounds().offset_y();
-    let mut extent = first_box.bounds().extent_y();
+    /// The characters the user may enter.
+    restrict: EditTextRestrict,
 
-    let mut line_data = Vec::new();
+    /// Previous states of the text, restored when the user undoes an edit.
+    #[collect(require_static)]
+    undo_history: Vec<(WString, TextSelection)>,
 
-    for layout_box in layout.get(1..).unwrap() {
-        let bounds = layout_box.bounds();
+    /// The style sheet applied to HTML text, and the object it was set from.
+    style_sheet: Option<(AvmObject<'gc>, StyleSheet)>,
 
-        // if the top of the new box is lower than the bottom of the old box, it's a new line
-        if bounds.offset_y() > extent {
-            // save old line and reset
-            line_data.push(LineData {
-                index,
-                offset,
-                extent,
-            });
+    /// The display objects of images embedded in HTML text, in the same
+    /// order as the images in `text_spans`.
+    images: Vec<DisplayObject<'gc>>,
+}
 
-            index += 1;
-            offset = bounds.offset_y();
-            extent = bounds.extent_y();
-        } else {
-            // otherwise we continue from the previous box
-            offset = offset.min(bounds.offset_y());
-            extent = extent.max(bounds.extent_y());
+// TODO: would be nicer to compute (and return) this during layout, instead of afterwards
+/// Compute line (index, offset, extent) and metrics from the layout data.
+fn get_line_data(layout: &[LayoutBox], text_length: usize) -> Vec<LineData> {
+    let mut line_data: Vec<LineData> = Vec::new();
+
+    for layout_box in layout {
+        let index = line_data.len() + 1;
+        match line_data.last_mut() {
+            // if the top of the new box is lower than the bottom of the old box, it's a new line
+            Some(line) if layout_box.bounds().offset_y() <= line.extent => line.add_box(layout_box),
+            _ => line_data.push(LineData::new(index, layout_box)),
         }
     }
 
-    // save the final line
-    line_data.push(LineData {
-        index,
-        offset,
-        extent,
-    });
+    // lines without any text (such as a lone drawing) start where the next line does
+    let mut next_start = text_length;
+    for line in line_data.iter_mut().rev() {
+        line.start = line.start.min(next_start);
+        next_start = line.start;
+    }
 
     line_data
 }
@@ -216,13 +211,18 @@ impl<'gc> EditText<'gc> {
         let is_password = swf_tag.is_password;
         let is_editable = !swf_tag.is_read_only;
         let is_html = swf_tag.is_html;
+        let font_type = if swf_tag.is_device_font {
+            FontType::Device
+        } else {
+            FontType::Embedded
+        };
         let text = swf_tag.initial_text.unwrap_or_default();
         let default_format = TextFormat::from_swf_tag(swf_tag.clone(), swf_movie.clone(), context);
         let encoding = swf_movie.encoding();
 
         let text = WString::from_utf8(&text.to_str_lossy(encoding));
         let mut text_spans = if is_html {
-            FormatSpans::from_html(&text, default_format, is_multiline)
+            FormatSpans::from_html(&text, default_format, None, is_multiline)
         } else {
             FormatSpans::from_text(text, default_format)
         };
@@ -239,15 +239,15 @@ impl<'gc> EditText<'gc> {
             swf_movie.clone(),
             bounds.width() - Twips::from_pixels(Self::INTERNAL_PADDING * 2.0),
             swf_tag.is_word_wrap,
-            swf_tag.is_device_font,
+            font_type,
+            &[],
         );
-        let line_data = get_line_data(&layout);
+        let line_data = get_line_data(&layout, text_spans.text().len());
 
         let has_background = swf_tag.has_border;
         let background_color = 0xFFFFFF; // Default is white
         let has_border = swf_tag.has_border;
         let border_color = 0; // Default is black
-        let is_device_font = swf_tag.is_device_font;
 
         let mut base = InteractiveObjectBase::default();
 
@@ -308,7 +308,7 @@ impl<'gc> EditText<'gc> {
                 background_color,
                 has_border,
                 border_color,
-                is_device_font,
+                font_type,
                 is_html,
                 drawing: Drawing::new(),
                 object: None,
@@ -325,6 +325,11 @@ impl<'gc> EditText<'gc> {
                 hscroll: 0.0,
                 line_data,
                 scroll: 1,
+                max_chars: swf_tag.max_length.unwrap_or(0).into(),
+                restrict: EditTextRestrict::new(None),
+                undo_history: Vec::new(),
+                style_sheet: None,
+                images: Vec::new(),
             },
         ));
 
@@ -359,7 +364,7 @@ impl<'gc> EditText<'gc> {
                 b: 0,
                 a: 0xFF,
             }),
-            max_length: Some(width as u16),
+            max_length: None,
             layout: Some(swf::TextLayout {
                 align: swf::TextAlign::Left,
                 left_margin: Twips::from_pixels(0.0),
@@ -406,6 +411,8 @@ impl<'gc> EditText<'gc> {
         let mut edit_text = self.0.write(context.gc_context);
         let default_format = edit_text.text_spans.default_format().clone();
         edit_text.text_spans = FormatSpans::from_text(text.into(), default_format);
+        edit_text.undo_history.clear();
+        edit_text.images.clear();
         drop(edit_text);
 
         self.relayout(context);
@@ -430,9 +437,17 @@ impl<'gc> EditText<'gc> {
         if self.is_html() {
             let mut write = self.0.write(context.gc_context);
             let default_format = write.text_spans.default_format().clone();
-            write.text_spans = FormatSpans::from_html(text, default_format, write.is_multiline);
+            let style_sheet = write
+                .style_sheet
+                .as_ref()
+                .map(|(_, style_sheet)| style_sheet);
+            let text_spans =
+                FormatSpans::from_html(text, default_format, style_sheet, write.is_multiline);
+            write.text_spans = text_spans;
+            write.undo_history.clear();
             drop(write);
 
+            self.load_images(context);
             self.relayout(context);
 
             Ok(())
@@ -565,7 +580,7 @@ impl<'gc> EditText<'gc> {
     }
 
     pub fn is_device_font(self) -> bool {
-        self.0.read().is_device_font
+        self.0.read().font_type == FontType::Device
     }
 
     pub fn set_is_device_font(
@@ -573,7 +588,20 @@ impl<'gc> EditText<'gc> {
         context: &mut UpdateContext<'_, 'gc, '_>,
         is_device_font: bool,
     ) {
-        self.0.write(context.gc_context).is_device_font = is_device_font;
+        let font_type = if is_device_font {
+            FontType::Device
+        } else {
+            FontType::Embedded
+        };
+        self.set_font_type(context, font_type);
+    }
+
+    pub fn font_type(self) -> FontType {
+        self.0.read().font_type
+    }
+
+    pub fn set_font_type(self, context: &mut UpdateContext<'_, 'gc, '_>, font_type: FontType) {
+        self.0.write(context.gc_context).font_type = font_type;
         self.relayout(context);
     }
 
@@ -585,6 +613,124 @@ impl<'gc> EditText<'gc> {
         self.0.write(context.gc_context).is_html = is_html;
     }
 
+    pub fn max_chars(self) -> i32 {
+        self.0.read().max_chars
+    }
+
+    pub fn set_max_chars(self, value: i32, context: &mut UpdateContext<'_, 'gc, '_>) {
+        self.0.write(context.gc_context).max_chars = value.max(0);
+    }
+
+    pub fn restrict(self) -> Option<WString> {
+        self.0.read().restrict.value().map(WString::from)
+    }
+
+    pub fn set_restrict(self, value: Option<&WStr>, context: &mut UpdateContext<'_, 'gc, '_>) {
+        self.0.write(context.gc_context).restrict = EditTextRestrict::new(value);
+    }
+
+    /// The object the style sheet of this field was set from.
+    pub fn style_sheet(self) -> Option<AvmObject<'gc>> {
+        self.0
+            .read()
+            .style_sheet
+            .as_ref()
+            .map(|(object, _)| *object)
+    }
+
+    /// Set the style sheet applied to the HTML text of this field.
+    ///
+    /// Fields with a style sheet cannot be edited by the user. The current
+    /// text is formatted again with the new styles.
+    pub fn set_style_sheet(
+        self,
+        style_sheet: Option<(AvmObject<'gc>, StyleSheet)>,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+    ) -> Result<(), Error> {
+        let has_style_sheet = style_sheet.is_some();
+        let html = self.html_text();
+
+        let mut write = self.0.write(context.gc_context);
+        write.style_sheet = style_sheet;
+        if has_style_sheet {
+            write.is_editable = false;
+        }
+        drop(write);
+
+        self.set_html_text(&html, context)
+    }
+
+    /// Create the display objects of the images embedded in the HTML text.
+    ///
+    /// Images are instantiated from the library if their source is the
+    /// export name of a symbol, and loaded through the navigator otherwise.
+    fn load_images(self, context: &mut UpdateContext<'_, 'gc, '_>) {
+        let movie = self.movie().unwrap();
+        let html_images = self.0.read().text_spans.images().to_vec();
+        let mut images = Vec::with_capacity(html_images.len());
+
+        for html_image in html_images {
+            let src = AvmString::new(context.gc_context, html_image.src.clone());
+            let symbol = context
+                .library
+                .library_for_movie(movie.clone())
+                .filter(|library| library.character_by_export_name(src).is_some())
+                .and_then(|library| {
+                    library
+                        .instantiate_by_export_name(src, context.gc_context)
+                        .ok()
+                });
+
+            let image = match symbol {
+                Some(symbol) => symbol,
+                None => {
+                    let clip: DisplayObject<'gc> = MovieClip::new(
+                        Arc::new(SwfMovie::empty(movie.version())),
+                        context.gc_context,
+                    )
+                    .into();
+                    let url = html_image.src.to_utf8_lossy().into_owned();
+                    let fetch = context.navigator.fetch(&url, RequestOptions::get());
+                    let process = context.load_manager.load_movie_into_clip(
+                        context.player.clone().unwrap(),
+                        clip,
+                        fetch,
+                        url,
+                        None,
+                        None,
+                    );
+                    context.navigator.spawn_future(process);
+                    clip
+                }
+            };
+
+            image.set_parent(context.gc_context, Some(self.into()));
+            if let Some(id) = &html_image.id {
+                image.set_name(
+                    context.gc_context,
+                    AvmString::new(context.gc_context, id.clone()),
+                );
+            }
+            image.post_instantiation(context, None, Instantiator::Movie, false);
+            images.push(image);
+        }
+
+        self.0.write(context.gc_context).images = images;
+    }
+
+    /// The display object of an image embedded with an `<img>` tag, by its
+    /// `id` attribute.
+    pub fn image_by_id(self, id: &WStr) -> Option<DisplayObject<'gc>> {
+        let edit_text = self.0.read();
+        edit_text
+            .text_spans
+            .images()
+            .iter()
+            .zip(edit_text.images.iter())
+            .find(|(html_image, _)| html_image.id.as_deref() == Some(id))
+            .map(|(_, image)| *image)
+    }
+
     pub fn replace_text(
         self,
         from: usize,
@@ -760,16 +906,45 @@ impl<'gc> EditText<'gc> {
             edit_text.text_spans.clear_displayed_text();
         }
 
+        let image_sizes: Vec<Size<Twips>> = edit_text
+            .images
+            .iter()
+            .map(|image| {
+                let bounds = image.bounds_with_transform(&Matrix::default());
+                Size::from((bounds.width(), bounds.height()))
+            })
+            .collect();
+
         let (new_layout, intrinsic_bounds) = LayoutBox::lower_from_text_spans(
             &edit_text.text_spans,
             context,
             movie,
             width,
             is_word_wrap,
-            edit_text.is_device_font,
+            edit_text.font_type,
+            &image_sizes,
         );
 
-        edit_text.line_data = get_line_data(&new_layout);
+        // Scale each image to the size it was given, and offset it by its
+        // spacing within its layout box.
+        for (index, image) in edit_text.images.iter().enumerate() {
+            let html_image = &edit_text.text_spans.images()[index];
+            let natural_size = image_sizes[index];
+            let scale = |size: Option<f64>, natural: Twips| match size {
+                Some(size) if natural > Twips::ZERO => (size / natural.to_pixels()) as f32,
+                _ => 1.0,
+            };
+            let matrix = Matrix::translate(
+                Twips::from_pixels(html_image.hspace),
+                Twips::from_pixels(html_image.vspace),
+            ) * Matrix::scale(
+                scale(html_image.width, natural_size.width()),
+                scale(html_image.height, natural_size.height()),
+            );
+            image.set_matrix(context.gc_context, &matrix);
+        }
+
+        edit_text.line_data = get_line_data(&new_layout, edit_text.text_spans.text().len());
         edit_text.layout = new_layout;
         edit_text.intrinsic_bounds = intrinsic_bounds;
         // reset scroll
@@ -973,10 +1148,7 @@ impl<'gc> EditText<'gc> {
                     }
 
                     // Render glyph.
-                    let glyph_shape_handle = glyph.shape_handle(context.renderer);
-                    context
-                        .renderer
-                        .render_shape(glyph_shape_handle, context.transform_stack.transform());
+                    edit_text.render_settings.render_glyph(context, font, glyph);
                     context.transform_stack.pop();
 
                     if let Some((caret_pos, length)) = caret {
@@ -1010,6 +1182,13 @@ impl<'gc> EditText<'gc> {
             drawing.render(context);
         }
 
+        if let Some(image) = lbox
+            .as_image()
+            .and_then(|index| edit_text.images.get(index))
+        {
+            image.render(context);
+        }
+
         context.transform_stack.pop();
     }
 
@@ -1157,6 +1336,10 @@ impl<'gc> EditText<'gc> {
         }
     }
 
+    pub fn render_settings(self) -> TextRenderSettings {
+        self.0.read().render_settings.clone()
+    }
+
     pub fn set_render_settings(
         self,
         gc_context: MutationContext<'gc, '_>,
@@ -1191,12 +1374,24 @@ impl<'gc> EditText<'gc> {
     }
 
     pub fn screen_position_to_index(self, position: (Twips, Twips)) -> Option<usize> {
-        let text = self.0.read();
         let position = self.global_to_local(position);
-        let position = (
-            position.0 + Twips::from_pixels(Self::INTERNAL_PADDING),
-            position.1 + Twips::from_pixels(Self::INTERNAL_PADDING),
-        );
+        self.local_position_to_index(position, true)
+    }
+
+    /// Find the character at a position in the local coordinate space of this
+    /// text field.
+    ///
+    /// If `snap_to_caret` is set, positions in the right half of a character
+    /// resolve to the caret position after it, rather than to the character
+    /// itself.
+    fn local_position_to_index(
+        self,
+        position: (Twips, Twips),
+        snap_to_caret: bool,
+    ) -> Option<usize> {
+        let origin = self.layout_origin();
+        let position = (position.0 - origin.0, position.1 - origin.1);
+        let text = self.0.read();
 
         for layout_box in text.layout.iter() {
             let transform: Transform = layout_box.bounds().origin().into();
@@ -1204,6 +1399,11 @@ impl<'gc> EditText<'gc> {
             matrix.invert();
             let local_position = matrix * position;
 
+            let start = match layout_box.content() {
+                LayoutContent::Text { start, .. } => *start,
+                _ => continue,
+            };
+
             if let Some((text, _tf, font, params, color)) =
                 layout_box.as_renderable_text(text.text_spans.text())
             {
@@ -1220,10 +1420,10 @@ impl<'gc> EditText<'gc> {
                             && local_position.1 >= Twips::ZERO
                             && local_position.1 <= params.height()
                         {
-                            if local_position.0 >= x + (advance / 2) {
-                                result = Some(string_utils::next_char_boundary(text, pos));
+                            if snap_to_caret && local_position.0 >= x + (advance / 2) {
+                                result = Some(start + string_utils::next_char_boundary(text, pos));
                             } else {
-                                result = Some(pos);
+                                result = Some(start + pos);
                             }
                         }
                     },
@@ -1237,12 +1437,274 @@ impl<'gc> EditText<'gc> {
         None
     }
 
+    /// The position of the origin of the text layout, in the local
+    /// coordinate space of this text field.
+    fn layout_origin(self) -> (Twips, Twips) {
+        let edit_text = self.0.read();
+        let scroll_offset = if edit_text.scroll > 1 {
+            edit_text
+                .line_data
+                .get(edit_text.scroll - 1)
+                .map_or(Twips::ZERO, |line| line.offset)
+        } else {
+            Twips::ZERO
+        };
+
+        (
+            edit_text.bounds.x_min + Twips::from_pixels(Self::INTERNAL_PADDING)
+                - Twips::from_pixels(edit_text.hscroll),
+            edit_text.bounds.y_min + Twips::from_pixels(Self::INTERNAL_PADDING) - scroll_offset,
+        )
+    }
+
+    /// The number of lines of text in this field.
+    pub fn num_lines(self) -> usize {
+        self.0.read().line_data.len().max(1)
+    }
+
+    /// The range of characters on a given line, including any trailing line
+    /// break.
+    fn line_range(self, line: usize) -> Option<std::ops::Range<usize>> {
+        let edit_text = self.0.read();
+        let line_data = &edit_text.line_data;
+        let text_length = edit_text.text_spans.text().len();
+
+        if line_data.is_empty() && line == 0 {
+            return Some(0..text_length);
+        }
+
+        let start = line_data.get(line)?.start;
+        let end = line_data
+            .get(line + 1)
+            .map(|next| next.start)
+            .unwrap_or(text_length);
+        Some(start..end.max(start))
+    }
+
+    /// The index of the first character on a given line.
+    pub fn line_offset(self, line: usize) -> Option<usize> {
+        self.line_range(line).map(|range| range.start)
+    }
+
+    /// The number of characters on a given line.
+    pub fn line_length(self, line: usize) -> Option<usize> {
+        self.line_range(line).map(|range| range.len())
+    }
+
+    /// The text on a given line.
+    pub fn line_text(self, line: usize) -> Option<WString> {
+        let range = self.line_range(line)?;
+        let text = self.0.read().text_spans.text().slice(range)?.into();
+        Some(text)
+    }
+
+    /// The line that contains a given character.
+    pub fn line_index_of_char(self, index: usize) -> Option<usize> {
+        let edit_text = self.0.read();
+        if index >= edit_text.text_spans.text().len() {
+            return None;
+        }
+
+        let line = edit_text
+            .line_data
+            .iter()
+            .rposition(|line| line.start <= index)
+            .unwrap_or(0);
+        Some(line)
+    }
+
+    /// The line at a position in the local coordinate space of this text
+    /// field.
+    pub fn line_index_at_point(self, position: (Twips, Twips)) -> Option<usize> {
+        let origin = self.layout_origin();
+        let edit_text = self.0.read();
+        if position.0 < edit_text.bounds.x_min || position.0 > edit_text.bounds.x_max {
+            return None;
+        }
+
+        let y = position.1 - origin.1;
+        edit_text
+            .line_data
+            .iter()
+            .position(|line| y >= line.offset && y <= line.extent)
+    }
+
+    /// The character at a position in the local coordinate space of this text
+    /// field.
+    pub fn char_index_at_point(self, position: (Twips, Twips)) -> Option<usize> {
+        self.local_position_to_index(position, false)
+    }
+
+    /// The bounds of a given character, in the local coordinate space of this
+    /// text field.
+    pub fn char_bounds(self, index: usize) -> Option<BoxBounds<Twips>> {
+        let origin = self.layout_origin();
+        let edit_text = self.0.read();
+
+        for layout_box in edit_text.layout.iter() {
+            let start = match layout_box.content() {
+                LayoutContent::Text { start, end, .. } if (*start..*end).contains(&index) => *start,
+                _ => continue,
+            };
+
+            if let Some((text, _tf, font, params, _color)) =
+                layout_box.as_renderable_text(edit_text.text_spans.text())
+            {
+                let mut result = None;
+                font.evaluate(
+                    text,
+                    Default::default(),
+                    params,
+                    |pos, _transform, _glyph: &Glyph, advance, x| {
+                        if start + pos == index {
+                            result = Some((x, advance));
+                        }
+                    },
+                );
+
+                let (x, advance) = result?;
+                let bounds = layout_box.bounds();
+                return Some(BoxBounds::from_position_and_size(
+                    (
+                        origin.0 + bounds.offset_x() + x,
+                        origin.1 + bounds.offset_y(),
+                    )
+                        .into(),
+                    (advance, params.height()).into(),
+                ));
+            }
+        }
+
+        None
+    }
+
+    /// The metrics of a given line.
+    pub fn line_metrics(self, line: usize) -> Option<LineMetrics> {
+        let edit_text = self.0.read();
+        let line = edit_text.line_data.get(line)?;
+
+        Some(LineMetrics {
+            x: line.x_offset + Twips::from_pixels(Self::INTERNAL_PADDING),
+            width: line.x_extent - line.x_offset,
+            height: line.ascent + line.descent + line.leading,
+            ascent: line.ascent,
+            descent: line.descent,
+            leading: line.leading,
+        })
+    }
+
+    /// Move the bounds of this text field so that its origin lies on the
+    /// baseline at the start of the first line.
+    ///
+    /// This is how `flash.text.engine.TextLine` positions its text.
+    pub fn set_origin_to_baseline(self, context: &mut UpdateContext<'_, 'gc, '_>) {
+        let ascent = self
+            .line_metrics(0)
+            .map(|metrics| metrics.ascent)
+            .unwrap_or_default();
+        let padding = Twips::from_pixels(Self::INTERNAL_PADDING);
+
+        let mut edit_text = self.0.write(context.gc_context);
+        edit_text.bounds.set_x(Twips::default() - padding);
+        edit_text.bounds.set_y(Twips::default() - padding - ascent);
+        drop(edit_text);
+        self.redraw_border(context.gc_context);
+    }
+
+    /// The URL and target of the link at a position on the stage, if any.
+    fn link_at(self, position: (Twips, Twips)) -> Option<(WString, WString)> {
+        let position = self.global_to_local(position);
+        let index = self.local_position_to_index(position, false)?;
+        let edit_text = self.0.read();
+        let span = edit_text
+            .text_spans
+            .resolve_position_as_span(index)
+            .and_then(|(span, _)| edit_text.text_spans.span(span))?;
+
+        if span.url.is_empty() {
+            return None;
+        }
+
+        Some((span.url.clone(), span.target.clone()))
+    }
+
+    /// Follow a link that the user clicked on.
+    ///
+    /// `asfunction:` links call an AVM1 function, `event:` links dispatch a
+    /// `TextEvent.LINK` in AVM2, and all others are navigated to.
+    fn open_link(self, context: &mut UpdateContext<'_, 'gc, '_>, url: &WStr, target: &WStr) {
+        let asfunction = WStr::from_units(b"asfunction:");
+        let event = WStr::from_units(b"event:");
+
+        if url.len() >= asfunction.len() && url[..asfunction.len()].eq_ignore_case(asfunction) {
+            if context.avm_type() != AvmType::Avm1 {
+                return;
+            }
+
+            let call = &url[asfunction.len()..];
+            let (name, argument) = match call.find(b',') {
+                Some(comma) => (&call[..comma], Some(&call[comma + 1..])),
+                None => (call, None),
+            };
+
+            let parent = match self.avm1_parent() {
+                Some(parent) => parent,
+                None => return,
+            };
+            let parent_object = match parent.object() {
+                Avm1Value::Object(object) => object,
+                _ => return,
+            };
+
+            let globals = context.avm1.global_object_cell();
+            let swf_version = context.swf.version();
+            let mut activation = Avm1Activation::from_nothing(
+                context.reborrow(),
+                ActivationIdentifier::root("[Link]"),
+                swf_version,
+                globals,
+                parent,
+            );
+            let name = AvmString::new(activation.context.gc_context, name);
+            let args: Vec<Avm1Value<'gc>> = argument
+                .map(|argument| AvmString::new(activation.context.gc_context, argument).into())
+                .into_iter()
+                .collect();
+            let result = activation.get_variable(name).and_then(|function| {
+                function.call_with_default_this(parent_object, name, &mut activation, &args)
+            });
+            if let Err(e) = result {
+                log::error!("Error calling asfunction link {}: {}", name, e);
+            }
+        } else if url.len() >= event.len() && url[..event.len()].eq_ignore_case(event) {
+            if let Avm2Value::Object(object) = self.object2() {
+                let text = AvmString::new(context.gc_context, &url[event.len()..]);
+                let mut link_event = Avm2Event::new("link", Avm2EventData::Text { text });
+                link_event.set_bubbles(true);
+
+                if let Err(e) = Avm2::dispatch_event(context, link_event, object) {
+                    log::error!("Got error when dispatching link event to AVM2: {}", e);
+                }
+            }
+        } else {
+            let target = if target.is_empty() {
+                None
+            } else {
+                Some(target.to_utf8_lossy().into_owned())
+            };
+            context
+                .navigator
+                .navigate_to_url(url.to_utf8_lossy().into_owned(), target, None);
+        }
+    }
+
     pub fn text_input(self, character: char, context: &mut UpdateContext<'_, 'gc, '_>) {
         if !self.0.read().is_editable {
             return;
         }
 
         if let Some(selection) = self.selection() {
+            let previous_text = self.text();
             let mut changed = false;
             match character as u8 {
                 8 | 127 if !selection.is_caret() => {
@@ -1280,6 +1742,14 @@ impl<'gc> EditText<'gc> {
                     }
                 }
                 code if !(code as char).is_control() => {
+                    let character = match self.0.read().restrict.to_allowed(character) {
+                        Some(character) => character,
+                        None => return,
+                    };
+                    if self.available_chars(selection) == 0 {
+                        return;
+                    }
+
                     self.replace_text(
                         selection.start(),
                         selection.end(),
@@ -1297,19 +1767,167 @@ impl<'gc> EditText<'gc> {
             }
 
             if changed {
-                let globals = context.avm1.global_object_cell();
-                let swf_version = context.swf.version();
-                let mut activation = Avm1Activation::from_nothing(
-                    context.reborrow(),
-                    ActivationIdentifier::root("[Propagate Text Binding]"),
-                    swf_version,
-                    globals,
-                    self.into(),
+                self.push_undo_state(previous_text, selection, context.gc_context);
+                self.text_changed_by_user(context);
+            }
+        }
+    }
+
+    /// Performs a text editing command, such as a clipboard operation.
+    ///
+    /// Password fields never allow their text to be copied.
+    pub fn text_control_input(
+        self,
+        control_code: TextControlCode,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+    ) {
+        let selection = match self.selection() {
+            Some(selection) => selection,
+            None => return,
+        };
+
+        match control_code {
+            TextControlCode::SelectAll => {
+                self.set_selection(
+                    Some(TextSelection::for_range(0, self.text_length())),
+                    context.gc_context,
                 );
-                self.propagate_text_binding(&mut activation);
-                self.on_changed(&mut activation);
             }
+            TextControlCode::Copy | TextControlCode::Cut => {
+                if selection.is_caret() || self.is_password() {
+                    return;
+                }
+
+                let text = self.text();
+                if let Some(selected) = text.slice(selection.start()..selection.end()) {
+                    context
+                        .ui
+                        .set_clipboard_content(selected.to_utf8_lossy().into_owned());
+                }
+
+                if control_code == TextControlCode::Cut && self.is_editable() {
+                    self.push_undo_state(text, selection, context.gc_context);
+                    self.replace_text(selection.start(), selection.end(), WStr::empty(), context);
+                    self.set_selection(
+                        Some(TextSelection::for_position(selection.start())),
+                        context.gc_context,
+                    );
+                    self.text_changed_by_user(context);
+                }
+            }
+            TextControlCode::Paste => {
+                if !self.is_editable() {
+                    return;
+                }
+
+                let content = match context.ui.clipboard_content() {
+                    Some(content) => content,
+                    None => return,
+                };
+                let text = self.pasted_text(&content, selection);
+                if text.is_empty() && selection.is_caret() {
+                    return;
+                }
+
+                self.push_undo_state(self.text(), selection, context.gc_context);
+                self.replace_text(selection.start(), selection.end(), &text, context);
+                self.set_selection(
+                    Some(TextSelection::for_position(selection.start() + text.len())),
+                    context.gc_context,
+                );
+                self.text_changed_by_user(context);
+            }
+            TextControlCode::Undo => {
+                if !self.is_editable() {
+                    return;
+                }
+
+                let previous = self.0.write(context.gc_context).undo_history.pop();
+                if let Some((text, selection)) = previous {
+                    self.replace_text(0, self.text_length(), &text, context);
+                    self.set_selection(Some(selection), context.gc_context);
+                    self.text_changed_by_user(context);
+                }
+            }
+        }
+    }
+
+    /// Remembers the state of the text before an edit by the user, so that
+    /// the edit can be undone.
+    fn push_undo_state(
+        self,
+        text: WString,
+        selection: TextSelection,
+        gc_context: MutationContext<'gc, '_>,
+    ) {
+        const MAX_UNDO_HISTORY: usize = 100;
+
+        let mut write = self.0.write(gc_context);
+        if write.undo_history.len() >= MAX_UNDO_HISTORY {
+            write.undo_history.remove(0);
         }
+        write.undo_history.push((text, selection));
+    }
+
+    /// Returns how many characters the user may insert in place of the given
+    /// selection before reaching `maxChars`.
+    fn available_chars(self, selection: TextSelection) -> usize {
+        let max_chars = match self.max_chars() {
+            0 => return usize::MAX,
+            max_chars => max_chars as usize,
+        };
+        let kept_length = self.text_length() - (selection.end() - selection.start());
+        max_chars.saturating_sub(kept_length)
+    }
+
+    /// Converts text from the clipboard into the text to insert in place of
+    /// the given selection.
+    ///
+    /// Single-line fields only receive the first line of the clipboard.
+    /// Characters forbidden by `restrict` are dropped, and the text is truncated
+    /// so that the field doesn't grow past `maxChars`.
+    fn pasted_text(self, content: &str, selection: TextSelection) -> WString {
+        let content = if self.is_multiline() {
+            content.replace("\r\n", "\r").replace('\n', "\r")
+        } else {
+            content
+                .split(['\r', '\n'])
+                .next()
+                .unwrap_or_default()
+                .to_string()
+        };
+
+        let available = self.available_chars(selection);
+        let read = self.0.read();
+        let mut text = WString::new();
+        for character in content.chars() {
+            if character != '\r' && character.is_control() {
+                continue;
+            }
+            if let Some(character) = read.restrict.to_allowed(character) {
+                if text.len() + character.len_utf16() > available {
+                    break;
+                }
+                text.push_char(character);
+            }
+        }
+        text
+    }
+
+    /// Propagates a change of text made by the user to bound variables and
+    /// change listeners.
+    fn text_changed_by_user(self, context: &mut UpdateContext<'_, 'gc, '_>) {
+        let globals = context.avm1.global_object_cell();
+        let swf_version = context.swf.version();
+        let mut activation = Avm1Activation::from_nothing(
+            context.reborrow(),
+            ActivationIdentifier::root("[Propagate Text Binding]"),
+            swf_version,
+            globals,
+            self.into(),
+        );
+        self.propagate_text_binding(&mut activation);
+        self.on_changed(&mut activation);
     }
 
     /// Listens for keyboard text control commands.
@@ -1490,10 +2108,24 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
         if context.avm_type() == AvmType::Avm2 && matches!(self.object2(), Avm2Value::Undefined) {
             self.construct_as_avm2_object(context, (*self).into());
         }
+
+        if context.avm_type() == AvmType::Avm2 {
+            let images = self.0.read().images.clone();
+            for image in images {
+                image.construct_frame(context);
+            }
+        }
     }
 
-    fn run_frame(&self, _context: &mut UpdateContext) {
-        // Noop
+    fn run_frame(&self, context: &mut UpdateContext) {
+        // Embedded images are run by AVM1's execution list, but AVM2 only
+        // runs objects on the display list.
+        if context.avm_type() == AvmType::Avm2 {
+            let images = self.0.read().images.clone();
+            for image in images {
+                image.run_frame(context);
+            }
+        }
     }
 
     fn as_edit_text(&self) -> Option<EditText<'gc>> {
@@ -1526,6 +2158,11 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
         if vm_type == AvmType::Avm1 {
             self.construct_as_avm1_object(context, run_frame);
         }
+
+        if !self.0.read().text_spans.images().is_empty() {
+            self.load_images(context);
+            self.relayout(context);
+        }
     }
 
     fn object(&self) -> Avm1Value<'gc> {
@@ -1802,6 +2439,14 @@ impl<'gc> TInteractiveObject<'gc> for EditText<'gc> {
         context: &mut UpdateContext<'_, 'gc, '_>,
         event: ClipEvent<'gc>,
     ) -> ClipEventResult {
+        if let Some((url, target)) = self.link_at(*context.mouse_position) {
+            self.open_link(context, &url, &target);
+        }
+
+        if !self.is_selectable() {
+            return ClipEventResult::Handled;
+        }
+
         let tracker = context.focus_tracker;
         tracker.set(Some(self.into()), context);
         if let Some(position) = self
@@ -1828,7 +2473,7 @@ impl<'gc> TInteractiveObject<'gc> for EditText<'gc> {
         // The button is hovered if the mouse is over any child nodes.
         if self.visible()
             && self.mouse_enabled()
-            && self.is_selectable()
+            && (self.is_selectable() || self.link_at(point).is_some())
             && self.hit_test_shape(context, point, HitTestOptions::MOUSE_PICK)
         {
             Some((*self).into())
@@ -1837,8 +2482,12 @@ impl<'gc> TInteractiveObject<'gc> for EditText<'gc> {
         }
     }
 
-    fn mouse_cursor(self, _context: &mut UpdateContext<'_, 'gc, '_>) -> MouseCursor {
-        MouseCursor::IBeam
+    fn mouse_cursor(self, context: &mut UpdateContext<'_, 'gc, '_>) -> MouseCursor {
+        if self.link_at(*context.mouse_position).is_some() {
+            MouseCursor::Hand
+        } else {
+            MouseCursor::IBeam
+        }
     }
 }
 
@@ -1876,6 +2525,118 @@ struct EditTextStaticData {
     is_device_font: bool,
 }
 
+/// The set of characters that the user may enter into a text field, as set by
+/// `TextField.restrict`.
+///
+/// The restrict string lists the allowed characters, with `-` denoting ranges
+/// such as `A-Z`. A `^` switches between allowing and forbidding the
+/// characters that follow it, and `\` escapes any of these special characters.
+/// If the string begins with `^`, all characters that aren't forbidden are
+/// allowed.
+#[derive(Clone, Debug, Collect)]
+#[collect(require_static)]
+pub struct EditTextRestrict {
+    /// The restrict string, or `None` if any character may be entered.
+    value: Option<WString>,
+
+    /// Whether characters not covered by any interval may be entered.
+    allowed_by_default: bool,
+
+    /// Character intervals, and whether their characters are allowed.
+    /// Later intervals take precedence over earlier ones.
+    intervals: Vec<(char, char, bool)>,
+}
+
+impl EditTextRestrict {
+    pub fn new(value: Option<&WStr>) -> Self {
+        let value = match value {
+            Some(value) => value,
+            None => {
+                return Self {
+                    value: None,
+                    allowed_by_default: true,
+                    intervals: Vec::new(),
+                }
+            }
+        };
+
+        let string = value.to_utf8_lossy();
+        let mut chars = string.chars().peekable();
+        let allowed_by_default = chars.peek() == Some(&'^');
+        let mut allowed = true;
+        let mut intervals = Vec::new();
+        while let Some(character) = chars.next() {
+            let start = match character {
+                '^' => {
+                    allowed = !allowed;
+                    continue;
+                }
+                '\\' => match chars.next() {
+                    Some(character) => character,
+                    None => break,
+                },
+                character => character,
+            };
+
+            let mut end = start;
+            if chars.peek() == Some(&'-') {
+                let mut range = chars.clone();
+                range.next();
+                let range_end = match range.next() {
+                    Some('\\') => range.next(),
+                    range_end => range_end,
+                };
+                if let Some(range_end) = range_end {
+                    end = range_end;
+                    chars = range;
+                }
+            }
+
+            intervals.push((start, end, allowed));
+        }
+
+        Self {
+            value: Some(value.into()),
+            allowed_by_default,
+            intervals,
+        }
+    }
+
+    pub fn value(&self) -> Option<&WStr> {
+        self.value.as_deref()
+    }
+
+    pub fn is_allowed(&self, character: char) -> bool {
+        self.intervals
+            .iter()
+            .rev()
+            .find(|(start, end, _)| (*start..=*end).contains(&character))
+            .map(|(_, _, allowed)| *allowed)
+            .unwrap_or(self.allowed_by_default)
+    }
+
+    /// Returns the character to insert when the user types `character`, or
+    /// `None` if it can't be entered.
+    ///
+    /// Like in Flash Player, letters are converted to the other case when only
+    /// that case is allowed.
+    pub fn to_allowed(&self, character: char) -> Option<char> {
+        if self.is_allowed(character) {
+            return Some(character);
+        }
+
+        let mut other_case = if character.is_lowercase() {
+            character.to_uppercase()
+        } else {
+            character.to_lowercase()
+        };
+        match (other_case.next(), other_case.next()) {
+            (Some(other_case), None) if self.is_allowed(other_case) => Some(other_case),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Copy, Clone, Debug, Collect)]
 #[collect(require_static)]
 pub struct TextSelection {
@@ -1892,6 +2653,90 @@ pub struct LineData {
     offset: Twips,
     /// How many twips down the lowest point of the line is
     extent: Twips,
+    /// The index of the first character on the line
+    start: usize,
+    /// How many twips right the leftmost point of the line is
+    x_offset: Twips,
+    /// How many twips right the rightmost point of the line is
+    x_extent: Twips,
+    /// The largest ascent of any text on the line
+    ascent: Twips,
+    /// The largest descent of any text on the line
+    descent: Twips,
+    /// The largest leading of any text on the line
+    leading: Twips,
+}
+
+impl LineData {
+    fn new(index: usize, layout_box: &LayoutBox) -> Self {
+        let bounds = layout_box.bounds();
+        let mut line = Self {
+            index,
+            offset: bounds.offset_y(),
+            extent: bounds.extent_y(),
+            start: usize::MAX,
+            x_offset: bounds.offset_x(),
+            x_extent: bounds.extent_x(),
+            ascent: Twips::ZERO,
+            descent: Twips::ZERO,
+            leading: Twips::ZERO,
+        };
+        line.add_metrics(layout_box);
+        line
+    }
+
+    /// Extend this line to include another box on it.
+    fn add_box(&mut self, layout_box: &LayoutBox) {
+        let bounds = layout_box.bounds();
+        self.offset = self.offset.min(bounds.offset_y());
+        self.extent = self.extent.max(bounds.extent_y());
+        self.x_offset = self.x_offset.min(bounds.offset_x());
+        self.x_extent = self.x_extent.max(bounds.extent_x());
+        self.add_metrics(layout_box);
+    }
+
+    fn add_metrics(&mut self, layout_box: &LayoutBox) {
+        let (text_format, font, params) = match layout_box.content() {
+            LayoutContent::Text {
+                start,
+                text_format,
+                font,
+                params,
+                ..
+            } => {
+                self.start = self.start.min(*start);
+                (text_format, font, params)
+            }
+            LayoutContent::Bullet {
+                text_format,
+                font,
+                params,
+                ..
+            } => (text_format, font, params),
+            LayoutContent::Drawing(..) | LayoutContent::Image(..) => return,
+        };
+
+        let leading = Twips::from_pixels(text_format.leading.unwrap_or(0.0));
+        self.ascent = self
+            .ascent
+            .max(font.get_baseline_for_height(params.height()));
+        self.descent = self
+            .descent
+            .max(font.get_descent_for_height(params.height()));
+        self.leading = self.leading.max(leading);
+    }
+}
+
+/// The metrics of a single line of text, as reported by `getLineMetrics`.
+#[derive(Copy, Clone, Debug)]
+pub struct LineMetrics {
+    /// How many twips right the line starts, relative to the text field
+    pub x: Twips,
+    pub width: Twips,
+    pub height: Twips,
+    pub ascent: Twips,
+    pub descent: Twips,
+    pub leading: Twips,
 }
 
 impl TextSelection {
@@ -1956,3 +2801,67 @@ impl TextSelection {
         self.to == self.from
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::EditTextRestrict;
+    use crate::string::WStr;
+
+    fn parse(value: &str) -> EditTextRestrict {
+        EditTextRestrict::new(Some(WStr::from_units(value.as_bytes())))
+    }
+
+    #[test]
+    fn restrict_null_allows_everything() {
+        let restrict = EditTextRestrict::new(None);
+        assert!(restrict.is_allowed('a'));
+        assert!(restrict.is_allowed('^'));
+    }
+
+    #[test]
+    fn restrict_empty_allows_nothing() {
+        let restrict = parse("");
+        assert!(!restrict.is_allowed('a'));
+        assert!(!restrict.is_allowed(' '));
+    }
+
+    #[test]
+    fn restrict_ranges() {
+        let restrict = parse("A-Z 0-9");
+        assert!(restrict.is_allowed('A'));
+        assert!(restrict.is_allowed('Q'));
+        assert!(restrict.is_allowed('9'));
+        assert!(restrict.is_allowed(' '));
+        assert!(!restrict.is_allowed('-'));
+        assert!(!restrict.is_allowed('a'));
+    }
+
+    #[test]
+    fn restrict_negation() {
+        let restrict = parse("^a-z");
+        assert!(!restrict.is_allowed('q'));
+        assert!(restrict.is_allowed('Q'));
+
+        let restrict = parse("A-Z^Q");
+        assert!(restrict.is_allowed('P'));
+        assert!(!restrict.is_allowed('Q'));
+        assert!(!restrict.is_allowed('1'));
+    }
+
+    #[test]
+    fn restrict_escapes() {
+        let restrict = parse("0-9\\-\\^\\\\");
+        assert!(restrict.is_allowed('-'));
+        assert!(restrict.is_allowed('^'));
+        assert!(restrict.is_allowed('\\'));
+        assert!(restrict.is_allowed('5'));
+        assert!(!restrict.is_allowed('a'));
+    }
+
+    #[test]
+    fn restrict_converts_case() {
+        let restrict = parse("A-Z");
+        assert_eq!(restrict.to_allowed('a'), Some('A'));
+        assert_eq!(restrict.to_allowed('1'), None);
+    }
+}
@@ -173,130 +173,8 @@ impl MorphShapeStatic {
             .library_for_movie(Arc::clone(&self.movie))
             .unwrap();
 
-        // Interpolate MorphShapes into a Shape.
-        use swf::{FillStyle, LineStyle, ShapeRecord, ShapeStyles};
-        // Start shape is ratio 65535, end shape is ratio 0.
-        let b = f32::from(ratio) / 65535.0;
-        let a = 1.0 - b;
-        let fill_styles: Vec<FillStyle> = self
-            .start
-            .fill_styles
-            .iter()
-            .zip(self.end.fill_styles.iter())
-            .map(|(start, end)| lerp_fill(start, end, a, b))
-            .collect();
-        let line_styles: Vec<LineStyle> = self
-            .start
-            .line_styles
-            .iter()
-            .zip(self.end.line_styles.iter())
-            .map(|(start, end)| LineStyle {
-                width: lerp_twips(start.width, end.width, a, b),
-                color: lerp_color(&start.color, &end.color, a, b),
-                start_cap: start.start_cap,
-                end_cap: start.end_cap,
-                join_style: start.join_style,
-                fill_style: None,
-                allow_scale_x: start.allow_scale_x,
-                allow_scale_y: start.allow_scale_y,
-                is_pixel_hinted: start.is_pixel_hinted,
-                allow_close: start.allow_close,
-            })
-            .collect();
-
-        let mut shape = Vec::with_capacity(self.start.shape.len());
-        let mut start_iter = self.start.shape.iter();
-        let mut end_iter = self.end.shape.iter();
-        let mut start = start_iter.next();
-        let mut end = end_iter.next();
-        let mut start_x = Twips::ZERO;
-        let mut start_y = Twips::ZERO;
-        let mut end_x = Twips::ZERO;
-        let mut end_y = Twips::ZERO;
-        // TODO: Feels like this could be cleaned up a bit.
-        // We step through both the start records and end records, interpolating edges pairwise.
-        // Fill style/line style changes should only appear in the start records.
-        // However, StyleChangeRecord move_to can appear it both start and end records,
-        // and not necessarily in matching pairs; therefore, we have to keep track of the pen position
-        // in case one side is missing a move_to; it will implicitly use the last pen position.
-        while let (Some(s), Some(e)) = (start, end) {
-            match (s, e) {
-                (ShapeRecord::StyleChange(start_change), ShapeRecord::StyleChange(end_change)) => {
-                    let mut style_change = start_change.clone();
-                    if start_change.move_to.is_some() || end_change.move_to.is_some() {
-                        if let Some((s_x, s_y)) = start_change.move_to {
-                            start_x = s_x;
-                            start_y = s_y;
-                        }
-                        if let Some((e_x, e_y)) = end_change.move_to {
-                            end_x = e_x;
-                            end_y = e_y;
-                        }
-                        style_change.move_to = Some((
-                            lerp_twips(start_x, end_x, a, b),
-                            lerp_twips(start_y, end_y, a, b),
-                        ));
-                    }
-                    shape.push(ShapeRecord::StyleChange(style_change));
-                    start = start_iter.next();
-                    end = end_iter.next();
-                }
-                (ShapeRecord::StyleChange(start_change), _) => {
-                    let mut style_change = start_change.clone();
-                    if let Some((s_x, s_y)) = start_change.move_to {
-                        start_x = s_x;
-                        start_y = s_y;
-                        style_change.move_to = Some((
-                            lerp_twips(start_x, end_x, a, b),
-                            lerp_twips(start_y, end_y, a, b),
-                        ));
-                    }
-                    shape.push(ShapeRecord::StyleChange(style_change));
-                    Self::update_pos(&mut start_x, &mut start_y, s);
-                    start = start_iter.next();
-                }
-                (_, ShapeRecord::StyleChange(end_change)) => {
-                    let mut style_change = end_change.clone();
-                    if let Some((e_x, e_y)) = end_change.move_to {
-                        end_x = e_x;
-                        end_y = e_y;
-                        style_change.move_to = Some((
-                            lerp_twips(start_x, end_x, a, b),
-                            lerp_twips(start_y, end_y, a, b),
-                        ));
-                    }
-                    shape.push(ShapeRecord::StyleChange(style_change));
-                    Self::update_pos(&mut end_x, &mut end_y, s);
-                    end = end_iter.next();
-                    continue;
-                }
-                _ => {
-                    shape.push(lerp_edges(s, e, a, b));
-                    Self::update_pos(&mut start_x, &mut start_y, s);
-                    Self::update_pos(&mut end_x, &mut end_y, e);
-                    start = start_iter.next();
-                    end = end_iter.next();
-                }
-            }
-        }
-
-        let styles = ShapeStyles {
-            fill_styles,
-            line_styles,
-        };
-
-        let bounds = crate::shape_utils::calculate_shape_bounds(&shape[..]);
-        let shape = swf::Shape {
-            version: 4,
-            id: 0,
-            shape_bounds: bounds.clone(),
-            edge_bounds: bounds.clone(),
-            has_fill_winding_rule: false,
-            has_non_scaling_strokes: false,
-            has_scaling_strokes: true,
-            styles,
-            shape,
-        };
+        let shape = interpolate_morph_shape(&self.start, &self.end, ratio);
+        let bounds = shape.shape_bounds.clone();
 
         let shape_handle = context.renderer.register_shape((&shape).into(), library);
         let frame = Frame {
@@ -333,6 +211,136 @@ impl MorphShapeStatic {
     }
 }
 
+/// Interpolate a morph shape into a shape, at a ratio between 0 (the start
+/// shape) and 65535 (the end shape).
+pub fn interpolate_morph_shape(
+    start: &swf::MorphShape,
+    end: &swf::MorphShape,
+    ratio: u16,
+) -> swf::Shape {
+    use swf::{FillStyle, LineStyle, ShapeRecord, ShapeStyles};
+    // Start shape is ratio 65535, end shape is ratio 0.
+    let b = f32::from(ratio) / 65535.0;
+    let a = 1.0 - b;
+    let fill_styles: Vec<FillStyle> = start
+        .fill_styles
+        .iter()
+        .zip(end.fill_styles.iter())
+        .map(|(start, end)| lerp_fill(start, end, a, b))
+        .collect();
+    let line_styles: Vec<LineStyle> = start
+        .line_styles
+        .iter()
+        .zip(end.line_styles.iter())
+        .map(|(start, end)| LineStyle {
+            width: lerp_twips(start.width, end.width, a, b),
+            color: lerp_color(&start.color, &end.color, a, b),
+            start_cap: start.start_cap,
+            end_cap: start.end_cap,
+            join_style: start.join_style,
+            fill_style: None,
+            allow_scale_x: start.allow_scale_x,
+            allow_scale_y: start.allow_scale_y,
+            is_pixel_hinted: start.is_pixel_hinted,
+            allow_close: start.allow_close,
+        })
+        .collect();
+
+    let mut shape = Vec::with_capacity(start.shape.len());
+    let mut start_iter = start.shape.iter();
+    let mut end_iter = end.shape.iter();
+    let mut start_record = start_iter.next();
+    let mut end_record = end_iter.next();
+    let mut start_x = Twips::ZERO;
+    let mut start_y = Twips::ZERO;
+    let mut end_x = Twips::ZERO;
+    let mut end_y = Twips::ZERO;
+    // TODO: Feels like this could be cleaned up a bit.
+    // We step through both the start records and end records, interpolating edges pairwise.
+    // Fill style/line style changes should only appear in the start records.
+    // However, StyleChangeRecord move_to can appear it both start and end records,
+    // and not necessarily in matching pairs; therefore, we have to keep track of the pen position
+    // in case one side is missing a move_to; it will implicitly use the last pen position.
+    while let (Some(s), Some(e)) = (start_record, end_record) {
+        match (s, e) {
+            (ShapeRecord::StyleChange(start_change), ShapeRecord::StyleChange(end_change)) => {
+                let mut style_change = start_change.clone();
+                if start_change.move_to.is_some() || end_change.move_to.is_some() {
+                    if let Some((s_x, s_y)) = start_change.move_to {
+                        start_x = s_x;
+                        start_y = s_y;
+                    }
+                    if let Some((e_x, e_y)) = end_change.move_to {
+                        end_x = e_x;
+                        end_y = e_y;
+                    }
+                    style_change.move_to = Some((
+                        lerp_twips(start_x, end_x, a, b),
+                        lerp_twips(start_y, end_y, a, b),
+                    ));
+                }
+                shape.push(ShapeRecord::StyleChange(style_change));
+                start_record = start_iter.next();
+                end_record = end_iter.next();
+            }
+            (ShapeRecord::StyleChange(start_change), _) => {
+                let mut style_change = start_change.clone();
+                if let Some((s_x, s_y)) = start_change.move_to {
+                    start_x = s_x;
+                    start_y = s_y;
+                    style_change.move_to = Some((
+                        lerp_twips(start_x, end_x, a, b),
+                        lerp_twips(start_y, end_y, a, b),
+                    ));
+                }
+                shape.push(ShapeRecord::StyleChange(style_change));
+                MorphShapeStatic::update_pos(&mut start_x, &mut start_y, s);
+                start_record = start_iter.next();
+            }
+            (_, ShapeRecord::StyleChange(end_change)) => {
+                let mut style_change = end_change.clone();
+                if let Some((e_x, e_y)) = end_change.move_to {
+                    end_x = e_x;
+                    end_y = e_y;
+                    style_change.move_to = Some((
+                        lerp_twips(start_x, end_x, a, b),
+                        lerp_twips(start_y, end_y, a, b),
+                    ));
+                }
+                shape.push(ShapeRecord::StyleChange(style_change));
+                MorphShapeStatic::update_pos(&mut end_x, &mut end_y, s);
+                end_record = end_iter.next();
+                continue;
+            }
+            _ => {
+                shape.push(lerp_edges(s, e, a, b));
+                MorphShapeStatic::update_pos(&mut start_x, &mut start_y, s);
+                MorphShapeStatic::update_pos(&mut end_x, &mut end_y, e);
+                start_record = start_iter.next();
+                end_record = end_iter.next();
+            }
+        }
+    }
+
+    let styles = ShapeStyles {
+        fill_styles,
+        line_styles,
+    };
+
+    let bounds = crate::shape_utils::calculate_shape_bounds(&shape[..]);
+    swf::Shape {
+        version: 4,
+        id: 0,
+        shape_bounds: bounds.clone(),
+        edge_bounds: bounds,
+        has_fill_winding_rule: false,
+        has_non_scaling_strokes: false,
+        has_scaling_strokes: true,
+        styles,
+        shape,
+    }
+}
+
 // Interpolation functions
 // These interpolate between two SWF shape structures.
 // a + b should = 1.0
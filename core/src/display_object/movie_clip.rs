@@ -9,6 +9,7 @@ use crate::avm2::{
     TObject as Avm2TObject, Value as Avm2Value,
 };
 use crate::backend::audio::{PreloadStreamHandle, SoundHandle, SoundInstanceHandle};
+use crate::backend::navigator::{url_from_relative_url, RequestOptions};
 use crate::backend::ui::MouseCursor;
 use bitflags::bitflags;
 
@@ -335,6 +336,10 @@ impl<'gc> MovieClip<'gc> {
                 .0
                 .write(context.gc_context)
                 .define_font_4(context, reader),
+            TagCode::DefineFontAlignZones => self
+                .0
+                .write(context.gc_context)
+                .define_font_align_zones(context, reader),
             TagCode::DefineMorphShape => self.0.write(context.gc_context).define_morph_shape(
                 context,
                 reader,
@@ -395,6 +400,14 @@ impl<'gc> MovieClip<'gc> {
                 .0
                 .write(context.gc_context)
                 .export_assets(context, reader),
+            TagCode::ImportAssets => self
+                .0
+                .write(context.gc_context)
+                .import_assets(context, reader, 1),
+            TagCode::ImportAssets2 => self
+                .0
+                .write(context.gc_context)
+                .import_assets(context, reader, 2),
             TagCode::FrameLabel => self.0.write(context.gc_context).frame_label(
                 context,
                 reader,
@@ -1147,6 +1160,15 @@ impl<'gc> MovieClip<'gc> {
         write.queued_script_frame = Some(frame_id);
     }
 
+    /// Returns whether the movie of this clip is still loading the shared
+    /// libraries that it imports characters from.
+    fn is_waiting_for_imports(self, context: &mut UpdateContext<'_, 'gc, '_>) -> bool {
+        context
+            .library
+            .library_for_movie(self.movie().unwrap())
+            .map_or(false, |library| library.is_importing())
+    }
+
     /// Instantiate a given child object on the timeline at a given depth.
     fn instantiate_child(
         self,
@@ -1769,7 +1791,9 @@ impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
                 false
             };
 
-            if self.determine_next_frame() != NextFrame::First {
+            if self.determine_next_frame() != NextFrame::First
+                && !self.is_waiting_for_imports(context)
+            {
                 let mc = self.0.read();
                 let data = mc.static_data.swf.clone();
                 let mut reader = data.read_from(mc.tag_stream_pos);
@@ -1793,6 +1817,11 @@ impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
     }
 
     fn run_frame(&self, context: &mut UpdateContext<'_, 'gc, '_>) {
+        // Wait for shared libraries to load before playing.
+        if self.is_waiting_for_imports(context) {
+            return;
+        }
+
         // Run my load/enterFrame clip event.
         let is_load_frame = !self.0.read().initialized();
         if is_load_frame {
@@ -2897,10 +2926,47 @@ impl<'gc, 'a> MovieClipData<'gc> {
     #[inline]
     fn define_font_4(
         &mut self,
-        _context: &mut UpdateContext<'_, 'gc, '_>,
-        _reader: &mut SwfStream<'a>,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        reader: &mut SwfStream<'a>,
     ) -> DecodeResult {
-        log::warn!("DefineFont4 tag (TLF text) is not implemented");
+        let font = reader.read_define_font_4()?;
+        let font_id = font.id;
+        match Font::from_font4_tag(
+            context.gc_context,
+            context.renderer,
+            font,
+            reader.encoding(),
+        ) {
+            Ok(font_object) => context
+                .library
+                .library_for_movie_mut(self.movie())
+                .register_character(font_id, Character::Font(font_object)),
+            Err(e) => log::warn!("Unable to parse DefineFont4 font {}: {}", font_id, e),
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn define_font_align_zones(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        reader: &mut SwfStream<'a>,
+    ) -> DecodeResult {
+        if let Tag::DefineFontAlignZones { id, zones, .. } =
+            reader.read_define_font_align_zones()?
+        {
+            match context
+                .library
+                .library_for_movie_mut(self.movie())
+                .character_by_id(id)
+            {
+                Some(Character::Font(font)) => font.set_align_zones(&zones),
+                _ => log::warn!(
+                    "Tried to apply DefineFontAlignZones to non-font character ID {}",
+                    id
+                ),
+            }
+        }
         Ok(())
     }
 
@@ -3049,6 +3115,51 @@ impl<'gc, 'a> MovieClipData<'gc> {
         Ok(())
     }
 
+    #[inline]
+    fn import_assets(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        reader: &mut SwfStream<'a>,
+        version: u8,
+    ) -> DecodeResult {
+        let (url, imports) = if version == 1 {
+            reader.read_import_assets()?
+        } else {
+            reader.read_import_assets_2()?
+        };
+        let url = url.to_str_lossy(reader.encoding());
+        let imports = imports
+            .into_iter()
+            .map(|import| {
+                let name = import.name.to_str_lossy(reader.encoding()).into_owned();
+                (import.id, name)
+            })
+            .collect();
+
+        let player = match context.player.clone() {
+            Some(player) => player,
+            None => return Ok(()),
+        };
+
+        // Shared libraries are relative to the importing movie.
+        let movie = self.movie();
+        let url = movie
+            .url()
+            .and_then(|base| url_from_relative_url(base, &url).ok())
+            .map_or_else(|| url.into_owned(), |url| url.to_string());
+
+        context
+            .library
+            .library_for_movie_mut(movie.clone())
+            .begin_import();
+        let fetch = context.navigator.fetch(&url, RequestOptions::get());
+        let process = context
+            .load_manager
+            .load_asset_imports(player, movie, fetch, url, imports);
+        context.navigator.spawn_future(process);
+        Ok(())
+    }
+
     #[inline]
     fn frame_label(
         &mut self,
@@ -462,6 +462,30 @@ impl<'gc> Stage<'gc> {
         }
     }
 
+    /// Draw a translucent box over the given bounds, in stage coordinates.
+    fn draw_highlight(&self, context: &mut RenderContext<'_, 'gc>, bounds: BoundingBox) {
+        if !bounds.valid {
+            return;
+        }
+
+        let bounds = bounds.transform(self.base().matrix());
+        context.renderer.draw_rect(
+            Color {
+                r: 0x33,
+                g: 0x99,
+                b: 0xff,
+                a: 0x60,
+            },
+            &Matrix::create_box(
+                bounds.width().to_pixels() as f32,
+                bounds.height().to_pixels() as f32,
+                0.0,
+                bounds.x_min,
+                bounds.y_min,
+            ),
+        );
+    }
+
     /// Draw the stage's letterbox.
     fn draw_letterbox(&self, context: &mut RenderContext<'_, 'gc>) {
         let black = Color::from_rgb(0, 255);
@@ -685,6 +709,10 @@ impl<'gc> TDisplayObject<'gc> for Stage<'gc> {
             self.draw_letterbox(context);
         }
 
+        if let Some(bounds) = &context.highlight_bounds {
+            self.draw_highlight(context, bounds.clone());
+        }
+
         context.renderer.end_frame();
     }
 
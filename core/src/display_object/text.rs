@@ -134,10 +134,7 @@ impl<'gc> TDisplayObject<'gc> for Text<'gc> {
                 for c in &block.glyphs {
                     if let Some(glyph) = font.get_glyph(c.index as usize) {
                         context.transform_stack.push(&transform);
-                        let glyph_shape_handle = glyph.shape_handle(context.renderer);
-                        context
-                            .renderer
-                            .render_shape(glyph_shape_handle, context.transform_stack.transform());
+                        tf.render_settings.render_glyph(context, font, glyph);
                         context.transform_stack.pop();
                         transform.matrix.tx += Twips::new(c.advance);
                     }
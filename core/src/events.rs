@@ -1,7 +1,8 @@
 use crate::display_object::InteractiveObject;
+use serde::{Deserialize, Serialize};
 use swf::ClipEventFlag;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum PlayerEvent {
     KeyDown {
         key_code: KeyCode,
@@ -35,7 +36,7 @@ pub enum PlayerEvent {
 }
 
 /// The distance scrolled by the mouse wheel.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub enum MouseWheelDelta {
     Lines(f64),
     Pixels(f64),
@@ -327,7 +328,7 @@ impl<'gc> ClipEvent<'gc> {
 }
 
 /// Flash virtual keycode.
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, FromPrimitive)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, FromPrimitive, Serialize, Deserialize)]
 pub enum KeyCode {
     Unknown = 0,
     MouseLeft = 1,
@@ -437,7 +438,7 @@ impl KeyCode {
 }
 
 /// Subset of `KeyCode` that contains only mouse buttons.
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum MouseButton {
     Unknown = KeyCode::Unknown as isize,
     Left = KeyCode::MouseLeft as isize,
@@ -601,3 +602,28 @@ pub fn key_code_to_button_key_code(key_code: KeyCode) -> Option<ButtonKeyCode> {
     };
     Some(out)
 }
+
+/// A text editing command, usually triggered by a keyboard shortcut.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextControlCode {
+    SelectAll,
+    Copy,
+    Cut,
+    Paste,
+    Undo,
+}
+
+impl TextControlCode {
+    /// Returns the command for a key pressed while Control is held, if any.
+    pub fn from_control_key(key_code: KeyCode) -> Option<Self> {
+        let out = match key_code {
+            KeyCode::A => TextControlCode::SelectAll,
+            KeyCode::C => TextControlCode::Copy,
+            KeyCode::X => TextControlCode::Cut,
+            KeyCode::V => TextControlCode::Paste,
+            KeyCode::Z => TextControlCode::Undo,
+            _ => return None,
+        };
+        Some(out)
+    }
+}
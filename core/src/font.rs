@@ -1,13 +1,17 @@
 use crate::backend::render::{RenderBackend, ShapeHandle};
+use crate::context::RenderContext;
 use crate::html::TextSpan;
 use crate::prelude::*;
 use crate::string::WStr;
 use crate::transform::Transform;
 use gc_arena::{Collect, Gc, MutationContext};
 use std::cell::{Cell, Ref, RefCell};
+use swf::Fixed8;
 
 pub use swf::TextGridFit;
 
+mod opentype;
+
 /// Certain Flash routines measure text by rounding down to the nearest whole pixel.
 pub fn round_down_to_pixel(t: Twips) -> Twips {
     Twips::from_pixels(t.to_pixels().floor())
@@ -15,6 +19,20 @@ pub fn round_down_to_pixel(t: Twips) -> Twips {
 
 type Error = Box<dyn std::error::Error>;
 
+/// The kind of fonts that a text field lays out its text with.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Collect)]
+#[collect(require_static)]
+pub enum FontType {
+    /// Fonts embedded with `DefineFont`, `DefineFont2` or `DefineFont3`.
+    Embedded,
+
+    /// CFF fonts embedded with `DefineFont4`, used by `flash.text.engine`.
+    EmbeddedCFF,
+
+    /// Fonts provided by the player.
+    Device,
+}
+
 /// Parameters necessary to evaluate a font.
 #[derive(Copy, Clone, Debug, Collect)]
 #[collect(require_static)]
@@ -99,6 +117,9 @@ struct FontData {
 
     /// The identity of the font.
     descriptor: FontDescriptor,
+
+    /// Whether this font was defined by `DefineFont4`.
+    is_cff: bool,
 }
 
 impl<'gc> Font<'gc> {
@@ -131,6 +152,7 @@ impl<'gc> Font<'gc> {
                 shape_handle: Cell::new(handle),
                 advance: swf_glyph.advance.unwrap_or(0),
                 shape: RefCell::new(None),
+                align_zone: Cell::new(None),
                 swf_glyph,
             };
             let index = glyphs.len();
@@ -161,16 +183,101 @@ impl<'gc> Font<'gc> {
                 descent,
                 leading,
                 descriptor,
+                is_cff: tag_version >= 4,
             },
         )))
     }
 
+    /// Construct a font from a `DefineFont4` tag.
+    ///
+    /// The embedded OpenType CFF font is converted into glyph shapes at the
+    /// same scale as `DefineFont3`. Tags without font data only reference a
+    /// font by name, and are treated as device fonts.
+    pub fn from_font4_tag(
+        gc_context: MutationContext<'gc, '_>,
+        renderer: &mut dyn RenderBackend,
+        tag: swf::Font4,
+        encoding: &'static swf::Encoding,
+    ) -> Result<Font<'gc>, Error> {
+        let (glyphs, layout) = if let Some(data) = tag.data {
+            let font = opentype::parse(data)?;
+            let layout = swf::FontLayout {
+                ascent: font.ascent,
+                descent: font.descent,
+                leading: font.leading,
+                kerning: vec![],
+            };
+            (font.glyphs, Some(layout))
+        } else {
+            (vec![], None)
+        };
+
+        let font = swf::Font {
+            version: 4,
+            id: tag.id,
+            name: tag.name,
+            language: swf::Language::Unknown,
+            layout,
+            glyphs,
+            is_small_text: false,
+            is_shift_jis: false,
+            is_ansi: false,
+            is_bold: tag.is_bold,
+            is_italic: tag.is_italic,
+        };
+        Self::from_swf_tag(gc_context, renderer, font, encoding)
+    }
+
+    /// Construct a font from a TrueType or OpenType font file, such as a
+    /// device font provided by a `FontBackend`.
+    pub fn from_font_file(
+        gc_context: MutationContext<'gc, '_>,
+        renderer: &mut dyn RenderBackend,
+        data: &[u8],
+    ) -> Result<Font<'gc>, Error> {
+        let info = opentype::face_info(data)?;
+        let font = opentype::parse(data)?;
+        let font = swf::Font {
+            version: 3,
+            id: 0,
+            name: swf::SwfStr::from_utf8_str(&info.name),
+            language: swf::Language::Unknown,
+            layout: Some(swf::FontLayout {
+                ascent: font.ascent,
+                descent: font.descent,
+                leading: font.leading,
+                kerning: vec![],
+            }),
+            glyphs: font.glyphs,
+            is_small_text: false,
+            is_shift_jis: false,
+            is_ansi: false,
+            is_bold: info.is_bold,
+            is_italic: info.is_italic,
+        };
+        Self::from_swf_tag(gc_context, renderer, font, swf::UTF_8)
+    }
+
+    /// Returns whether this font was defined by `DefineFont4`, and is only
+    /// usable by `flash.text.engine`.
+    pub fn is_cff(&self) -> bool {
+        self.0.is_cff
+    }
+
     /// Returns whether this font contains glyph shapes.
     /// If not, this font should be rendered as a device font.
     pub fn has_glyphs(&self) -> bool {
         !self.0.glyphs.is_empty()
     }
 
+    /// Set the alignment zones of this font's glyphs from a
+    /// `DefineFontAlignZones` tag, in glyph order.
+    pub fn set_align_zones(&self, zones: &[swf::FontAlignZone]) {
+        for (glyph, zone) in self.0.glyphs.iter().zip(zones) {
+            glyph.align_zone.set(Some(zone.into()));
+        }
+    }
+
     /// Returns a glyph entry by index.
     /// Used by `Text` display objects.
     pub fn get_glyph(&self, i: usize) -> Option<&Glyph> {
@@ -229,6 +336,13 @@ impl<'gc> Font<'gc> {
         Twips::new((self.0.ascent as f32 * scale) as i32)
     }
 
+    /// Get the descent below the baseline at a given height.
+    pub fn get_descent_for_height(&self, height: Twips) -> Twips {
+        let scale = height.get() as f32 / self.scale();
+
+        Twips::new((self.0.descent as f32 * scale) as i32)
+    }
+
     /// Returns whether this font contains kerning information.
     pub fn has_kerning_info(&self) -> bool {
         !self.0.kerning_pairs.is_empty()
@@ -417,6 +531,9 @@ pub struct Glyph {
     shape: RefCell<Option<swf::Shape>>,
     // The underlying glyph record, containing its shape.
     swf_glyph: swf::Glyph,
+    // The alignment zone used to fit this glyph to the pixel grid.
+    // Set by a `DefineFontAlignZones` tag following the font definition.
+    align_zone: Cell<Option<AlignZone>>,
 }
 
 impl Glyph {
@@ -437,6 +554,49 @@ impl Glyph {
         let read = self.shape.borrow();
         Ref::map(read, |s| s.as_ref().unwrap())
     }
+
+    pub fn align_zone(&self) -> Option<AlignZone> {
+        self.align_zone.get()
+    }
+}
+
+/// The box of a glyph whose edges should be aligned to the pixel grid by the
+/// advanced text rendering engine.
+///
+/// Coordinates are fractions of the EM square, with Y pointing up from the
+/// baseline.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AlignZone {
+    pub left: f32,
+    pub width: f32,
+    pub bottom: f32,
+    pub height: f32,
+}
+
+impl From<&swf::FontAlignZone> for AlignZone {
+    fn from(zone: &swf::FontAlignZone) -> Self {
+        Self {
+            left: f16_to_f32(zone.left),
+            width: f16_to_f32(zone.width),
+            bottom: f16_to_f32(zone.bottom),
+            height: f16_to_f32(zone.height),
+        }
+    }
+}
+
+/// Decode the bits of a half-precision float, as stored in
+/// `DefineFontAlignZones` tags.
+fn f16_to_f32(bits: i16) -> f32 {
+    let bits = bits as u16;
+    let sign = if bits & 0x8000 != 0 { -1.0 } else { 1.0 };
+    let exponent = i32::from((bits >> 10) & 0x1f);
+    let mantissa = f32::from(bits & 0x3ff);
+    match exponent {
+        0 => sign * mantissa * 2f32.powi(-24),
+        0x1f if mantissa == 0.0 => sign * f32::INFINITY,
+        0x1f => f32::NAN,
+        _ => sign * (1.0 + mantissa / 1024.0) * 2f32.powi(exponent - 15),
+    }
 }
 
 /// Structure which identifies a particular font by name and properties.
@@ -460,6 +620,16 @@ impl FontDescriptor {
         }
     }
 
+    /// Obtain a font descriptor from a TrueType or OpenType font file.
+    pub fn from_font_file(data: &[u8]) -> Result<Self, Error> {
+        let info = opentype::face_info(data)?;
+        Ok(Self {
+            name: info.name,
+            is_bold: info.is_bold,
+            is_italic: info.is_italic,
+        })
+    }
+
     /// Obtain a font descriptor from a name/bold/italic triplet.
     pub fn from_parts(name: &str, is_bold: bool, is_italic: bool) -> Self {
         let mut name = name.to_string();
@@ -492,58 +662,208 @@ impl FontDescriptor {
 }
 
 /// The text rendering engine that a text field should use.
-/// This is controlled by the "Anti-alias" setting in the Flash IDE.
-/// Using "Anti-alias for readibility" switches to the "Advanced" text
-/// rendering engine.
-#[derive(Debug, PartialEq, Clone, Collect)]
+/// This is controlled by the "Anti-alias" setting in the Flash IDE, or the
+/// `antiAliasType` property of a text field.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Collect)]
 #[collect(require_static)]
-pub enum TextRenderSettings {
+pub enum AntiAliasType {
     /// This text should render with the standard rendering engine.
     /// Set via "Anti-alias for animation" in the Flash IDE.
-    Default,
+    Normal,
 
     /// This text should render with the advanced rendering engine.
     /// Set via "Anti-alias for readibility" in the Flash IDE.
-    /// The parameters are set via the CSMTextSettings SWF tag.
-    /// Ruffle does not support this currently, but this also affects
-    /// hit-testing behavior.
-    Advanced {
-        grid_fit: TextGridFit,
-        thickness: f32,
-        sharpness: f32,
-    },
+    /// This also affects hit-testing behavior.
+    Advanced,
+}
+
+/// The largest distance, in pixels, that glyph strokes are widened or
+/// narrowed by at the extremes of the thickness range.
+const MAX_STROKE_OFFSET: f32 = 0.5;
+
+/// The settings of the text rendering engine for a text field.
+/// The parameters are set via the CSMTextSettings SWF tag, or the
+/// `antiAliasType`, `gridFitType`, `thickness` and `sharpness` properties of
+/// a text field.
+///
+/// Ruffle renders glyphs as regular shapes, so sharpness has no effect.
+#[derive(Debug, PartialEq, Clone, Collect)]
+#[collect(require_static)]
+pub struct TextRenderSettings {
+    pub anti_alias_type: AntiAliasType,
+
+    /// How the advanced rendering engine aligns glyphs to the pixel grid.
+    pub grid_fit: TextGridFit,
+
+    /// The thickness of glyph edges, from -200 to 200.
+    pub thickness: f32,
+
+    /// The sharpness of glyph edges, from -400 to 400.
+    pub sharpness: f32,
 }
 
 impl TextRenderSettings {
     pub fn is_advanced(&self) -> bool {
-        matches!(self, TextRenderSettings::Advanced { .. })
+        self.anti_alias_type == AntiAliasType::Advanced
+    }
+
+    /// Render a glyph of `font` with the current transform.
+    ///
+    /// The advanced rendering engine fits the glyph's alignment zone to the
+    /// pixel grid, and widens or narrows its strokes according to the
+    /// thickness and any anti-aliasing table registered for the font.
+    pub fn render_glyph(&self, context: &mut RenderContext<'_, '_>, font: Font<'_>, glyph: &Glyph) {
+        let shape_handle = glyph.shape_handle(context.renderer);
+        let mut transform = context.transform_stack.transform().clone();
+        if !self.is_advanced() {
+            context.renderer.render_shape(shape_handle, &transform);
+            return;
+        }
+
+        if let Some(zone) = glyph.align_zone() {
+            transform.matrix = self.fit_to_grid(transform.matrix, zone, font.scale());
+        }
+
+        let [r, g, b, _] = transform.color_transform.mult_rgba_normalized();
+        let is_dark = 0.299 * r + 0.587 * g + 0.114 * b < 0.5;
+        let pixel_size = font.scale() * transform.matrix.d.abs() / Twips::TWIPS_PER_PIXEL as f32;
+        let table_offset = context
+            .library
+            .anti_aliasing_table(font.descriptor(), is_dark)
+            .and_then(|table| {
+                table
+                    .iter()
+                    .filter(|entry| entry.font_size <= pixel_size)
+                    .max_by(|a, b| {
+                        a.font_size
+                            .partial_cmp(&b.font_size)
+                            .unwrap_or(std::cmp::Ordering::Equal)
+                    })
+            })
+            .map(|entry| -(entry.inside_cutoff + entry.outside_cutoff) / 2.0)
+            .unwrap_or(0.0);
+        let stroke_offset = (self.thickness / 200.0 * MAX_STROKE_OFFSET + table_offset)
+            .clamp(-MAX_STROKE_OFFSET, MAX_STROKE_OFFSET);
+
+        if stroke_offset < 0.0 {
+            // Thin strokes cover less of each pixel; fade the glyph instead.
+            let alpha = f32::from(transform.color_transform.a_mult) * (1.0 + stroke_offset);
+            transform.color_transform.a_mult = Fixed8::from_f32(alpha);
+        }
+        context.renderer.render_shape(shape_handle, &transform);
+        if stroke_offset > 0.0 {
+            // Embolden the glyph by drawing it again slightly to the right.
+            transform.matrix.tx += Twips::from_pixels(stroke_offset.into());
+            context.renderer.render_shape(shape_handle, &transform);
+        }
     }
+
+    /// Fit the alignment zone of a glyph to the pixel grid.
+    ///
+    /// `matrix` transforms the glyph into device space, and `em_size` is the
+    /// size of the EM square in glyph coordinates.
+    fn fit_to_grid(&self, mut matrix: Matrix, zone: AlignZone, em_size: f32) -> Matrix {
+        let (x_grid, y_grid) = match self.grid_fit {
+            TextGridFit::None => return matrix,
+            TextGridFit::Pixel => (1.0, 1.0),
+            // Subpixel rendering on LCD screens triples the horizontal resolution.
+            TextGridFit::SubPixel => (1.0 / 3.0, 1.0),
+        };
+
+        // Rotated and skewed glyphs can't be aligned to the grid.
+        if matrix.b != 0.0 || matrix.c != 0.0 {
+            return matrix;
+        }
+
+        // Fit in pixels, rather than twips.
+        let twips_per_pixel = Twips::TWIPS_PER_PIXEL as f32;
+        if let Some((scale, translate)) = fit_axis(
+            matrix.a / twips_per_pixel,
+            matrix.tx.to_pixels() as f32,
+            zone.left * em_size,
+            zone.width * em_size,
+            x_grid,
+        ) {
+            matrix.a = scale * twips_per_pixel;
+            matrix.tx = Twips::from_pixels(translate.into());
+        }
+
+        // Glyph coordinates point down from the baseline.
+        if let Some((scale, translate)) = fit_axis(
+            matrix.d / twips_per_pixel,
+            matrix.ty.to_pixels() as f32,
+            -(zone.bottom + zone.height) * em_size,
+            zone.height * em_size,
+            y_grid,
+        ) {
+            matrix.d = scale * twips_per_pixel;
+            matrix.ty = Twips::from_pixels(translate.into());
+        }
+
+        matrix
+    }
+}
+
+/// Snap both edges of the glyph range `start..start + size` to multiples of
+/// `grid` pixels, once mapped to device space by `scale` and `translate`.
+///
+/// Returns the scale and translation that map the range onto the snapped
+/// edges, or `None` if the range can't be fitted.
+fn fit_axis(scale: f32, translate: f32, start: f32, size: f32, grid: f32) -> Option<(f32, f32)> {
+    if !(size.is_finite() && start.is_finite() && size > 0.0 && scale > 0.0) {
+        return None;
+    }
+
+    let snap = |x: f32| (x / grid).round() * grid;
+    let min = snap(scale * start + translate);
+    let max = snap(scale * (start + size) + translate).max(min + grid);
+    let scale = (max - min) / size;
+    Some((scale, min - scale * start))
 }
 
 impl Default for TextRenderSettings {
     fn default() -> Self {
-        TextRenderSettings::Default
+        Self {
+            anti_alias_type: AntiAliasType::Normal,
+            grid_fit: TextGridFit::Pixel,
+            thickness: 0.0,
+            sharpness: 0.0,
+        }
     }
 }
 
 impl From<swf::CsmTextSettings> for TextRenderSettings {
     fn from(settings: swf::CsmTextSettings) -> Self {
-        if settings.use_advanced_rendering {
-            TextRenderSettings::Advanced {
-                grid_fit: settings.grid_fit,
-                thickness: settings.thickness,
-                sharpness: settings.sharpness,
-            }
-        } else {
-            TextRenderSettings::Default
+        Self {
+            anti_alias_type: if settings.use_advanced_rendering {
+                AntiAliasType::Advanced
+            } else {
+                AntiAliasType::Normal
+            },
+            grid_fit: settings.grid_fit,
+            thickness: settings.thickness,
+            sharpness: settings.sharpness,
         }
     }
 }
 
+/// An entry of an anti-aliasing table, registered for a font with
+/// `TextRenderer.setAdvancedAntiAliasingTable`.
+///
+/// The cutoffs are the distances from a glyph's edge, in pixels, at which
+/// the anti-aliasing ramp starts and ends.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CsmSettings {
+    /// The smallest font size, in pixels, that this entry applies to.
+    pub font_size: f32,
+    pub inside_cutoff: f32,
+    pub outside_cutoff: f32,
+}
+
 #[cfg(test)]
 mod tests {
     use crate::backend::render::{NullRenderer, RenderBackend};
-    use crate::font::{EvalParameters, Font};
+    use crate::font::{f16_to_f32, EvalParameters, Font};
     use crate::player::{Player, DEVICE_FONT_TAG};
     use crate::string::WStr;
     use gc_arena::{rootless_arena, MutationContext};
@@ -720,4 +1040,15 @@ mod tests {
             assert_eq!(None, breakpoint5);
         });
     }
+
+    #[test]
+    fn decode_half_floats() {
+        assert_eq!(f16_to_f32(0x0000), 0.0);
+        assert_eq!(f16_to_f32(0x3c00), 1.0);
+        assert_eq!(f16_to_f32(0xc000_u16 as i16), -2.0);
+        assert_eq!(f16_to_f32(0x3555), 0.33325195);
+        assert_eq!(f16_to_f32(0x0001), 2f32.powi(-24));
+        assert_eq!(f16_to_f32(0x7c00), f32::INFINITY);
+        assert!(f16_to_f32(0x7e00).is_nan());
+    }
 }
@@ -0,0 +1,1504 @@
+//! OpenType font parsing, used by `DefineFont4` tags and device fonts.
+//!
+//! Only the parts of the format needed to turn glyph outlines into SWF glyph
+//! shapes are supported: the `cmap`, `head`, `hhea`, `hmtx` and `name`
+//! tables, Type 2 charstrings in the `CFF ` table, and TrueType outlines in
+//! the `glyf` table.
+
+use swf::{ShapeRecord, StyleChangeData, Twips};
+
+type Error = Box<dyn std::error::Error>;
+
+/// The size of the EM square that glyph shapes are scaled to, matching the
+/// coordinates used by `DefineFont3`.
+const EM_SQUARE: f64 = 20480.0;
+
+/// The maximum nesting depth of charstring subroutine calls.
+const MAX_SUBR_DEPTH: usize = 10;
+
+/// The glyph outlines and metrics of an OpenType font.
+pub struct OpenTypeFont {
+    /// The glyphs of the font, keyed by Unicode code point.
+    pub glyphs: Vec<swf::Glyph>,
+
+    /// The distance from the baseline to the top of the font.
+    pub ascent: u16,
+
+    /// The distance from the baseline to the bottom of the font.
+    pub descent: u16,
+
+    /// The additional spacing between lines.
+    pub leading: i16,
+}
+
+/// A bounds-checked big-endian reader over a font table.
+#[derive(Clone, Copy)]
+struct Stream<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Stream<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    fn slice(&self, offset: usize, len: usize) -> Result<&'a [u8], Error> {
+        offset
+            .checked_add(len)
+            .and_then(|end| self.data.get(offset..end))
+            .ok_or_else(|| "Unexpected end of font data".into())
+    }
+
+    fn u8(&self, offset: usize) -> Result<u8, Error> {
+        Ok(self.slice(offset, 1)?[0])
+    }
+
+    fn u16(&self, offset: usize) -> Result<u16, Error> {
+        let bytes = self.slice(offset, 2)?;
+        Ok(u16::from_be_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn i16(&self, offset: usize) -> Result<i16, Error> {
+        Ok(self.u16(offset)? as i16)
+    }
+
+    fn u32(&self, offset: usize) -> Result<u32, Error> {
+        let bytes = self.slice(offset, 4)?;
+        Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    /// Read an unsigned integer of 1 to 4 bytes.
+    fn offset(&self, offset: usize, size: u8) -> Result<usize, Error> {
+        let mut value = 0;
+        for byte in self.slice(offset, size.into())? {
+            value = (value << 8) | usize::from(*byte);
+        }
+        Ok(value)
+    }
+}
+
+/// The table directory of an OpenType font file.
+struct Tables<'a> {
+    tables: fnv::FnvHashMap<&'a [u8], &'a [u8]>,
+}
+
+impl<'a> Tables<'a> {
+    /// Read the table directory of a font file.
+    ///
+    /// Only the first font of a TrueType collection is used.
+    fn parse(data: &'a [u8]) -> Result<Self, Error> {
+        let font = Stream::new(data);
+        let directory = if font.slice(0, 4)? == b"ttcf" {
+            font.u32(12)? as usize
+        } else {
+            0
+        };
+
+        let num_tables = font.u16(directory + 4)?;
+        let mut tables = fnv::FnvHashMap::default();
+        for i in 0..usize::from(num_tables) {
+            let record = directory + 12 + i * 16;
+            let tag = font.slice(record, 4)?;
+            let offset = font.u32(record + 8)? as usize;
+            let length = font.u32(record + 12)? as usize;
+            tables.insert(tag, font.slice(offset, length)?);
+        }
+        Ok(Self { tables })
+    }
+
+    fn find(&self, tag: &[u8]) -> Option<Stream<'a>> {
+        self.tables.get(tag).map(|&table| Stream::new(table))
+    }
+
+    fn get(&self, tag: &[u8]) -> Result<Stream<'a>, Error> {
+        self.find(tag).ok_or_else(|| -> Error {
+            format!("Missing font table {}", String::from_utf8_lossy(tag)).into()
+        })
+    }
+}
+
+/// The glyph outlines of a font, either as Type 2 charstrings or as
+/// TrueType contours.
+enum Outlines<'a> {
+    Cff(Cff<'a>),
+    TrueType(Glyf<'a>),
+}
+
+/// Parse the outlines of an OpenType font with CFF or TrueType outlines.
+pub fn parse(data: &[u8]) -> Result<OpenTypeFont, Error> {
+    let tables = Tables::parse(data)?;
+
+    let head = tables.get(b"head")?;
+    let units_per_em = head.u16(18)?;
+    if units_per_em == 0 {
+        return Err("Invalid units per EM".into());
+    }
+    let scale = EM_SQUARE / f64::from(units_per_em);
+
+    let hhea = tables.get(b"hhea")?;
+    let ascender = f64::from(hhea.i16(4)?);
+    let descender = f64::from(hhea.i16(6)?);
+    let line_gap = f64::from(hhea.i16(8)?);
+    let num_h_metrics = hhea.u16(34)?;
+
+    let hmtx = tables.get(b"hmtx")?;
+    let advance = |glyph_id: u16| -> Result<f64, Error> {
+        let index = glyph_id.min(num_h_metrics.saturating_sub(1));
+        Ok(f64::from(hmtx.u16(usize::from(index) * 4)?))
+    };
+
+    let outlines = match tables.find(b"CFF ") {
+        Some(cff) => Outlines::Cff(Cff::parse(cff)?),
+        None => Outlines::TrueType(Glyf {
+            glyf: tables.get(b"glyf")?,
+            loca: tables.get(b"loca")?,
+            long_offsets: head.i16(50)? != 0,
+        }),
+    };
+    let code_points = parse_cmap(tables.get(b"cmap")?)?;
+
+    let mut glyphs = Vec::with_capacity(code_points.len());
+    for (code, glyph_id) in code_points {
+        let mut outline = Outline::new(scale);
+        match &outlines {
+            Outlines::Cff(cff) => cff.glyph_outline(glyph_id, &mut outline)?,
+            Outlines::TrueType(glyf) => glyf.glyph_outline(glyph_id, &mut outline, IDENTITY, 0)?,
+        }
+        glyphs.push(swf::Glyph {
+            shape_records: outline.finish(),
+            code,
+            advance: Some(
+                (advance(glyph_id)? * scale)
+                    .round()
+                    .clamp(0.0, i16::MAX.into()) as i16,
+            ),
+            bounds: None,
+        });
+    }
+
+    Ok(OpenTypeFont {
+        glyphs,
+        ascent: (ascender * scale).round().clamp(0.0, u16::MAX.into()) as u16,
+        descent: (-descender * scale).round().clamp(0.0, u16::MAX.into()) as u16,
+        leading: (line_gap * scale)
+            .round()
+            .clamp(i16::MIN.into(), i16::MAX.into()) as i16,
+    })
+}
+
+/// The family name and style of a font file.
+pub struct FaceInfo {
+    pub name: String,
+    pub is_bold: bool,
+    pub is_italic: bool,
+}
+
+/// Read the family name and style of a font, without parsing its outlines.
+pub fn face_info(data: &[u8]) -> Result<FaceInfo, Error> {
+    let tables = Tables::parse(data)?;
+    let mac_style = tables.get(b"head")?.u16(44)?;
+    Ok(FaceInfo {
+        name: parse_family_name(tables.get(b"name")?)?,
+        is_bold: mac_style & 1 != 0,
+        is_italic: mac_style & 2 != 0,
+    })
+}
+
+/// Find the font family name in the `name` table, preferring the English
+/// Windows name.
+fn parse_family_name(name: Stream<'_>) -> Result<String, Error> {
+    let count = name.u16(2)?;
+    let storage = usize::from(name.u16(4)?);
+    let mut best = None;
+    for i in 0..usize::from(count) {
+        let record = 6 + i * 12;
+        if name.u16(record + 6)? != 1 {
+            continue;
+        }
+        let platform_id = name.u16(record)?;
+        let encoding_id = name.u16(record + 2)?;
+        let language_id = name.u16(record + 4)?;
+        let priority = match (platform_id, encoding_id, language_id) {
+            (3, 0 | 1 | 10, 0x409) => 3,
+            (3, 0 | 1 | 10, _) => 2,
+            (0, _, _) => 2,
+            (1, 0, 0) => 1,
+            _ => continue,
+        };
+        if best.map_or(true, |(best_priority, _, _)| priority > best_priority) {
+            best = Some((priority, platform_id, record));
+        }
+    }
+    let (_, platform_id, record) = best.ok_or("No font family name")?;
+
+    let length = usize::from(name.u16(record + 8)?);
+    let offset = storage + usize::from(name.u16(record + 10)?);
+    let bytes = name.slice(offset, length)?;
+    let family = if platform_id == 1 {
+        // Mac Roman; non-ASCII names are rare enough to treat as Latin-1.
+        bytes.iter().map(|&b| char::from(b)).collect()
+    } else {
+        let units: Vec<u16> = bytes
+            .chunks_exact(2)
+            .map(|unit| u16::from_be_bytes([unit[0], unit[1]]))
+            .collect();
+        String::from_utf16_lossy(&units)
+    };
+    Ok(family)
+}
+
+/// Parse the `cmap` table into a sorted list of BMP code points and their
+/// glyph IDs.
+fn parse_cmap(cmap: Stream<'_>) -> Result<Vec<(u16, u16)>, Error> {
+    let num_tables = cmap.u16(2)?;
+    let mut best = None;
+    for i in 0..usize::from(num_tables) {
+        let record = 4 + i * 8;
+        let platform_id = cmap.u16(record)?;
+        let encoding_id = cmap.u16(record + 2)?;
+        let offset = cmap.u32(record + 4)? as usize;
+        let priority = match (platform_id, encoding_id) {
+            (3, 10) | (0, 4) | (0, 6) => 2,
+            (3, 1) | (0, _) => 1,
+            _ => continue,
+        };
+        if best.map_or(true, |(best_priority, _)| priority > best_priority) {
+            best = Some((priority, offset));
+        }
+    }
+    let (_, offset) = best.ok_or("No Unicode cmap subtable")?;
+
+    let mut code_points = fnv::FnvHashMap::default();
+    match cmap.u16(offset)? {
+        4 => {
+            let seg_count = usize::from(cmap.u16(offset + 6)? / 2);
+            let end_codes = offset + 14;
+            let start_codes = end_codes + seg_count * 2 + 2;
+            let id_deltas = start_codes + seg_count * 2;
+            let id_range_offsets = id_deltas + seg_count * 2;
+            for i in 0..seg_count {
+                let end = cmap.u16(end_codes + i * 2)?;
+                let start = cmap.u16(start_codes + i * 2)?;
+                let delta = cmap.u16(id_deltas + i * 2)?;
+                let range_offset_pos = id_range_offsets + i * 2;
+                let range_offset = usize::from(cmap.u16(range_offset_pos)?);
+                if start > end || start == 0xFFFF {
+                    continue;
+                }
+                for code in start..=end {
+                    let glyph_id = if range_offset == 0 {
+                        code.wrapping_add(delta)
+                    } else {
+                        let address =
+                            range_offset_pos + range_offset + usize::from(code - start) * 2;
+                        match cmap.u16(address)? {
+                            0 => 0,
+                            glyph_id => glyph_id.wrapping_add(delta),
+                        }
+                    };
+                    if glyph_id != 0 {
+                        code_points.insert(code, glyph_id);
+                    }
+                }
+            }
+        }
+        12 => {
+            let num_groups = cmap.u32(offset + 12)? as usize;
+            for i in 0..num_groups {
+                let group = offset + 16 + i * 12;
+                let start = cmap.u32(group)?;
+                let end = cmap.u32(group + 4)?.min(0xFFFF);
+                let start_glyph_id = cmap.u32(group + 8)?;
+                for code in start..=end {
+                    let glyph_id = start_glyph_id
+                        .checked_add(code - start)
+                        .and_then(|glyph_id| u16::try_from(glyph_id).ok());
+                    if let Some(glyph_id) = glyph_id {
+                        code_points.insert(code as u16, glyph_id);
+                    }
+                }
+            }
+        }
+        format => return Err(format!("Unsupported cmap format {}", format).into()),
+    }
+
+    let mut code_points: Vec<_> = code_points.into_iter().collect();
+    code_points.sort_unstable();
+    Ok(code_points)
+}
+
+/// A CFF INDEX structure: a list of variable-length objects.
+#[derive(Clone, Copy)]
+struct Index<'a> {
+    data: Stream<'a>,
+    count: usize,
+    off_size: u8,
+    offsets_start: usize,
+    data_start: usize,
+}
+
+impl<'a> Index<'a> {
+    /// Parse the index at `offset`, returning it and the offset of the byte
+    /// following it.
+    fn parse(data: Stream<'a>, offset: usize) -> Result<(Self, usize), Error> {
+        let count = usize::from(data.u16(offset)?);
+        if count == 0 {
+            let index = Self {
+                data,
+                count,
+                off_size: 1,
+                offsets_start: offset + 2,
+                data_start: offset + 2,
+            };
+            return Ok((index, offset + 2));
+        }
+
+        let off_size = data.u8(offset + 2)?;
+        if !(1..=4).contains(&off_size) {
+            return Err("Invalid CFF INDEX offset size".into());
+        }
+        let offsets_start = offset + 3;
+        let data_start = offsets_start + (count + 1) * usize::from(off_size) - 1;
+        let index = Self {
+            data,
+            count,
+            off_size,
+            offsets_start,
+            data_start,
+        };
+        let end = data_start + index.offset(count)?;
+        Ok((index, end))
+    }
+
+    fn offset(&self, i: usize) -> Result<usize, Error> {
+        self.data.offset(
+            self.offsets_start + i * usize::from(self.off_size),
+            self.off_size,
+        )
+    }
+
+    fn get(&self, i: usize) -> Result<&'a [u8], Error> {
+        if i >= self.count {
+            return Err("CFF INDEX out of bounds".into());
+        }
+        let start = self.offset(i)?;
+        let end = self.offset(i + 1)?;
+        if end < start {
+            return Err("Invalid CFF INDEX offsets".into());
+        }
+        self.data.slice(self.data_start + start, end - start)
+    }
+
+    /// The bias added to subroutine numbers in charstrings.
+    fn subr_bias(&self) -> i32 {
+        match self.count {
+            0..=1239 => 107,
+            1240..=33899 => 1131,
+            _ => 32768,
+        }
+    }
+}
+
+/// Parse a CFF DICT into a list of operators and their operands.
+fn parse_dict(data: &[u8]) -> Result<Vec<(u16, Vec<f64>)>, Error> {
+    let mut entries = Vec::new();
+    let mut operands = Vec::new();
+    let mut i = 0;
+    while i < data.len() {
+        let b0 = data[i];
+        i += 1;
+        match b0 {
+            0..=11 | 13..=21 => entries.push((u16::from(b0), std::mem::take(&mut operands))),
+            12 => {
+                let b1 = *data.get(i).ok_or("Truncated CFF DICT")?;
+                i += 1;
+                entries.push((1200 + u16::from(b1), std::mem::take(&mut operands)));
+            }
+            28 => {
+                let bytes = data.get(i..i + 2).ok_or("Truncated CFF DICT")?;
+                operands.push(f64::from(i16::from_be_bytes([bytes[0], bytes[1]])));
+                i += 2;
+            }
+            29 => {
+                let bytes = data.get(i..i + 4).ok_or("Truncated CFF DICT")?;
+                operands.push(f64::from(i32::from_be_bytes([
+                    bytes[0], bytes[1], bytes[2], bytes[3],
+                ])));
+                i += 4;
+            }
+            30 => {
+                let mut number = String::new();
+                'real: while let Some(&byte) = data.get(i) {
+                    i += 1;
+                    for nibble in [byte >> 4, byte & 0xF] {
+                        match nibble {
+                            0..=9 => number.push(char::from(b'0' + nibble)),
+                            0xA => number.push('.'),
+                            0xB => number.push('E'),
+                            0xC => number.push_str("E-"),
+                            0xE => number.push('-'),
+                            0xF => break 'real,
+                            _ => {}
+                        }
+                    }
+                }
+                operands.push(number.parse().unwrap_or(0.0));
+            }
+            32..=246 => operands.push(f64::from(i32::from(b0) - 139)),
+            247..=250 => {
+                let b1 = *data.get(i).ok_or("Truncated CFF DICT")?;
+                i += 1;
+                operands.push(f64::from((i32::from(b0) - 247) * 256 + i32::from(b1) + 108));
+            }
+            251..=254 => {
+                let b1 = *data.get(i).ok_or("Truncated CFF DICT")?;
+                i += 1;
+                operands.push(f64::from(
+                    -(i32::from(b0) - 251) * 256 - i32::from(b1) - 108,
+                ));
+            }
+            _ => return Err("Invalid CFF DICT operand".into()),
+        }
+    }
+    Ok(entries)
+}
+
+fn dict_operands(dict: &[(u16, Vec<f64>)], operator: u16) -> Option<&[f64]> {
+    dict.iter()
+        .find(|(op, _)| *op == operator)
+        .map(|(_, operands)| &operands[..])
+}
+
+/// Parse the local subroutines referenced by a Private DICT.
+fn parse_private<'a>(
+    data: Stream<'a>,
+    operands: Option<&[f64]>,
+) -> Result<Option<Index<'a>>, Error> {
+    let (size, offset) = match operands {
+        Some(&[size, offset]) => (size as usize, offset as usize),
+        _ => return Ok(None),
+    };
+    let private = parse_dict(data.slice(offset, size)?)?;
+    match dict_operands(&private, 19) {
+        Some(&[subrs]) => Ok(Some(Index::parse(data, offset + subrs as usize)?.0)),
+        _ => Ok(None),
+    }
+}
+
+/// The `CFF ` table of an OpenType font.
+struct Cff<'a> {
+    char_strings: Index<'a>,
+    global_subrs: Index<'a>,
+
+    /// The local subroutines of each font DICT; non-CID fonts have a single
+    /// font DICT.
+    local_subrs: Vec<Option<Index<'a>>>,
+
+    /// Maps glyph IDs to font DICTs in CID-keyed fonts.
+    fd_select: Option<(Stream<'a>, usize)>,
+}
+
+impl<'a> Cff<'a> {
+    fn parse(data: Stream<'a>) -> Result<Self, Error> {
+        let header_size = usize::from(data.u8(2)?);
+        let (_names, offset) = Index::parse(data, header_size)?;
+        let (top_dicts, offset) = Index::parse(data, offset)?;
+        let (_strings, offset) = Index::parse(data, offset)?;
+        let (global_subrs, _) = Index::parse(data, offset)?;
+
+        let top_dict = parse_dict(top_dicts.get(0)?)?;
+        let char_strings = match dict_operands(&top_dict, 17) {
+            Some(&[offset]) => Index::parse(data, offset as usize)?.0,
+            _ => return Err("Missing CFF CharStrings".into()),
+        };
+
+        let (local_subrs, fd_select) = match (
+            dict_operands(&top_dict, 1236),
+            dict_operands(&top_dict, 1237),
+        ) {
+            (Some(&[fd_array]), Some(&[fd_select])) => {
+                let (fd_array, _) = Index::parse(data, fd_array as usize)?;
+                let mut local_subrs = Vec::with_capacity(fd_array.count);
+                for i in 0..fd_array.count {
+                    let font_dict = parse_dict(fd_array.get(i)?)?;
+                    local_subrs.push(parse_private(data, dict_operands(&font_dict, 18))?);
+                }
+                (local_subrs, Some((data, fd_select as usize)))
+            }
+            _ => (
+                vec![parse_private(data, dict_operands(&top_dict, 18))?],
+                None,
+            ),
+        };
+
+        Ok(Self {
+            char_strings,
+            global_subrs,
+            local_subrs,
+            fd_select,
+        })
+    }
+
+    /// Find the font DICT used by a glyph.
+    fn font_dict_index(&self, glyph_id: u16) -> Result<usize, Error> {
+        let (data, offset) = match self.fd_select {
+            Some(fd_select) => fd_select,
+            None => return Ok(0),
+        };
+        match data.u8(offset)? {
+            0 => Ok(data.u8(offset + 1 + usize::from(glyph_id))?.into()),
+            3 => {
+                let num_ranges = usize::from(data.u16(offset + 1)?);
+                for i in 0..num_ranges {
+                    let range = offset + 3 + i * 3;
+                    let first = data.u16(range)?;
+                    let next = data.u16(range + 3)?;
+                    if (first..next).contains(&glyph_id) {
+                        return Ok(data.u8(range + 2)?.into());
+                    }
+                }
+                Err("Glyph not covered by CFF FDSelect".into())
+            }
+            format => Err(format!("Unsupported CFF FDSelect format {}", format).into()),
+        }
+    }
+
+    fn glyph_outline(&self, glyph_id: u16, outline: &mut Outline) -> Result<(), Error> {
+        let char_string = self.char_strings.get(glyph_id.into())?;
+        let local_subrs = self
+            .local_subrs
+            .get(self.font_dict_index(glyph_id)?)
+            .copied()
+            .flatten();
+        let mut interpreter = CharStringInterpreter {
+            global_subrs: self.global_subrs,
+            local_subrs,
+            outline,
+            stack: Vec::with_capacity(48),
+            num_stems: 0,
+            has_width: false,
+            x: 0.0,
+            y: 0.0,
+        };
+        interpreter.run(char_string, 0)?;
+        Ok(())
+    }
+}
+
+/// An affine transform applied to the components of a composite glyph, as
+/// `[a, b, c, d, tx, ty]`.
+type Matrix = [f64; 6];
+
+const IDENTITY: Matrix = [1.0, 0.0, 0.0, 1.0, 0.0, 0.0];
+
+/// The maximum nesting depth of composite TrueType glyphs.
+const MAX_COMPONENT_DEPTH: usize = 8;
+
+fn apply(m: &Matrix, x: f64, y: f64) -> (f64, f64) {
+    (m[0] * x + m[2] * y + m[4], m[1] * x + m[3] * y + m[5])
+}
+
+/// Combine a parent transform with the transform of one of its components.
+fn concat(parent: &Matrix, child: &Matrix) -> Matrix {
+    let (tx, ty) = apply(parent, child[4], child[5]);
+    [
+        parent[0] * child[0] + parent[2] * child[1],
+        parent[1] * child[0] + parent[3] * child[1],
+        parent[0] * child[2] + parent[2] * child[3],
+        parent[1] * child[2] + parent[3] * child[3],
+        tx,
+        ty,
+    ]
+}
+
+/// Read an F2DOT14 fixed point number.
+fn f2dot14(data: Stream<'_>, offset: usize) -> Result<f64, Error> {
+    Ok(f64::from(data.i16(offset)?) / 16384.0)
+}
+
+/// The `glyf` and `loca` tables of a TrueType font.
+struct Glyf<'a> {
+    glyf: Stream<'a>,
+    loca: Stream<'a>,
+    long_offsets: bool,
+}
+
+impl<'a> Glyf<'a> {
+    /// Get the data of a glyph, or `None` if the glyph has no outline.
+    fn glyph_data(&self, glyph_id: u16) -> Result<Option<Stream<'a>>, Error> {
+        let i = usize::from(glyph_id);
+        let (start, end) = if self.long_offsets {
+            (
+                self.loca.u32(i * 4)? as usize,
+                self.loca.u32(i * 4 + 4)? as usize,
+            )
+        } else {
+            (
+                usize::from(self.loca.u16(i * 2)?) * 2,
+                usize::from(self.loca.u16(i * 2 + 2)?) * 2,
+            )
+        };
+        if end <= start {
+            return Ok(None);
+        }
+        Ok(Some(Stream::new(self.glyf.slice(start, end - start)?)))
+    }
+
+    fn glyph_outline(
+        &self,
+        glyph_id: u16,
+        outline: &mut Outline,
+        transform: Matrix,
+        depth: usize,
+    ) -> Result<(), Error> {
+        let data = match self.glyph_data(glyph_id)? {
+            Some(data) => data,
+            None => return Ok(()),
+        };
+        match data.i16(0)? {
+            num_contours if num_contours >= 0 => {
+                simple_outline(data, num_contours as usize, outline, &transform)
+            }
+            _ => self.composite_outline(data, outline, &transform, depth),
+        }
+    }
+
+    fn composite_outline(
+        &self,
+        data: Stream<'a>,
+        outline: &mut Outline,
+        transform: &Matrix,
+        depth: usize,
+    ) -> Result<(), Error> {
+        const ARG_1_AND_2_ARE_WORDS: u16 = 0x1;
+        const ARGS_ARE_XY_VALUES: u16 = 0x2;
+        const WE_HAVE_A_SCALE: u16 = 0x8;
+        const MORE_COMPONENTS: u16 = 0x20;
+        const WE_HAVE_AN_X_AND_Y_SCALE: u16 = 0x40;
+        const WE_HAVE_A_TWO_BY_TWO: u16 = 0x80;
+
+        if depth >= MAX_COMPONENT_DEPTH {
+            return Err("Composite glyphs nested too deeply".into());
+        }
+
+        let mut offset = 10;
+        loop {
+            let flags = data.u16(offset)?;
+            let glyph_id = data.u16(offset + 2)?;
+            offset += 4;
+
+            let (arg1, arg2) = if flags & ARG_1_AND_2_ARE_WORDS != 0 {
+                offset += 4;
+                (data.i16(offset - 4)?.into(), data.i16(offset - 2)?.into())
+            } else {
+                offset += 2;
+                (
+                    f64::from(data.u8(offset - 2)? as i8),
+                    f64::from(data.u8(offset - 1)? as i8),
+                )
+            };
+            // Components positioned by matching points are rare, and are
+            // placed at the origin instead.
+            let (tx, ty) = if flags & ARGS_ARE_XY_VALUES != 0 {
+                (arg1, arg2)
+            } else {
+                (0.0, 0.0)
+            };
+
+            let mut matrix = [1.0, 0.0, 0.0, 1.0, tx, ty];
+            if flags & WE_HAVE_A_SCALE != 0 {
+                let scale = f2dot14(data, offset)?;
+                matrix[0] = scale;
+                matrix[3] = scale;
+                offset += 2;
+            } else if flags & WE_HAVE_AN_X_AND_Y_SCALE != 0 {
+                matrix[0] = f2dot14(data, offset)?;
+                matrix[3] = f2dot14(data, offset + 2)?;
+                offset += 4;
+            } else if flags & WE_HAVE_A_TWO_BY_TWO != 0 {
+                matrix[0] = f2dot14(data, offset)?;
+                matrix[1] = f2dot14(data, offset + 2)?;
+                matrix[2] = f2dot14(data, offset + 4)?;
+                matrix[3] = f2dot14(data, offset + 6)?;
+                offset += 8;
+            }
+
+            self.glyph_outline(glyph_id, outline, concat(transform, &matrix), depth + 1)?;
+
+            if flags & MORE_COMPONENTS == 0 {
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// Draw the contours of a simple TrueType glyph.
+///
+/// Consecutive off-curve points have an implied on-curve point halfway
+/// between them.
+fn simple_outline(
+    data: Stream<'_>,
+    num_contours: usize,
+    outline: &mut Outline,
+    transform: &Matrix,
+) -> Result<(), Error> {
+    const ON_CURVE_POINT: u8 = 0x1;
+    const X_SHORT_VECTOR: u8 = 0x2;
+    const Y_SHORT_VECTOR: u8 = 0x4;
+    const REPEAT_FLAG: u8 = 0x8;
+    const X_IS_SAME_OR_POSITIVE: u8 = 0x10;
+    const Y_IS_SAME_OR_POSITIVE: u8 = 0x20;
+
+    if num_contours == 0 {
+        return Ok(());
+    }
+
+    let mut end_points = Vec::with_capacity(num_contours);
+    for i in 0..num_contours {
+        end_points.push(usize::from(data.u16(10 + i * 2)?));
+    }
+    let num_points = end_points[num_contours - 1] + 1;
+    let instructions_length = usize::from(data.u16(10 + num_contours * 2)?);
+    let mut offset = 12 + num_contours * 2 + instructions_length;
+
+    let mut flags = Vec::with_capacity(num_points);
+    while flags.len() < num_points {
+        let flag = data.u8(offset)?;
+        offset += 1;
+        flags.push(flag);
+        if flag & REPEAT_FLAG != 0 {
+            let count = data.u8(offset)?;
+            offset += 1;
+            for _ in 0..count {
+                flags.push(flag);
+            }
+        }
+    }
+    flags.truncate(num_points);
+
+    let mut read_coordinates = |short: u8, same_or_positive: u8| -> Result<Vec<f64>, Error> {
+        let mut value = 0i32;
+        let mut coordinates = Vec::with_capacity(num_points);
+        for &flag in &flags {
+            let delta = if flag & short != 0 {
+                let delta = i32::from(data.u8(offset)?);
+                offset += 1;
+                if flag & same_or_positive != 0 {
+                    delta
+                } else {
+                    -delta
+                }
+            } else if flag & same_or_positive == 0 {
+                offset += 2;
+                i32::from(data.i16(offset - 2)?)
+            } else {
+                0
+            };
+            value = value
+                .checked_add(delta)
+                .ok_or("Glyph coordinates out of range")?;
+            coordinates.push(f64::from(value));
+        }
+        Ok(coordinates)
+    };
+    let xs = read_coordinates(X_SHORT_VECTOR, X_IS_SAME_OR_POSITIVE)?;
+    let ys = read_coordinates(Y_SHORT_VECTOR, Y_IS_SAME_OR_POSITIVE)?;
+
+    let points: Vec<((f64, f64), bool)> = (0..num_points)
+        .map(|i| {
+            (
+                apply(transform, xs[i], ys[i]),
+                flags[i] & ON_CURVE_POINT != 0,
+            )
+        })
+        .collect();
+    let midpoint = |(ax, ay): (f64, f64), (bx, by): (f64, f64)| ((ax + bx) / 2.0, (ay + by) / 2.0);
+
+    let mut contour_start = 0;
+    for end in end_points {
+        let contour = match points.get(contour_start..=end) {
+            Some(contour) if !contour.is_empty() => contour,
+            _ => break,
+        };
+        contour_start = end + 1;
+
+        // Start the contour on an on-curve point, so that the remaining
+        // points can be drawn in order.
+        let (first, last) = (contour[0], contour[contour.len() - 1]);
+        let (start, rest) = if first.1 {
+            (first.0, &contour[1..])
+        } else if last.1 {
+            (last.0, &contour[..contour.len() - 1])
+        } else {
+            (midpoint(first.0, last.0), contour)
+        };
+
+        outline.move_to(start.0, start.1);
+        let mut control = None;
+        for &(point, on_curve) in rest {
+            match (control, on_curve) {
+                (Some((cx, cy)), true) => {
+                    outline.quad_to(cx, cy, point.0, point.1);
+                    control = None;
+                }
+                (None, true) => outline.line_to(point.0, point.1),
+                (Some(c), false) => {
+                    let (mx, my) = midpoint(c, point);
+                    outline.quad_to(c.0, c.1, mx, my);
+                    control = Some(point);
+                }
+                (None, false) => control = Some(point),
+            }
+        }
+        match control {
+            Some((cx, cy)) => outline.quad_to(cx, cy, start.0, start.1),
+            None => outline.line_to(start.0, start.1),
+        }
+    }
+
+    Ok(())
+}
+
+/// Evaluates Type 2 charstrings into an outline.
+struct CharStringInterpreter<'a, 'b> {
+    global_subrs: Index<'a>,
+    local_subrs: Option<Index<'a>>,
+    outline: &'b mut Outline,
+    stack: Vec<f64>,
+    num_stems: usize,
+    has_width: bool,
+    x: f64,
+    y: f64,
+}
+
+impl<'a, 'b> CharStringInterpreter<'a, 'b> {
+    /// Drop the optional advance width preceding the first stack-clearing
+    /// operator, if present.
+    fn take_width(&mut self, has_extra_arg: bool) {
+        if !self.has_width {
+            self.has_width = true;
+            if has_extra_arg && !self.stack.is_empty() {
+                self.stack.remove(0);
+            }
+        }
+    }
+
+    fn move_to(&mut self, dx: f64, dy: f64) {
+        self.x += dx;
+        self.y += dy;
+        self.outline.move_to(self.x, self.y);
+    }
+
+    fn line_to(&mut self, dx: f64, dy: f64) {
+        self.x += dx;
+        self.y += dy;
+        self.outline.line_to(self.x, self.y);
+    }
+
+    fn curve_to(&mut self, dxa: f64, dya: f64, dxb: f64, dyb: f64, dxc: f64, dyc: f64) {
+        let (xa, ya) = (self.x + dxa, self.y + dya);
+        let (xb, yb) = (xa + dxb, ya + dyb);
+        self.x = xb + dxc;
+        self.y = yb + dyc;
+        self.outline.curve_to(xa, ya, xb, yb, self.x, self.y);
+    }
+
+    /// Returns `true` once `endchar` has been reached.
+    fn run(&mut self, data: &[u8], depth: usize) -> Result<bool, Error> {
+        if depth > MAX_SUBR_DEPTH {
+            return Err("CFF subroutines nested too deeply".into());
+        }
+
+        let mut i = 0;
+        while i < data.len() {
+            let b0 = data[i];
+            i += 1;
+            match b0 {
+                // hstem, vstem, hstemhm, vstemhm
+                1 | 3 | 18 | 23 => {
+                    self.take_width(self.stack.len() % 2 == 1);
+                    self.num_stems += self.stack.len() / 2;
+                    self.stack.clear();
+                }
+                // hintmask, cntrmask
+                19 | 20 => {
+                    self.take_width(self.stack.len() % 2 == 1);
+                    self.num_stems += self.stack.len() / 2;
+                    self.stack.clear();
+                    i += (self.num_stems + 7) / 8;
+                }
+                // rmoveto
+                21 => {
+                    self.take_width(self.stack.len() > 2);
+                    let (dx, dy) = (self.arg(0)?, self.arg(1)?);
+                    self.move_to(dx, dy);
+                    self.stack.clear();
+                }
+                // hmoveto
+                22 => {
+                    self.take_width(self.stack.len() > 1);
+                    let dx = self.arg(0)?;
+                    self.move_to(dx, 0.0);
+                    self.stack.clear();
+                }
+                // vmoveto
+                4 => {
+                    self.take_width(self.stack.len() > 1);
+                    let dy = self.arg(0)?;
+                    self.move_to(0.0, dy);
+                    self.stack.clear();
+                }
+                // rlineto
+                5 => {
+                    for pair in std::mem::take(&mut self.stack).chunks_exact(2) {
+                        self.line_to(pair[0], pair[1]);
+                    }
+                }
+                // hlineto, vlineto
+                6 | 7 => {
+                    let mut horizontal = b0 == 6;
+                    for d in std::mem::take(&mut self.stack) {
+                        if horizontal {
+                            self.line_to(d, 0.0);
+                        } else {
+                            self.line_to(0.0, d);
+                        }
+                        horizontal = !horizontal;
+                    }
+                }
+                // rrcurveto
+                8 => {
+                    for c in std::mem::take(&mut self.stack).chunks_exact(6) {
+                        self.curve_to(c[0], c[1], c[2], c[3], c[4], c[5]);
+                    }
+                }
+                // callsubr
+                10 => {
+                    let subrs = self.local_subrs.ok_or("Missing CFF local subroutines")?;
+                    if self.call_subr(subrs, depth)? {
+                        return Ok(true);
+                    }
+                }
+                // return
+                11 => return Ok(false),
+                // escape
+                12 => {
+                    let b1 = *data.get(i).ok_or("Truncated charstring")?;
+                    i += 1;
+                    self.flex(b1)?;
+                }
+                // endchar
+                14 => {
+                    self.take_width(self.stack.len() == 1 || self.stack.len() == 5);
+                    self.stack.clear();
+                    return Ok(true);
+                }
+                // rcurveline
+                24 => {
+                    let args = std::mem::take(&mut self.stack);
+                    if args.len() < 8 {
+                        return Err("Invalid rcurveline".into());
+                    }
+                    let (curves, line) = args.split_at(args.len() - 2);
+                    for c in curves.chunks_exact(6) {
+                        self.curve_to(c[0], c[1], c[2], c[3], c[4], c[5]);
+                    }
+                    self.line_to(line[0], line[1]);
+                }
+                // rlinecurve
+                25 => {
+                    let args = std::mem::take(&mut self.stack);
+                    if args.len() < 8 {
+                        return Err("Invalid rlinecurve".into());
+                    }
+                    let (lines, c) = args.split_at(args.len() - 6);
+                    for pair in lines.chunks_exact(2) {
+                        self.line_to(pair[0], pair[1]);
+                    }
+                    self.curve_to(c[0], c[1], c[2], c[3], c[4], c[5]);
+                }
+                // vvcurveto
+                26 => {
+                    let args = std::mem::take(&mut self.stack);
+                    let mut args = &args[..];
+                    let mut dx1 = 0.0;
+                    if args.len() % 4 == 1 {
+                        dx1 = args[0];
+                        args = &args[1..];
+                    }
+                    for c in args.chunks_exact(4) {
+                        self.curve_to(dx1, c[0], c[1], c[2], 0.0, c[3]);
+                        dx1 = 0.0;
+                    }
+                }
+                // hhcurveto
+                27 => {
+                    let args = std::mem::take(&mut self.stack);
+                    let mut args = &args[..];
+                    let mut dy1 = 0.0;
+                    if args.len() % 4 == 1 {
+                        dy1 = args[0];
+                        args = &args[1..];
+                    }
+                    for c in args.chunks_exact(4) {
+                        self.curve_to(c[0], dy1, c[1], c[2], c[3], 0.0);
+                        dy1 = 0.0;
+                    }
+                }
+                // shortint
+                28 => {
+                    let bytes = data.get(i..i + 2).ok_or("Truncated charstring")?;
+                    self.stack
+                        .push(f64::from(i16::from_be_bytes([bytes[0], bytes[1]])));
+                    i += 2;
+                }
+                // callgsubr
+                29 => {
+                    if self.call_subr(self.global_subrs, depth)? {
+                        return Ok(true);
+                    }
+                }
+                // vhcurveto, hvcurveto
+                30 | 31 => {
+                    let args = std::mem::take(&mut self.stack);
+                    let mut horizontal = b0 == 31;
+                    let mut chunks = args.chunks(4).peekable();
+                    while let Some(c) = chunks.next() {
+                        if c.len() < 4 {
+                            break;
+                        }
+                        let last = if chunks.peek().map_or(false, |next| next.len() == 1) {
+                            chunks.next().map_or(0.0, |next| next[0])
+                        } else {
+                            0.0
+                        };
+                        if horizontal {
+                            self.curve_to(c[0], 0.0, c[1], c[2], last, c[3]);
+                        } else {
+                            self.curve_to(0.0, c[0], c[1], c[2], c[3], last);
+                        }
+                        horizontal = !horizontal;
+                    }
+                }
+                32..=246 => self.stack.push(f64::from(i32::from(b0) - 139)),
+                247..=250 => {
+                    let b1 = *data.get(i).ok_or("Truncated charstring")?;
+                    i += 1;
+                    self.stack
+                        .push(f64::from((i32::from(b0) - 247) * 256 + i32::from(b1) + 108));
+                }
+                251..=254 => {
+                    let b1 = *data.get(i).ok_or("Truncated charstring")?;
+                    i += 1;
+                    self.stack.push(f64::from(
+                        -(i32::from(b0) - 251) * 256 - i32::from(b1) - 108,
+                    ));
+                }
+                255 => {
+                    let bytes = data.get(i..i + 4).ok_or("Truncated charstring")?;
+                    let fixed = i32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+                    self.stack.push(f64::from(fixed) / 65536.0);
+                    i += 4;
+                }
+                _ => return Err(format!("Unsupported charstring operator {}", b0).into()),
+            }
+        }
+
+        Ok(false)
+    }
+
+    fn arg(&self, i: usize) -> Result<f64, Error> {
+        self.stack
+            .get(i)
+            .copied()
+            .ok_or_else(|| "Charstring stack underflow".into())
+    }
+
+    fn call_subr(&mut self, subrs: Index<'a>, depth: usize) -> Result<bool, Error> {
+        let number = self.stack.pop().ok_or("Charstring stack underflow")? as i32;
+        let index = usize::try_from(number + subrs.subr_bias())
+            .map_err(|_| "Invalid CFF subroutine number")?;
+        let subr = subrs.get(index)?;
+        self.run(subr, depth + 1)
+    }
+
+    /// Evaluate the flex operators, which are drawn as two plain curves.
+    fn flex(&mut self, operator: u8) -> Result<(), Error> {
+        let a = std::mem::take(&mut self.stack);
+        let arg = |i: usize| -> Result<f64, Error> {
+            a.get(i).copied().ok_or_else(|| "Invalid flex".into())
+        };
+        match operator {
+            // flex
+            35 => {
+                self.curve_to(arg(0)?, arg(1)?, arg(2)?, arg(3)?, arg(4)?, arg(5)?);
+                self.curve_to(arg(6)?, arg(7)?, arg(8)?, arg(9)?, arg(10)?, arg(11)?);
+            }
+            // hflex
+            34 => {
+                let y = self.y;
+                self.curve_to(arg(0)?, 0.0, arg(1)?, arg(2)?, arg(3)?, 0.0);
+                let dy = y - (self.y);
+                self.curve_to(arg(4)?, 0.0, arg(5)?, dy, arg(6)?, 0.0);
+            }
+            // hflex1
+            36 => {
+                let y = self.y;
+                self.curve_to(arg(0)?, arg(1)?, arg(2)?, arg(3)?, arg(4)?, 0.0);
+                let dy = y - (self.y + arg(7)?);
+                self.curve_to(arg(5)?, 0.0, arg(6)?, arg(7)?, arg(8)?, dy);
+            }
+            // flex1
+            37 => {
+                let (x, y) = (self.x, self.y);
+                let dx: f64 = [0, 2, 4, 6, 8]
+                    .iter()
+                    .map(|&i| a.get(i).unwrap_or(&0.0))
+                    .sum();
+                let dy: f64 = [1, 3, 5, 7, 9]
+                    .iter()
+                    .map(|&i| a.get(i).unwrap_or(&0.0))
+                    .sum();
+                self.curve_to(arg(0)?, arg(1)?, arg(2)?, arg(3)?, arg(4)?, arg(5)?);
+                let (x6, y6) = if dx.abs() > dy.abs() {
+                    (arg(10)?, y - (self.y + arg(7)? + arg(9)?))
+                } else {
+                    (x - (self.x + arg(6)? + arg(8)?), arg(10)?)
+                };
+                self.curve_to(arg(6)?, arg(7)?, arg(8)?, arg(9)?, x6, y6);
+            }
+            // Arithmetic and storage operators are deprecated and not
+            // produced by modern font tools.
+            _ => return Err(format!("Unsupported charstring operator 12 {}", operator).into()),
+        }
+        Ok(())
+    }
+}
+
+/// Converts glyph outlines from font units into SWF shape records.
+///
+/// SWF shapes only support quadratic curves, so cubic curves are split into
+/// several quadratic approximations.
+struct Outline {
+    scale: f64,
+    records: Vec<ShapeRecord>,
+    start: (i32, i32),
+    current: (i32, i32),
+    has_contour: bool,
+    has_fill: bool,
+    last: (f64, f64),
+}
+
+impl Outline {
+    fn new(scale: f64) -> Self {
+        Self {
+            scale,
+            records: Vec::new(),
+            start: (0, 0),
+            current: (0, 0),
+            has_contour: false,
+            has_fill: false,
+            last: (0.0, 0.0),
+        }
+    }
+
+    /// Convert a point in font units into twips, flipping the Y axis.
+    fn to_twips(&self, x: f64, y: f64) -> (i32, i32) {
+        (
+            (x * self.scale).round() as i32,
+            (-y * self.scale).round() as i32,
+        )
+    }
+
+    fn close(&mut self) {
+        if self.has_contour && self.current != self.start {
+            let (x, y) = self.start;
+            self.edge_to(x, y);
+        }
+        self.has_contour = false;
+    }
+
+    fn move_to(&mut self, x: f64, y: f64) {
+        self.close();
+        let (tx, ty) = self.to_twips(x, y);
+        self.records
+            .push(ShapeRecord::StyleChange(Box::new(StyleChangeData {
+                move_to: Some((Twips::new(tx), Twips::new(ty))),
+                fill_style_0: if self.has_fill { None } else { Some(1) },
+                fill_style_1: None,
+                line_style: None,
+                new_styles: None,
+            })));
+        self.has_fill = true;
+        self.start = (tx, ty);
+        self.current = (tx, ty);
+        self.last = (x, y);
+        self.has_contour = true;
+    }
+
+    fn edge_to(&mut self, x: i32, y: i32) {
+        if (x, y) != self.current {
+            self.records.push(ShapeRecord::StraightEdge {
+                delta_x: Twips::new(x - self.current.0),
+                delta_y: Twips::new(y - self.current.1),
+            });
+            self.current = (x, y);
+        }
+    }
+
+    fn line_to(&mut self, x: f64, y: f64) {
+        let (tx, ty) = self.to_twips(x, y);
+        self.edge_to(tx, ty);
+        self.last = (x, y);
+    }
+
+    fn quad_to(&mut self, cx: f64, cy: f64, x: f64, y: f64) {
+        let (tcx, tcy) = self.to_twips(cx, cy);
+        let (tx, ty) = self.to_twips(x, y);
+        if (tcx, tcy) == self.current || (tcx, tcy) == (tx, ty) {
+            self.edge_to(tx, ty);
+            return;
+        }
+        self.records.push(ShapeRecord::CurvedEdge {
+            control_delta_x: Twips::new(tcx - self.current.0),
+            control_delta_y: Twips::new(tcy - self.current.1),
+            anchor_delta_x: Twips::new(tx - tcx),
+            anchor_delta_y: Twips::new(ty - tcy),
+        });
+        self.current = (tx, ty);
+    }
+
+    fn curve_to(&mut self, x1: f64, y1: f64, x2: f64, y2: f64, x: f64, y: f64) {
+        let (x0, y0) = self.last;
+
+        // Estimate the error of a single quadratic approximation, and split
+        // the curve until each piece is within about a twip.
+        let ex = x - 3.0 * x2 + 3.0 * x1 - x0;
+        let ey = y - 3.0 * y2 + 3.0 * y1 - y0;
+        let error = (ex * ex + ey * ey).sqrt() * self.scale * 3f64.sqrt() / 36.0;
+        let pieces = error.cbrt().ceil().clamp(1.0, 16.0) as usize;
+
+        let point = |t: f64| {
+            let mt = 1.0 - t;
+            let a = mt * mt * mt;
+            let b = 3.0 * mt * mt * t;
+            let c = 3.0 * mt * t * t;
+            let d = t * t * t;
+            (
+                a * x0 + b * x1 + c * x2 + d * x,
+                a * y0 + b * y1 + c * y2 + d * y,
+            )
+        };
+        let derivative = |t: f64| {
+            let mt = 1.0 - t;
+            (
+                3.0 * (mt * mt * (x1 - x0) + 2.0 * mt * t * (x2 - x1) + t * t * (x - x2)),
+                3.0 * (mt * mt * (y1 - y0) + 2.0 * mt * t * (y2 - y1) + t * t * (y - y2)),
+            )
+        };
+
+        for i in 0..pieces {
+            let t0 = i as f64 / pieces as f64;
+            let t1 = (i + 1) as f64 / pieces as f64;
+            let dt = t1 - t0;
+            let (px0, py0) = point(t0);
+            let (px3, py3) = point(t1);
+            let (dx0, dy0) = derivative(t0);
+            let (dx1, dy1) = derivative(t1);
+
+            // The control points of this piece as a cubic, averaged into a
+            // single quadratic control point.
+            let (cx1, cy1) = (px0 + dx0 * dt / 3.0, py0 + dy0 * dt / 3.0);
+            let (cx2, cy2) = (px3 - dx1 * dt / 3.0, py3 - dy1 * dt / 3.0);
+            let cx = (3.0 * (cx1 + cx2) - px0 - px3) / 4.0;
+            let cy = (3.0 * (cy1 + cy2) - py0 - py3) / 4.0;
+            self.quad_to(cx, cy, px3, py3);
+        }
+        self.last = (x, y);
+    }
+
+    fn finish(mut self) -> Vec<ShapeRecord> {
+        self.close();
+        self.records
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse;
+    use swf::{ShapeRecord, Twips};
+
+    /// Build a font file from its tables.
+    fn sfnt(version: &[u8; 4], tables: &[(&[u8; 4], Vec<u8>)]) -> Vec<u8> {
+        let mut font = version.to_vec();
+        font.extend((tables.len() as u16).to_be_bytes());
+        font.extend([0; 6]);
+        let mut offset = 12 + tables.len() * 16;
+        for (tag, data) in tables {
+            font.extend(*tag);
+            font.extend([0; 4]);
+            font.extend((offset as u32).to_be_bytes());
+            font.extend((data.len() as u32).to_be_bytes());
+            offset += data.len();
+        }
+        for (_, data) in tables {
+            font.extend(data);
+        }
+        font
+    }
+
+    fn be16(values: &[i32]) -> Vec<u8> {
+        values
+            .iter()
+            .flat_map(|&value| (value as u16).to_be_bytes())
+            .collect()
+    }
+
+    /// The tables shared by every test font: 1000 units per EM, and two
+    /// glyphs, of which glyph 1 is mapped to 'A'.
+    fn common_tables(long_offsets: bool) -> Vec<(&'static [u8; 4], Vec<u8>)> {
+        let mut head = vec![0; 54];
+        head[18..20].copy_from_slice(&1000u16.to_be_bytes());
+        head[50..52].copy_from_slice(&u16::from(long_offsets).to_be_bytes());
+
+        let mut hhea = vec![0; 36];
+        hhea[4..10].copy_from_slice(&be16(&[800, -200, 100]));
+        hhea[34..36].copy_from_slice(&2u16.to_be_bytes());
+
+        let hmtx = be16(&[500, 0, 600, 0]);
+
+        // A format 4 subtable with a segment for 'A', and the final segment.
+        let mut cmap = be16(&[0, 1, 3, 1, 0, 12]);
+        cmap.extend(be16(&[4, 32, 0, 4, 4, 1, 0]));
+        cmap.extend(be16(&[0x41, 0xFFFF, 0]));
+        cmap.extend(be16(&[0x41, 0xFFFF]));
+        cmap.extend(be16(&[1 - 0x41, 1]));
+        cmap.extend(be16(&[0, 0]));
+
+        vec![
+            (b"head", head),
+            (b"hhea", hhea),
+            (b"hmtx", hmtx),
+            (b"cmap", cmap),
+        ]
+    }
+
+    fn truetype_font() -> Vec<u8> {
+        // A 100 unit square, drawn with on-curve points.
+        let mut square = be16(&[1, 0, 0, 100, 100, 3, 0]);
+        square.extend([0x01; 4]);
+        square.extend(be16(&[0, 0, 100, 0]));
+        square.extend(be16(&[0, 100, 0, -100]));
+
+        let mut tables = common_tables(false);
+        tables.push((b"glyf", square.clone()));
+        tables.push((b"loca", be16(&[0, 0, square.len() as i32 / 2])));
+        sfnt(&[0, 1, 0, 0], &tables)
+    }
+
+    fn cff_font() -> Vec<u8> {
+        let mut cff = vec![1, 0, 4, 1];
+        // Name INDEX
+        cff.extend([0, 1, 1, 1, 2, b'A']);
+        // Top DICT INDEX, with the offset of the CharStrings INDEX.
+        cff.extend([0, 1, 1, 1, 5, 28, 0, 23, 17]);
+        // String and global subroutine INDEXes
+        cff.extend([0, 0, 0, 0]);
+        assert_eq!(cff.len(), 23);
+        // CharStrings INDEX. Glyph 1 is `0 0 rmoveto 100 0 rlineto 0 100
+        // rlineto endchar`.
+        cff.extend([0, 2, 1, 1, 2, 12, 14]);
+        cff.extend([139, 139, 21, 239, 139, 5, 139, 239, 5, 14]);
+
+        let mut tables = common_tables(false);
+        tables.push((b"CFF ", cff));
+        sfnt(b"OTTO", &tables)
+    }
+
+    /// The edges of a glyph shape, in twips.
+    fn edges(records: &[ShapeRecord]) -> Vec<(i32, i32)> {
+        records
+            .iter()
+            .filter_map(|record| match record {
+                ShapeRecord::StraightEdge { delta_x, delta_y } => {
+                    Some((delta_x.get(), delta_y.get()))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn truetype_glyph() {
+        let font = parse(&truetype_font()).unwrap();
+        assert_eq!(font.ascent, 16384);
+        assert_eq!(font.descent, 4096);
+        assert_eq!(font.leading, 2048);
+        assert_eq!(font.glyphs.len(), 1);
+
+        let glyph = &font.glyphs[0];
+        assert_eq!(glyph.code, u16::from(b'A'));
+        assert_eq!(glyph.advance, Some(12288));
+        match &glyph.shape_records[0] {
+            ShapeRecord::StyleChange(style) => {
+                assert_eq!(style.move_to, Some((Twips::new(0), Twips::new(0))));
+                assert_eq!(style.fill_style_0, Some(1));
+            }
+            record => panic!("Expected a move, got {:?}", record),
+        }
+        assert_eq!(
+            edges(&glyph.shape_records),
+            [(0, -2048), (2048, 0), (0, 2048), (-2048, 0)]
+        );
+    }
+
+    #[test]
+    fn cff_charstring() {
+        let font = parse(&cff_font()).unwrap();
+        assert_eq!(font.glyphs.len(), 1);
+
+        let glyph = &font.glyphs[0];
+        assert_eq!(glyph.code, u16::from(b'A'));
+        assert_eq!(
+            edges(&glyph.shape_records),
+            [(2048, 0), (0, -2048), (-2048, 2048)]
+        );
+    }
+
+    #[test]
+    fn truncated_font() {
+        for font in [truetype_font(), cff_font()] {
+            for len in 0..font.len() {
+                assert!(parse(&font[..len]).is_err(), "Truncated to {}", len);
+            }
+        }
+    }
+
+    #[test]
+    fn glyph_past_end_of_table() {
+        let mut tables = common_tables(true);
+        tables.push((b"glyf", vec![0; 4]));
+        tables.push((
+            b"loca",
+            vec![0, 0, 0, 0, 0, 0, 0, 0, 0xFF, 0xFF, 0xFF, 0xFF],
+        ));
+        assert!(parse(&sfnt(&[0, 1, 0, 0], &tables)).is_err());
+    }
+
+    #[test]
+    fn cmap_glyph_ids_out_of_range() {
+        // A format 12 subtable mapping 'A' and 'B' past the largest glyph ID.
+        let mut cmap = be16(&[0, 1, 3, 10, 0, 12]);
+        cmap.extend(be16(&[12, 0, 0, 28, 0, 0, 0, 1]));
+        cmap.extend(be16(&[0, 0x41, 0, 0x42, -1, -1]));
+
+        let mut tables = common_tables(false);
+        tables[3].1 = cmap;
+        tables.push((b"glyf", vec![]));
+        tables.push((b"loca", be16(&[0, 0, 0])));
+        assert!(parse(&sfnt(&[0, 1, 0, 0], &tables))
+            .unwrap()
+            .glyphs
+            .is_empty());
+    }
+}
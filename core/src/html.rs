@@ -3,13 +3,15 @@
 mod dimensions;
 mod iterators;
 mod layout;
+mod style_sheet;
 mod text_format;
 
 pub use dimensions::BoxBounds;
 pub use dimensions::Position;
 pub use dimensions::Size;
 pub use layout::{LayoutBox, LayoutContent};
-pub use text_format::{FormatSpans, TextFormat, TextSpan};
+pub use style_sheet::{css_to_text_format, parse_css, StyleSheet, CSS_PROPERTIES};
+pub use text_format::{FormatSpans, HtmlImage, TextFormat, TextSpan};
 
 #[cfg(test)]
 mod test;
@@ -2,9 +2,9 @@
 
 use crate::context::UpdateContext;
 use crate::drawing::Drawing;
-use crate::font::{EvalParameters, Font};
+use crate::font::{EvalParameters, Font, FontType};
 use crate::html::dimensions::{BoxBounds, Position, Size};
-use crate::html::text_format::{FormatSpans, TextFormat, TextSpan};
+use crate::html::text_format::{FormatSpans, HtmlImage, TextFormat, TextSpan};
 use crate::shape_utils::DrawCommand;
 use crate::string::{utils as string_utils, WStr};
 use crate::tag_utils::SwfMovie;
@@ -229,14 +229,13 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
         let mut line_bounds = None;
         let mut box_count: i32 = 0;
         for linebox in self.boxes.get_mut(self.current_line..).unwrap() {
-            let (text, _tf, font, params, _color) =
-                linebox.as_renderable_text(self.text).expect("text");
-
-            //Flash ignores trailing spaces when aligning lines, so should we
-            if self.current_line_span.align != swf::TextAlign::Left {
-                linebox.bounds = linebox
-                    .bounds
-                    .with_size(font.measure(text.trim_end(), params, false).into());
+            if let Some((text, _tf, font, params, _color)) = linebox.as_renderable_text(self.text) {
+                //Flash ignores trailing spaces when aligning lines, so should we
+                if self.current_line_span.align != swf::TextAlign::Left {
+                    linebox.bounds = linebox
+                        .bounds
+                        .with_size(font.measure(text.trim_end(), params, false).into());
+                }
             }
 
             if let Some(line_bounds) = &mut line_bounds {
@@ -289,7 +288,7 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
             // which is information we don't have yet.
             let font_size_adjustment = self.max_font_size - linebox.bounds.height();
 
-            if linebox.is_text_box() {
+            if linebox.is_text_box() || linebox.is_image() {
                 linebox.bounds += Position::from((
                     left_adjustment + align_adjustment + (interim_adjustment * box_count),
                     font_size_adjustment,
@@ -392,16 +391,33 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
         &mut self,
         context: &mut UpdateContext<'_, 'gc, '_>,
         span: &TextSpan,
-        is_device_font: bool,
+        font_type: FontType,
     ) -> Option<Font<'gc>> {
         let library = context.library.library_for_movie_mut(self.movie.clone());
+        let font_name = span.font.to_utf8_lossy();
+        let font = match font_type {
+            FontType::Embedded => library.get_font_by_name(&font_name, span.bold, span.italic),
+            FontType::EmbeddedCFF => {
+                library.get_cff_font_by_name(&font_name, span.bold, span.italic)
+            }
+            FontType::Device => None,
+        };
 
-        // If this text field is set to use device fonts, fallback to using our embedded Noto Sans.
+        // If this text field is set to use device fonts, look for a matching font on the system,
+        // and fall back to using our embedded Noto Sans if there is none.
         // Note that the SWF can still contain a DefineFont tag with no glyphs/layout info in this case (see #451).
-        // In an ideal world, device fonts would search for a matching font on the system and render it in some way.
-        if let Some(font) = library
-            .get_font_by_name(&span.font.to_utf8_lossy(), span.bold, span.italic)
-            .filter(|f| !is_device_font && f.has_glyphs())
+        if let Some(font) = font
+            .filter(|f| f.has_glyphs())
+            .or_else(|| {
+                context.library.get_or_load_device_font(
+                    context.gc_context,
+                    context.renderer,
+                    context.font,
+                    &font_name,
+                    span.bold,
+                    span.italic,
+                )
+            })
             .or_else(|| context.library.device_font())
         {
             self.font = Some(font);
@@ -482,6 +498,40 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
         }
     }
 
+    /// Append an image to the current line of text.
+    ///
+    /// Images are laid out inline, as if they were a single large glyph. The
+    /// line grows to fit the tallest image on it.
+    fn append_image(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        index: usize,
+        image: &HtmlImage,
+        size: Size<Twips>,
+        is_word_wrap: bool,
+    ) {
+        let hspace = Twips::from_pixels(image.hspace);
+        let vspace = Twips::from_pixels(image.vspace);
+        let size = Size::from((
+            size.width() + hspace + hspace,
+            size.height() + vspace + vspace,
+        ));
+
+        if is_word_wrap
+            && !self.is_start_of_line()
+            && self.cursor.x() + size.width() > self.max_bounds
+        {
+            self.newline(context);
+        }
+
+        let mut new_image = LayoutBox::from_image(index);
+        new_image.bounds = BoxBounds::from_position_and_size(self.cursor, size);
+
+        self.cursor += Position::from((size.width(), Twips::default()));
+        self.max_font_size = max(self.max_font_size, size.height());
+        self.append_box(new_image);
+    }
+
     /// Add a box to the current line of text.
     ///
     /// The box should have been positioned according to the current cursor
@@ -619,6 +669,12 @@ pub enum LayoutContent<'gc> {
     /// layout box's bounds. The size of those bounds do not affect the
     /// rendering of the drawing.
     Drawing(Drawing),
+
+    /// A layout box containing an image embedded with an `<img>` tag.
+    ///
+    /// The image itself is owned by the text field; this holds its index
+    /// within the list of images in the text.
+    Image(usize),
 }
 
 impl<'gc> LayoutBox<'gc> {
@@ -662,22 +718,52 @@ impl<'gc> LayoutBox<'gc> {
         }
     }
 
+    /// Construct an image.
+    pub fn from_image(index: usize) -> Self {
+        Self {
+            bounds: Default::default(),
+            content: LayoutContent::Image(index),
+        }
+    }
+
     /// Construct a new layout hierarchy from text spans.
     ///
     /// The returned bounds will include both the text bounds itself, as well
     /// as left and right margins on any of the lines.
+    ///
+    /// `image_sizes` holds the natural size of each image in the text, for
+    /// images that don't specify their own width or height.
     pub fn lower_from_text_spans(
         fs: &FormatSpans,
         context: &mut UpdateContext<'_, 'gc, '_>,
         movie: Arc<SwfMovie>,
         bounds: Twips,
         is_word_wrap: bool,
-        is_device_font: bool,
+        font_type: FontType,
+        image_sizes: &[Size<Twips>],
     ) -> (Vec<LayoutBox<'gc>>, BoxBounds<Twips>) {
         let mut layout_context = LayoutContext::new(movie, bounds, fs.displayed_text());
+        let mut images = fs.images().iter().enumerate().peekable();
+        let mut append_images_until = |layout_context: &mut LayoutContext<'_, 'gc>,
+                                       context: &mut UpdateContext<'_, 'gc, '_>,
+                                       position: usize| {
+            while let Some((index, image)) = images.next_if(|(_, image)| image.position <= position)
+            {
+                let natural_size = image_sizes.get(index).copied().unwrap_or_default();
+                let size = Size::from((
+                    image.width.map_or(natural_size.width(), Twips::from_pixels),
+                    image
+                        .height
+                        .map_or(natural_size.height(), Twips::from_pixels),
+                ));
+                layout_context.append_image(context, index, image, size, is_word_wrap);
+            }
+        };
 
         for (span_start, _end, span_text, span) in fs.iter_spans() {
-            if let Some(font) = layout_context.resolve_font(context, span, is_device_font) {
+            append_images_until(&mut layout_context, context, span_start);
+
+            if let Some(font) = layout_context.resolve_font(context, span, font_type) {
                 layout_context.newspan(span);
 
                 let params = EvalParameters::from_span(span);
@@ -775,6 +861,8 @@ impl<'gc> LayoutBox<'gc> {
             }
         }
 
+        append_images_until(&mut layout_context, context, usize::MAX);
+
         layout_context.end_layout(context)
     }
 
@@ -819,7 +907,7 @@ impl<'gc> LayoutBox<'gc> {
                 *params,
                 swf::Color::from_rgb(color.to_rgb(), 0xFF),
             )),
-            LayoutContent::Drawing(..) => None,
+            LayoutContent::Drawing(..) | LayoutContent::Image(..) => None,
         }
     }
 
@@ -829,6 +917,15 @@ impl<'gc> LayoutBox<'gc> {
             LayoutContent::Text { .. } => None,
             LayoutContent::Bullet { .. } => None,
             LayoutContent::Drawing(drawing) => Some(drawing),
+            LayoutContent::Image(..) => None,
+        }
+    }
+
+    /// Returns the index of the image this box contains, if it has one.
+    pub fn as_image(&self) -> Option<usize> {
+        match &self.content {
+            LayoutContent::Image(index) => Some(*index),
+            _ => None,
         }
     }
 
@@ -839,4 +936,8 @@ impl<'gc> LayoutBox<'gc> {
     pub fn is_bullet(&self) -> bool {
         matches!(&self.content, LayoutContent::Bullet { .. })
     }
+
+    pub fn is_image(&self) -> bool {
+        matches!(&self.content, LayoutContent::Image(..))
+    }
 }
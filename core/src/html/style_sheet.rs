@@ -0,0 +1,238 @@
+//! CSS style sheets for HTML text
+
+use crate::html::text_format::TextFormat;
+use crate::string::{Integer, WStr, WString};
+use gc_arena::Collect;
+
+/// The CSS properties understood by Flash, in the camel-cased form used by
+/// ActionScript style objects.
+pub const CSS_PROPERTIES: &[&str] = &[
+    "color",
+    "display",
+    "fontFamily",
+    "fontSize",
+    "fontStyle",
+    "fontWeight",
+    "kerning",
+    "leading",
+    "letterSpacing",
+    "marginLeft",
+    "marginRight",
+    "textAlign",
+    "textDecoration",
+    "textIndent",
+];
+
+/// Convert a hyphenated CSS property name (such as `font-size`) into the
+/// camel-cased form used by ActionScript (such as `fontSize`).
+fn camel_case(name: &WStr) -> WString {
+    let mut result = WString::new();
+    let mut capitalize = false;
+    for c in name.chars() {
+        let c = c.unwrap_or(char::REPLACEMENT_CHARACTER);
+        if c == '-' {
+            capitalize = true;
+        } else if capitalize {
+            result.push_char(c.to_ascii_uppercase());
+            capitalize = false;
+        } else {
+            result.push_char(c);
+        }
+    }
+    result
+}
+
+/// Parse a CSS length such as `12`, `12px` or `12pt` into pixels.
+fn parse_length(value: &WStr) -> Option<f64> {
+    let value = value.trim();
+    let value =
+        if value.ends_with(WStr::from_units(b"px")) || value.ends_with(WStr::from_units(b"pt")) {
+            &value[..value.len() - 2]
+        } else {
+            value
+        };
+    value.trim().parse().ok()
+}
+
+/// Parse a CSS color of the form `#RRGGBB`.
+fn parse_color(value: &WStr) -> Option<swf::Color> {
+    let value = value.trim();
+    if !value.starts_with(b'#') {
+        return None;
+    }
+
+    let rgb = u32::from_wstr_radix(&value[1..], 16).ok()?;
+    Some(swf::Color::from_rgb(rgb, 0))
+}
+
+/// Parse the contents of a CSS style sheet.
+///
+/// Each rule is returned as a selector, lowercased, along with its
+/// declarations. Rules with several comma-separated selectors are returned
+/// once for each selector. Property names are converted to camel case.
+pub fn parse_css(css: &WStr) -> Vec<(WString, Vec<(WString, WString)>)> {
+    let mut rules = Vec::new();
+    let mut rest = css;
+
+    while let Some(open) = rest.find(b'{') {
+        let selectors = &rest[..open];
+        let body = &rest[open + 1..];
+        let close = body.find(b'}').unwrap_or(body.len());
+        let declarations: Vec<(WString, WString)> = body[..close]
+            .split(b';')
+            .filter_map(|declaration| {
+                let colon = declaration.find(b':')?;
+                let name = declaration[..colon].trim();
+                let value = declaration[colon + 1..].trim();
+                if name.is_empty() {
+                    return None;
+                }
+                Some((camel_case(name), value.into()))
+            })
+            .collect();
+
+        for selector in selectors.split(b',') {
+            let selector = selector.trim();
+            if !selector.is_empty() {
+                rules.push((selector.to_ascii_lowercase(), declarations.clone()));
+            }
+        }
+
+        rest = body.slice(close + 1..).unwrap_or_default();
+    }
+
+    rules
+}
+
+/// Build the text format described by a set of CSS properties.
+///
+/// `property` is called with the camel-cased name of each property in
+/// `CSS_PROPERTIES`, and should return that property's value if it is set.
+pub fn css_to_text_format(mut property: impl FnMut(&'static str) -> Option<WString>) -> TextFormat {
+    let mut format = TextFormat::default();
+
+    if let Some(color) = property("color") {
+        format.color = parse_color(&color);
+    }
+
+    if let Some(family) = property("fontFamily") {
+        let family = family.split(b',').next().unwrap_or_default().trim();
+        let family = if family == WStr::from_units(b"mono") {
+            WStr::from_units(b"_typewriter")
+        } else if family == WStr::from_units(b"sans-serif") {
+            WStr::from_units(b"_sans")
+        } else if family == WStr::from_units(b"serif") {
+            WStr::from_units(b"_serif")
+        } else {
+            family
+        };
+        format.font = Some(family.into());
+    }
+
+    if let Some(size) = property("fontSize") {
+        format.size = parse_length(&size);
+    }
+
+    if let Some(style) = property("fontStyle") {
+        format.italic = Some(style.trim() == WStr::from_units(b"italic"));
+    }
+
+    if let Some(weight) = property("fontWeight") {
+        format.bold = Some(weight.trim() == WStr::from_units(b"bold"));
+    }
+
+    if let Some(kerning) = property("kerning") {
+        format.kerning = Some(kerning.trim() == WStr::from_units(b"true"));
+    }
+
+    if let Some(leading) = property("leading") {
+        format.leading = parse_length(&leading);
+    }
+
+    if let Some(letter_spacing) = property("letterSpacing") {
+        format.letter_spacing = parse_length(&letter_spacing);
+    }
+
+    if let Some(margin) = property("marginLeft") {
+        format.left_margin = parse_length(&margin);
+    }
+
+    if let Some(margin) = property("marginRight") {
+        format.right_margin = parse_length(&margin);
+    }
+
+    if let Some(align) = property("textAlign") {
+        let align = align.trim();
+        if align == WStr::from_units(b"left") {
+            format.align = Some(swf::TextAlign::Left);
+        } else if align == WStr::from_units(b"center") {
+            format.align = Some(swf::TextAlign::Center);
+        } else if align == WStr::from_units(b"right") {
+            format.align = Some(swf::TextAlign::Right);
+        } else if align == WStr::from_units(b"justify") {
+            format.align = Some(swf::TextAlign::Justify);
+        }
+    }
+
+    if let Some(decoration) = property("textDecoration") {
+        format.underline = Some(decoration.trim() == WStr::from_units(b"underline"));
+    }
+
+    if let Some(indent) = property("textIndent") {
+        format.indent = parse_length(&indent);
+    }
+
+    format
+}
+
+/// A set of styles applied to the HTML text of a text field.
+///
+/// Styles are keyed by selector: either a tag name (such as `p`), or a class
+/// name prefixed with a period (such as `.title`).
+#[derive(Clone, Debug, Default, Collect)]
+#[collect(require_static)]
+pub struct StyleSheet {
+    styles: Vec<(WString, TextFormat)>,
+}
+
+impl StyleSheet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the style for a selector, replacing any previous style.
+    pub fn set_style(&mut self, selector: &WStr, format: TextFormat) {
+        let selector = selector.to_ascii_lowercase();
+        if let Some((_, style)) = self.styles.iter_mut().find(|(name, _)| *name == selector) {
+            *style = format;
+        } else {
+            self.styles.push((selector, format));
+        }
+    }
+
+    /// Retrieve the style for a selector.
+    pub fn style(&self, selector: &WStr) -> Option<&TextFormat> {
+        let selector = selector.to_ascii_lowercase();
+        self.styles
+            .iter()
+            .find(|(name, _)| *name == selector)
+            .map(|(_, style)| style)
+    }
+
+    /// Compute the style applied to a tag with an optional class.
+    ///
+    /// Class styles take precedence over tag styles.
+    pub fn format_for_tag(&self, tag: &WStr, class: Option<&WStr>) -> Option<TextFormat> {
+        let tag_style = self.style(tag).cloned();
+        let class_style = class.and_then(|class| {
+            let mut selector = WString::from(WStr::from_units(b"."));
+            selector.push_str(class);
+            self.style(&selector).cloned()
+        });
+
+        match (class_style, tag_style) {
+            (Some(class_style), Some(tag_style)) => Some(class_style.mix_with(tag_style)),
+            (class_style, tag_style) => class_style.or(tag_style),
+        }
+    }
+}
@@ -1,6 +1,7 @@
 //! Tests for HTML module
 
 use crate::html::dimensions::{BoxBounds, Position, Size};
+use crate::html::style_sheet::{css_to_text_format, parse_css, StyleSheet};
 use crate::html::text_format::{FormatSpans, TextFormat, TextSpan};
 use crate::string::{WStr, WString};
 use swf::{Rectangle, Twips};
@@ -864,3 +865,254 @@ fn formatspans_replace_text_degenerate() {
     assert_eq!((0, 1), fs.get_span_boundaries(0, 5));
     assert_eq!((1, 2), fs.get_span_boundaries(5, 9));
 }
+
+#[test]
+fn formatspans_from_html_img() {
+    let fs = FormatSpans::from_html(
+        WStr::from_units(
+            b"ab<img src=\"pic.png\" id=\"pic\" width=\"20\" height=\"10\" hspace=\"2\"/>cd<img/>",
+        ),
+        TextFormat::default(),
+        None,
+        false,
+    );
+
+    assert_eq!(WStr::from_units(b"abcd"), fs.text());
+
+    // Images without a `src` are dropped.
+    assert_eq!(fs.images().len(), 1);
+
+    let image = &fs.images()[0];
+    assert_eq!(image.position, 2);
+    assert_eq!(image.src, WString::from_utf8("pic.png"));
+    assert_eq!(image.id, Some(WString::from_utf8("pic")));
+    assert_eq!(image.width, Some(20.0));
+    assert_eq!(image.height, Some(10.0));
+    assert_eq!(image.hspace, 2.0);
+    assert_eq!(image.vspace, 8.0);
+}
+
+#[test]
+fn formatspans_img_position_after_replace_text() {
+    let mut fs = FormatSpans::from_html(
+        WStr::from_units(b"ab<img src=\"a.png\"/>cd<img src=\"b.png\"/>ef"),
+        TextFormat::default(),
+        None,
+        false,
+    );
+
+    assert_eq!(fs.images()[0].position, 2);
+    assert_eq!(fs.images()[1].position, 4);
+
+    // Images after the replaced text move with it.
+    fs.replace_text(0, 1, WStr::from_units(b"XYZ"), None);
+    assert_eq!(WStr::from_units(b"XYZbcdef"), fs.text());
+    assert_eq!(fs.images()[0].position, 4);
+    assert_eq!(fs.images()[1].position, 6);
+
+    // Images inside the replaced text move to its start.
+    fs.replace_text(3, 7, WStr::from_units(b""), None);
+    assert_eq!(WStr::from_units(b"XYZf"), fs.text());
+    assert_eq!(fs.images()[0].position, 3);
+    assert_eq!(fs.images()[1].position, 3);
+}
+
+#[test]
+fn formatspans_normalize_keeps_span_break_at_img() {
+    let mut fs = FormatSpans::from_html(
+        WStr::from_units(b"ab<img src=\"pic.png\"/>cd"),
+        TextFormat::default(),
+        None,
+        false,
+    );
+
+    fs.normalize();
+
+    assert_eq!((0, 1), fs.get_span_boundaries(0, 2));
+    assert_eq!((1, 2), fs.get_span_boundaries(2, 4));
+}
+
+#[test]
+fn formatspans_from_html_style_sheet() {
+    let mut style_sheet = StyleSheet::new();
+    style_sheet.set_style(
+        WStr::from_units(b"span"),
+        TextFormat {
+            font: Some(WString::from_utf8("Tag")),
+            size: Some(10.0),
+            ..Default::default()
+        },
+    );
+    style_sheet.set_style(
+        WStr::from_units(b".big"),
+        TextFormat {
+            size: Some(20.0),
+            ..Default::default()
+        },
+    );
+
+    let default_format = TextFormat {
+        font: Some(WString::from_utf8("Default")),
+        size: Some(12.0),
+        ..Default::default()
+    };
+    let fs = FormatSpans::from_html(
+        WStr::from_units(b"<span class=\"big\">ab</span><span>cd</span><b class=\"big\">ef</b>gh"),
+        default_format,
+        Some(&style_sheet),
+        false,
+    );
+
+    let class_and_tag = fs.get_text_format(0, 2);
+    assert_eq!(class_and_tag.font, Some(WString::from_utf8("Tag")));
+    assert_eq!(class_and_tag.size, Some(20.0));
+
+    let tag_only = fs.get_text_format(2, 4);
+    assert_eq!(tag_only.font, Some(WString::from_utf8("Tag")));
+    assert_eq!(tag_only.size, Some(10.0));
+
+    let class_only = fs.get_text_format(4, 6);
+    assert_eq!(class_only.font, Some(WString::from_utf8("Default")));
+    assert_eq!(class_only.size, Some(20.0));
+    assert_eq!(class_only.bold, Some(true));
+
+    let unstyled = fs.get_text_format(6, 8);
+    assert_eq!(unstyled.font, Some(WString::from_utf8("Default")));
+    assert_eq!(unstyled.size, Some(12.0));
+}
+
+#[test]
+fn css_parse_selectors() {
+    let rules = parse_css(WStr::from_units(
+        b"p, H1 { font-size: 12px; color: #FF0000 }\n.Title{font-weight:bold;}",
+    ));
+
+    let size_and_color = vec![
+        (WString::from_utf8("fontSize"), WString::from_utf8("12px")),
+        (WString::from_utf8("color"), WString::from_utf8("#FF0000")),
+    ];
+    assert_eq!(
+        rules,
+        vec![
+            (WString::from_utf8("p"), size_and_color.clone()),
+            (WString::from_utf8("h1"), size_and_color),
+            (
+                WString::from_utf8(".title"),
+                vec![(WString::from_utf8("fontWeight"), WString::from_utf8("bold"))],
+            ),
+        ]
+    );
+}
+
+#[test]
+fn css_parse_bad_input() {
+    assert!(parse_css(WStr::from_units(b"")).is_empty());
+    assert!(parse_css(WStr::from_units(b"not css at all")).is_empty());
+
+    // Declarations without a name or value separator are skipped, and an
+    // unclosed rule runs until the end of the style sheet.
+    let rules = parse_css(WStr::from_units(
+        b"{ color: #000000 } p { color #00FF00; : bold;; text-align: center",
+    ));
+    assert_eq!(
+        rules,
+        vec![(
+            WString::from_utf8("p"),
+            vec![(
+                WString::from_utf8("textAlign"),
+                WString::from_utf8("center")
+            )],
+        )]
+    );
+}
+
+#[test]
+fn css_to_text_format_properties() {
+    let properties = [
+        ("color", "#FF8000"),
+        ("fontFamily", "sans-serif, Arial"),
+        ("fontSize", "12px"),
+        ("fontStyle", "italic"),
+        ("fontWeight", "bold"),
+        ("leading", "3pt"),
+        ("marginLeft", "4"),
+        ("textAlign", "center"),
+        ("textDecoration", "underline"),
+    ];
+    let format = css_to_text_format(|name| {
+        properties
+            .iter()
+            .find(|(property, _)| *property == name)
+            .map(|(_, value)| WString::from_utf8(value))
+    });
+
+    assert_eq!(format.color, Some(swf::Color::from_rgb(0xFF8000, 0)));
+    assert_eq!(format.font, Some(WString::from_utf8("_sans")));
+    assert_eq!(format.size, Some(12.0));
+    assert_eq!(format.italic, Some(true));
+    assert_eq!(format.bold, Some(true));
+    assert_eq!(format.leading, Some(3.0));
+    assert_eq!(format.left_margin, Some(4.0));
+    assert_eq!(format.align, Some(swf::TextAlign::Center));
+    assert_eq!(format.underline, Some(true));
+    assert_eq!(format.right_margin, None);
+}
+
+#[test]
+fn css_to_text_format_bad_values() {
+    let properties = [
+        ("color", "red"),
+        ("fontSize", "big"),
+        ("textAlign", "middle"),
+    ];
+    let format = css_to_text_format(|name| {
+        properties
+            .iter()
+            .find(|(property, _)| *property == name)
+            .map(|(_, value)| WString::from_utf8(value))
+    });
+
+    assert_eq!(format.color, None);
+    assert_eq!(format.size, None);
+    assert_eq!(format.align, None);
+}
+
+#[test]
+fn style_sheet_format_for_tag() {
+    let mut style_sheet = StyleSheet::new();
+    style_sheet.set_style(
+        WStr::from_units(b"P"),
+        TextFormat {
+            font: Some(WString::from_utf8("Tag")),
+            size: Some(10.0),
+            ..Default::default()
+        },
+    );
+    style_sheet.set_style(
+        WStr::from_units(b".Big"),
+        TextFormat {
+            size: Some(20.0),
+            ..Default::default()
+        },
+    );
+
+    // Class styles take precedence over tag styles, and selectors are
+    // case-insensitive.
+    let format = style_sheet
+        .format_for_tag(WStr::from_units(b"p"), Some(WStr::from_units(b"BIG")))
+        .unwrap();
+    assert_eq!(format.font, Some(WString::from_utf8("Tag")));
+    assert_eq!(format.size, Some(20.0));
+
+    let format = style_sheet
+        .format_for_tag(WStr::from_units(b"p"), None)
+        .unwrap();
+    assert_eq!(format.size, Some(10.0));
+
+    assert!(style_sheet
+        .format_for_tag(
+            WStr::from_units(b"span"),
+            Some(WStr::from_units(b"missing"))
+        )
+        .is_none());
+}
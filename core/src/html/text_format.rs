@@ -2,6 +2,7 @@
 
 use crate::context::UpdateContext;
 use crate::html::iterators::TextSpanIter;
+use crate::html::style_sheet::StyleSheet;
 use crate::string::{Integer, Units, WStr, WString};
 use crate::tag_utils::SwfMovie;
 use gc_arena::Collect;
@@ -515,6 +516,32 @@ impl TextSpan {
     }
 }
 
+/// An image embedded in HTML text with an `<img>` tag.
+#[derive(Clone, Debug, Collect)]
+#[collect(require_static)]
+pub struct HtmlImage {
+    /// The position in the text that the image is anchored to.
+    pub position: usize,
+
+    /// The URL or library export name of the image.
+    pub src: WString,
+
+    /// The instance name given to the image.
+    pub id: Option<WString>,
+
+    /// The width to display the image at, if not its natural width.
+    pub width: Option<f64>,
+
+    /// The height to display the image at, if not its natural height.
+    pub height: Option<f64>,
+
+    /// The horizontal space left on either side of the image.
+    pub hspace: f64,
+
+    /// The vertical space left above and below the image.
+    pub vspace: f64,
+}
+
 /// Struct which contains text formatted by `TextSpan`s.
 #[derive(Clone, Debug, Collect)]
 #[collect(require_static)]
@@ -523,6 +550,7 @@ pub struct FormatSpans {
     displayed_text: WString,
     spans: Vec<TextSpan>,
     default_format: TextFormat,
+    images: Vec<HtmlImage>,
 }
 
 impl Default for FormatSpans {
@@ -538,6 +566,7 @@ impl FormatSpans {
             displayed_text: WString::new(),
             spans: vec![TextSpan::default()],
             default_format: TextFormat::default(),
+            images: Vec::new(),
         }
     }
 
@@ -549,6 +578,7 @@ impl FormatSpans {
             displayed_text: WString::new(),
             spans: spans.to_vec(),
             default_format: Default::default(),
+            images: Vec::new(),
         }
     }
 
@@ -559,6 +589,7 @@ impl FormatSpans {
             displayed_text: WString::new(),
             spans: vec![TextSpan::with_length_and_format(len, format.clone())],
             default_format: format,
+            images: Vec::new(),
         }
     }
 
@@ -568,10 +599,16 @@ impl FormatSpans {
     /// a handful of presentational attributes in the HTML tree to generate
     /// styling. There's also a `lower_from_css` that respects both
     /// presentational markup and CSS stylesheets.
-    pub fn from_html(html: &WStr, default_format: TextFormat, is_multiline: bool) -> Self {
+    pub fn from_html(
+        html: &WStr,
+        default_format: TextFormat,
+        style_sheet: Option<&StyleSheet>,
+        is_multiline: bool,
+    ) -> Self {
         let mut format_stack = vec![default_format.clone()];
         let mut text = WString::new();
         let mut spans: Vec<TextSpan> = Vec::new();
+        let mut images = Vec::new();
 
         // quick_xml::Reader requires a [u8] slice, but doesn't actually care about Unicode;
         // this means we can pass the raw buffer in the Latin1 case.
@@ -631,7 +668,8 @@ impl FormatSpans {
                         })
                     };
                     let mut format = format_stack.last().unwrap().clone();
-                    match &e.name().to_ascii_lowercase()[..] {
+                    let tag_name = e.name().to_ascii_lowercase();
+                    match &tag_name[..] {
                         b"br" => {
                             if is_multiline {
                                 text.push_byte(b'\n');
@@ -654,6 +692,26 @@ impl FormatSpans {
                             // Skip push to `format_stack`.
                             continue;
                         }
+                        b"img" => {
+                            if let Some(src) = attribute(b"src") {
+                                images.push(HtmlImage {
+                                    position: text.len(),
+                                    src,
+                                    id: attribute(b"id"),
+                                    width: attribute(b"width").and_then(|v| v.parse().ok()),
+                                    height: attribute(b"height").and_then(|v| v.parse().ok()),
+                                    hspace: attribute(b"hspace")
+                                        .and_then(|v| v.parse().ok())
+                                        .unwrap_or(8.0),
+                                    vspace: attribute(b"vspace")
+                                        .and_then(|v| v.parse().ok())
+                                        .unwrap_or(8.0),
+                                });
+                            }
+
+                            // Skip push to `format_stack`.
+                            continue;
+                        }
                         b"p" if is_multiline => {
                             if let Some(align) = attribute(b"align") {
                                 if align == WStr::from_units(b"left") {
@@ -759,6 +817,16 @@ impl FormatSpans {
                         }
                         _ => {}
                     }
+
+                    if let Some(style_sheet) = style_sheet {
+                        let tag_name = WString::from_buf(tag_name);
+                        let class = attribute(b"class");
+                        if let Some(style) = style_sheet.format_for_tag(&tag_name, class.as_deref())
+                        {
+                            format = style.mix_with(format);
+                        }
+                    }
+
                     format_stack.push(format);
                 }
                 Ok(Event::Text(e)) if !e.is_empty() => {
@@ -783,7 +851,7 @@ impl FormatSpans {
                     }
 
                     match &e.name().to_ascii_lowercase()[..] {
-                        b"br" | b"sbr" => {
+                        b"br" | b"sbr" | b"img" => {
                             // Skip pop from `format_stack`.
                             continue;
                         }
@@ -811,6 +879,7 @@ impl FormatSpans {
             displayed_text: WString::new(),
             spans,
             default_format,
+            images,
         }
     }
 
@@ -834,6 +903,11 @@ impl FormatSpans {
         !self.displayed_text.is_empty()
     }
 
+    /// The images embedded in this text, in the order they appear.
+    pub fn images(&self) -> &[HtmlImage] {
+        &self.images
+    }
+
     /// Retrieve the text backing the format spans.
     pub fn text(&self) -> &WStr {
         &self.text
@@ -996,12 +1070,17 @@ impl FormatSpans {
             self.spans.remove(0);
         }
 
+        // Spans are never merged across the position of an image, so that
+        // layout can place images at the boundaries between spans.
         let mut i = 0;
+        let mut span_start = 0;
         while i < self.spans.len().saturating_sub(1) {
             let remove_next = {
                 let spans = self.spans.get_mut(i..i + 2).unwrap();
+                let boundary = span_start + spans[0].span_length;
+                let has_image = self.images.iter().any(|image| image.position == boundary);
 
-                if spans[0].can_merge(&spans[1]) || spans[1].span_length == 0 {
+                if (spans[0].can_merge(&spans[1]) && !has_image) || spans[1].span_length == 0 {
                     spans[0].span_length += spans[1].span_length;
                     true
                 } else {
@@ -1012,6 +1091,7 @@ impl FormatSpans {
             if remove_next {
                 self.spans.remove(i + 1);
             } else {
+                span_start += self.spans[i].span_length;
                 i += 1;
             }
         }
@@ -1130,6 +1210,14 @@ impl FormatSpans {
 
         self.text = new_string;
 
+        for image in self.images.iter_mut() {
+            if image.position >= to {
+                image.position = image.position - to + from + with.len();
+            } else if image.position > from {
+                image.position = from;
+            }
+        }
+
         self.normalize();
     }
 
@@ -0,0 +1,209 @@
+//! Display list inspection
+//!
+//! A read-only snapshot of the display list, for frontends that want to show
+//! what is on stage.
+
+use crate::avm1::activation::{Activation as Avm1Activation, ActivationIdentifier};
+use crate::avm1::{TObject as _, Value as Avm1Value};
+use crate::avm2::{Activation as Avm2Activation, TObject as _};
+use crate::context::UpdateContext;
+use crate::debugger::Variable;
+use crate::display_object::Stage;
+use crate::prelude::*;
+use serde::Serialize;
+
+/// A display object and its children.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DisplayObjectInfo {
+    /// The index of this object and each of its ancestors in their parent's
+    /// render list, starting from the stage.
+    ///
+    /// The stage itself has an empty path.
+    pub path: Vec<usize>,
+
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    pub name: String,
+    pub depth: Depth,
+    pub character_id: CharacterId,
+    pub matrix: MatrixInfo,
+    pub color_transform: ColorTransformInfo,
+
+    /// The bounds of this object and its children, in stage pixels.
+    pub bounds: Option<BoundsInfo>,
+    pub visible: bool,
+
+    /// The properties of this object's AVM1 or AVM2 object.
+    pub properties: Vec<Variable>,
+    pub children: Vec<DisplayObjectInfo>,
+}
+
+/// A display object's matrix, with the translation in pixels.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct MatrixInfo {
+    pub a: f32,
+    pub b: f32,
+    pub c: f32,
+    pub d: f32,
+    pub tx: f64,
+    pub ty: f64,
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ColorTransformInfo {
+    pub r_mult: f32,
+    pub g_mult: f32,
+    pub b_mult: f32,
+    pub a_mult: f32,
+    pub r_add: i16,
+    pub g_add: i16,
+    pub b_add: i16,
+    pub a_add: i16,
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BoundsInfo {
+    pub x_min: f64,
+    pub y_min: f64,
+    pub x_max: f64,
+    pub y_max: f64,
+}
+
+/// Describe a display object and all of its descendants.
+pub fn inspect<'gc>(
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    display_object: DisplayObject<'gc>,
+    path: Vec<usize>,
+) -> DisplayObjectInfo {
+    let matrix = *display_object.base().matrix();
+    let color_transform = display_object.base().color_transform().clone();
+    let bounds = display_object.world_bounds();
+
+    let children = display_object
+        .as_container()
+        .map(|container| container.iter_render_list().collect())
+        .unwrap_or_else(Vec::new)
+        .into_iter()
+        .enumerate()
+        .map(|(i, child)| {
+            let mut child_path = path.clone();
+            child_path.push(i);
+            inspect(context, child, child_path)
+        })
+        .collect();
+
+    DisplayObjectInfo {
+        kind: kind(display_object),
+        name: display_object.name().to_string(),
+        depth: display_object.depth(),
+        character_id: display_object.id(),
+        matrix: MatrixInfo {
+            a: matrix.a,
+            b: matrix.b,
+            c: matrix.c,
+            d: matrix.d,
+            tx: matrix.tx.to_pixels(),
+            ty: matrix.ty.to_pixels(),
+        },
+        color_transform: ColorTransformInfo {
+            r_mult: color_transform.r_mult.to_f32(),
+            g_mult: color_transform.g_mult.to_f32(),
+            b_mult: color_transform.b_mult.to_f32(),
+            a_mult: color_transform.a_mult.to_f32(),
+            r_add: color_transform.r_add,
+            g_add: color_transform.g_add,
+            b_add: color_transform.b_add,
+            a_add: color_transform.a_add,
+        },
+        bounds: bounds.valid.then(|| BoundsInfo {
+            x_min: bounds.x_min.to_pixels(),
+            y_min: bounds.y_min.to_pixels(),
+            x_max: bounds.x_max.to_pixels(),
+            y_max: bounds.y_max.to_pixels(),
+        }),
+        visible: display_object.visible(),
+        properties: properties(context, display_object),
+        children,
+        path,
+    }
+}
+
+/// Find the display object at a path given by `DisplayObjectInfo::path`.
+pub fn find<'gc>(stage: Stage<'gc>, path: &[usize]) -> Option<DisplayObject<'gc>> {
+    let mut display_object: DisplayObject<'gc> = stage.into();
+    for &i in path {
+        display_object = display_object.as_container()?.iter_render_list().nth(i)?;
+    }
+    Some(display_object)
+}
+
+/// Find the path of the topmost visible display object whose bounds contain
+/// a point on the stage.
+pub fn path_at_point(stage: Stage<'_>, point: (Twips, Twips)) -> Option<Vec<usize>> {
+    fn search(display_object: DisplayObject<'_>, point: (Twips, Twips)) -> Option<Vec<usize>> {
+        let children: Vec<_> = display_object
+            .as_container()
+            .map(|container| container.iter_render_list().collect())
+            .unwrap_or_default();
+        for (i, child) in children.into_iter().enumerate().rev() {
+            if !child.visible() || !child.world_bounds().contains(point) {
+                continue;
+            }
+            let mut path = vec![i];
+            if let Some(child_path) = search(child, point) {
+                path.extend(child_path);
+            }
+            return Some(path);
+        }
+        None
+    }
+
+    search(stage.into(), point)
+}
+
+fn kind(display_object: DisplayObject<'_>) -> &'static str {
+    match display_object {
+        DisplayObject::Stage(_) => "Stage",
+        DisplayObject::Bitmap(_) => "Bitmap",
+        DisplayObject::Avm1Button(_) | DisplayObject::Avm2Button(_) => "Button",
+        DisplayObject::EditText(_) => "EditText",
+        DisplayObject::Graphic(_) => "Graphic",
+        DisplayObject::MorphShape(_) => "MorphShape",
+        DisplayObject::MovieClip(_) => "MovieClip",
+        DisplayObject::Text(_) => "Text",
+        DisplayObject::Video(_) => "Video",
+    }
+}
+
+/// List the properties of a display object's script object, without running
+/// any ActionScript.
+fn properties<'gc>(
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    display_object: DisplayObject<'gc>,
+) -> Vec<Variable> {
+    if let Avm1Value::Object(object) = display_object.object() {
+        let mut activation = Avm1Activation::from_stub(
+            context.reborrow(),
+            ActivationIdentifier::root("[Inspector]"),
+        );
+        let mut properties = vec![];
+        for name in object.get_keys(&mut activation) {
+            if let Some(value) = object.get_local_stored(name, &mut activation) {
+                properties.push(activation.debug_variable(name.to_string(), value));
+            }
+        }
+        properties
+    } else if let Avm2Value::Object(object) = display_object.object2() {
+        let mut activation = Avm2Activation::from_nothing(context.reborrow());
+        let properties = object.base().debug_properties();
+        properties
+            .into_iter()
+            .filter_map(|(name, value)| activation.debug_variable(name.to_string(), value).ok())
+            .collect()
+    } else {
+        vec![]
+    }
+}
@@ -1,6 +1,6 @@
 #[macro_use]
 mod display_object;
-pub use display_object::StageDisplayState;
+pub use display_object::{interpolate_morph_shape, StageDisplayState};
 
 #[macro_use]
 extern crate smallvec;
@@ -32,6 +32,7 @@ pub mod loader;
 pub mod matrix;
 mod player;
 mod prelude;
+mod printing;
 pub mod shape_utils;
 pub mod string;
 pub mod tag_utils;
@@ -42,7 +43,10 @@ mod xml;
 
 pub mod backend;
 pub mod config;
+pub mod debugger;
 pub mod external;
+pub mod inspector;
+pub mod recording;
 
 pub use chrono;
 pub use context_menu::ContextMenuItem;
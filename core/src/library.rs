@@ -1,9 +1,9 @@
 use crate::avm1::property_map::PropertyMap as Avm1PropertyMap;
 use crate::avm2::{ClassObject as Avm2ClassObject, Domain as Avm2Domain};
-use crate::backend::{audio::SoundHandle, render};
+use crate::backend::{audio::SoundHandle, font::FontBackend, render};
 use crate::character::Character;
 use crate::display_object::{Bitmap, Graphic, MorphShape, TDisplayObject, Text};
-use crate::font::{Font, FontDescriptor};
+use crate::font::{CsmSettings, Font, FontDescriptor};
 use crate::prelude::*;
 use crate::string::AvmString;
 use crate::tag_utils::SwfMovie;
@@ -99,8 +99,16 @@ pub struct MovieLibrary<'gc> {
     export_characters: Avm1PropertyMap<'gc, Character<'gc>>,
     jpeg_tables: Option<Vec<u8>>,
     fonts: HashMap<FontDescriptor, Font<'gc>>,
+
+    /// Fonts defined by `DefineFont4`, which are only usable by
+    /// `flash.text.engine`.
+    cff_fonts: HashMap<FontDescriptor, Font<'gc>>,
     avm_type: AvmType,
     avm2_domain: Option<Avm2Domain<'gc>>,
+
+    /// The number of `ImportAssets` tags whose shared library has not
+    /// finished loading yet.
+    pending_imports: usize,
 }
 
 impl<'gc> MovieLibrary<'gc> {
@@ -110,8 +118,10 @@ impl<'gc> MovieLibrary<'gc> {
             export_characters: Avm1PropertyMap::new(),
             jpeg_tables: None,
             fonts: HashMap::new(),
+            cff_fonts: HashMap::new(),
             avm_type,
             avm2_domain: None,
+            pending_imports: 0,
         }
     }
 
@@ -119,7 +129,11 @@ impl<'gc> MovieLibrary<'gc> {
         // TODO(Herschel): What is the behavior if id already exists?
         if !self.contains_character(id) {
             if let Character::Font(font) = character.clone() {
-                self.fonts.insert(font.descriptor().clone(), font);
+                if font.is_cff() {
+                    self.cff_fonts.insert(font.descriptor().clone(), font);
+                } else {
+                    self.fonts.insert(font.descriptor().clone(), font);
+                }
             }
 
             self.characters.insert(id, character);
@@ -149,6 +163,23 @@ impl<'gc> MovieLibrary<'gc> {
         }
     }
 
+    /// Marks that characters are being imported from a shared library.
+    pub fn begin_import(&mut self) {
+        self.pending_imports += 1;
+    }
+
+    /// Marks that a shared library has finished loading, whether or not its
+    /// characters could be imported.
+    pub fn end_import(&mut self) {
+        self.pending_imports = self.pending_imports.saturating_sub(1);
+    }
+
+    /// Returns whether this movie is still waiting for shared libraries to
+    /// load. The movie should not play until all imports are resolved.
+    pub fn is_importing(&self) -> bool {
+        self.pending_imports > 0
+    }
+
     pub fn contains_character(&self, id: CharacterId) -> bool {
         self.characters.contains_key(&id)
     }
@@ -243,6 +274,18 @@ impl<'gc> MovieLibrary<'gc> {
         self.fonts.get(&descriptor).copied()
     }
 
+    /// Find a `DefineFont4` font by it's name and parameters.
+    pub fn get_cff_font_by_name(
+        &self,
+        name: &str,
+        is_bold: bool,
+        is_italic: bool,
+    ) -> Option<Font<'gc>> {
+        let descriptor = FontDescriptor::from_parts(name, is_bold, is_italic);
+
+        self.cff_fonts.get(&descriptor).copied()
+    }
+
     /// Returns the `Graphic` with the given character ID.
     /// Returns `None` if the ID does not exist or is not a `Graphic`.
     pub fn get_graphic(&self, id: CharacterId) -> Option<Graphic<'gc>> {
@@ -350,6 +393,14 @@ pub struct Library<'gc> {
     /// The embedded device font.
     device_font: Option<Font<'gc>>,
 
+    /// Device fonts loaded from the font backend. Fonts that are not
+    /// available are cached as `None`.
+    device_fonts: HashMap<FontDescriptor, Option<Font<'gc>>>,
+
+    /// Anti-aliasing tables set by `TextRenderer.setAdvancedAntiAliasingTable`,
+    /// keyed by font and whether they apply to dark text.
+    anti_aliasing_tables: HashMap<(FontDescriptor, bool), Vec<CsmSettings>>,
+
     /// A list of the symbols associated with specific AVM2 constructor
     /// prototypes.
     avm2_class_registry: Avm2ClassRegistry<'gc>,
@@ -362,6 +413,9 @@ unsafe impl<'gc> gc_arena::Collect for Library<'gc> {
             val.trace(cc);
         }
         self.device_font.trace(cc);
+        for font in self.device_fonts.values() {
+            font.trace(cc);
+        }
         self.avm2_class_registry.trace(cc);
     }
 }
@@ -371,6 +425,8 @@ impl<'gc> Library<'gc> {
         Self {
             movie_libraries: PtrWeakKeyHashMap::new(),
             device_font: None,
+            device_fonts: HashMap::new(),
+            anti_aliasing_tables: HashMap::new(),
             avm2_class_registry: Default::default(),
         }
     }
@@ -396,6 +452,87 @@ impl<'gc> Library<'gc> {
         self.device_font = font;
     }
 
+    /// Returns the anti-aliasing table used to render dark or light text in
+    /// the given font.
+    pub fn anti_aliasing_table(
+        &self,
+        descriptor: &FontDescriptor,
+        is_dark: bool,
+    ) -> Option<&[CsmSettings]> {
+        if self.anti_aliasing_tables.is_empty() {
+            return None;
+        }
+
+        // Font names from SWF tags may contain a trailing null.
+        let descriptor =
+            FontDescriptor::from_parts(descriptor.class(), descriptor.bold(), descriptor.italic());
+        self.anti_aliasing_tables
+            .get(&(descriptor, is_dark))
+            .map(|table| table.as_slice())
+    }
+
+    /// Sets the anti-aliasing table used to render dark or light text in the
+    /// given font.
+    pub fn set_anti_aliasing_table(
+        &mut self,
+        descriptor: FontDescriptor,
+        is_dark: bool,
+        table: Vec<CsmSettings>,
+    ) {
+        self.anti_aliasing_tables
+            .insert((descriptor, is_dark), table);
+    }
+
+    /// Find a device font provided by the font backend.
+    ///
+    /// The generic font names `_sans`, `_serif` and `_typewriter` resolve to
+    /// common fonts of that kind, and unavailable fonts fall back to `_sans`.
+    pub fn get_or_load_device_font(
+        &mut self,
+        gc_context: MutationContext<'gc, '_>,
+        renderer: &mut dyn render::RenderBackend,
+        font_backend: &mut dyn FontBackend,
+        name: &str,
+        is_bold: bool,
+        is_italic: bool,
+    ) -> Option<Font<'gc>> {
+        device_font_families(name).into_iter().find_map(|family| {
+            self.load_device_font(
+                gc_context,
+                renderer,
+                font_backend,
+                FontDescriptor::from_parts(family, is_bold, is_italic),
+            )
+        })
+    }
+
+    /// Load a font family from the font backend, converting its glyph shapes
+    /// the first time it is requested.
+    fn load_device_font(
+        &mut self,
+        gc_context: MutationContext<'gc, '_>,
+        renderer: &mut dyn render::RenderBackend,
+        font_backend: &mut dyn FontBackend,
+        descriptor: FontDescriptor,
+    ) -> Option<Font<'gc>> {
+        if let Some(font) = self.device_fonts.get(&descriptor) {
+            return *font;
+        }
+
+        let font =
+            font_backend.load_font(&descriptor).and_then(|data| {
+                match Font::from_font_file(gc_context, renderer, &data) {
+                    Ok(font) => Some(font),
+                    Err(e) => {
+                        log::warn!("Unable to load device font {}: {}", descriptor.class(), e);
+                        None
+                    }
+                }
+            });
+        self.device_fonts.insert(descriptor, font);
+        font
+    }
+
     /// Get the AVM2 class registry.
     pub fn avm2_class_registry(&self) -> &Avm2ClassRegistry<'gc> {
         &self.avm2_class_registry
@@ -406,3 +543,39 @@ impl<'gc> Library<'gc> {
         &mut self.avm2_class_registry
     }
 }
+
+const SANS_FONTS: &[&str] = &[
+    "Arial",
+    "Helvetica",
+    "Liberation Sans",
+    "DejaVu Sans",
+    "Noto Sans",
+];
+
+const SERIF_FONTS: &[&str] = &[
+    "Times New Roman",
+    "Times",
+    "Liberation Serif",
+    "DejaVu Serif",
+    "Noto Serif",
+];
+
+const TYPEWRITER_FONTS: &[&str] = &[
+    "Courier New",
+    "Courier",
+    "Liberation Mono",
+    "DejaVu Sans Mono",
+    "Noto Sans Mono",
+];
+
+/// The font families to try, in order, when looking up a device font.
+fn device_font_families(name: &str) -> Vec<&str> {
+    match name {
+        "_sans" | "_ゴシック" => SANS_FONTS.to_vec(),
+        "_serif" | "_明朝" => SERIF_FONTS.to_vec(),
+        "_typewriter" | "_等幅" => TYPEWRITER_FONTS.to_vec(),
+        _ => std::iter::once(name)
+            .chain(SANS_FONTS.iter().copied())
+            .collect(),
+    }
+}
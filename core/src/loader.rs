@@ -4,8 +4,9 @@ use crate::avm1::activation::{Activation, ActivationIdentifier};
 use crate::avm1::{Avm1, Object, TObject, Value};
 use crate::avm2::{Activation as Avm2Activation, Domain as Avm2Domain};
 use crate::backend::navigator::OwnedFuture;
-use crate::context::{ActionQueue, ActionType};
-use crate::display_object::{DisplayObject, MorphShape, TDisplayObject};
+use crate::character::Character;
+use crate::context::{ActionQueue, ActionType, UpdateContext};
+use crate::display_object::{DisplayObject, MorphShape, MovieClip, TDisplayObject};
 use crate::player::{Player, NEWEST_PLAYER_VERSION};
 use crate::string::AvmString;
 use crate::tag_utils::SwfMovie;
@@ -14,6 +15,7 @@ use encoding_rs::UTF_8;
 use gc_arena::{Collect, CollectionContext};
 use generational_arena::{Arena, Index};
 use std::sync::{Arc, Mutex, Weak};
+use swf::CharacterId;
 use thiserror::Error;
 use url::form_urlencoded;
 
@@ -36,6 +38,9 @@ pub enum Error {
     #[error("Non-load vars loader spawned as load vars loader")]
     NotLoadVarsLoader,
 
+    #[error("Non-import assets loader spawned as import assets loader")]
+    NotImportAssetsLoader,
+
     #[error("Could not fetch movie {0}")]
     FetchError(String),
 
@@ -93,11 +98,17 @@ impl<'gc> LoadManager<'gc> {
             Loader::RootMovie { self_handle, .. }
             | Loader::Movie { self_handle, .. }
             | Loader::Form { self_handle, .. }
-            | Loader::LoadVars { self_handle, .. } => *self_handle = Some(handle),
+            | Loader::LoadVars { self_handle, .. }
+            | Loader::ImportAssets { self_handle, .. } => *self_handle = Some(handle),
         }
         handle
     }
 
+    /// Remove a finished loader, invalidating its handle.
+    pub fn remove_loader(&mut self, handle: Handle) {
+        self.0.remove(handle);
+    }
+
     /// Retrieve a loader by handle.
     pub fn get_loader(&self, handle: Handle) -> Option<&Loader<'gc>> {
         self.0.get(handle)
@@ -207,6 +218,27 @@ impl<'gc> LoadManager<'gc> {
 
         loader.load_vars_loader(player, fetch)
     }
+
+    /// Kick off a shared library load for an `ImportAssets` tag.
+    ///
+    /// Returns the loader's async process, which you will need to spawn.
+    pub fn load_asset_imports(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        importer: Arc<SwfMovie>,
+        fetch: OwnedFuture<Vec<u8>, Error>,
+        url: String,
+        imports: Vec<(CharacterId, String)>,
+    ) -> OwnedFuture<(), Error> {
+        let loader = Loader::ImportAssets {
+            self_handle: None,
+            importer,
+        };
+        let handle = self.add_loader(loader);
+        let loader = self.get_loader_mut(handle).unwrap();
+
+        loader.import_assets_loader(player, fetch, url, imports)
+    }
 }
 
 impl<'gc> Default for LoadManager<'gc> {
@@ -281,6 +313,17 @@ pub enum Loader<'gc> {
         /// The target AVM1 object to load form data into.
         target_object: Object<'gc>,
     },
+
+    /// Loader that is loading a shared library to import characters from.
+    ImportAssets {
+        /// The handle to refer to this loader instance.
+        #[collect(require_static)]
+        self_handle: Option<Handle>,
+
+        /// The movie that is importing characters from the shared library.
+        #[collect(require_static)]
+        importer: Arc<SwfMovie>,
+    },
 }
 
 impl<'gc> Loader<'gc> {
@@ -644,6 +687,69 @@ impl<'gc> Loader<'gc> {
         })
     }
 
+    /// Creates a future for an `ImportAssets` load.
+    ///
+    /// Once the shared library has loaded, the characters it exports are
+    /// registered in the importing movie's library, and the importing movie
+    /// may start playing.
+    pub fn import_assets_loader(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        fetch: OwnedFuture<Vec<u8>, Error>,
+        url: String,
+        imports: Vec<(CharacterId, String)>,
+    ) -> OwnedFuture<(), Error> {
+        let (handle, importer) = match self {
+            Loader::ImportAssets {
+                self_handle,
+                importer,
+            } => (
+                self_handle.expect("Loader not self-introduced"),
+                importer.clone(),
+            ),
+            _ => return Box::pin(async { Err(Error::NotImportAssetsLoader) }),
+        };
+
+        let player = player
+            .upgrade()
+            .expect("Could not upgrade weak reference to player");
+
+        Box::pin(async move {
+            let data = (fetch.await)
+                .and_then(|data| Ok(SwfMovie::from_data(&data, Some(url.clone()), None)?));
+
+            player.lock().unwrap().update(|uc| {
+                let result = match uc.load_manager.get_loader(handle) {
+                    Some(Loader::ImportAssets { .. }) => Ok(()),
+                    None => Err(Error::Cancelled),
+                    _ => Err(Error::NotImportAssetsLoader),
+                };
+
+                // Flash keeps playing the importing movie if the shared
+                // library fails to load, without the imported characters.
+                let result = result.and_then(|()| {
+                    uc.load_manager.remove_loader(handle);
+                    match data {
+                        Ok(movie) => {
+                            import_characters(uc, importer.clone(), Arc::new(movie), &imports);
+                            Ok(())
+                        }
+                        Err(e) => {
+                            log::warn!("Can't load shared library {}: {}", url, e);
+                            Ok(())
+                        }
+                    }
+                });
+
+                // However this load ended, the importing movie must not wait
+                // for it any longer.
+                uc.library.library_for_movie_mut(importer).end_import();
+
+                result
+            })
+        })
+    }
+
     /// Event handler morally equivalent to `onLoad` on a movie clip.
     ///
     /// Returns `true` if the loader has completed and should be removed.
@@ -680,3 +786,51 @@ impl<'gc> Loader<'gc> {
         }
     }
 }
+
+/// Preload a shared library, and register the characters it exports in the
+/// library of the importing movie, under the IDs that the importer uses.
+fn import_characters<'gc>(
+    uc: &mut UpdateContext<'_, 'gc, '_>,
+    importer: Arc<SwfMovie>,
+    shared_library: Arc<SwfMovie>,
+    imports: &[(CharacterId, String)],
+) {
+    let mut activation = Avm2Activation::from_nothing(uc.reborrow());
+    let parent_domain = activation.avm2().global_domain();
+    let domain = Avm2Domain::movie_domain(&mut activation, parent_domain);
+    uc.library
+        .library_for_movie_mut(shared_library.clone())
+        .set_avm2_domain(domain);
+
+    // The shared library is never displayed; this clip only defines its
+    // characters.
+    let clip = MovieClip::from_movie(uc.gc_context, shared_library.clone());
+    let mut morph_shapes = fnv::FnvHashMap::default();
+    clip.preload(uc, &mut morph_shapes);
+    for (id, static_data) in morph_shapes {
+        let morph_shape = MorphShape::new(uc.gc_context, static_data);
+        uc.library
+            .library_for_movie_mut(shared_library.clone())
+            .register_character(id, Character::MorphShape(morph_shape));
+    }
+
+    for (id, name) in imports {
+        let name = AvmString::new_utf8(uc.gc_context, name);
+        let character = uc
+            .library
+            .library_for_movie_mut(shared_library.clone())
+            .character_by_export_name(name)
+            .cloned();
+        if let Some(character) = character {
+            let library = uc.library.library_for_movie_mut(importer.clone());
+            library.register_character(*id, character);
+            library.register_export(*id, name);
+        } else {
+            log::warn!(
+                "Can't import {}: {} does not export it",
+                name,
+                shared_library.url().unwrap_or_default()
+            );
+        }
+    }
+}
@@ -7,9 +7,11 @@ use crate::avm1::{Avm1, ScriptObject, TObject, Timers, Value};
 use crate::avm2::{Activation as Avm2Activation, Avm2, Domain as Avm2Domain};
 use crate::backend::{
     audio::{AudioBackend, AudioManager},
+    font::FontBackend,
     locale::LocaleBackend,
     log::LogBackend,
     navigator::{NavigatorBackend, RequestOptions},
+    print::PrintBackend,
     render::RenderBackend,
     storage::StorageBackend,
     ui::{InputManager, MouseCursor, UiBackend},
@@ -18,17 +20,23 @@ use crate::backend::{
 use crate::config::Letterbox;
 use crate::context::{ActionQueue, ActionType, RenderContext, UpdateContext};
 use crate::context_menu::{ContextMenuCallback, ContextMenuItem, ContextMenuState};
+use crate::debugger::{Debugger, DebuggerBackend};
 use crate::display_object::{
     EditText, InteractiveObject, MorphShape, MovieClip, Stage, StageAlign, StageDisplayState,
     StageQuality, StageScaleMode, TInteractiveObject,
 };
-use crate::events::{ButtonKeyCode, ClipEvent, ClipEventResult, KeyCode, MouseButton, PlayerEvent};
+use crate::events::{
+    ButtonKeyCode, ClipEvent, ClipEventResult, KeyCode, MouseButton, PlayerEvent, TextControlCode,
+};
 use crate::external::Value as ExternalValue;
 use crate::external::{ExternalInterface, ExternalInterfaceProvider};
 use crate::focus_tracker::FocusTracker;
+use crate::inspector::{self, DisplayObjectInfo};
 use crate::library::Library;
 use crate::loader::LoadManager;
 use crate::prelude::*;
+use crate::printing::PrintManager;
+use crate::recording::{Recorder, Step};
 use crate::string::AvmString;
 use crate::tag_utils::SwfMovie;
 use crate::transform::TransformStack;
@@ -103,6 +111,9 @@ struct GcRootData<'gc> {
 
     /// Manager of active sound instances.
     audio_manager: AudioManager<'gc>,
+
+    /// Print jobs started by `PrintJob`.
+    print_manager: PrintManager,
 }
 
 impl<'gc> GcRootData<'gc> {
@@ -125,6 +136,7 @@ impl<'gc> GcRootData<'gc> {
         &mut Option<ContextMenuState<'gc>>,
         &mut ExternalInterface<'gc>,
         &mut AudioManager<'gc>,
+        &mut PrintManager,
     ) {
         (
             self.stage,
@@ -140,6 +152,7 @@ impl<'gc> GcRootData<'gc> {
             &mut self.current_context_menu,
             &mut self.external_interface,
             &mut self.audio_manager,
+            &mut self.print_manager,
         )
     }
 }
@@ -149,6 +162,8 @@ make_arena!(GcArena, GcRoot);
 
 type Audio = Box<dyn AudioBackend>;
 type Navigator = Box<dyn NavigatorBackend>;
+type Print = Box<dyn PrintBackend>;
+type Font = Box<dyn FontBackend>;
 type Renderer = Box<dyn RenderBackend>;
 type Storage = Box<dyn StorageBackend>;
 type Locale = Box<dyn LocaleBackend>;
@@ -184,6 +199,8 @@ pub struct Player {
     log: Log,
     ui: Ui,
     video: Video,
+    print: Print,
+    font: Font,
 
     transform_stack: TransformStack,
 
@@ -233,6 +250,16 @@ pub struct Player {
     /// The current frame of the main timeline, if available.
     /// The first frame is frame 1.
     current_frame: Option<u16>,
+
+    /// The ActionScript debugger, if a client is attached.
+    debugger: Debugger,
+
+    /// The path of the display object whose bounds are highlighted when
+    /// rendering, as given by `DisplayObjectInfo::path`.
+    highlighted_display_object: Option<Vec<usize>>,
+
+    /// Records the session, if enabled.
+    recorder: Option<Recorder>,
 }
 
 impl Player {
@@ -246,6 +273,8 @@ impl Player {
         video: Video,
         log: Log,
         ui: Ui,
+        print: Print,
+        font: Font,
     ) -> Result<Arc<Mutex<Self>>, Error> {
         let fake_movie = Arc::new(SwfMovie::empty(NEWEST_PLAYER_VERSION));
         let movie_width = 550;
@@ -288,6 +317,7 @@ impl Player {
                         external_interface: ExternalInterface::new(),
                         focus_tracker: FocusTracker::new(gc_context),
                         audio_manager: AudioManager::new(),
+                        print_manager: PrintManager::new(),
                     },
                 ))
             }),
@@ -310,6 +340,8 @@ impl Player {
             log,
             ui,
             video,
+            print,
+            font,
             self_reference: None,
             system: SystemProperties::default(),
             instance_counter: 0,
@@ -317,6 +349,9 @@ impl Player {
             storage,
             max_execution_duration: Duration::from_secs(max_execution_duration),
             current_frame: None,
+            debugger: Debugger::default(),
+            highlighted_display_object: None,
+            recorder: None,
         };
 
         player.mutate_with_update_context(|context| {
@@ -498,12 +533,20 @@ impl Player {
     }
 
     pub fn tick(&mut self, dt: f64) {
+        self.record(Step::Tick(dt));
+
         // Don't run until preloading is complete.
         // TODO: Eventually we want to stream content similar to the Flash player.
         if !self.audio.is_loading_complete() {
             return;
         }
 
+        // A suspended player stays frozen until the debugger client resumes it.
+        self.debugger.poll();
+        if self.debugger.is_suspended() {
+            return;
+        }
+
         if self.is_playing() {
             self.frame_accumulator += dt;
             let frame_rate = self.frame_rate;
@@ -751,6 +794,7 @@ impl Player {
     }
 
     pub fn set_letterbox(&mut self, letterbox: Letterbox) {
+        self.record(Step::SetLetterbox(letterbox));
         self.mutate_with_update_context(|context| {
             context.stage.set_letterbox(context.gc_context, letterbox)
         })
@@ -777,6 +821,11 @@ impl Player {
     }
 
     pub fn set_viewport_dimensions(&mut self, width: u32, height: u32, scale_factor: f64) {
+        self.record(Step::SetViewportDimensions {
+            width,
+            height,
+            scale_factor,
+        });
         self.mutate_with_update_context(|context| {
             let stage = context.stage;
             stage.set_viewport_size(context, width, height, scale_factor);
@@ -800,6 +849,7 @@ impl Player {
     }
 
     pub fn set_quality(&mut self, quality: &str) {
+        self.record(Step::SetQuality(quality.to_string()));
         self.mutate_with_update_context(|context| {
             let stage = context.stage;
             if let Ok(quality) = StageQuality::from_str(quality) {
@@ -809,6 +859,7 @@ impl Player {
     }
 
     pub fn set_scale_mode(&mut self, scale_mode: &str) {
+        self.record(Step::SetScaleMode(scale_mode.to_string()));
         self.mutate_with_update_context(|context| {
             let stage = context.stage;
             if let Ok(scale_mode) = StageScaleMode::from_str(scale_mode) {
@@ -842,6 +893,14 @@ impl Player {
     /// 9. Mouse state is updated. This triggers button rollovers, which are a
     ///    second wave of event processing.
     pub fn handle_event(&mut self, event: PlayerEvent) {
+        if let Some(recorder) = &self.recorder {
+            recorder.record(Step::Event(event.clone()));
+        }
+
+        if self.debugger.is_suspended() {
+            return;
+        }
+
         let prev_is_mouse_down = self.input.is_mouse_down();
         self.input.handle_event(&event);
         let is_mouse_button_changed = self.input.is_mouse_down() != prev_is_mouse_down;
@@ -960,9 +1019,22 @@ impl Player {
 
             // keyPress events take precedence over text input.
             if !key_press_handled {
-                if let PlayerEvent::TextInput { codepoint } = event {
-                    if let Some(text) = context.focus_tracker.get().and_then(|o| o.as_edit_text()) {
-                        text.text_input(codepoint, context);
+                if let Some(text) = context.focus_tracker.get().and_then(|o| o.as_edit_text()) {
+                    // Characters typed while Control is held are shortcuts, not text.
+                    // Control+Alt is left alone, as it acts as AltGr on Windows.
+                    let is_shortcut = context.input.is_key_down(KeyCode::Control)
+                        && !context.input.is_key_down(KeyCode::Alt);
+                    match event {
+                        PlayerEvent::TextInput { codepoint } if !is_shortcut => {
+                            text.text_input(codepoint, context);
+                        }
+                        PlayerEvent::KeyDown { key_code, .. } if is_shortcut => {
+                            if let Some(control_code) = TextControlCode::from_control_key(key_code)
+                            {
+                                text.text_control_input(control_code, context);
+                            }
+                        }
+                        _ => {}
                     }
                 }
             }
@@ -1344,6 +1416,7 @@ impl Player {
     }
 
     pub fn run_frame(&mut self) {
+        self.record(Step::RunFrame);
         self.update(|context| {
             let stage = context.stage;
             match context.swf.avm_type() {
@@ -1382,9 +1455,14 @@ impl Player {
     pub fn render(&mut self) {
         let (renderer, ui, transform_stack) =
             (&mut self.renderer, &mut self.ui, &mut self.transform_stack);
+        let highlighted_display_object = &self.highlighted_display_object;
 
         self.gc_arena.mutate(|_gc_context, gc_root| {
             let root_data = gc_root.0.read();
+            let highlight_bounds = highlighted_display_object
+                .as_ref()
+                .and_then(|path| inspector::find(root_data.stage, path))
+                .map(|display_object| display_object.world_bounds());
             let mut render_context = RenderContext {
                 renderer: renderer.deref_mut(),
                 ui: ui.deref_mut(),
@@ -1393,6 +1471,7 @@ impl Player {
                 stage: root_data.stage,
                 clip_depth_stack: vec![],
                 allow_mask: true,
+                highlight_bounds,
             };
 
             root_data.stage.render(&mut render_context);
@@ -1407,6 +1486,34 @@ impl Player {
         self.current_frame
     }
 
+    /// Take a snapshot of the whole display list, starting from the stage.
+    pub fn inspect_display_list(&mut self) -> DisplayObjectInfo {
+        self.mutate_with_update_context(|context| {
+            let stage = context.stage;
+            inspector::inspect(context, stage.into(), vec![])
+        })
+    }
+
+    /// Find the path of the topmost display object under a point of the
+    /// viewport, in pixels.
+    pub fn display_object_path_at(&mut self, x: f64, y: f64) -> Option<Vec<usize>> {
+        self.mutate_with_update_context(|context| {
+            let point = context.stage.inverse_view_matrix()
+                * (Twips::from_pixels(x), Twips::from_pixels(y));
+            inspector::path_at_point(context.stage, point)
+        })
+    }
+
+    /// Highlight the bounds of a display object when rendering.
+    ///
+    /// The display object is given by its `DisplayObjectInfo::path`.
+    pub fn set_highlighted_display_object(&mut self, path: Option<Vec<usize>>) {
+        if self.highlighted_display_object != path {
+            self.highlighted_display_object = path;
+            self.needs_render = true;
+        }
+    }
+
     pub fn audio(&self) -> &Audio {
         &self.audio
     }
@@ -1452,6 +1559,22 @@ impl Player {
         &self.locale
     }
 
+    pub fn print(&self) -> &Print {
+        &self.print
+    }
+
+    pub fn print_mut(&mut self) -> &mut Print {
+        &mut self.print
+    }
+
+    pub fn font(&self) -> &Font {
+        &self.font
+    }
+
+    pub fn font_mut(&mut self) -> &mut Font {
+        &mut self.font
+    }
+
     pub fn run_actions<'gc>(context: &mut UpdateContext<'_, 'gc, '_>) {
         // Note that actions can queue further actions, so a while loop is necessary here.
         while let Some(actions) = context.action_queue.pop_action() {
@@ -1598,6 +1721,7 @@ impl Player {
                 current_context_menu,
                 external_interface,
                 audio_manager,
+                print_manager,
             ) = root_data.update_context_params();
 
             let mut update_context = UpdateContext {
@@ -1605,7 +1729,7 @@ impl Player {
                 swf: &self.swf,
                 library,
                 rng: &mut self.rng,
-                renderer: self.renderer.deref_mut(),
+                renderer: &mut self.renderer,
                 audio: self.audio.deref_mut(),
                 navigator: self.navigator.deref_mut(),
                 ui: self.ui.deref_mut(),
@@ -1625,6 +1749,9 @@ impl Player {
                 locale: self.locale.deref_mut(),
                 log: self.log.deref_mut(),
                 video: self.video.deref_mut(),
+                print: self.print.deref_mut(),
+                print_manager,
+                font: self.font.deref_mut(),
                 shared_objects,
                 unbound_text_fields,
                 timers,
@@ -1640,6 +1767,7 @@ impl Player {
                 time_offset: &mut self.time_offset,
                 audio_manager,
                 frame_rate: &mut self.frame_rate,
+                debugger: &mut self.debugger,
             };
 
             let old_frame_rate = *update_context.frame_rate;
@@ -1733,6 +1861,7 @@ impl Player {
     /// Update all AVM-based timers (such as created via setInterval).
     /// Returns the approximate amount of time until the next timer tick.
     pub fn update_timers(&mut self, dt: f64) {
+        self.record(Step::UpdateTimers(dt));
         self.time_til_next_timer =
             self.mutate_with_update_context(|context| Timers::update_timers(context, dt));
     }
@@ -1743,6 +1872,34 @@ impl Player {
         self.mutate_with_update_context(|context| context.avm1.has_mouse_listener())
     }
 
+    /// Record this session with `recorder` from now on.
+    ///
+    /// The random number generator is reseeded with the recorder's seed.
+    pub fn set_recorder(&mut self, recorder: Recorder) {
+        self.set_rng_seed(recorder.seed());
+        self.recorder = Some(recorder);
+    }
+
+    fn record(&self, step: Step) {
+        if let Some(recorder) = &self.recorder {
+            recorder.record(step);
+        }
+    }
+
+    /// Attach a debugger client.
+    ///
+    /// While it is attached, ActionScript pauses on breakpoints and steps
+    /// requested by the client.
+    pub fn attach_debugger(&mut self, backend: Box<dyn DebuggerBackend>) {
+        self.debugger = Debugger::new(backend);
+    }
+
+    /// Seed the random number generator used by ActionScript, such as by
+    /// `Math.random`.
+    pub fn set_rng_seed(&mut self, seed: u64) {
+        self.rng = SmallRng::seed_from_u64(seed);
+    }
+
     pub fn add_external_interface(&mut self, provider: Box<dyn ExternalInterfaceProvider>) {
         self.mutate_with_update_context(|context| {
             context.external_interface.add_provider(provider)
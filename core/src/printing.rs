@@ -0,0 +1,201 @@
+//! Management of print jobs started by `PrintJob`
+
+use crate::backend::print::{PrintJobSettings, PrintPage};
+use crate::backend::render::{CommandRecorder, NullRenderer};
+use crate::context::{RenderContext, UpdateContext};
+use crate::prelude::*;
+use crate::transform::{Transform, TransformStack};
+use gc_arena::Collect;
+
+/// A page to add to a print job.
+#[derive(Clone)]
+pub struct PrintPageRequest<'gc> {
+    /// The display object to print.
+    pub target: DisplayObject<'gc>,
+
+    /// The area of the display object to print, in its own coordinate space.
+    pub area: BoundingBox,
+
+    /// Whether to print this page as a bitmap.
+    pub print_as_bitmap: bool,
+}
+
+/// A print job, from the time it is started until it is sent.
+struct PrintJob {
+    id: u32,
+
+    settings: PrintJobSettings,
+
+    /// The number of pages added so far.
+    num_pages: usize,
+}
+
+/// Keeps track of the print jobs started by either VM.
+///
+/// Only one print job may accept pages at a time. Each page is rendered and
+/// handed to the `PrintBackend` as soon as it is added, as its content may
+/// change before the job is sent.
+#[derive(Collect)]
+#[collect(require_static)]
+pub struct PrintManager {
+    /// The print job currently accepting pages, if any.
+    active_job: Option<PrintJob>,
+
+    /// The ID to give to the next print job.
+    next_id: u32,
+}
+
+impl PrintManager {
+    pub fn new() -> Self {
+        Self {
+            active_job: None,
+            next_id: 1,
+        }
+    }
+
+    /// Starts a new print job, returning its ID.
+    ///
+    /// Returns `None` if the print backend refused to start a job, such as
+    /// when the user cancels the print dialog. Any previously started job
+    /// that was never sent is abandoned.
+    pub fn start_job(context: &mut UpdateContext<'_, '_, '_>) -> Option<u32> {
+        let settings = context.print.start_job()?;
+        let print_manager = &mut *context.print_manager;
+
+        if let Some(old_job) = print_manager.active_job.take() {
+            log::warn!(
+                "PrintJob: Abandoning print job {} with {} page(s) that was never sent",
+                old_job.id,
+                old_job.num_pages
+            );
+        }
+
+        let id = print_manager.next_id;
+        print_manager.next_id = print_manager.next_id.wrapping_add(1).max(1);
+        print_manager.active_job = Some(PrintJob {
+            id,
+            settings,
+            num_pages: 0,
+        });
+        Some(id)
+    }
+
+    /// Returns the settings of the given job, if it is still accepting pages.
+    pub fn settings(&self, id: u32) -> Option<&PrintJobSettings> {
+        self.active_job
+            .as_ref()
+            .filter(|job| job.id == id)
+            .map(|job| &job.settings)
+    }
+
+    /// Renders a page as it currently looks and adds it to the given job.
+    ///
+    /// Returns `false` if the job is not accepting pages.
+    pub fn add_page<'gc>(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        id: u32,
+        page: &PrintPageRequest<'gc>,
+    ) -> bool {
+        let settings = match context.print_manager.settings(id) {
+            Some(settings) => settings.clone(),
+            None => return false,
+        };
+
+        let page = render_page(context, page, &settings);
+        if let Err(e) = context.print.add_page(&mut **context.renderer, page) {
+            log::error!("Unable to add page to print job: {}", e);
+        }
+        if let Some(job) = &mut context.print_manager.active_job {
+            job.num_pages += 1;
+        }
+        true
+    }
+
+    /// Sends the given job to the printer.
+    ///
+    /// Returns `false` if the job is not accepting pages.
+    pub fn send(context: &mut UpdateContext<'_, '_, '_>, id: u32) -> bool {
+        match context.print_manager.active_job.take() {
+            Some(job) if job.id == id => {
+                if let Err(e) = context.print.send() {
+                    log::error!("Unable to send print job: {}", e);
+                }
+                true
+            }
+            other => {
+                context.print_manager.active_job = other;
+                false
+            }
+        }
+    }
+}
+
+impl Default for PrintManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Renders a page of a print job into a list of drawing commands.
+///
+/// The player's renderer is temporarily wrapped in a `CommandRecorder`, so
+/// any shapes registered while rendering remain valid on it afterwards.
+fn render_page<'gc>(
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    page: &PrintPageRequest<'gc>,
+    settings: &PrintJobSettings,
+) -> PrintPage {
+    let mut recorder = CommandRecorder::new(std::mem::replace(
+        context.renderer,
+        Box::new(NullRenderer::new()),
+    ));
+    let mut transform_stack = TransformStack::new();
+    let mut render_context = RenderContext {
+        renderer: &mut recorder,
+        ui: context.ui,
+        library: context.library,
+        transform_stack: &mut transform_stack,
+        stage: context.stage,
+        clip_depth_stack: vec![],
+        allow_mask: true,
+        highlight_bounds: None,
+    };
+
+    // Anything outside of the print area is clipped away.
+    let area = &page.area;
+    let clip_matrix = Matrix::create_box(
+        area.width().to_pixels() as f32,
+        area.height().to_pixels() as f32,
+        0.0,
+        Twips::ZERO,
+        Twips::ZERO,
+    );
+    render_context.renderer.push_mask();
+    render_context
+        .renderer
+        .draw_rect(Color::from_rgb(0, 255), &clip_matrix);
+    render_context.renderer.activate_mask();
+
+    render_context.transform_stack.push(&Transform {
+        matrix: Matrix::translate(Twips::ZERO - area.x_min, Twips::ZERO - area.y_min),
+        ..Default::default()
+    });
+    page.target.render_self(&mut render_context);
+    render_context.transform_stack.pop();
+
+    render_context.renderer.deactivate_mask();
+    render_context
+        .renderer
+        .draw_rect(Color::from_rgb(0, 255), &clip_matrix);
+    render_context.renderer.pop_mask();
+
+    let (inner, commands) = recorder.finish();
+    *context.renderer = inner;
+
+    PrintPage {
+        width: settings.page_width,
+        height: settings.page_height,
+        commands,
+        print_as_bitmap: page.print_as_bitmap,
+    }
+}
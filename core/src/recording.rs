@@ -0,0 +1,754 @@
+//! Session recording and replay
+//!
+//! A recording holds everything that a player session takes from outside of
+//! the movie: input events, the frames and timer updates run by each tick, the
+//! random seed, clock readings, fetch responses and stored shared objects.
+//! Replaying it drives a new player with the same movie through exactly the
+//! same steps, so that a session can be reproduced in a desktop window or
+//! headlessly.
+
+use crate::backend::locale::LocaleBackend;
+use crate::backend::navigator::{NavigationMethod, NavigatorBackend, OwnedFuture, RequestOptions};
+use crate::backend::storage::StorageBackend;
+use crate::config::Letterbox;
+use crate::events::PlayerEvent;
+use crate::loader::Error;
+use crate::Player;
+use chrono::{DateTime, FixedOffset, TimeZone, Utc};
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, VecDeque};
+use std::fs::File;
+use std::future::Future;
+use std::io::{BufReader, BufWriter};
+use std::path::Path;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+use std::time::Duration;
+use url::Url;
+
+/// The version of the recording format, increased on incompatible changes.
+const RECORDING_VERSION: u32 = 1;
+
+/// A recorded player session.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Recording {
+    version: u32,
+
+    /// The URL of the recorded movie, for reference.
+    pub movie_url: Option<String>,
+
+    /// The movie's parameters ("flashvars").
+    pub parameters: Vec<(String, String)>,
+
+    /// The seed of the player's random number generator.
+    pub seed: u64,
+
+    /// The local timezone, in seconds east of UTC.
+    pub timezone: i32,
+
+    /// The steps that drove the player, in order.
+    pub steps: Vec<Step>,
+
+    /// The dates returned by `LocaleBackend::get_current_date_time`, in
+    /// milliseconds since the Unix epoch.
+    pub date_times: Vec<i64>,
+
+    /// The times returned by `NavigatorBackend::time_since_launch`.
+    pub times_since_launch: Vec<Duration>,
+
+    /// The values returned by `StorageBackend::get`.
+    #[serde(default)]
+    pub storage_reads: Vec<Option<Vec<u8>>>,
+}
+
+impl Recording {
+    fn new(movie_url: Option<String>, parameters: Vec<(String, String)>, seed: u64) -> Self {
+        Self {
+            version: RECORDING_VERSION,
+            movie_url,
+            parameters,
+            seed,
+            timezone: 0,
+            steps: Vec::new(),
+            date_times: Vec::new(),
+            times_since_launch: Vec::new(),
+            storage_reads: Vec::new(),
+        }
+    }
+
+    /// Load a recording saved by `Recorder::save`.
+    pub fn load(path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let recording: Self = serde_json::from_reader(BufReader::new(File::open(path)?))?;
+        if recording.version != RECORDING_VERSION {
+            return Err(format!(
+                "Unsupported recording version {} (expected {})",
+                recording.version, RECORDING_VERSION
+            )
+            .into());
+        }
+        Ok(recording)
+    }
+}
+
+/// Something that changed the player's state during a recorded session.
+#[derive(Clone, Serialize, Deserialize)]
+pub enum Step {
+    /// The start of a `Player::tick` of the given number of milliseconds.
+    ///
+    /// The frames and timer updates that the tick ran follow as their own
+    /// steps, so this is only used to pace replays.
+    Tick(f64),
+    Event(PlayerEvent),
+    RunFrame,
+    UpdateTimers(f64),
+    SetViewportDimensions {
+        width: u32,
+        height: u32,
+        scale_factor: f64,
+    },
+    SetScaleMode(String),
+    SetQuality(String),
+    SetLetterbox(Letterbox),
+
+    /// The completion of the `id`th fetch of the session.
+    FetchComplete {
+        id: usize,
+        url: String,
+        response: Result<Vec<u8>, String>,
+    },
+}
+
+/// Records a player session.
+///
+/// The navigator, locale and storage backends given to the player must be
+/// wrapped with `Recorder::navigator`, `Recorder::locale` and
+/// `Recorder::storage`, and the recorder given to `Player::set_recorder`
+/// before the movie starts.
+#[derive(Clone)]
+pub struct Recorder(Rc<RefCell<Recording>>);
+
+impl Recorder {
+    pub fn new(movie_url: Option<String>, parameters: Vec<(String, String)>) -> Self {
+        let seed = Utc::now().timestamp_millis() as u64;
+        Self(Rc::new(RefCell::new(Recording::new(
+            movie_url, parameters, seed,
+        ))))
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.0.borrow().seed
+    }
+
+    /// A copy of the session recorded so far.
+    pub fn snapshot(&self) -> Recording {
+        self.0.borrow().clone()
+    }
+
+    pub fn record(&self, step: Step) {
+        self.0.borrow_mut().steps.push(step);
+    }
+
+    /// Wrap a navigator backend to record its clock readings and fetches.
+    pub fn navigator(&self, navigator: Box<dyn NavigatorBackend>) -> Box<dyn NavigatorBackend> {
+        Box::new(RecordingNavigatorBackend {
+            inner: navigator,
+            recorder: self.clone(),
+            next_fetch_id: Cell::new(0),
+        })
+    }
+
+    /// Wrap a locale backend to record its clock readings and timezone.
+    pub fn locale(&self, locale: Box<dyn LocaleBackend>) -> Box<dyn LocaleBackend> {
+        self.0.borrow_mut().timezone = locale.get_timezone().local_minus_utc();
+        Box::new(RecordingLocaleBackend {
+            inner: locale,
+            recorder: self.clone(),
+        })
+    }
+
+    /// Wrap a storage backend to record what is read from it.
+    pub fn storage(&self, storage: Box<dyn StorageBackend>) -> Box<dyn StorageBackend> {
+        Box::new(RecordingStorageBackend {
+            inner: storage,
+            recorder: self.clone(),
+        })
+    }
+
+    /// Save the session recorded so far.
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        serde_json::to_writer(BufWriter::new(File::create(path)?), &*self.0.borrow())?;
+        Ok(())
+    }
+}
+
+struct RecordingNavigatorBackend {
+    inner: Box<dyn NavigatorBackend>,
+    recorder: Recorder,
+    next_fetch_id: Cell<usize>,
+}
+
+impl NavigatorBackend for RecordingNavigatorBackend {
+    fn navigate_to_url(
+        &self,
+        url: String,
+        window: Option<String>,
+        vars_method: Option<(NavigationMethod, IndexMap<String, String>)>,
+    ) {
+        self.inner.navigate_to_url(url, window, vars_method)
+    }
+
+    fn fetch(&self, url: &str, request_options: RequestOptions) -> OwnedFuture<Vec<u8>, Error> {
+        let id = self.next_fetch_id.get();
+        self.next_fetch_id.set(id + 1);
+        let fetch = self.inner.fetch(url, request_options);
+        let recorder = self.recorder.clone();
+        let url = url.to_string();
+        Box::pin(async move {
+            let result = fetch.await;
+            recorder.record(Step::FetchComplete {
+                id,
+                url,
+                response: match &result {
+                    Ok(data) => Ok(data.clone()),
+                    Err(e) => Err(e.to_string()),
+                },
+            });
+            result
+        })
+    }
+
+    fn time_since_launch(&mut self) -> Duration {
+        let time = self.inner.time_since_launch();
+        self.recorder.0.borrow_mut().times_since_launch.push(time);
+        time
+    }
+
+    fn spawn_future(&mut self, future: OwnedFuture<(), Error>) {
+        self.inner.spawn_future(future)
+    }
+
+    fn resolve_relative_url<'a>(&self, url: &'a str) -> Cow<'a, str> {
+        self.inner.resolve_relative_url(url)
+    }
+
+    fn pre_process_url(&self, url: Url) -> Url {
+        self.inner.pre_process_url(url)
+    }
+}
+
+struct RecordingLocaleBackend {
+    inner: Box<dyn LocaleBackend>,
+    recorder: Recorder,
+}
+
+impl LocaleBackend for RecordingLocaleBackend {
+    fn get_current_date_time(&self) -> DateTime<Utc> {
+        let date_time = self.inner.get_current_date_time();
+        self.recorder
+            .0
+            .borrow_mut()
+            .date_times
+            .push(date_time.timestamp_millis());
+        date_time
+    }
+
+    fn get_timezone(&self) -> FixedOffset {
+        self.inner.get_timezone()
+    }
+}
+
+struct RecordingStorageBackend {
+    inner: Box<dyn StorageBackend>,
+    recorder: Recorder,
+}
+
+impl StorageBackend for RecordingStorageBackend {
+    fn get(&self, name: &str) -> Option<Vec<u8>> {
+        let value = self.inner.get(name);
+        self.recorder
+            .0
+            .borrow_mut()
+            .storage_reads
+            .push(value.clone());
+        value
+    }
+
+    fn put(&mut self, name: &str, value: &[u8]) -> bool {
+        self.inner.put(name, value)
+    }
+
+    fn remove_key(&mut self, name: &str) {
+        self.inner.remove_key(name)
+    }
+}
+
+/// Replays a recorded session.
+///
+/// The player must be created with the navigator, locale and storage
+/// backends from `Replayer::navigator`, `Replayer::locale` and
+/// `Replayer::storage`, seeded with `Replayer::seed`,
+/// and given the recorded movie. It is then driven only by the replayer.
+///
+/// Fetches complete at the same step as they did when recording. The
+/// replayer calls `poll` after each, while the player is unlocked, so that
+/// the executor can run the futures waiting on them.
+pub struct Replayer {
+    steps: VecDeque<Step>,
+    seed: u64,
+    parameters: Vec<(String, String)>,
+    timezone: i32,
+    date_times: Vec<i64>,
+    times_since_launch: Vec<Duration>,
+    storage_reads: Vec<Option<Vec<u8>>>,
+    fetches: Rc<RefCell<ReplayFetches>>,
+
+    /// The number of frames replayed so far.
+    frame: u32,
+
+    /// Milliseconds of wall-clock time not yet spent on recorded ticks.
+    time_budget: f64,
+
+    /// Whether recorded viewport changes are skipped.
+    ignore_viewport: bool,
+}
+
+impl Replayer {
+    pub fn new(recording: Recording) -> Self {
+        Self {
+            steps: recording.steps.into(),
+            seed: recording.seed,
+            parameters: recording.parameters,
+            timezone: recording.timezone,
+            date_times: recording.date_times,
+            times_since_launch: recording.times_since_launch,
+            storage_reads: recording.storage_reads,
+            fetches: Default::default(),
+            frame: 0,
+            time_budget: 0.0,
+            ignore_viewport: false,
+        }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// The recorded movie's parameters ("flashvars").
+    pub fn parameters(&self) -> &[(String, String)] {
+        &self.parameters
+    }
+
+    /// Wrap a navigator backend to answer clock readings and fetches from the
+    /// recording. Everything else is left to `navigator`.
+    pub fn navigator(&mut self, navigator: Box<dyn NavigatorBackend>) -> Box<dyn NavigatorBackend> {
+        Box::new(ReplayNavigatorBackend {
+            inner: navigator,
+            times_since_launch: ReplayQueue::new(std::mem::take(&mut self.times_since_launch)),
+            fetches: self.fetches.clone(),
+        })
+    }
+
+    /// A locale backend that answers from the recording.
+    pub fn locale(&mut self) -> Box<dyn LocaleBackend> {
+        Box::new(ReplayLocaleBackend {
+            date_times: RefCell::new(ReplayQueue::new(std::mem::take(&mut self.date_times))),
+            timezone: FixedOffset::east(self.timezone),
+        })
+    }
+
+    /// A storage backend that answers reads from the recording. Writes are
+    /// dropped, so that replays leave the stored shared objects untouched.
+    pub fn storage(&mut self) -> Box<dyn StorageBackend> {
+        Box::new(ReplayStorageBackend {
+            reads: RefCell::new(ReplayQueue::new(std::mem::take(&mut self.storage_reads))),
+        })
+    }
+
+    /// Skip the recorded viewport changes, for replaying into a viewport of
+    /// fixed size. The movie may behave differently if it depends on the
+    /// stage size.
+    pub fn ignore_viewport_changes(&mut self) {
+        self.ignore_viewport = true;
+    }
+
+    /// The number of frames replayed so far.
+    pub fn frame(&self) -> u32 {
+        self.frame
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// Replay the next step. Returns `false` if there are none left.
+    pub fn step(&mut self, player: &Arc<Mutex<Player>>, poll: &mut dyn FnMut()) -> bool {
+        let step = match self.steps.pop_front() {
+            Some(step) => step,
+            None => return false,
+        };
+
+        match step {
+            Step::Tick(_) => {}
+            Step::Event(event) => player.lock().unwrap().handle_event(event),
+            Step::RunFrame => {
+                player.lock().unwrap().run_frame();
+                self.frame += 1;
+            }
+            Step::UpdateTimers(dt) => player.lock().unwrap().update_timers(dt),
+            Step::SetViewportDimensions { .. } if self.ignore_viewport => {}
+            Step::SetViewportDimensions {
+                width,
+                height,
+                scale_factor,
+            } => player
+                .lock()
+                .unwrap()
+                .set_viewport_dimensions(width, height, scale_factor),
+            Step::SetScaleMode(scale_mode) => player.lock().unwrap().set_scale_mode(&scale_mode),
+            Step::SetQuality(quality) => player.lock().unwrap().set_quality(&quality),
+            Step::SetLetterbox(letterbox) => player.lock().unwrap().set_letterbox(letterbox),
+            Step::FetchComplete { id, response, .. } => {
+                self.fetches.borrow_mut().complete(id, response);
+                poll();
+            }
+        }
+
+        true
+    }
+
+    /// Replay until `frame` frames have run, or the recording ends.
+    ///
+    /// Seeking backwards needs a new player and replayer.
+    pub fn seek(&mut self, player: &Arc<Mutex<Player>>, frame: u32, poll: &mut dyn FnMut()) {
+        while self.frame < frame && self.step(player, poll) {}
+    }
+
+    /// Replay the recorded ticks that fit into `dt` more milliseconds, for
+    /// playing the recording back in real time.
+    pub fn advance(&mut self, player: &Arc<Mutex<Player>>, dt: f64, poll: &mut dyn FnMut()) {
+        self.time_budget += dt;
+        while let Some(step) = self.steps.front() {
+            if let Step::Tick(tick_dt) = step {
+                if *tick_dt > self.time_budget {
+                    break;
+                }
+                self.time_budget -= tick_dt;
+            }
+            self.step(player, poll);
+        }
+    }
+}
+
+/// Recorded values that are handed out in order.
+///
+/// If the replay asks for more values than were recorded, it has diverged
+/// from the recording, and the last value is repeated.
+struct ReplayQueue<T> {
+    values: VecDeque<T>,
+    last: Option<T>,
+}
+
+impl<T: Clone> ReplayQueue<T> {
+    fn new(values: Vec<T>) -> Self {
+        Self {
+            values: values.into(),
+            last: None,
+        }
+    }
+
+    fn next(&mut self) -> Option<T> {
+        match self.values.pop_front() {
+            Some(value) => {
+                self.last = Some(value.clone());
+                Some(value)
+            }
+            None => {
+                log::warn!("Replay asked for more values than were recorded");
+                self.last.clone()
+            }
+        }
+    }
+}
+
+/// The responses of fetches that have completed, and the wakers of those
+/// still waiting.
+#[derive(Default)]
+struct ReplayFetches {
+    next_id: usize,
+    responses: HashMap<usize, Result<Vec<u8>, String>>,
+    wakers: HashMap<usize, Waker>,
+}
+
+impl ReplayFetches {
+    fn complete(&mut self, id: usize, response: Result<Vec<u8>, String>) {
+        self.responses.insert(id, response);
+        if let Some(waker) = self.wakers.remove(&id) {
+            waker.wake();
+        }
+    }
+}
+
+struct ReplayFetch {
+    id: usize,
+    url: String,
+    fetches: Rc<RefCell<ReplayFetches>>,
+}
+
+impl Future for ReplayFetch {
+    type Output = Result<Vec<u8>, Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut fetches = self.fetches.borrow_mut();
+        match fetches.responses.remove(&self.id) {
+            Some(response) => {
+                Poll::Ready(response.map_err(|_| Error::FetchError(self.url.clone())))
+            }
+            None => {
+                fetches.wakers.insert(self.id, cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+struct ReplayNavigatorBackend {
+    inner: Box<dyn NavigatorBackend>,
+    times_since_launch: ReplayQueue<Duration>,
+    fetches: Rc<RefCell<ReplayFetches>>,
+}
+
+impl NavigatorBackend for ReplayNavigatorBackend {
+    fn navigate_to_url(
+        &self,
+        url: String,
+        window: Option<String>,
+        vars_method: Option<(NavigationMethod, IndexMap<String, String>)>,
+    ) {
+        self.inner.navigate_to_url(url, window, vars_method)
+    }
+
+    fn fetch(&self, url: &str, _request_options: RequestOptions) -> OwnedFuture<Vec<u8>, Error> {
+        let id = {
+            let mut fetches = self.fetches.borrow_mut();
+            fetches.next_id += 1;
+            fetches.next_id - 1
+        };
+        Box::pin(ReplayFetch {
+            id,
+            url: url.to_string(),
+            fetches: self.fetches.clone(),
+        })
+    }
+
+    fn time_since_launch(&mut self) -> Duration {
+        self.times_since_launch.next().unwrap_or_default()
+    }
+
+    fn spawn_future(&mut self, future: OwnedFuture<(), Error>) {
+        self.inner.spawn_future(future)
+    }
+
+    fn resolve_relative_url<'a>(&self, url: &'a str) -> Cow<'a, str> {
+        self.inner.resolve_relative_url(url)
+    }
+
+    fn pre_process_url(&self, url: Url) -> Url {
+        self.inner.pre_process_url(url)
+    }
+}
+
+struct ReplayLocaleBackend {
+    date_times: RefCell<ReplayQueue<i64>>,
+    timezone: FixedOffset,
+}
+
+impl LocaleBackend for ReplayLocaleBackend {
+    fn get_current_date_time(&self) -> DateTime<Utc> {
+        Utc.timestamp_millis(self.date_times.borrow_mut().next().unwrap_or_default())
+    }
+
+    fn get_timezone(&self) -> FixedOffset {
+        self.timezone
+    }
+}
+
+struct ReplayStorageBackend {
+    reads: RefCell<ReplayQueue<Option<Vec<u8>>>>,
+}
+
+impl StorageBackend for ReplayStorageBackend {
+    fn get(&self, _name: &str) -> Option<Vec<u8>> {
+        self.reads.borrow_mut().next().flatten()
+    }
+
+    fn put(&mut self, _name: &str, _value: &[u8]) -> bool {
+        true
+    }
+
+    fn remove_key(&mut self, _name: &str) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::audio::NullAudioBackend;
+    use crate::backend::font::NullFontBackend;
+    use crate::backend::locale::NullLocaleBackend;
+    use crate::backend::log::LogBackend;
+    use crate::backend::navigator::NullNavigatorBackend;
+    use crate::backend::print::NullPrintBackend;
+    use crate::backend::render::NullRenderer;
+    use crate::backend::storage::MemoryStorageBackend;
+    use crate::backend::ui::NullUiBackend;
+    use crate::backend::video::NullVideoBackend;
+    use crate::tag_utils::SwfMovie;
+    use swf::Tag;
+
+    /// A movie that traces `random(1000000)` and `getTimer()` on each of its
+    /// two frames.
+    fn movie() -> Arc<SwfMovie> {
+        // Push 1000000, RandomNumber, Trace, GetTime, Trace
+        let actions = [
+            0x96, 0x05, 0x00, 0x07, 0x40, 0x42, 0x0f, 0x00, 0x30, 0x26, 0x34, 0x26, 0x00,
+        ];
+        let header = swf::Header {
+            num_frames: 2,
+            ..swf::Header::default_with_swf_version(8)
+        };
+        let tags = [
+            Tag::DoAction(&actions),
+            Tag::ShowFrame,
+            Tag::DoAction(&actions),
+            Tag::ShowFrame,
+        ];
+        let mut data = vec![];
+        swf::write_swf(&header, &tags, &mut data).unwrap();
+        Arc::new(SwfMovie::from_data(&data, None, None).unwrap())
+    }
+
+    struct TestLogBackend(Rc<RefCell<Vec<String>>>);
+
+    impl LogBackend for TestLogBackend {
+        fn avm_trace(&self, message: &str) {
+            self.0.borrow_mut().push(message.to_string());
+        }
+    }
+
+    /// A navigator whose clock advances by 10 milliseconds each time it is read.
+    #[derive(Default)]
+    struct TickingNavigatorBackend {
+        inner: NullNavigatorBackend,
+        time: Duration,
+    }
+
+    impl NavigatorBackend for TickingNavigatorBackend {
+        fn navigate_to_url(
+            &self,
+            url: String,
+            window: Option<String>,
+            vars_method: Option<(NavigationMethod, IndexMap<String, String>)>,
+        ) {
+            self.inner.navigate_to_url(url, window, vars_method)
+        }
+
+        fn fetch(&self, url: &str, options: RequestOptions) -> OwnedFuture<Vec<u8>, Error> {
+            self.inner.fetch(url, options)
+        }
+
+        fn time_since_launch(&mut self) -> Duration {
+            self.time += Duration::from_millis(10);
+            self.time
+        }
+
+        fn spawn_future(&mut self, future: OwnedFuture<(), Error>) {
+            self.inner.spawn_future(future)
+        }
+
+        fn resolve_relative_url<'a>(&self, url: &'a str) -> Cow<'a, str> {
+            self.inner.resolve_relative_url(url)
+        }
+
+        fn pre_process_url(&self, url: Url) -> Url {
+            self.inner.pre_process_url(url)
+        }
+    }
+
+    /// A player seeded with `seed`, and the trace output that it logs.
+    fn player(
+        navigator: Box<dyn NavigatorBackend>,
+        locale: Box<dyn LocaleBackend>,
+        storage: Box<dyn StorageBackend>,
+        seed: u64,
+    ) -> (Arc<Mutex<Player>>, Rc<RefCell<Vec<String>>>) {
+        let trace = Rc::new(RefCell::new(Vec::new()));
+        let player = Player::new(
+            Box::new(NullRenderer::new()),
+            Box::new(NullAudioBackend::new()),
+            navigator,
+            storage,
+            locale,
+            Box::new(NullVideoBackend::new()),
+            Box::new(TestLogBackend(trace.clone())),
+            Box::new(NullUiBackend::new()),
+            Box::new(NullPrintBackend::new()),
+            Box::new(NullFontBackend::new()),
+        )
+        .unwrap();
+        player.lock().unwrap().set_rng_seed(seed);
+        (player, trace)
+    }
+
+    /// Record a session of `frames` frames of `movie()`, seeded with `seed`.
+    fn record(seed: u64, frames: u32) -> (Recorder, Vec<String>) {
+        let recorder = Recorder(Rc::new(RefCell::new(Recording::new(None, vec![], seed))));
+        let (player, trace) = player(
+            recorder.navigator(Box::new(TickingNavigatorBackend::default())),
+            recorder.locale(Box::new(NullLocaleBackend::new())),
+            recorder.storage(Box::new(MemoryStorageBackend::default())),
+            recorder.seed(),
+        );
+        let mut player = player.lock().unwrap();
+        player.set_recorder(recorder.clone());
+        player.set_root_movie(movie());
+        for _ in 0..frames {
+            player.run_frame();
+            player.update_timers(50.0);
+        }
+        drop(player);
+        let trace = trace.borrow().clone();
+        (recorder, trace)
+    }
+
+    #[test]
+    fn replay_matches_recording() {
+        let (recorder, recorded_trace) = record(1234, 4);
+        assert_eq!(recorded_trace.len(), 8);
+
+        // The replay's own clock never advances, so the times traced can only
+        // come from the recording.
+        let mut replayer = Replayer::new(recorder.snapshot());
+        let (player, replayed_trace) = player(
+            replayer.navigator(Box::new(NullNavigatorBackend::new())),
+            replayer.locale(),
+            replayer.storage(),
+            replayer.seed(),
+        );
+        player.lock().unwrap().set_root_movie(movie());
+        while replayer.step(&player, &mut || {}) {}
+
+        assert_eq!(replayer.frame(), 4);
+        assert_eq!(*replayed_trace.borrow(), recorded_trace);
+    }
+
+    #[test]
+    fn fixed_seed_is_deterministic() {
+        let (_, first) = record(1234, 4);
+        let (_, second) = record(1234, 4);
+        assert_eq!(first, second);
+    }
+}
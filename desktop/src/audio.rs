@@ -16,7 +16,9 @@ pub struct CpalAudioBackend {
 type Error = Box<dyn std::error::Error>;
 
 impl CpalAudioBackend {
-    pub fn new() -> Result<Self, Error> {
+    /// Create a backend that plays on the default output device, at the given
+    /// volume from 0 to 1.
+    pub fn new(volume: f32) -> Result<Self, Error> {
         // Create CPAL audio device.
         let host = cpal::default_host();
         let device = host
@@ -37,17 +39,26 @@ impl CpalAudioBackend {
             match sample_format {
                 cpal::SampleFormat::F32 => device.build_output_stream(
                     &config,
-                    move |buffer, _| mixer.mix::<f32>(buffer),
+                    move |buffer, _| {
+                        mixer.mix::<f32>(buffer);
+                        apply_volume(buffer, volume);
+                    },
                     error_handler,
                 ),
                 cpal::SampleFormat::I16 => device.build_output_stream(
                     &config,
-                    move |buffer, _| mixer.mix::<i16>(buffer),
+                    move |buffer, _| {
+                        mixer.mix::<i16>(buffer);
+                        apply_volume(buffer, volume);
+                    },
                     error_handler,
                 ),
                 cpal::SampleFormat::U16 => device.build_output_stream(
                     &config,
-                    move |buffer, _| mixer.mix::<u16>(buffer),
+                    move |buffer, _| {
+                        mixer.mix::<u16>(buffer);
+                        apply_volume(buffer, volume);
+                    },
                     error_handler,
                 ),
             }?
@@ -64,6 +75,15 @@ impl CpalAudioBackend {
     }
 }
 
+/// Scale the mixed samples in `buffer` by `volume`.
+fn apply_volume<T: cpal::Sample>(buffer: &mut [T], volume: f32) {
+    if volume != 1.0 {
+        for sample in buffer {
+            *sample = T::from(&(sample.to_f32() * volume));
+        }
+    }
+}
+
 impl AudioBackend for CpalAudioBackend {
     impl_audio_mixer_backend!(mixer);
 
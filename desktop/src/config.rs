@@ -0,0 +1,291 @@
+//! Settings read from the configuration file.
+//!
+//! The file is `config.toml` in Ruffle's data directory, next to the stored
+//! shared objects, unless another is given with `--config`. Options given on
+//! the command line take precedence over it.
+//!
+//! ```toml
+//! graphics = "vulkan"
+//! upgrade_to_https = true
+//!
+//! # Settings for every movie.
+//! [defaults]
+//! quality = "medium"
+//! volume = 0.5
+//!
+//! # Settings for one movie, keyed by its URL or by the SHA-256 hash of the
+//! # SWF file, in hex.
+//! [movies."https://example.com/game.swf"]
+//! scale_mode = "noScale"
+//! parameters = { level = "3" }
+//! key_map = { W = "Up", A = "Left", S = "Down", D = "Right" }
+//! ```
+
+use clap::ArgEnum;
+use ruffle_core::config::Letterbox;
+use ruffle_core::events::KeyCode;
+use ruffle_render_wgpu::clap::{GraphicsBackend, PowerPreference};
+use serde::{Deserialize, Deserializer};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use url::Url;
+
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    #[serde(deserialize_with = "deserialize_arg_enum")]
+    pub graphics: Option<GraphicsBackend>,
+
+    #[serde(deserialize_with = "deserialize_arg_enum")]
+    pub power: Option<PowerPreference>,
+
+    /// Proxy to use when loading movies via URL.
+    #[serde(deserialize_with = "deserialize_url")]
+    pub proxy: Option<Url>,
+
+    /// Replace all embedded HTTP URLs with HTTPS.
+    pub upgrade_to_https: bool,
+
+    defaults: MovieSettings,
+
+    /// Settings for single movies, keyed by URL or by the hex SHA-256 hash of
+    /// the SWF file. These take precedence over the defaults.
+    movies: HashMap<String, MovieSettings>,
+}
+
+impl Config {
+    /// Read the configuration file at `path`, or in Ruffle's data directory.
+    ///
+    /// A missing file gives the default settings.
+    pub fn read(path: Option<&Path>) -> Result<Self, Box<dyn std::error::Error>> {
+        let path = match path {
+            Some(path) => path.to_owned(),
+            None => match dirs::data_local_dir() {
+                Some(dir) => dir.join("ruffle").join("config.toml"),
+                None => return Ok(Self::default()),
+            },
+        };
+
+        match std::fs::read_to_string(&path) {
+            Ok(config) => toml::from_str(&config)
+                .map_err(|e| format!("Invalid config file {}: {}", path.display(), e).into()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// The settings for the movie at `url`, whose SWF file has the given hash.
+    pub fn movie_settings(&self, url: &Url, hash: &str) -> MovieSettings {
+        match self
+            .movies
+            .get(url.as_str())
+            .or_else(|| self.movies.get(hash))
+        {
+            Some(overrides) => self.defaults.merge(overrides),
+            None => self.defaults.clone(),
+        }
+    }
+}
+
+/// Settings that can be set for every movie, or for a single one.
+#[derive(Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MovieSettings {
+    /// Width of window in pixels.
+    pub width: Option<f64>,
+
+    /// Height of window in pixels.
+    pub height: Option<f64>,
+
+    /// The stage scale mode, as given to `Stage.scaleMode`.
+    pub scale_mode: Option<String>,
+
+    /// The stage quality, as given to `Stage.quality`.
+    pub quality: Option<String>,
+
+    pub letterbox: Option<Letterbox>,
+
+    /// "flashvars" parameters to provide to the movie, before those given
+    /// with `-P`.
+    pub parameters: BTreeMap<String, String>,
+
+    /// Volume of the movie's audio, from 0 to 1.
+    pub volume: Option<f32>,
+
+    /// How long in seconds ActionScript may run before timing out.
+    pub max_execution_duration: Option<f64>,
+
+    /// Keys that are sent to the movie in place of those pressed.
+    pub key_map: HashMap<KeyCode, KeyCode>,
+
+    /// The URL that the movie's relative URLs are resolved against, in place
+    /// of its own.
+    #[serde(deserialize_with = "deserialize_url")]
+    pub base_url: Option<Url>,
+}
+
+impl MovieSettings {
+    /// These settings, with any set in `overrides` taking precedence.
+    fn merge(&self, overrides: &Self) -> Self {
+        let mut parameters = self.parameters.clone();
+        parameters.extend(overrides.parameters.clone());
+        let mut key_map = self.key_map.clone();
+        key_map.extend(overrides.key_map.clone());
+
+        Self {
+            width: overrides.width.or(self.width),
+            height: overrides.height.or(self.height),
+            scale_mode: overrides
+                .scale_mode
+                .clone()
+                .or_else(|| self.scale_mode.clone()),
+            quality: overrides.quality.clone().or_else(|| self.quality.clone()),
+            letterbox: overrides.letterbox.or(self.letterbox),
+            parameters,
+            volume: overrides.volume.or(self.volume),
+            max_execution_duration: overrides
+                .max_execution_duration
+                .or(self.max_execution_duration),
+            key_map,
+            base_url: overrides.base_url.clone().or_else(|| self.base_url.clone()),
+        }
+    }
+}
+
+/// Deserialize one of the values of a command line option.
+fn deserialize_arg_enum<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: ArgEnum,
+{
+    let value = String::deserialize(deserializer)?;
+    T::from_str(&value, true)
+        .map(Some)
+        .map_err(serde::de::Error::custom)
+}
+
+fn deserialize_url<'de, D>(deserializer: D) -> Result<Option<Url>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = String::deserialize(deserializer)?;
+    Url::parse(&value)
+        .map(Some)
+        .map_err(serde::de::Error::custom)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(toml: &str) -> Config {
+        toml::from_str(toml).unwrap()
+    }
+
+    fn url(url: &str) -> Url {
+        Url::parse(url).unwrap()
+    }
+
+    #[test]
+    fn movie_settings_default_when_unmatched() {
+        let config = config(
+            r#"
+            [defaults]
+            quality = "medium"
+
+            [movies."https://example.com/game.swf"]
+            quality = "low"
+            "#,
+        );
+
+        let settings = config.movie_settings(&url("https://example.com/other.swf"), "abc");
+        assert_eq!(settings.quality.as_deref(), Some("medium"));
+    }
+
+    #[test]
+    fn movie_settings_by_url_or_hash() {
+        let config = config(
+            r#"
+            [movies."https://example.com/game.swf"]
+            quality = "low"
+
+            [movies.abc]
+            quality = "best"
+            "#,
+        );
+
+        let by_url = config.movie_settings(&url("https://example.com/game.swf"), "def");
+        assert_eq!(by_url.quality.as_deref(), Some("low"));
+        let by_hash = config.movie_settings(&url("https://example.com/other.swf"), "abc");
+        assert_eq!(by_hash.quality.as_deref(), Some("best"));
+
+        // The URL is looked up first.
+        let both = config.movie_settings(&url("https://example.com/game.swf"), "abc");
+        assert_eq!(both.quality.as_deref(), Some("low"));
+    }
+
+    #[test]
+    fn movie_settings_take_precedence_over_defaults() {
+        let config = config(
+            r#"
+            [defaults]
+            scale_mode = "showAll"
+            quality = "medium"
+            volume = 0.5
+            parameters = { level = "1", lang = "en" }
+            key_map = { W = "Up", A = "Left" }
+
+            [movies."https://example.com/game.swf"]
+            scale_mode = "noScale"
+            parameters = { level = "3" }
+            key_map = { A = "Right" }
+            base_url = "https://example.com/assets/"
+            "#,
+        );
+
+        let settings = config.movie_settings(&url("https://example.com/game.swf"), "abc");
+        assert_eq!(settings.scale_mode.as_deref(), Some("noScale"));
+        assert_eq!(settings.base_url, Some(url("https://example.com/assets/")));
+
+        // Unset settings fall back to the defaults.
+        assert_eq!(settings.quality.as_deref(), Some("medium"));
+        assert_eq!(settings.volume, Some(0.5));
+        assert_eq!(settings.width, None);
+
+        // Maps are merged, with the movie's entries replacing the defaults'.
+        assert_eq!(
+            settings.parameters,
+            BTreeMap::from([
+                ("lang".to_string(), "en".to_string()),
+                ("level".to_string(), "3".to_string()),
+            ])
+        );
+        assert_eq!(
+            settings.key_map,
+            HashMap::from([(KeyCode::W, KeyCode::Up), (KeyCode::A, KeyCode::Right)])
+        );
+    }
+
+    #[test]
+    fn command_line_values_are_parsed() {
+        let config = config(
+            r#"
+            graphics = "vulkan"
+            power = "low"
+            proxy = "http://localhost:8080"
+            upgrade_to_https = true
+            "#,
+        );
+
+        assert_eq!(config.graphics, Some(GraphicsBackend::Vulkan));
+        assert_eq!(config.power, Some(PowerPreference::Low));
+        assert_eq!(config.proxy, Some(url("http://localhost:8080")));
+        assert!(config.upgrade_to_https);
+    }
+
+    #[test]
+    fn unknown_settings_are_rejected() {
+        assert!(toml::from_str::<Config>("colour = \"red\"").is_err());
+        assert!(toml::from_str::<Config>("[defaults]\nspeed = 2").is_err());
+    }
+}
@@ -0,0 +1,320 @@
+//! Debug Adapter Protocol server for the ActionScript debugger.
+//!
+//! A single client connects over TCP. Requests are read on a background
+//! thread: those that only inspect a paused player are answered there, and
+//! everything else is forwarded to the player as a `DebugCommand`.
+
+use ruffle_core::debugger::{DebugCommand, DebuggerBackend, PauseReason, PausedState, Variable};
+use serde_json::{json, Value};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::PathBuf;
+use std::sync::mpsc::{channel, Receiver, Sender, TryRecvError};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+/// The only thread reported to the client.
+const THREAD_ID: u64 = 1;
+
+/// Variables reference of the `this` and local register scope.
+const LOCALS_REFERENCE: u64 = 1;
+
+/// Writes framed messages to the client.
+struct Connection {
+    stream: TcpStream,
+    seq: u64,
+}
+
+impl Connection {
+    fn send(&mut self, mut message: Value) -> io::Result<()> {
+        self.seq += 1;
+        message["seq"] = self.seq.into();
+        let body = message.to_string();
+        write!(
+            self.stream,
+            "Content-Length: {}\r\n\r\n{}",
+            body.len(),
+            body
+        )?;
+        self.stream.flush()
+    }
+
+    fn event(&mut self, event: &str, body: Value) -> io::Result<()> {
+        self.send(json!({ "type": "event", "event": event, "body": body }))
+    }
+}
+
+pub struct DapBackend {
+    connection: Arc<Mutex<Connection>>,
+    paused: Arc<Mutex<Option<PausedState>>>,
+    commands: Receiver<DebugCommand>,
+}
+
+impl DapBackend {
+    /// Wait for a client to connect on `port` and finish configuring its
+    /// initial breakpoints.
+    pub fn listen(port: u16) -> io::Result<Self> {
+        let listener = TcpListener::bind(("127.0.0.1", port))?;
+        log::info!("Waiting for a debugger to connect on port {}", port);
+        let (stream, address) = listener.accept()?;
+        log::info!("Debugger connected from {}", address);
+
+        let connection = Arc::new(Mutex::new(Connection {
+            stream: stream.try_clone()?,
+            seq: 0,
+        }));
+        let paused = Arc::new(Mutex::new(None));
+        let (commands_sender, commands) = channel();
+        let (configured_sender, configured) = channel();
+
+        let mut handler = RequestHandler {
+            connection: connection.clone(),
+            paused: paused.clone(),
+            commands: commands_sender,
+            configured: Some(configured_sender),
+        };
+        thread::spawn(move || {
+            let mut reader = BufReader::new(stream);
+            while let Ok(Some(request)) = read_message(&mut reader) {
+                if !handler.handle(&request) {
+                    break;
+                }
+            }
+        });
+
+        // If the client goes away before configuring, just run without it.
+        let _ = configured.recv();
+
+        Ok(Self {
+            connection,
+            paused,
+            commands,
+        })
+    }
+}
+
+impl DebuggerBackend for DapBackend {
+    fn poll(&mut self) -> Vec<DebugCommand> {
+        let mut commands = vec![];
+        loop {
+            match self.commands.try_recv() {
+                Ok(command) => commands.push(command),
+                Err(TryRecvError::Empty | TryRecvError::Disconnected) => break,
+            }
+        }
+        commands
+    }
+
+    fn paused(&mut self, state: &PausedState) {
+        let reason = match state.reason {
+            PauseReason::Breakpoint => "breakpoint",
+            PauseReason::Step => "step",
+            PauseReason::Pause => "pause",
+        };
+        *self.paused.lock().unwrap() = Some(state.clone());
+        let _ = self.connection.lock().unwrap().event(
+            "stopped",
+            json!({ "reason": reason, "threadId": THREAD_ID, "allThreadsStopped": true }),
+        );
+    }
+
+    fn resumed(&mut self) {
+        *self.paused.lock().unwrap() = None;
+        let _ = self.connection.lock().unwrap().event(
+            "continued",
+            json!({ "threadId": THREAD_ID, "allThreadsContinued": true }),
+        );
+    }
+
+    fn wait(&mut self) -> Option<DebugCommand> {
+        self.commands.recv().ok()
+    }
+}
+
+struct RequestHandler {
+    connection: Arc<Mutex<Connection>>,
+    paused: Arc<Mutex<Option<PausedState>>>,
+    commands: Sender<DebugCommand>,
+    configured: Option<Sender<()>>,
+}
+
+impl RequestHandler {
+    /// Respond to a request. Returns false once the client disconnects.
+    fn handle(&mut self, request: &Value) -> bool {
+        let command = request["command"].as_str().unwrap_or_default();
+        let arguments = &request["arguments"];
+
+        let mut events = vec![];
+        let body = match command {
+            "initialize" => {
+                events.push("initialized");
+                Some(json!({ "supportsConfigurationDoneRequest": true }))
+            }
+            "launch" | "attach" => Some(json!({})),
+            "configurationDone" => {
+                if let Some(configured) = self.configured.take() {
+                    let _ = configured.send(());
+                }
+                Some(json!({}))
+            }
+            "setBreakpoints" => {
+                let file = arguments["source"]["path"]
+                    .as_str()
+                    .unwrap_or_default()
+                    .to_string();
+                let lines: Vec<u32> = arguments["breakpoints"]
+                    .as_array()
+                    .map(|breakpoints| {
+                        breakpoints
+                            .iter()
+                            .filter_map(|breakpoint| breakpoint["line"].as_u64())
+                            .map(|line| line as u32)
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                let breakpoints: Vec<_> = lines
+                    .iter()
+                    .map(|line| json!({ "verified": true, "line": line }))
+                    .collect();
+                self.command(DebugCommand::SetBreakpoints { file, lines });
+                Some(json!({ "breakpoints": breakpoints }))
+            }
+            "threads" => Some(json!({ "threads": [{ "id": THREAD_ID, "name": "main" }] })),
+            "stackTrace" => self.stack_trace(),
+            "scopes" => self.scopes(),
+            "variables" => self.variables(arguments["variablesReference"].as_u64()),
+            "continue" => {
+                self.command(DebugCommand::Continue);
+                Some(json!({ "allThreadsContinued": true }))
+            }
+            "next" => self.command(DebugCommand::StepOver),
+            "stepIn" => self.command(DebugCommand::StepIn),
+            "stepOut" => self.command(DebugCommand::StepOut),
+            "pause" => self.command(DebugCommand::Pause),
+            "disconnect" => self.command(DebugCommand::Disconnect),
+            _ => None,
+        };
+
+        let mut connection = self.connection.lock().unwrap();
+        let _ = connection.send(json!({
+            "type": "response",
+            "request_seq": request["seq"],
+            "command": command,
+            "success": body.is_some(),
+            "body": body.unwrap_or_default(),
+        }));
+        for event in events {
+            let _ = connection.event(event, json!({}));
+        }
+
+        command != "disconnect"
+    }
+
+    fn command(&mut self, command: DebugCommand) -> Option<Value> {
+        let _ = self.commands.send(command);
+        Some(json!({}))
+    }
+
+    fn stack_trace(&self) -> Option<Value> {
+        let paused = self.paused.lock().unwrap();
+        let frames = paused
+            .as_ref()
+            .map(|state| &state.frames[..])
+            .unwrap_or(&[]);
+        let stack_frames: Vec<_> = frames
+            .iter()
+            .enumerate()
+            .map(|(id, frame)| {
+                let mut stack_frame = json!({
+                    "id": id,
+                    "name": frame.name,
+                    "line": frame.line.unwrap_or(0),
+                    "column": 0,
+                });
+                if let Some(file) = &frame.file {
+                    stack_frame["source"] = json!({ "path": source_path(file) });
+                }
+                stack_frame
+            })
+            .collect();
+        Some(json!({ "stackFrames": stack_frames, "totalFrames": frames.len() }))
+    }
+
+    fn scopes(&self) -> Option<Value> {
+        let paused = self.paused.lock().unwrap();
+        let mut scopes = vec![json!({
+            "name": "Locals",
+            "variablesReference": LOCALS_REFERENCE,
+            "expensive": false,
+        })];
+        if let Some(state) = &*paused {
+            for (i, scope) in state.variables.scopes.iter().enumerate() {
+                scopes.push(json!({
+                    "name": scope.name,
+                    "variablesReference": LOCALS_REFERENCE + 1 + i as u64,
+                    "expensive": false,
+                }));
+            }
+        }
+        Some(json!({ "scopes": scopes }))
+    }
+
+    fn variables(&self, reference: Option<u64>) -> Option<Value> {
+        let paused = self.paused.lock().unwrap();
+        let variables = &paused.as_ref()?.variables;
+        let variables: Vec<&Variable> = match reference? {
+            LOCALS_REFERENCE => variables.this.iter().chain(&variables.locals).collect(),
+            reference => variables
+                .scopes
+                .get((reference - LOCALS_REFERENCE - 1) as usize)?
+                .variables
+                .iter()
+                .collect(),
+        };
+        let variables: Vec<_> = variables
+            .into_iter()
+            .map(|variable| {
+                json!({
+                    "name": variable.name,
+                    "value": variable.value,
+                    "type": variable.type_name,
+                    "variablesReference": 0,
+                })
+            })
+            .collect();
+        Some(json!({ "variables": variables }))
+    }
+}
+
+/// Read a single `Content-Length` framed message.
+///
+/// Returns `None` once the client closes the connection.
+fn read_message(reader: &mut impl BufRead) -> io::Result<Option<Value>> {
+    let mut content_length = None;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 {
+            return Ok(None);
+        }
+        let header = header.trim();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("Content-Length") {
+                content_length = value.trim().parse::<usize>().ok();
+            }
+        }
+    }
+
+    let content_length = content_length
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "Missing Content-Length"))?;
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+    Ok(Some(serde_json::from_slice(&body)?))
+}
+
+/// Convert a `source root;package;File.as` debug file name into a path.
+fn source_path(file: &str) -> PathBuf {
+    file.split(';').collect()
+}
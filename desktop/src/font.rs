@@ -0,0 +1,86 @@
+use ruffle_core::backend::font::{best_match, FontBackend, FontDescriptor};
+use std::path::{Path, PathBuf};
+
+/// Provides the fonts found in a list of directories.
+pub struct DiskFontBackend {
+    /// The font files that were found, with the font each one contains.
+    fonts: Vec<(FontDescriptor, PathBuf)>,
+}
+
+impl DiskFontBackend {
+    /// Index the font files in the given directories and their
+    /// subdirectories.
+    pub fn new(directories: &[PathBuf]) -> Self {
+        let mut fonts = Vec::new();
+        for directory in directories {
+            scan_directory(directory, &mut fonts);
+        }
+        log::info!("Found {} device fonts", fonts.len());
+        Self { fonts }
+    }
+}
+
+fn scan_directory(directory: &Path, fonts: &mut Vec<(FontDescriptor, PathBuf)>) {
+    let entries = match std::fs::read_dir(directory) {
+        Ok(entries) => entries,
+        Err(e) => {
+            log::warn!("Unable to read font directory {:?}: {}", directory, e);
+            return;
+        }
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            scan_directory(&path, fonts);
+            continue;
+        }
+
+        let is_font_file = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map_or(false, |extension| {
+                ["ttf", "otf", "ttc"]
+                    .iter()
+                    .any(|font_extension| extension.eq_ignore_ascii_case(font_extension))
+            });
+        if !is_font_file {
+            continue;
+        }
+
+        match std::fs::read(&path)
+            .map_err(|e| e.into())
+            .and_then(|data| FontDescriptor::from_font_file(&data))
+        {
+            Ok(descriptor) => fonts.push((descriptor, path)),
+            Err(e) => log::warn!("Unable to read font {:?}: {}", path, e),
+        }
+    }
+}
+
+impl FontBackend for DiskFontBackend {
+    fn font_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .fonts
+            .iter()
+            .map(|(font, _)| font.class().to_string())
+            .collect();
+        names.sort_unstable();
+        names.dedup();
+        names
+    }
+
+    fn load_font(&mut self, descriptor: &FontDescriptor) -> Option<Vec<u8>> {
+        let path = best_match(
+            self.fonts.iter().map(|(font, path)| (font, path)),
+            descriptor,
+        )?;
+        match std::fs::read(path) {
+            Ok(data) => Some(data),
+            Err(e) => {
+                log::warn!("Unable to read font {:?}: {}", path, e);
+                None
+            }
+        }
+    }
+}
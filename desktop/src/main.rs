@@ -6,14 +6,19 @@
 #![windows_subsystem = "windows"]
 
 mod audio;
+mod config;
 mod custom_event;
+mod debugger;
 mod executor;
+mod font;
 mod locale;
 mod navigator;
+mod print;
 mod storage;
 mod task;
 mod ui;
 
+use crate::config::{Config, MovieSettings};
 use crate::custom_event::RuffleEvent;
 use crate::executor::GlutinAsyncExecutor;
 use clap::Parser;
@@ -22,24 +27,29 @@ use rfd::FileDialog;
 use ruffle_core::{
     backend::{
         audio::{AudioBackend, NullAudioBackend},
+        locale::LocaleBackend,
         log as log_backend,
-        navigator::NullNavigatorBackend,
-        storage::MemoryStorageBackend,
+        navigator::{NavigatorBackend, NullNavigatorBackend},
+        print::NullPrintBackend,
+        storage::{MemoryStorageBackend, StorageBackend},
         ui::NullUiBackend,
         video,
     },
     config::Letterbox,
     events::KeyCode,
+    recording::{Recorder, Recording, Replayer},
     tag_utils::SwfMovie,
     Player, PlayerEvent, StageDisplayState,
 };
 use ruffle_render_wgpu::clap::{GraphicsBackend, PowerPreference};
 use ruffle_render_wgpu::WgpuRenderBackend;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
 use std::io::Read;
 use std::path::{Path, PathBuf};
 use std::rc::Rc;
 use std::sync::{Arc, Mutex};
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use url::Url;
 use winit::dpi::{LogicalSize, PhysicalPosition, PhysicalSize, Size};
 use winit::event::{
@@ -67,13 +77,13 @@ struct Opt {
 
     /// Type of graphics backend to use. Not all options may be supported by your current system.
     /// Default will attempt to pick the most supported graphics backend.
-    #[clap(long, short, default_value = "default", arg_enum)]
-    graphics: GraphicsBackend,
+    #[clap(long, short, arg_enum)]
+    graphics: Option<GraphicsBackend>,
 
     /// Power preference for the graphics device used. High power usage tends to prefer dedicated GPUs,
-    /// whereas a low power usage tends prefer integrated GPUs.
-    #[clap(long, short, default_value = "high", arg_enum)]
-    power: PowerPreference,
+    /// whereas a low power usage tends prefer integrated GPUs. Defaults to high.
+    #[clap(long, short, arg_enum)]
+    power: Option<PowerPreference>,
 
     /// Width of window in pixels.
     #[clap(long, display_order = 1)]
@@ -88,6 +98,11 @@ struct Opt {
     #[cfg(feature = "render_trace")]
     trace_path: Option<PathBuf>,
 
+    /// Directory to load device fonts from. Can be given multiple times.
+    /// Text using device fonts is rendered with a built-in font if no matching font is found.
+    #[clap(long = "fonts", parse(from_os_str), multiple_occurrences = true)]
+    font_directories: Vec<PathBuf>,
+
     /// Proxy to use when loading movies via URL.
     #[clap(long)]
     proxy: Option<Url>,
@@ -101,6 +116,52 @@ struct Opt {
 
     #[clap(long, takes_value = false)]
     dont_warn_on_unsupported_content: bool,
+
+    /// Wait for an ActionScript debugger to connect on this port before starting.
+    /// The debugger speaks the Debug Adapter Protocol.
+    #[clap(long)]
+    debugger_port: Option<u16>,
+
+    /// Write the display list as JSON to this file when the player exits.
+    #[clap(long, parse(from_os_str))]
+    dump_display_list: Option<PathBuf>,
+
+    /// Save the session to this file on exit, to be replayed with `--replay`.
+    #[clap(long, parse(from_os_str), conflicts_with = "replay")]
+    record: Option<PathBuf>,
+
+    /// Replay a session recorded with `--record`, instead of taking input.
+    /// The movie must be the same one that was recorded.
+    #[clap(long, parse(from_os_str))]
+    replay: Option<PathBuf>,
+
+    /// When replaying, skip ahead to this frame before showing the movie.
+    #[clap(long, requires = "replay")]
+    seek: Option<u32>,
+
+    /// Configuration file to read, instead of config.toml in Ruffle's data directory.
+    #[clap(long, parse(from_os_str))]
+    config: Option<PathBuf>,
+}
+
+impl Opt {
+    /// Fill in the options that weren't given on the command line from `config`.
+    fn apply_config(&mut self, config: &Config) {
+        self.graphics = self.graphics.or(config.graphics);
+        self.power = self.power.or(config.power);
+        if self.proxy.is_none() {
+            self.proxy = config.proxy.clone();
+        }
+        self.upgrade_to_https |= config.upgrade_to_https;
+    }
+
+    fn graphics(&self) -> GraphicsBackend {
+        self.graphics.unwrap_or(GraphicsBackend::Default)
+    }
+
+    fn power(&self) -> PowerPreference {
+        self.power.unwrap_or(PowerPreference::High)
+    }
 }
 
 #[cfg(feature = "render_trace")]
@@ -119,10 +180,15 @@ fn trace_path(_opt: &Opt) -> Option<&Path> {
 }
 
 // TODO: Return just `SwfMovie` by making it hold `Url`?
+/// Load a movie, along with its settings from the configuration file.
+///
+/// The movie's parameters are left to the caller, as a replay takes them
+/// from its recording instead.
 fn load_movie_from_path(
     path: &Path,
     opt: &Opt,
-) -> Result<(SwfMovie, Url), Box<dyn std::error::Error>> {
+    config: &Config,
+) -> Result<(SwfMovie, Url, MovieSettings), Box<dyn std::error::Error>> {
     let movie_url = if path.exists() {
         let absolute_path = path.canonicalize().unwrap_or_else(|_| path.to_owned());
         Url::from_file_path(absolute_path)
@@ -134,8 +200,8 @@ fn load_movie_from_path(
             .ok_or("Input path is not a file and could not be parsed as a URL.")?
     };
 
-    let mut movie = if movie_url.scheme() == "file" {
-        SwfMovie::from_path(movie_url.to_file_path().unwrap(), None)?
+    let data = if movie_url.scheme() == "file" {
+        std::fs::read(movie_url.to_file_path().unwrap())?
     } else {
         let proxy = opt.proxy.as_ref().and_then(|url| url.as_str().parse().ok());
         let builder = HttpClient::builder()
@@ -145,24 +211,34 @@ fn load_movie_from_path(
         let response = client.get(movie_url.to_string())?;
         let mut buffer: Vec<u8> = Vec::new();
         response.into_body().read_to_end(&mut buffer)?;
-
-        SwfMovie::from_data(&buffer, Some(movie_url.to_string()), None)?
+        buffer
     };
+    let movie = SwfMovie::from_data(&data, Some(movie_url.to_string()), None)?;
+
+    let hash: String = Sha256::digest(&data)
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+    let settings = config.movie_settings(&movie_url, &hash);
+
+    Ok((movie, movie_url, settings))
+}
 
-    let parameters = opt.parameters.iter().map(|parameter| {
+fn parse_parameters(opt: &Opt) -> impl Iterator<Item = (String, String)> + '_ {
+    opt.parameters.iter().map(|parameter| {
         let mut split = parameter.splitn(2, '=');
         if let (Some(key), Some(value)) = (split.next(), split.next()) {
             (key.to_owned(), value.to_owned())
         } else {
             (parameter.clone(), "".to_string())
         }
-    });
-    movie.append_parameters(parameters);
-
-    Ok((movie, movie_url))
+    })
 }
 
-fn load_from_file_dialog(opt: &Opt) -> Result<Option<(SwfMovie, Url)>, Box<dyn std::error::Error>> {
+fn load_from_file_dialog(
+    opt: &Opt,
+    config: &Config,
+) -> Result<Option<(SwfMovie, Url, MovieSettings)>, Box<dyn std::error::Error>> {
     let result = FileDialog::new()
         .add_filter(".swf", &["swf"])
         .set_title("Load a Flash File")
@@ -177,7 +253,7 @@ fn load_from_file_dialog(opt: &Opt) -> Result<Option<(SwfMovie, Url)>, Box<dyn s
         .canonicalize()
         .unwrap_or_else(|_| selected.to_owned());
 
-    Ok(Some(load_movie_from_path(&absolute_path, opt)?))
+    Ok(Some(load_movie_from_path(&absolute_path, opt, config)?))
 }
 
 struct App {
@@ -188,16 +264,24 @@ struct App {
     executor: Arc<Mutex<GlutinAsyncExecutor>>,
     player: Arc<Mutex<Player>>,
     movie: Option<Arc<SwfMovie>>,
+    key_map: HashMap<KeyCode, KeyCode>,
+    recorder: Option<Recorder>,
+    replayer: Option<Replayer>,
 }
 
 impl App {
     const DEFAULT_WINDOW_SIZE: LogicalSize<f64> = LogicalSize::new(1280.0, 720.0);
 
-    fn new(opt: Opt) -> Result<Self, Box<dyn std::error::Error>> {
-        let movie = if let Some(path) = opt.input_path.to_owned() {
-            Some(load_movie_from_path(&path, &opt)?)
+    fn new(opt: Opt, config: &Config) -> Result<Self, Box<dyn std::error::Error>> {
+        let recording = match &opt.replay {
+            Some(path) => Some(Recording::load(path)?),
+            None => None,
+        };
+
+        let mut movie = if let Some(path) = opt.input_path.to_owned() {
+            Some(load_movie_from_path(&path, &opt, config)?)
         } else {
-            match load_from_file_dialog(&opt)? {
+            match load_from_file_dialog(&opt, config)? {
                 Some(movie) => Some(movie),
                 None => {
                     shutdown(&Ok(()));
@@ -206,12 +290,44 @@ impl App {
             }
         };
 
+        if let Some((movie, _, settings)) = &mut movie {
+            match &recording {
+                Some(recording) => {
+                    // The recorded parameters already include those that were
+                    // configured or given with -P when recording.
+                    if !opt.parameters.is_empty() {
+                        log::warn!("Ignoring -P parameters, the recorded ones are used");
+                    }
+                    movie.append_parameters(recording.parameters.iter().cloned());
+
+                    // A replay shows the session as it was recorded, so the
+                    // configured settings are not applied.
+                    *settings = MovieSettings::default();
+                }
+                None => {
+                    movie.append_parameters(settings.parameters.clone());
+                    movie.append_parameters(parse_parameters(&opt));
+                }
+            }
+        }
+
+        let mut replayer = recording.map(Replayer::new);
+        let recorder = opt.record.as_ref().map(|_| {
+            Recorder::new(
+                movie.as_ref().map(|(_, url, _)| url.to_string()),
+                movie
+                    .as_ref()
+                    .map(|(movie, _, _)| movie.parameters().to_vec())
+                    .unwrap_or_default(),
+            )
+        });
+
         let icon_bytes = include_bytes!("../assets/favicon-32.rgba");
         let icon = Icon::from_rgba(icon_bytes.to_vec(), 32, 32)?;
 
         let event_loop: EventLoop<RuffleEvent> = EventLoop::with_user_event();
 
-        let (title, movie_size) = if let Some((movie, movie_url)) = &movie {
+        let (title, movie_size) = if let Some((movie, movie_url, _)) = &movie {
             let filename = movie_url
                 .path_segments()
                 .and_then(|segments| segments.last())
@@ -225,20 +341,28 @@ impl App {
             ("Ruffle".into(), Self::DEFAULT_WINDOW_SIZE)
         };
 
-        let window_size: Size = if opt.width.is_none() && opt.height.is_none() {
+        let settings = movie
+            .as_ref()
+            .map(|(_, _, settings)| settings.clone())
+            .unwrap_or_default();
+
+        // A size given on the command line replaces the configured one.
+        let (width, height) = if opt.width.is_some() || opt.height.is_some() {
+            (opt.width, opt.height)
+        } else {
+            (settings.width, settings.height)
+        };
+        let window_size: Size = if width.is_none() && height.is_none() {
             movie_size.into()
         } else {
-            let window_width = opt
-                .width
+            let window_width = width
                 .unwrap_or(
-                    movie_size.width
-                        * (opt.height.unwrap_or(movie_size.height) / movie_size.height),
+                    movie_size.width * (height.unwrap_or(movie_size.height) / movie_size.height),
                 )
                 .max(1.0);
-            let window_height = opt
-                .height
+            let window_height = height
                 .unwrap_or(
-                    movie_size.height * (opt.width.unwrap_or(movie_size.width) / movie_size.width),
+                    movie_size.height * (width.unwrap_or(movie_size.width) / movie_size.width),
                 )
                 .max(1.0);
             PhysicalSize::new(window_width, window_height).into()
@@ -258,11 +382,12 @@ impl App {
         let renderer = Box::new(WgpuRenderBackend::for_window(
             window.as_ref(),
             (viewport_size.width, viewport_size.height),
-            opt.graphics.into(),
-            opt.power.into(),
+            opt.graphics().into(),
+            opt.power().into(),
             trace_path(&opt),
         )?);
-        let audio: Box<dyn AudioBackend> = match audio::CpalAudioBackend::new() {
+        let volume = settings.volume.unwrap_or(1.0);
+        let audio: Box<dyn AudioBackend> = match audio::CpalAudioBackend::new(volume) {
             Ok(audio) => Box::new(audio),
             Err(e) => {
                 log::error!("Unable to create audio device: {}", e);
@@ -270,35 +395,94 @@ impl App {
             }
         };
         let (executor, channel) = GlutinAsyncExecutor::new(event_loop.create_proxy());
-        let navigator = Box::new(navigator::ExternalNavigatorBackend::new(
-            movie.as_ref().unwrap().1.clone(), // TODO: Get rid of this parameter.
-            channel,
-            event_loop.create_proxy(),
-            opt.proxy.clone(),
-            opt.upgrade_to_https,
-        ));
-        let storage = Box::new(storage::DiskStorageBackend::new());
-        let locale = Box::new(locale::DesktopLocaleBackend::new());
+        // A replay resolves URLs against the movie's own, and takes the stage
+        // settings from its recording.
+        let movie_url = &movie.as_ref().unwrap().1;
+        let base_url = match &settings.base_url {
+            Some(base_url) if replayer.is_none() => base_url,
+            _ => movie_url,
+        };
+        let mut navigator: Box<dyn NavigatorBackend> =
+            Box::new(navigator::ExternalNavigatorBackend::new(
+                base_url.clone(), // TODO: Get rid of this parameter.
+                channel,
+                event_loop.create_proxy(),
+                opt.proxy.clone(),
+                opt.upgrade_to_https,
+            ));
+        let mut storage: Box<dyn StorageBackend> = Box::new(storage::DiskStorageBackend::new());
+        let mut locale: Box<dyn LocaleBackend> = Box::new(locale::DesktopLocaleBackend::new());
+        if let Some(recorder) = &recorder {
+            navigator = recorder.navigator(navigator);
+            storage = recorder.storage(storage);
+            locale = recorder.locale(locale);
+        } else if let Some(replayer) = &mut replayer {
+            navigator = replayer.navigator(navigator);
+            storage = replayer.storage();
+            locale = replayer.locale();
+        }
         let video = Box::new(video::SoftwareVideoBackend::new());
         let log = Box::new(log_backend::NullLogBackend::new());
         let ui = Box::new(ui::DesktopUiBackend::new(window.clone()));
-        let player = Player::new(renderer, audio, navigator, storage, locale, video, log, ui)?;
+        let print = Box::new(print::DesktopPrintBackend::new());
+        let font = Box::new(font::DiskFontBackend::new(&opt.font_directories));
+        let player = Player::new(
+            renderer, audio, navigator, storage, locale, video, log, ui, print, font,
+        )?;
 
-        let movie = movie.map(|(movie, _)| Arc::new(movie));
+        let movie = movie.map(|(movie, _, _)| Arc::new(movie));
 
         {
             let mut player_lock = player.lock().unwrap();
+            if let Some(recorder) = &recorder {
+                player_lock.set_recorder(recorder.clone());
+            } else if let Some(replayer) = &replayer {
+                player_lock.set_rng_seed(replayer.seed());
+            }
             player_lock.set_warn_on_unsupported_content(!opt.dont_warn_on_unsupported_content);
             if let Some(movie) = &movie {
                 player_lock.set_root_movie(movie.to_owned());
                 player_lock.set_is_playing(true); // Desktop player will auto-play.
             }
-            player_lock.set_letterbox(Letterbox::On);
-            player_lock.set_viewport_dimensions(
-                viewport_size.width,
-                viewport_size.height,
-                viewport_scale_factor,
-            );
+            if replayer.is_none() {
+                player_lock.set_letterbox(settings.letterbox.unwrap_or(Letterbox::On));
+                if let Some(scale_mode) = &settings.scale_mode {
+                    player_lock.set_scale_mode(scale_mode);
+                }
+                if let Some(quality) = &settings.quality {
+                    player_lock.set_quality(quality);
+                }
+            }
+            if let Some(max_execution_duration) = settings.max_execution_duration {
+                player_lock
+                    .set_max_execution_duration(Duration::from_secs_f64(max_execution_duration));
+            }
+            if let Some(port) = opt.debugger_port {
+                let backend = debugger::DapBackend::listen(port)?;
+                player_lock.attach_debugger(Box::new(backend));
+            }
+            if replayer.is_some() {
+                // The recording sets the viewport dimensions that the movie
+                // saw; only the window is sized to this machine.
+                player_lock
+                    .renderer_mut()
+                    .set_viewport_dimensions(viewport_size.width, viewport_size.height);
+            } else {
+                player_lock.set_viewport_dimensions(
+                    viewport_size.width,
+                    viewport_size.height,
+                    viewport_scale_factor,
+                );
+            }
+        }
+
+        if let (Some(replayer), Some(frame)) = (&mut replayer, opt.seek) {
+            replayer.seek(&player, frame, &mut || {
+                executor
+                    .lock()
+                    .expect("active executor reference")
+                    .poll_all()
+            });
         }
 
         Ok(Self {
@@ -308,6 +492,9 @@ impl App {
             executor,
             player,
             movie,
+            key_map: settings.key_map,
+            recorder,
+            replayer,
         })
     }
 
@@ -317,12 +504,18 @@ impl App {
         let player = self.player;
         let executor = self.executor;
         let movie = self.movie;
+        let key_map = self.key_map;
+        let dump_display_list = self.opt.dump_display_list.clone();
+        let recorder = self.recorder;
+        let record_path = self.opt.record.clone();
+        let mut replayer = self.replayer;
 
         let mut mouse_pos = PhysicalPosition::new(0.0, 0.0);
         let mut time = Instant::now();
         let mut next_frame_time = Instant::now();
         let mut minimized = false;
         let mut fullscreen_down = false;
+        let mut inspector_enabled = false;
         loop {
             // Poll UI events
             self.event_loop
@@ -335,6 +528,20 @@ impl App {
                     #[allow(deprecated)]
                     match &event {
                         winit::event::Event::LoopDestroyed => {
+                            if let Some(path) = &dump_display_list {
+                                let display_list = player.lock().unwrap().inspect_display_list();
+                                let result = serde_json::to_string_pretty(&display_list)
+                                    .map_err(std::io::Error::from)
+                                    .and_then(|json| std::fs::write(path, json));
+                                if let Err(e) = result {
+                                    log::error!("Couldn't write display list: {}", e);
+                                }
+                            }
+                            if let (Some(recorder), Some(path)) = (&recorder, &record_path) {
+                                if let Err(e) = recorder.save(path) {
+                                    log::error!("Couldn't save recording: {}", e);
+                                }
+                            }
                             player.lock().unwrap().flush_shared_objects();
                             shutdown(&Ok(()));
                             return;
@@ -390,6 +597,11 @@ impl App {
                         return;
                     }
 
+                    // A replay takes its input from the recording instead.
+                    if replayer.is_some() && is_input_event(&event) {
+                        return;
+                    }
+
                     // Allow KeyboardInput.modifiers (ModifiersChanged event not functional yet).
                     #[allow(deprecated)]
                     match event {
@@ -399,8 +611,17 @@ impl App {
                             let dt = new_time.duration_since(time).as_micros();
                             if dt > 0 {
                                 time = new_time;
-                                let mut player_lock = player.lock().unwrap();
-                                player_lock.tick(dt as f64 / 1000.0);
+                                if let Some(replayer) = &mut replayer {
+                                    replayer.advance(&player, dt as f64 / 1000.0, &mut || {
+                                        executor
+                                            .lock()
+                                            .expect("active executor reference")
+                                            .poll_all()
+                                    });
+                                } else {
+                                    player.lock().unwrap().tick(dt as f64 / 1000.0);
+                                }
+                                let player_lock = player.lock().unwrap();
                                 next_frame_time = new_time + player_lock.time_til_next_frame();
                                 if player_lock.needs_render() {
                                     window.request_redraw();
@@ -423,19 +644,45 @@ impl App {
 
                                 let viewport_scale_factor = window.scale_factor();
                                 let mut player_lock = player.lock().unwrap();
-                                player_lock.set_viewport_dimensions(
-                                    size.width,
-                                    size.height,
-                                    viewport_scale_factor,
-                                );
+                                if replayer.is_none() {
+                                    player_lock.set_viewport_dimensions(
+                                        size.width,
+                                        size.height,
+                                        viewport_scale_factor,
+                                    );
+                                }
                                 player_lock
                                     .renderer_mut()
                                     .set_viewport_dimensions(size.width, size.height);
                                 window.request_redraw();
                             }
+                            WindowEvent::KeyboardInput {
+                                input:
+                                    KeyboardInput {
+                                        state: ElementState::Pressed,
+                                        virtual_keycode: Some(VirtualKeyCode::F12),
+                                        ..
+                                    },
+                                ..
+                            } => {
+                                inspector_enabled = !inspector_enabled;
+                                let mut player_lock = player.lock().unwrap();
+                                let path = if inspector_enabled {
+                                    player_lock.display_object_path_at(mouse_pos.x, mouse_pos.y)
+                                } else {
+                                    None
+                                };
+                                player_lock.set_highlighted_display_object(path);
+                                window.request_redraw();
+                            }
                             WindowEvent::CursorMoved { position, .. } => {
                                 let mut player_lock = player.lock().unwrap();
                                 mouse_pos = position;
+                                if inspector_enabled {
+                                    let path =
+                                        player_lock.display_object_path_at(position.x, position.y);
+                                    player_lock.set_highlighted_display_object(path);
+                                }
                                 let event = PlayerEvent::MouseMove {
                                     x: position.x,
                                     y: position.y,
@@ -495,10 +742,18 @@ impl App {
                                 let mut player_lock = player.lock().unwrap();
                                 if let Some(key) = input.virtual_keycode {
                                     let key_code = winit_to_ruffle_key_code(key);
-                                    let key_char = winit_key_to_char(
+                                    let mut key_char = winit_key_to_char(
                                         key,
                                         input.modifiers.contains(ModifiersState::SHIFT),
                                     );
+                                    let key_code = match key_map.get(&key_code) {
+                                        Some(mapped) => {
+                                            // The pressed key's character no longer applies.
+                                            key_char = None;
+                                            *mapped
+                                        }
+                                        None => key_code,
+                                    };
                                     let event = match input.state {
                                         ElementState::Pressed => {
                                             PlayerEvent::KeyDown { key_code, key_char }
@@ -539,6 +794,22 @@ impl App {
     }
 }
 
+/// Whether `event` is input that would be passed on to the player.
+fn is_input_event(event: &winit::event::Event<RuffleEvent>) -> bool {
+    matches!(
+        event,
+        winit::event::Event::WindowEvent {
+            event: WindowEvent::CursorMoved { .. }
+                | WindowEvent::MouseInput { .. }
+                | WindowEvent::MouseWheel { .. }
+                | WindowEvent::CursorLeft { .. }
+                | WindowEvent::KeyboardInput { .. }
+                | WindowEvent::ReceivedCharacter(_),
+            ..
+        }
+    )
+}
+
 /// Convert a winit `VirtualKeyCode` into a Ruffle `KeyCode`.
 /// Return `KeyCode::Unknown` if there is no matching Flash key code.
 fn winit_to_ruffle_key_code(key_code: VirtualKeyCode) -> KeyCode {
@@ -757,12 +1028,12 @@ fn winit_key_to_char(key_code: VirtualKeyCode, is_shift_down: bool) -> Option<ch
     })
 }
 
-fn run_timedemo(opt: Opt) -> Result<(), Box<dyn std::error::Error>> {
+fn run_timedemo(opt: Opt, config: &Config) -> Result<(), Box<dyn std::error::Error>> {
     let path = opt
         .input_path
         .as_ref()
         .ok_or("Input file necessary for timedemo")?;
-    let (movie, _) = load_movie_from_path(path, &opt)?;
+    let (movie, _, _) = load_movie_from_path(path, &opt, config)?;
     let movie_frames = Some(movie.num_frames());
 
     let viewport_width = 1920;
@@ -771,8 +1042,8 @@ fn run_timedemo(opt: Opt) -> Result<(), Box<dyn std::error::Error>> {
 
     let renderer = Box::new(WgpuRenderBackend::for_offscreen(
         (viewport_width, viewport_height),
-        opt.graphics.into(),
-        opt.power.into(),
+        opt.graphics().into(),
+        opt.power().into(),
         trace_path(&opt),
     )?);
     let audio = Box::new(NullAudioBackend::new());
@@ -782,7 +1053,11 @@ fn run_timedemo(opt: Opt) -> Result<(), Box<dyn std::error::Error>> {
     let video = Box::new(video::SoftwareVideoBackend::new());
     let log = Box::new(log_backend::NullLogBackend::new());
     let ui = Box::new(NullUiBackend::new());
-    let player = Player::new(renderer, audio, navigator, storage, locale, video, log, ui)?;
+    let print = Box::new(NullPrintBackend::new());
+    let font = Box::new(font::DiskFontBackend::new(&opt.font_directories));
+    let player = Player::new(
+        renderer, audio, navigator, storage, locale, video, log, ui, print, font,
+    )?;
 
     let mut player_lock = player.lock().unwrap();
     player_lock.set_root_movie(Arc::new(movie));
@@ -834,12 +1109,15 @@ fn shutdown(result: &Result<(), Box<dyn std::error::Error>>) {
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     init();
-    let opt = Opt::parse();
-    let result = if opt.timedemo {
-        run_timedemo(opt)
-    } else {
-        App::new(opt).map(|app| app.run())
-    };
+    let mut opt = Opt::parse();
+    let result = Config::read(opt.config.as_deref()).and_then(|config| {
+        opt.apply_config(&config);
+        if opt.timedemo {
+            run_timedemo(opt, &config)
+        } else {
+            App::new(opt, &config).map(|app| app.run())
+        }
+    });
     shutdown(&result);
     result
 }
@@ -0,0 +1,182 @@
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+use pdf_writer::{Content, Filter, Finish, Name, Pdf, Rect, Ref};
+use rfd::FileDialog;
+use ruffle_core::backend::print::{Error, PrintBackend, PrintJobSettings, PrintPage};
+use ruffle_core::backend::render::{Bitmap, BitmapFormat, RenderBackend};
+use ruffle_core::swf::Color;
+use std::io::Write;
+use std::path::PathBuf;
+
+/// Prints pages into a PDF file chosen by the user, with a PDF page for each
+/// page added to the print job.
+pub struct DesktopPrintBackend {
+    /// The file that the current print job is written to.
+    output_path: Option<PathBuf>,
+
+    /// The settings of the current print job.
+    settings: PrintJobSettings,
+
+    /// The rasterized pages of the current print job.
+    pages: Vec<Bitmap>,
+}
+
+impl DesktopPrintBackend {
+    pub fn new() -> Self {
+        Self {
+            output_path: None,
+            settings: PrintJobSettings::default(),
+            pages: Vec::new(),
+        }
+    }
+}
+
+impl PrintBackend for DesktopPrintBackend {
+    fn start_job(&mut self) -> Option<PrintJobSettings> {
+        let output_path = FileDialog::new()
+            .set_title("Print to PDF")
+            .add_filter("PDF document", &["pdf"])
+            .set_file_name("print.pdf")
+            .save_file()?;
+        self.output_path = Some(output_path);
+        self.settings = PrintJobSettings::default();
+        self.pages.clear();
+        Some(self.settings.clone())
+    }
+
+    fn add_page(&mut self, renderer: &mut dyn RenderBackend, page: PrintPage) -> Result<(), Error> {
+        let bitmap = renderer
+            .render_offscreen(
+                &page.commands,
+                page.width,
+                page.height,
+                Color::from_rgb(0xFFFFFF, 255),
+            )
+            .ok_or("Renderer is unable to rasterize pages")?;
+        self.pages.push(bitmap);
+        Ok(())
+    }
+
+    fn send(&mut self) -> Result<(), Error> {
+        let output_path = self.output_path.take().ok_or("No print job was started")?;
+        let pages = std::mem::take(&mut self.pages);
+        std::fs::write(&output_path, write_pdf(&self.settings, &pages)?)?;
+        log::info!(
+            "Printed {} page(s) to {}",
+            pages.len(),
+            output_path.to_string_lossy()
+        );
+        Ok(())
+    }
+}
+
+/// Write the pages of a print job as a PDF document.
+///
+/// Each page is drawn at one pixel per point, centered on a sheet of the
+/// job's paper size.
+fn write_pdf(settings: &PrintJobSettings, pages: &[Bitmap]) -> Result<Vec<u8>, Error> {
+    let catalog_id = Ref::new(1);
+    let page_tree_id = Ref::new(2);
+    // The IDs of each page, its content stream and its image.
+    let page_ids = |i: usize| {
+        let first = 3 + 3 * i as i32;
+        (Ref::new(first), Ref::new(first + 1), Ref::new(first + 2))
+    };
+    let image_name = Name(b"Page");
+
+    let mut pdf = Pdf::new();
+    pdf.catalog(catalog_id).pages(page_tree_id);
+    pdf.pages(page_tree_id)
+        .kids((0..pages.len()).map(|i| page_ids(i).0))
+        .count(pages.len().try_into()?);
+
+    let paper_width = settings.paper_width as f32;
+    let paper_height = settings.paper_height as f32;
+    for (i, bitmap) in pages.iter().enumerate() {
+        let (page_id, content_id, image_id) = page_ids(i);
+
+        let mut page = pdf.page(page_id);
+        page.media_box(Rect::new(0.0, 0.0, paper_width, paper_height))
+            .parent(page_tree_id)
+            .contents(content_id);
+        page.resources().x_objects().pair(image_name, image_id);
+        page.finish();
+
+        // Images are drawn into the unit square, so it is scaled up to the
+        // size of the page.
+        let width = bitmap.width as f32;
+        let height = bitmap.height as f32;
+        let mut content = Content::new();
+        content
+            .save_state()
+            .transform([
+                width,
+                0.0,
+                0.0,
+                height,
+                (paper_width - width) / 2.0,
+                (paper_height - height) / 2.0,
+            ])
+            .x_object(image_name)
+            .restore_state();
+        pdf.stream(content_id, &content.finish());
+
+        let data = compress(&rgb_data(bitmap))?;
+        let mut image = pdf.image_xobject(image_id, &data);
+        image.filter(Filter::FlateDecode);
+        image
+            .width(bitmap.width.try_into()?)
+            .height(bitmap.height.try_into()?)
+            .bits_per_component(8);
+        image.color_space().device_rgb();
+        image.finish();
+    }
+
+    Ok(pdf.finish())
+}
+
+/// The RGB data of a page, which is opaque as it is rendered onto white.
+fn rgb_data(bitmap: &Bitmap) -> Vec<u8> {
+    match &bitmap.data {
+        BitmapFormat::Rgb(data) => data.clone(),
+        BitmapFormat::Rgba(data) => data
+            .chunks_exact(4)
+            .flat_map(|pixel| &pixel[..3])
+            .copied()
+            .collect(),
+    }
+}
+
+fn compress(data: &[u8]) -> Result<Vec<u8>, Error> {
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(data)?;
+    Ok(encoder.finish()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(width: u32, height: u32) -> Bitmap {
+        Bitmap {
+            width,
+            height,
+            data: BitmapFormat::Rgba(vec![255; (width * height * 4) as usize]),
+        }
+    }
+
+    #[test]
+    fn pdf_has_a_page_per_printed_page() {
+        let settings = PrintJobSettings::default();
+        let pdf = write_pdf(&settings, &[page(576, 756), page(576, 756)]).unwrap();
+        let pdf = String::from_utf8_lossy(&pdf);
+
+        assert!(pdf.starts_with("%PDF-"));
+        assert!(pdf.contains("/Type /Pages\n  /Kids [3 0 R 6 0 R]\n  /Count 2"));
+        assert_eq!(pdf.matches("/Type /Page\n").count(), 2);
+        assert_eq!(pdf.matches("/MediaBox [0 0 612 792]").count(), 2);
+        // The pages are centered within the quarter-inch margins.
+        assert_eq!(pdf.matches("576 0 0 756 18 18 cm").count(), 2);
+        assert_eq!(pdf.matches("/Subtype /Image").count(), 2);
+    }
+}
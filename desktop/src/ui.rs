@@ -1,6 +1,7 @@
 use clipboard::{ClipboardContext, ClipboardProvider};
-use rfd::{MessageButtons, MessageDialog, MessageLevel};
-use ruffle_core::backend::ui::{Error, MouseCursor, UiBackend};
+use rfd::{FileDialog, MessageButtons, MessageDialog, MessageLevel};
+use ruffle_core::backend::ui::{Error, FileDialogResult, FileFilter, MouseCursor, UiBackend};
+use std::path::Path;
 use std::rc::Rc;
 use winit::window::{Fullscreen, Window};
 
@@ -54,6 +55,10 @@ impl UiBackend for DesktopUiBackend {
         self.clipboard.set_contents(content).unwrap();
     }
 
+    fn clipboard_content(&mut self) -> Option<String> {
+        self.clipboard.get_contents().ok()
+    }
+
     fn set_fullscreen(&mut self, is_full: bool) -> Result<(), Error> {
         self.window.set_fullscreen(if is_full {
             Some(Fullscreen::Borderless(None))
@@ -89,4 +94,56 @@ impl UiBackend for DesktopUiBackend {
             .set_buttons(MessageButtons::Ok);
         dialog.show();
     }
+
+    fn display_file_open_dialog(
+        &mut self,
+        filters: &[FileFilter],
+        multiple: bool,
+    ) -> Option<Vec<FileDialogResult>> {
+        let mut dialog = FileDialog::new();
+        for filter in filters {
+            let extensions: Vec<&str> = filter.extensions.iter().map(String::as_str).collect();
+            dialog = dialog.add_filter(&filter.description, &extensions);
+        }
+
+        let paths = if multiple {
+            dialog.pick_files()?
+        } else {
+            vec![dialog.pick_file()?]
+        };
+
+        let mut files = Vec::with_capacity(paths.len());
+        for path in paths {
+            match std::fs::read(&path) {
+                Ok(contents) => files.push(FileDialogResult {
+                    file_name: file_name(&path),
+                    contents,
+                }),
+                Err(e) => log::error!("Unable to read {}: {}", path.to_string_lossy(), e),
+            }
+        }
+        Some(files)
+    }
+
+    fn display_file_save_dialog(
+        &mut self,
+        default_file_name: &str,
+        data: &[u8],
+    ) -> Result<Option<String>, Error> {
+        let path = match FileDialog::new()
+            .set_file_name(default_file_name)
+            .save_file()
+        {
+            Some(path) => path,
+            None => return Ok(None),
+        };
+        std::fs::write(&path, data)?;
+        Ok(Some(file_name(&path)))
+    }
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default()
 }
@@ -0,0 +1,112 @@
+//! Offline audio capture
+
+use ruffle_core::backend::audio::{
+    swf, AudioBackend, AudioMixer, AudioMixerProxy, SoundHandle, SoundInstanceHandle,
+    SoundTransform,
+};
+use ruffle_core::impl_audio_mixer_backend;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+type Error = Box<dyn std::error::Error>;
+
+/// The sample rate of captured audio.
+pub const SAMPLE_RATE: u32 = 44100;
+
+/// The number of channels of captured audio.
+pub const NUM_CHANNELS: u16 = 2;
+
+/// An audio backend that mixes sounds on demand instead of playing them.
+pub struct OfflineAudioBackend {
+    mixer: AudioMixer,
+}
+
+impl OfflineAudioBackend {
+    /// Create a backend, along with the capture that mixes its audio.
+    pub fn new(frame_rate: f64) -> (Self, AudioCapture) {
+        let mixer = AudioMixer::new(NUM_CHANNELS as u8, SAMPLE_RATE);
+        let capture = AudioCapture {
+            proxy: mixer.proxy(),
+            frame_rate,
+            frames_mixed: 0,
+            sample_frames_mixed: 0,
+            samples: vec![],
+        };
+        (Self { mixer }, capture)
+    }
+}
+
+impl AudioBackend for OfflineAudioBackend {
+    impl_audio_mixer_backend!(mixer);
+
+    fn play(&mut self) {}
+
+    fn pause(&mut self) {}
+}
+
+/// The audio mixed from an `OfflineAudioBackend`, one movie frame at a time.
+pub struct AudioCapture {
+    proxy: AudioMixerProxy,
+    frame_rate: f64,
+    frames_mixed: u64,
+    sample_frames_mixed: u64,
+
+    /// The captured samples, interleaved.
+    pub samples: Vec<i16>,
+}
+
+impl AudioCapture {
+    /// Mix the audio played during one movie frame, keeping it only if
+    /// `keep` is set.
+    ///
+    /// Frames are not a whole number of samples long at most frame rates, so
+    /// the length of each frame is rounded such that the audio never drifts
+    /// from the frames.
+    pub fn mix_frame(&mut self, keep: bool) {
+        self.frames_mixed += 1;
+        let end = (self.frames_mixed as f64 * f64::from(SAMPLE_RATE) / self.frame_rate).round();
+        let sample_frames = end as u64 - self.sample_frames_mixed;
+        self.sample_frames_mixed = end as u64;
+
+        let mut buffer = vec![0i16; sample_frames as usize * usize::from(NUM_CHANNELS)];
+        self.proxy.mix(&mut buffer);
+        if keep {
+            self.samples.extend(buffer);
+        }
+    }
+
+    /// Write the captured audio as a 16-bit PCM WAV file.
+    pub fn save_wav(&self, path: &Path) -> Result<(), Error> {
+        write_wav(path, SAMPLE_RATE, &self.samples)
+    }
+}
+
+/// Write interleaved stereo samples as a 16-bit PCM WAV file.
+pub fn write_wav(path: &Path, sample_rate: u32, samples: &[i16]) -> Result<(), Error> {
+    const BYTES_PER_SAMPLE: u16 = 2;
+
+    let data_len = (samples.len() * usize::from(BYTES_PER_SAMPLE)) as u32;
+    let block_align = NUM_CHANNELS * BYTES_PER_SAMPLE;
+
+    let mut out = BufWriter::new(File::create(path)?);
+    out.write_all(b"RIFF")?;
+    out.write_all(&(36 + data_len).to_le_bytes())?;
+    out.write_all(b"WAVE")?;
+    out.write_all(b"fmt ")?;
+    out.write_all(&16u32.to_le_bytes())?;
+    out.write_all(&1u16.to_le_bytes())?; // PCM
+    out.write_all(&NUM_CHANNELS.to_le_bytes())?;
+    out.write_all(&sample_rate.to_le_bytes())?;
+    out.write_all(&(sample_rate * u32::from(block_align)).to_le_bytes())?;
+    out.write_all(&block_align.to_le_bytes())?;
+    out.write_all(&(BYTES_PER_SAMPLE * 8).to_le_bytes())?;
+    out.write_all(b"data")?;
+    out.write_all(&data_len.to_le_bytes())?;
+    for sample in samples {
+        out.write_all(&sample.to_le_bytes())?;
+    }
+    out.flush()?;
+
+    Ok(())
+}
@@ -0,0 +1,139 @@
+//! Encoding captured frames as animations and videos
+
+use image::RgbaImage;
+use std::error::Error;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, ChildStdin, Command, Stdio};
+
+/// Save frames as a looping animated GIF.
+pub fn save_gif(frames: &[RgbaImage], frame_rate: f64, path: &Path) -> Result<(), Box<dyn Error>> {
+    let (width, height) = frames.first().ok_or("No frames to save")?.dimensions();
+    let (width, height): (u16, u16) = (width.try_into()?, height.try_into()?);
+    let mut encoder = gif::Encoder::new(BufWriter::new(File::create(path)?), width, height, &[])?;
+    encoder.set_repeat(gif::Repeat::Infinite)?;
+
+    // GIF delays are in hundredths of a second, and most viewers slow down
+    // anything shorter than two.
+    let delay = ((100.0 / frame_rate).round() as u16).max(2);
+    for image in frames {
+        let mut pixels = image.clone().into_raw();
+        let mut frame = gif::Frame::from_rgba_speed(width, height, &mut pixels, 10);
+        frame.delay = delay;
+        encoder.write_frame(&frame)?;
+    }
+
+    Ok(())
+}
+
+/// Save frames as a looping animated PNG.
+pub fn save_apng(frames: &[RgbaImage], frame_rate: f64, path: &Path) -> Result<(), Box<dyn Error>> {
+    let (width, height) = frames.first().ok_or("No frames to save")?.dimensions();
+    let mut encoder = png::Encoder::new(BufWriter::new(File::create(path)?), width, height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.set_animated(frames.len().try_into()?, 0)?;
+    encoder.set_frame_delay((1000.0 / frame_rate).round() as u16, 1000)?;
+
+    let mut writer = encoder.write_header()?;
+    for image in frames {
+        writer.write_image_data(image)?;
+    }
+    writer.finish()?;
+
+    Ok(())
+}
+
+/// An external encoder that frames are piped into as raw RGBA video, as they
+/// are captured.
+///
+/// The command is split on whitespace, and the placeholders `{width}`,
+/// `{height}`, `{fps}`, `{audio}` and `{output}` in its arguments are replaced
+/// with the frame size, the movie's frame rate, the path of the captured
+/// audio and the output path respectively. For example:
+///
+/// `ffmpeg -y -f rawvideo -pix_fmt rgba -s {width}x{height} -r {fps} -i - -i {audio} -pix_fmt yuv420p {output}`
+pub struct Encoder {
+    command: String,
+    audio: PathBuf,
+    output: PathBuf,
+
+    /// The program name, process and input of the encoder, once it has been
+    /// started.
+    process: Option<(String, Child, ChildStdin)>,
+}
+
+impl Encoder {
+    pub fn new(command: &str, audio: &Path, output: &Path) -> Self {
+        Self {
+            command: command.to_string(),
+            audio: audio.to_path_buf(),
+            output: output.to_path_buf(),
+            process: None,
+        }
+    }
+
+    /// Whether an encoder command reads the captured audio.
+    ///
+    /// The audio file must then be complete before the encoder starts.
+    pub fn uses_audio(command: &str) -> bool {
+        command.contains("{audio}")
+    }
+
+    /// Write a frame to the encoder.
+    ///
+    /// The encoder is started on the first frame, which the frame size is
+    /// taken from.
+    pub fn write_frame(
+        &mut self,
+        image: &RgbaImage,
+        frame_rate: f64,
+    ) -> Result<(), Box<dyn Error>> {
+        if self.process.is_none() {
+            self.process = Some(self.start(image.dimensions(), frame_rate)?);
+        }
+        if let Some((_, _, stdin)) = &mut self.process {
+            stdin.write_all(image)?;
+        }
+
+        Ok(())
+    }
+
+    fn start(
+        &self,
+        (width, height): (u32, u32),
+        frame_rate: f64,
+    ) -> Result<(String, Child, ChildStdin), Box<dyn Error>> {
+        let mut args = self.command.split_whitespace().map(|arg| {
+            arg.replace("{width}", &width.to_string())
+                .replace("{height}", &height.to_string())
+                .replace("{fps}", &frame_rate.to_string())
+                .replace("{audio}", &self.audio.to_string_lossy())
+                .replace("{output}", &self.output.to_string_lossy())
+        });
+        let program = args.next().ok_or("Encoder command is empty")?;
+
+        let mut child = Command::new(&program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .spawn()
+            .map_err(|e| format!("Couldn't start encoder {}: {}", program, e))?;
+        let stdin = child.stdin.take().unwrap();
+
+        Ok((program, child, stdin))
+    }
+
+    /// Close the encoder's input, and wait for it to finish.
+    pub fn finish(self) -> Result<(), Box<dyn Error>> {
+        let (program, mut child, stdin) = self.process.ok_or("No frames to encode")?;
+        drop(stdin);
+
+        let status = child.wait()?;
+        if !status.success() {
+            return Err(format!("Encoder {} failed with {}", program, status).into());
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,579 @@
+//! Extraction of a movie's library assets
+//!
+//! Assets are read from the movie's tags rather than from a running player,
+//! which hands bitmaps and sounds over to its backends and only keeps handles
+//! to them.
+
+use crate::audio::write_wav;
+use crate::svg::{shape_to_svg, BitmapRef};
+use ruffle_core::backend::audio::decoders::{make_decoder, make_stream_decoder, Decoder};
+use ruffle_core::backend::render::{
+    decode_define_bits_jpeg, decode_define_bits_lossless, determine_jpeg_tag_format,
+    glue_tables_to_jpeg, remove_invalid_jpeg_data, unmultiply_alpha_rgba, Bitmap, BitmapFormat,
+    JpegTagFormat,
+};
+use ruffle_core::interpolate_morph_shape;
+use ruffle_core::shape_utils::swf_glyph_to_shape;
+use ruffle_core::swf::{
+    self, AudioCompression, CharacterId, Color, FillStyle, Glyph, ShapeRecord, SoundStreamHead,
+    SwfStr, Tag, TagCode,
+};
+use ruffle_core::tag_utils::{SwfMovie, SwfSlice};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs::{create_dir_all, write};
+use std::io::Cursor;
+use std::path::Path;
+use std::sync::Arc;
+
+/// The character ID that the main timeline's stream sound is saved under.
+const ROOT_TIMELINE: CharacterId = 0;
+
+/// What an asset is saved as.
+enum Content {
+    /// A file to save as is.
+    Bytes(Vec<u8>),
+
+    /// A shape, saved as SVG once the names of the bitmaps it uses are known.
+    Shape(swf::Shape),
+
+    /// Interleaved stereo samples, saved as WAV.
+    Samples { sample_rate: u32, samples: Vec<i16> },
+}
+
+/// A file to extract.
+struct Asset {
+    /// The character the file belongs to.
+    id: CharacterId,
+
+    /// What follows the character's ID and name in the file name.
+    suffix: String,
+
+    /// The name of the file inside a directory for the character, for
+    /// characters with many files such as fonts.
+    inner_file: Option<String>,
+
+    content: Content,
+}
+
+/// Collects the assets of a movie.
+///
+/// Names of assets are only known once all of the movie's `ExportAssets` and
+/// `SymbolClass` tags are read, so nothing is written until the end.
+#[derive(Default)]
+struct Extractor {
+    assets: Vec<Asset>,
+    names: HashMap<CharacterId, String>,
+    bitmaps: HashMap<CharacterId, (&'static str, u32, u32)>,
+    jpeg_tables: Option<Vec<u8>>,
+    v1_fonts: Vec<(CharacterId, Vec<Vec<ShapeRecord>>)>,
+    font_code_tables: HashMap<CharacterId, Vec<u16>>,
+    errors: Vec<String>,
+}
+
+/// Extract the shapes, bitmaps, sounds, fonts and binary data of a movie into
+/// the `output` directory.
+///
+/// Each file is named after its character ID, followed by the name it is
+/// exported or linked to a class under, if any. Assets that cannot be decoded
+/// are skipped and reported on stderr.
+///
+/// Returns the number of files written.
+pub fn extract_assets(movie: &Arc<SwfMovie>, output: &Path) -> Result<usize, Box<dyn Error>> {
+    let mut extractor = Extractor::default();
+    extractor.read_timeline(movie, movie.data(), ROOT_TIMELINE);
+    extractor.resolve_v1_fonts();
+
+    for error in &extractor.errors {
+        eprintln!("{}", error);
+    }
+
+    create_dir_all(output)?;
+    extractor.write(output)
+}
+
+impl Extractor {
+    /// Read the tags of a timeline, recursing into the timelines of sprites.
+    fn read_timeline(&mut self, movie: &Arc<SwfMovie>, data: &[u8], timeline: CharacterId) {
+        let version = movie.version();
+        let encoding = SwfStr::encoding_for_version(version);
+        let mut stream_head = None;
+
+        let mut reader = swf::read::Reader::new(data, version);
+        loop {
+            let tag_start: &[u8] = *reader.get_mut();
+            let (tag_code, length) = match reader.read_tag_code_and_length() {
+                Ok((0, _)) | Err(_) => break,
+                Ok(tag) => tag,
+            };
+            let input = reader.get_mut();
+            let length = length.min(input.len());
+            let body = &input[..length];
+            *input = &input[length..];
+
+            // Sprites are walked here rather than parsed, as their stream
+            // sound is decoded from the sprite's own tags.
+            if tag_code == TagCode::DefineSprite as u16 {
+                if body.len() >= 4 {
+                    let id = u16::from_le_bytes([body[0], body[1]]);
+                    self.read_timeline(movie, &body[4..], id);
+                }
+                continue;
+            }
+
+            let tag_data = &tag_start[..tag_start.len() - input.len()];
+            match swf::read::Reader::new(tag_data, version).read_tag() {
+                Ok(Tag::SoundStreamHead(head)) | Ok(Tag::SoundStreamHead2(head)) => {
+                    stream_head = Some(head)
+                }
+                Ok(tag) => self.read_tag(tag, encoding),
+                Err(e) => self.errors.push(format!(
+                    "Couldn't read {}: {}",
+                    TagCode::format(tag_code),
+                    e
+                )),
+            }
+        }
+
+        if let Some(head) = stream_head {
+            if let Err(e) = self.read_stream(movie, data, timeline, &head) {
+                self.errors.push(format!(
+                    "Couldn't decode stream sound of character {}: {}",
+                    timeline, e
+                ));
+            }
+        }
+    }
+
+    fn read_tag(&mut self, tag: Tag<'_>, encoding: &'static swf::Encoding) {
+        match tag {
+            Tag::ExportAssets(exports) => {
+                for export in exports {
+                    let name = export.name.to_str_lossy(encoding).into_owned();
+                    self.names.insert(export.id, name);
+                }
+            }
+            Tag::SymbolClass(links) => {
+                for link in links {
+                    let name = link.class_name.to_str_lossy(encoding).into_owned();
+                    self.names.entry(link.id).or_insert(name);
+                }
+            }
+            Tag::JpegTables(data) => self.jpeg_tables = Some(data.to_vec()),
+            Tag::DefineFontInfo(info) => {
+                self.font_code_tables.insert(info.id, info.code_table);
+            }
+            tag => {
+                if let Some(id) = tag_character_id(&tag) {
+                    if let Err(e) = self.read_character(tag, encoding) {
+                        self.errors
+                            .push(format!("Couldn't extract character {}: {}", id, e));
+                    }
+                }
+            }
+        }
+    }
+
+    fn read_character(
+        &mut self,
+        tag: Tag<'_>,
+        encoding: &'static swf::Encoding,
+    ) -> Result<(), Box<dyn Error>> {
+        match tag {
+            Tag::DefineShape(shape) => self.add(shape.id, ".svg", Content::Shape(shape)),
+            Tag::DefineMorphShape(morph_shape) => {
+                let start = interpolate_morph_shape(&morph_shape.start, &morph_shape.end, 0);
+                let end = interpolate_morph_shape(&morph_shape.start, &morph_shape.end, 65535);
+                self.add(morph_shape.id, "_start.svg", Content::Shape(start));
+                self.add(morph_shape.id, "_end.svg", Content::Shape(end));
+            }
+            Tag::DefineBits { id, jpeg_data } => {
+                let jpeg_tables = self.jpeg_tables.as_deref();
+                let jpeg = remove_invalid_jpeg_data(&glue_tables_to_jpeg(jpeg_data, jpeg_tables))
+                    .into_owned();
+                let bitmap = decode_define_bits_jpeg(&jpeg, None)?;
+                self.add_bitmap(id, ".jpg", &bitmap, jpeg);
+            }
+            Tag::DefineBitsJpeg2 { id, jpeg_data } => {
+                let bitmap = decode_define_bits_jpeg(jpeg_data, None)?;
+                let (suffix, data) = match determine_jpeg_tag_format(jpeg_data) {
+                    JpegTagFormat::Jpeg => (".jpg", remove_invalid_jpeg_data(jpeg_data)),
+                    JpegTagFormat::Png => (".png", jpeg_data.into()),
+                    JpegTagFormat::Gif => (".gif", jpeg_data.into()),
+                    JpegTagFormat::Unknown => return Err("Unknown bitmap data format".into()),
+                };
+                self.add_bitmap(id, suffix, &bitmap, data.into_owned());
+            }
+            Tag::DefineBitsJpeg3(jpeg) => {
+                let bitmap = decode_define_bits_jpeg(jpeg.data, Some(jpeg.alpha_data))?;
+                let png = encode_png(&bitmap)?;
+                self.add_bitmap(jpeg.id, ".png", &bitmap, png);
+            }
+            Tag::DefineBitsLossless(lossless) => {
+                let bitmap = decode_define_bits_lossless(&lossless)?;
+                let png = encode_png(&bitmap)?;
+                self.add_bitmap(lossless.id, ".png", &bitmap, png);
+            }
+            Tag::DefineSound(sound) => {
+                if sound.format.compression == AudioCompression::Mp3 {
+                    // MP3 sounds start with the number of samples to skip.
+                    let mp3 = sound.data.get(2..).unwrap_or_default().to_vec();
+                    self.add(sound.id, ".mp3", Content::Bytes(mp3));
+                } else {
+                    let decoder = make_decoder(&sound.format, Cursor::new(sound.data.to_vec()))?;
+                    self.add(sound.id, ".wav", decode(decoder));
+                }
+            }
+            Tag::DefineFont(font) => self.v1_fonts.push((font.id, font.glyphs)),
+            Tag::DefineFont2(font) => {
+                let name = font.name.to_str_lossy(encoding).into_owned();
+                self.names.entry(font.id).or_insert(name);
+                for glyph in &font.glyphs {
+                    self.add_glyph(font.id, format!("U+{:04X}", glyph.code), glyph);
+                }
+            }
+            Tag::DefineFont4(font) => {
+                let name = font.name.to_str_lossy(encoding).into_owned();
+                self.names.entry(font.id).or_insert(name);
+                if let Some(data) = font.data {
+                    self.add(font.id, ".otf", Content::Bytes(data.to_vec()));
+                }
+            }
+            Tag::DefineBinaryData(binary_data) => {
+                let data = binary_data.data.to_vec();
+                self.add(binary_data.id, ".bin", Content::Bytes(data));
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    /// Decode the stream sound of a timeline from its `SoundStreamBlock` tags.
+    fn read_stream(
+        &mut self,
+        movie: &Arc<SwfMovie>,
+        data: &[u8],
+        timeline: CharacterId,
+        head: &SoundStreamHead,
+    ) -> Result<(), Box<dyn Error>> {
+        let slice = SwfSlice::from(movie.clone())
+            .to_subslice(data)
+            .ok_or("Timeline is outside of the movie")?;
+        let decoder = make_stream_decoder(head, slice)?;
+        self.add(timeline, "_stream.wav", decode(decoder));
+        Ok(())
+    }
+
+    /// Add the glyphs of `DefineFont` tags, which are only known by their
+    /// code points if the font has a `DefineFontInfo` tag.
+    fn resolve_v1_fonts(&mut self) {
+        for (id, glyphs) in std::mem::take(&mut self.v1_fonts) {
+            let code_table = self.font_code_tables.remove(&id);
+            for (index, shape_records) in glyphs.into_iter().enumerate() {
+                let name = match code_table.as_ref().and_then(|codes| codes.get(index)) {
+                    Some(code) => format!("U+{:04X}", code),
+                    None => index.to_string(),
+                };
+                let glyph = Glyph {
+                    shape_records,
+                    code: 0,
+                    advance: None,
+                    bounds: None,
+                };
+                self.add_glyph(id, name, &glyph);
+            }
+        }
+    }
+
+    fn add(&mut self, id: CharacterId, suffix: &str, content: Content) {
+        self.assets.push(Asset {
+            id,
+            suffix: suffix.to_string(),
+            inner_file: None,
+            content,
+        });
+    }
+
+    fn add_bitmap(
+        &mut self,
+        id: CharacterId,
+        suffix: &'static str,
+        bitmap: &Bitmap,
+        data: Vec<u8>,
+    ) {
+        self.bitmaps
+            .insert(id, (suffix, bitmap.width, bitmap.height));
+        self.add(id, suffix, Content::Bytes(data));
+    }
+
+    fn add_glyph(&mut self, font_id: CharacterId, name: String, glyph: &Glyph) {
+        let mut shape = swf_glyph_to_shape(glyph);
+        shape.styles.fill_styles = vec![FillStyle::Color(Color::from_rgb(0, 255))];
+        self.assets.push(Asset {
+            id: font_id,
+            suffix: String::new(),
+            inner_file: Some(format!("{}.svg", name)),
+            content: Content::Shape(shape),
+        });
+    }
+
+    /// The name of the file that an asset of a character is saved to.
+    fn file_name(&self, id: CharacterId, suffix: &str) -> String {
+        match self.names.get(&id) {
+            Some(name) => format!("{}_{}{}", id, sanitize(name), suffix),
+            None => format!("{}{}", id, suffix),
+        }
+    }
+
+    fn write(&self, output: &Path) -> Result<usize, Box<dyn Error>> {
+        let bitmap = |id| {
+            self.bitmaps
+                .get(&id)
+                .map(|(suffix, width, height)| BitmapRef {
+                    href: self.file_name(id, suffix),
+                    width: *width,
+                    height: *height,
+                })
+        };
+
+        for asset in &self.assets {
+            let mut path = output.join(self.file_name(asset.id, &asset.suffix));
+            if let Some(inner_file) = &asset.inner_file {
+                create_dir_all(&path)?;
+                path.push(inner_file);
+            }
+
+            match &asset.content {
+                Content::Bytes(data) => write(&path, data)?,
+                Content::Shape(shape) => write(&path, shape_to_svg(shape, bitmap))?,
+                Content::Samples {
+                    sample_rate,
+                    samples,
+                } => write_wav(&path, *sample_rate, samples)?,
+            }
+        }
+
+        Ok(self.assets.len())
+    }
+}
+
+/// The character that a tag defines, if any.
+fn tag_character_id(tag: &Tag<'_>) -> Option<CharacterId> {
+    match tag {
+        Tag::DefineShape(shape) => Some(shape.id),
+        Tag::DefineMorphShape(morph_shape) => Some(morph_shape.id),
+        Tag::DefineBits { id, .. } | Tag::DefineBitsJpeg2 { id, .. } => Some(*id),
+        Tag::DefineBitsJpeg3(jpeg) => Some(jpeg.id),
+        Tag::DefineBitsLossless(lossless) => Some(lossless.id),
+        Tag::DefineSound(sound) => Some(sound.id),
+        Tag::DefineFont(font) => Some(font.id),
+        Tag::DefineFont2(font) => Some(font.id),
+        Tag::DefineFont4(font) => Some(font.id),
+        Tag::DefineBinaryData(binary_data) => Some(binary_data.id),
+        _ => None,
+    }
+}
+
+/// Decode a whole sound.
+fn decode(decoder: Box<dyn Decoder + Send>) -> Content {
+    let sample_rate = decoder.sample_rate().into();
+    let samples = decoder.flatten().collect();
+    Content::Samples {
+        sample_rate,
+        samples,
+    }
+}
+
+/// Encode a decoded bitmap as PNG, with straight alpha.
+fn encode_png(bitmap: &Bitmap) -> Result<Vec<u8>, Box<dyn Error>> {
+    let (color_type, data) = match &bitmap.data {
+        BitmapFormat::Rgb(data) => (png::ColorType::Rgb, data.clone()),
+        BitmapFormat::Rgba(data) => {
+            let mut data = data.clone();
+            unmultiply_alpha_rgba(&mut data);
+            (png::ColorType::Rgba, data)
+        }
+    };
+
+    let mut png = vec![];
+    {
+        let mut encoder = png::Encoder::new(&mut png, bitmap.width, bitmap.height);
+        encoder.set_color(color_type);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder.write_header()?.write_image_data(&data)?;
+    }
+    Ok(png)
+}
+
+/// Make an export or class name safe to use in a file name.
+fn sanitize(name: &str) -> String {
+    name.chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::fs::{read_to_string, remove_dir_all};
+    use std::path::PathBuf;
+    use walkdir::WalkDir;
+    use xml::reader::{EventReader, XmlEvent};
+
+    /// A movie with 12 shapes, a morph shape and two fonts of 15 and 4 glyphs.
+    const SWF: &str = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../tests/tests/swfs/avm1/movieclip_hittest_shapeflag/test.swf"
+    );
+
+    /// Check that path data is a move followed by the commands `path_data`
+    /// writes, each with the right number of coordinates.
+    fn check_path_data(data: &str) {
+        assert!(
+            data.starts_with('M'),
+            "Path doesn't start with a move: {}",
+            data
+        );
+        let mut rest = data;
+        while let Some(command) = rest.chars().next() {
+            let end = rest[1..]
+                .find(|c: char| c.is_ascii_alphabetic())
+                .map_or(rest.len(), |i| i + 1);
+            let arguments = &rest[1..end];
+            let expected = match command {
+                'M' | 'L' => 2,
+                'Q' => 4,
+                'Z' => 0,
+                _ => panic!("Unexpected command {} in path: {}", command, data),
+            };
+            let coordinates: Vec<f64> = if arguments.is_empty() {
+                vec![]
+            } else {
+                arguments
+                    .split(' ')
+                    .map(|n| n.parse().unwrap_or(f64::NAN))
+                    .collect()
+            };
+            assert_eq!(coordinates.len(), expected, "Bad path: {}", data);
+            assert!(
+                coordinates.iter().all(|n| n.is_finite()),
+                "Bad coordinates in path: {}",
+                data
+            );
+            rest = &rest[end..];
+        }
+    }
+
+    /// Check that a file is a well-formed SVG document with a view box, whose
+    /// paths are valid and whose paints refer to gradients or patterns in it.
+    ///
+    /// Returns the number of paths in the document.
+    fn check_svg(path: &Path) -> usize {
+        let svg = read_to_string(path).unwrap();
+        let mut is_root = true;
+        let mut ids = HashSet::new();
+        let mut references = vec![];
+        let mut paths = 0;
+        for event in EventReader::from_str(&svg) {
+            let event = event.unwrap_or_else(|e| panic!("{:?} isn't well-formed: {}", path, e));
+            if let XmlEvent::StartElement {
+                name, attributes, ..
+            } = event
+            {
+                let attribute = |name: &str| {
+                    attributes
+                        .iter()
+                        .find(|attribute| attribute.name.local_name == name)
+                        .map(|attribute| attribute.value.as_str())
+                };
+                if is_root {
+                    assert_eq!(name.local_name, "svg", "{:?}", path);
+                    assert_eq!(
+                        name.namespace.as_deref(),
+                        Some("http://www.w3.org/2000/svg"),
+                        "{:?}",
+                        path
+                    );
+                    let view_box: Vec<f64> = attribute("viewBox")
+                        .unwrap()
+                        .split(' ')
+                        .map(|n| n.parse().unwrap())
+                        .collect();
+                    assert_eq!(view_box.len(), 4, "{:?}", path);
+                    assert!(view_box[2] >= 0.0 && view_box[3] >= 0.0, "{:?}", path);
+                    is_root = false;
+                }
+
+                if let Some(id) = attribute("id") {
+                    ids.insert(id.to_string());
+                }
+                for paint in [attribute("fill"), attribute("stroke")]
+                    .into_iter()
+                    .flatten()
+                {
+                    if let Some(id) = paint.strip_prefix("url(#") {
+                        references.push(id.trim_end_matches(')').to_string());
+                    }
+                }
+                if name.local_name == "path" {
+                    check_path_data(attribute("d").unwrap());
+                    paths += 1;
+                }
+            }
+        }
+
+        for id in references {
+            assert!(
+                ids.contains(&id),
+                "{:?} refers to missing paint {}",
+                path,
+                id
+            );
+        }
+        paths
+    }
+
+    #[test]
+    fn extracted_shapes_are_valid_svg() {
+        let output = std::env::temp_dir().join("ruffle_exporter_extract");
+        let _ = remove_dir_all(&output);
+        let movie = Arc::new(SwfMovie::from_path(SWF, None).unwrap());
+        let count = extract_assets(&movie, &output).unwrap();
+
+        let files: Vec<PathBuf> = WalkDir::new(&output)
+            .into_iter()
+            .map(|entry| entry.unwrap().into_path())
+            .filter(|path| path.is_file())
+            .collect();
+        // The movie has nothing but shapes and fonts, so every asset is an SVG.
+        assert_eq!(count, 12 + 2 + 15 + 4);
+        assert_eq!(files.len(), count);
+        for file in [
+            "1.svg",
+            "28.svg",
+            "27_start.svg",
+            "27_end.svg",
+            "21_Arial/U+0041.svg",
+            "22_Courier New/U+0048.svg",
+        ] {
+            assert!(output.join(file).is_file(), "{} wasn't extracted", file);
+        }
+
+        let mut paths = 0;
+        for file in &files {
+            assert_eq!(file.extension().unwrap(), "svg");
+            paths += check_svg(file);
+        }
+        assert!(paths > 0);
+        // A space has no outline.
+        assert_eq!(check_svg(&output.join("21_Arial/U+0020.svg")), 0);
+
+        remove_dir_all(output).unwrap();
+    }
+}
@@ -1,15 +1,27 @@
+mod audio;
+mod encode;
+mod extract;
+mod svg;
+
+use crate::audio::{AudioCapture, OfflineAudioBackend};
+use crate::encode::Encoder;
 use clap::Parser;
 use image::RgbaImage;
 use indicatif::{ProgressBar, ProgressStyle};
-use ruffle_core::backend::audio::NullAudioBackend;
-use ruffle_core::backend::locale::NullLocaleBackend;
+use ruffle_core::backend::audio::{AudioBackend, NullAudioBackend};
+use ruffle_core::backend::font::NullFontBackend;
+use ruffle_core::backend::locale::{LocaleBackend, NullLocaleBackend};
 use ruffle_core::backend::log::NullLogBackend;
-use ruffle_core::backend::navigator::NullNavigatorBackend;
-use ruffle_core::backend::storage::MemoryStorageBackend;
+use ruffle_core::backend::navigator::{NavigatorBackend, NullExecutor, NullNavigatorBackend};
+use ruffle_core::backend::print::NullPrintBackend;
+use ruffle_core::backend::render::{BitmapFormat, RenderBackend};
+use ruffle_core::backend::storage::{MemoryStorageBackend, StorageBackend};
 use ruffle_core::backend::ui::NullUiBackend;
 use ruffle_core::backend::video::SoftwareVideoBackend;
+use ruffle_core::recording::{Recording, Replayer};
 use ruffle_core::tag_utils::SwfMovie;
 use ruffle_core::Player;
+use ruffle_render_software::SoftwareRenderBackend;
 use ruffle_render_wgpu::clap::{GraphicsBackend, PowerPreference};
 use ruffle_render_wgpu::target::TextureTarget;
 use ruffle_render_wgpu::{wgpu, Descriptors, WgpuRenderBackend};
@@ -34,6 +46,51 @@ struct SizeOpt {
     height: Option<u32>,
 }
 
+#[derive(Parser, Debug, Copy, Clone)]
+struct FramesOpt {
+    /// Number of frames to capture per file
+    #[clap(short = 'f', long = "frames", default_value = "1")]
+    frames: u32,
+
+    /// Number of frames to skip
+    #[clap(long = "skipframes", default_value = "0")]
+    skipframes: u32,
+
+    /// The last frame to capture per file, counting from 0. Overrides --frames
+    #[clap(long = "end-frame")]
+    end_frame: Option<u32>,
+
+    /// Number of seconds to capture per file, at the movie's frame rate.
+    /// Overrides --frames
+    #[clap(long = "duration")]
+    duration: Option<f64>,
+}
+
+impl FramesOpt {
+    /// The number of frames to capture from a movie with the given frame rate.
+    fn count(&self, frame_rate: f64) -> u32 {
+        if let Some(end_frame) = self.end_frame {
+            (end_frame + 1).saturating_sub(self.skipframes)
+        } else if let Some(duration) = self.duration {
+            (duration * frame_rate).ceil() as u32
+        } else {
+            self.frames
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Debug, clap::ArgEnum)]
+enum ExportFormat {
+    /// A PNG per frame
+    Png,
+
+    /// An animated GIF
+    Gif,
+
+    /// An animated PNG
+    Apng,
+}
+
 #[derive(Parser, Debug)]
 #[clap(name = "Ruffle Exporter", author, version)]
 struct Opt {
@@ -46,16 +103,45 @@ struct Opt {
     /// - If given one swf and one frame, the name of the swf + ".png"
     /// - If given one swf and multiple frames, the name of the swf as a directory
     /// - If given multiple swfs, this field is required.
+    /// When extracting assets, this is always a directory.
     #[clap(name = "output", parse(from_os_str))]
     output_path: Option<PathBuf>,
 
-    /// Number of frames to capture per file
-    #[clap(short = 'f', long = "frames", default_value = "1")]
-    frames: u32,
-
-    /// Number of frames to skip
-    #[clap(long = "skipframes", default_value = "0")]
-    skipframes: u32,
+    #[clap(flatten)]
+    range: FramesOpt,
+
+    /// Format to save the captured frames in
+    #[clap(long, ignore_case = true, default_value = "png", arg_enum)]
+    format: ExportFormat,
+
+    /// Capture the movie's audio, and save it as a WAV file alongside the frames
+    #[clap(long)]
+    audio: bool,
+
+    /// Pipe the frames as raw RGBA video to an external encoder, such as ffmpeg.
+    /// The audio is always captured for the encoder.
+    /// The placeholders {width}, {height}, {fps}, {audio} and {output} in the
+    /// command are replaced with the frame size, frame rate, path of the
+    /// captured audio and output path.
+    /// If the command reads the audio, the movie is run once beforehand to
+    /// capture all of it
+    #[clap(long)]
+    encoder: Option<String>,
+
+    /// The file extension, and so the container format, of videos made with
+    /// --encoder. An output path given for a single swf is used as is
+    #[clap(long, default_value = "mp4")]
+    container: String,
+
+    /// Extract the shapes, bitmaps, sounds, fonts and binary data in each swf's
+    /// library instead of capturing frames
+    #[clap(long)]
+    extract: bool,
+
+    /// Drive the movie with a session recorded by the desktop player's --record,
+    /// so that frames are captured as they were seen. Only for a single swf
+    #[clap(long, parse(from_os_str))]
+    replay: Option<PathBuf>,
 
     /// Don't show a progress bar
     #[clap(short, long)]
@@ -64,6 +150,11 @@ struct Opt {
     #[clap(flatten)]
     size: SizeOpt,
 
+    /// Render on the CPU instead of through wgpu. This is slower, but needs no
+    /// graphics device and gives the same images on every machine
+    #[clap(long)]
+    software: bool,
+
     /// Type of graphics backend to use. Not all options may be supported by your current system.
     /// Default will attempt to pick the most supported graphics backend.
     #[clap(long, short, ignore_case = true, default_value = "default", arg_enum)]
@@ -80,15 +171,96 @@ struct Opt {
     trace_path: Option<PathBuf>,
 }
 
+/// The seed of the random number generator when not replaying, so that each
+/// run of a movie, such as the audio and video runs of an encoder export,
+/// behaves the same.
+const RNG_SEED: u64 = 0;
+
+impl Opt {
+    /// The extension of the file to save a capture with the given number of
+    /// frames to, or `None` if it should be saved to a directory.
+    fn file_extension(&self, num_frames: usize) -> Option<&str> {
+        if self.encoder.is_some() {
+            return Some(&self.container);
+        }
+        match self.format {
+            ExportFormat::Png if num_frames == 1 => Some("png"),
+            ExportFormat::Png => None,
+            ExportFormat::Gif => Some("gif"),
+            ExportFormat::Apng => Some("png"),
+        }
+    }
+}
+
+/// The renderer that frames are captured with.
+enum Renderer {
+    Wgpu(Descriptors),
+    Software,
+}
+
+/// Where captured frames go.
+enum Frames {
+    /// Kept in memory, to be saved once the capture is done.
+    Collected(Vec<RgbaImage>),
+
+    /// Piped into an external encoder as they are captured.
+    Encoded { encoder: Encoder, count: usize },
+
+    /// Not rendered at all, as only the audio is wanted.
+    Skipped,
+}
+
+impl Frames {
+    fn push(&mut self, image: RgbaImage, frame_rate: f64) -> Result<(), Box<dyn Error>> {
+        match self {
+            Frames::Collected(frames) => frames.push(image),
+            Frames::Encoded { encoder, count } => {
+                encoder.write_frame(&image, frame_rate)?;
+                *count += 1;
+            }
+            Frames::Skipped => {}
+        }
+
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        match self {
+            Frames::Collected(frames) => frames.len(),
+            Frames::Encoded { count, .. } => *count,
+            Frames::Skipped => 0,
+        }
+    }
+}
+
+/// The frames and audio captured from a movie.
+struct Capture {
+    frames: Frames,
+    frame_rate: f64,
+    audio: Option<AudioCapture>,
+}
+
 fn take_screenshot(
-    descriptors: Descriptors,
+    renderer: Renderer,
     swf_path: &Path,
-    frames: u32,
-    skipframes: u32,
+    opt: &Opt,
     progress: &Option<ProgressBar>,
-    size: SizeOpt,
-) -> Result<(Descriptors, Vec<RgbaImage>), Box<dyn std::error::Error>> {
-    let movie = SwfMovie::from_path(&swf_path, None)?;
+    capture_audio: bool,
+    mut frames: Frames,
+) -> Result<(Renderer, Capture), Box<dyn std::error::Error>> {
+    let range = opt.range;
+    let size = opt.size;
+    let mut movie = SwfMovie::from_path(&swf_path, None)?;
+    let frame_rate = movie.frame_rate().to_f64();
+    let num_frames = range.count(frame_rate);
+    let skipframes = range.skipframes;
+
+    if let Some(progress) = &progress {
+        if num_frames != range.frames {
+            let length = progress.length() + u64::from(num_frames);
+            progress.set_length(length.saturating_sub(u64::from(range.frames)));
+        }
+    }
 
     let width = size
         .width
@@ -102,26 +274,63 @@ fn take_screenshot(
         .unwrap_or_else(|| movie.height().to_pixels());
     let height = (height * size.scale).round() as u32;
 
-    let target = TextureTarget::new(&descriptors.device, (width, height));
+    let (audio_backend, mut audio): (Box<dyn AudioBackend>, _) = if capture_audio {
+        let (backend, capture) = OfflineAudioBackend::new(frame_rate);
+        (Box::new(backend), Some(capture))
+    } else {
+        (Box::new(NullAudioBackend::new()), None)
+    };
+
+    let render_backend: Box<dyn RenderBackend> = match renderer {
+        Renderer::Wgpu(descriptors) => {
+            let target = TextureTarget::new(&descriptors.device, (width, height));
+            Box::new(WgpuRenderBackend::new(descriptors, target)?)
+        }
+        Renderer::Software => Box::new(SoftwareRenderBackend::new(width, height)),
+    };
+    let mut navigator: Box<dyn NavigatorBackend> = Box::new(NullNavigatorBackend::new());
+    let mut storage: Box<dyn StorageBackend> = Box::new(MemoryStorageBackend::default());
+    let mut locale: Box<dyn LocaleBackend> = Box::new(NullLocaleBackend::new());
+    let mut replay = None;
+    if let Some(path) = &opt.replay {
+        let mut replayer = Replayer::new(Recording::load(path)?);
+        replayer.ignore_viewport_changes();
+        movie.append_parameters(replayer.parameters().iter().cloned());
+        let (executor, channel) = NullExecutor::new();
+        navigator = replayer.navigator(Box::new(NullNavigatorBackend::with_base_path(
+            swf_path.parent().unwrap_or_else(|| Path::new("")),
+            channel,
+        )));
+        storage = replayer.storage();
+        locale = replayer.locale();
+        replay = Some((replayer, executor));
+    }
+
     let player = Player::new(
-        Box::new(WgpuRenderBackend::new(descriptors, target)?),
-        Box::new(NullAudioBackend::new()),
-        Box::new(NullNavigatorBackend::new()),
-        Box::new(MemoryStorageBackend::default()),
-        Box::new(NullLocaleBackend::new()),
+        render_backend,
+        audio_backend,
+        navigator,
+        storage,
+        locale,
         Box::new(SoftwareVideoBackend::new()),
         Box::new(NullLogBackend::new()),
         Box::new(NullUiBackend::new()),
+        Box::new(NullPrintBackend::new()),
+        Box::new(NullFontBackend::new()),
     )?;
 
+    let seed = match &replay {
+        Some((replayer, _)) => replayer.seed(),
+        None => RNG_SEED,
+    };
+    player.lock().unwrap().set_rng_seed(seed);
     player
         .lock()
         .unwrap()
         .set_viewport_dimensions(width, height, size.scale as f64);
     player.lock().unwrap().set_root_movie(Arc::new(movie));
 
-    let mut result = Vec::new();
-    let totalframes = frames + skipframes;
+    let totalframes = num_frames + skipframes;
 
     for i in 0..totalframes {
         if let Some(progress) = &progress {
@@ -131,17 +340,40 @@ fn take_screenshot(
                 i
             ));
         }
-        player.lock().unwrap().run_frame();
-        if i >= skipframes {
+        if let Some((replayer, executor)) = &mut replay {
+            replayer.seek(&player, i + 1, &mut || {
+                if let Err(e) = executor.poll_all() {
+                    log::error!("Error in replayed fetch: {}", e);
+                }
+            });
+        } else {
+            player.lock().unwrap().run_frame();
+            player.lock().unwrap().update_timers(1000.0 / frame_rate);
+        }
+        if let Some(audio) = &mut audio {
+            audio.mix_frame(i >= skipframes);
+        }
+        if i >= skipframes && !matches!(frames, Frames::Skipped) {
             player.lock().unwrap().render();
             let mut player = player.lock().unwrap();
-            let renderer = player
-                .renderer_mut()
-                .downcast_mut::<WgpuRenderBackend<TextureTarget>>()
-                .unwrap();
-            let target = renderer.target();
-            if let Some(image) = target.capture(renderer.device()) {
-                result.push(image);
+            let renderer = player.renderer_mut();
+            let image = if let Some(renderer) =
+                renderer.downcast_mut::<WgpuRenderBackend<TextureTarget>>()
+            {
+                renderer.target().capture(renderer.device())
+            } else if let Some(renderer) = renderer.downcast_mut::<SoftwareRenderBackend>() {
+                let frame = renderer.capture_frame();
+                match frame.data {
+                    BitmapFormat::Rgba(rgba) => {
+                        RgbaImage::from_raw(frame.width, frame.height, rgba)
+                    }
+                    BitmapFormat::Rgb(_) => None,
+                }
+            } else {
+                None
+            };
+            if let Some(image) = image {
+                frames.push(image, frame_rate)?;
             } else {
                 return Err(format!("Unable to capture frame {} of {:?}", i, swf_path).into());
             }
@@ -152,16 +384,121 @@ fn take_screenshot(
         }
     }
 
-    let descriptors = Arc::try_unwrap(player)
+    let renderer = match Arc::try_unwrap(player)
         .ok()
         .unwrap()
         .into_inner()?
         .destroy()
         .downcast::<WgpuRenderBackend<TextureTarget>>()
-        .ok()
-        .unwrap()
-        .descriptors();
-    Ok((descriptors, result))
+    {
+        Ok(renderer) => Renderer::Wgpu(renderer.descriptors()),
+        Err(_) => Renderer::Software,
+    };
+    Ok((
+        renderer,
+        Capture {
+            frames,
+            frame_rate,
+            audio,
+        },
+    ))
+}
+
+/// Capture a movie, and save it to the path `output` gives for the number of
+/// frames captured.
+///
+/// With an encoder, frames are piped into it as they are captured, so the
+/// output path is settled beforehand. If the encoder reads the audio, it must
+/// all be there when the encoder starts, so the movie is run once before to
+/// capture it.
+fn export_swf(
+    renderer: Renderer,
+    swf_path: &Path,
+    opt: &Opt,
+    progress: &Option<ProgressBar>,
+    output: impl Fn(usize) -> PathBuf,
+) -> Result<(Renderer, usize, PathBuf), Box<dyn Error>> {
+    if let Some(progress) = progress {
+        progress.set_message(swf_path.file_stem().unwrap().to_string_lossy().into_owned());
+    }
+
+    let command = match &opt.encoder {
+        Some(command) => command,
+        None => {
+            let frames = Frames::Collected(vec![]);
+            let (renderer, capture) =
+                take_screenshot(renderer, swf_path, opt, progress, opt.audio, frames)?;
+            let num_frames = capture.frames.len();
+            let output = output(num_frames);
+            save_capture(capture, &output, opt)?;
+            return Ok((renderer, num_frames, output));
+        }
+    };
+
+    // The name of a video doesn't depend on its length.
+    let output = output(0);
+    let audio_path = output.with_extension("wav");
+    let mut renderer = renderer;
+    let mut capture_audio = opt.audio;
+    if Encoder::uses_audio(command) {
+        let (new_renderer, capture) =
+            take_screenshot(renderer, swf_path, opt, &None, true, Frames::Skipped)?;
+        renderer = new_renderer;
+        save_capture(capture, &output, opt)?;
+        capture_audio = false;
+    }
+
+    let frames = Frames::Encoded {
+        encoder: Encoder::new(command, &audio_path, &output),
+        count: 0,
+    };
+    let (renderer, capture) =
+        take_screenshot(renderer, swf_path, opt, progress, capture_audio, frames)?;
+    let num_frames = capture.frames.len();
+    save_capture(capture, &output, opt)?;
+
+    Ok((renderer, num_frames, output))
+}
+
+/// Save a capture to `output`, which is a directory when saving each frame
+/// to its own PNG, and a file otherwise.
+///
+/// Any captured audio is saved next to the file, or inside the directory.
+fn save_capture(capture: Capture, output: &Path, opt: &Opt) -> Result<(), Box<dyn Error>> {
+    let is_directory = opt.file_extension(capture.frames.len()).is_none();
+    if is_directory {
+        let _ = create_dir_all(output);
+    }
+
+    if let Some(audio) = &capture.audio {
+        let audio_path = if is_directory {
+            output.join("audio.wav")
+        } else {
+            output.with_extension("wav")
+        };
+        audio.save_wav(&audio_path)?;
+    }
+
+    let frames = match capture.frames {
+        Frames::Collected(frames) => frames,
+        Frames::Encoded { encoder, .. } => return encoder.finish(),
+        Frames::Skipped => return Ok(()),
+    };
+
+    match opt.format {
+        ExportFormat::Png if is_directory => {
+            for (frame, image) in frames.iter().enumerate() {
+                let mut path: PathBuf = output.into();
+                path.push(format!("{}.png", frame));
+                image.save(&path)?;
+            }
+        }
+        ExportFormat::Png => frames.get(0).ok_or("No frames captured")?.save(output)?,
+        ExportFormat::Gif => encode::save_gif(&frames, capture.frame_rate, output)?,
+        ExportFormat::Apng => encode::save_apng(&frames, capture.frame_rate, output)?,
+    }
+
+    Ok(())
 }
 
 fn find_files(root: &Path, with_progress: bool) -> Vec<DirEntry> {
@@ -194,22 +531,9 @@ fn find_files(root: &Path, with_progress: bool) -> Vec<DirEntry> {
     results
 }
 
-fn capture_single_swf(descriptors: Descriptors, opt: &Opt) -> Result<(), Box<dyn Error>> {
-    let output = opt.output_path.clone().unwrap_or_else(|| {
-        let mut result = PathBuf::new();
-        result.set_file_name(opt.swf.file_stem().unwrap());
-        if opt.frames == 1 {
-            result.set_extension("png");
-        }
-        result
-    });
-
-    if opt.frames > 1 {
-        let _ = create_dir_all(&output);
-    }
-
+fn capture_single_swf(renderer: Renderer, opt: &Opt) -> Result<(), Box<dyn Error>> {
     let progress = if !opt.silent {
-        let progress = ProgressBar::new(opt.frames as u64);
+        let progress = ProgressBar::new(opt.range.frames as u64);
         progress.set_style(
             ProgressStyle::default_bar()
                 .template(
@@ -222,30 +546,19 @@ fn capture_single_swf(descriptors: Descriptors, opt: &Opt) -> Result<(), Box<dyn
         None
     };
 
-    let (_, frames) = take_screenshot(
-        descriptors,
-        &opt.swf,
-        opt.frames,
-        opt.skipframes,
-        &progress,
-        opt.size,
-    )?;
-
-    if let Some(progress) = &progress {
-        progress.set_message(opt.swf.file_stem().unwrap().to_string_lossy().into_owned());
-    }
-
-    if frames.len() == 1 {
-        frames.get(0).unwrap().save(&output)?;
-    } else {
-        for (frame, image) in frames.iter().enumerate() {
-            let mut path: PathBuf = (&output).into();
-            path.push(format!("{}.png", frame));
-            image.save(&path)?;
-        }
-    }
+    let output = |num_frames| {
+        opt.output_path.clone().unwrap_or_else(|| {
+            let mut result = PathBuf::new();
+            result.set_file_name(opt.swf.file_stem().unwrap());
+            if let Some(extension) = opt.file_extension(num_frames) {
+                result.set_extension(extension);
+            }
+            result
+        })
+    };
+    let (_, num_frames, output) = export_swf(renderer, &opt.swf, opt, &progress, output)?;
 
-    let message = if frames.len() == 1 {
+    let message = if num_frames == 1 {
         format!(
             "Saved first frame of {} to {}",
             opt.swf.to_string_lossy(),
@@ -254,7 +567,7 @@ fn capture_single_swf(descriptors: Descriptors, opt: &Opt) -> Result<(), Box<dyn
     } else {
         format!(
             "Saved first {} frames of {} to {}",
-            frames.len(),
+            num_frames,
             opt.swf.to_string_lossy(),
             output.to_string_lossy()
         )
@@ -269,13 +582,12 @@ fn capture_single_swf(descriptors: Descriptors, opt: &Opt) -> Result<(), Box<dyn
     Ok(())
 }
 
-#[allow(clippy::branches_sharing_code)]
-fn capture_multiple_swfs(mut descriptors: Descriptors, opt: &Opt) -> Result<(), Box<dyn Error>> {
+fn capture_multiple_swfs(mut renderer: Renderer, opt: &Opt) -> Result<(), Box<dyn Error>> {
     let output = opt.output_path.clone().unwrap();
     let files = find_files(&opt.swf, !opt.silent);
 
     let progress = if !opt.silent {
-        let progress = ProgressBar::new((files.len() as u64) * (opt.frames as u64));
+        let progress = ProgressBar::new((files.len() as u64) * (opt.range.frames as u64));
         progress.set_style(
             ProgressStyle::default_bar()
                 .template(
@@ -289,54 +601,26 @@ fn capture_multiple_swfs(mut descriptors: Descriptors, opt: &Opt) -> Result<(),
     };
 
     for file in &files {
-        let (new_descriptors, frames) = take_screenshot(
-            descriptors,
-            file.path(),
-            opt.frames,
-            opt.skipframes,
-            &progress,
-            opt.size,
-        )?;
-        descriptors = new_descriptors;
-
-        if let Some(progress) = &progress {
-            progress.set_message(
-                file.path()
-                    .file_stem()
-                    .unwrap()
-                    .to_string_lossy()
-                    .into_owned(),
-            );
-        }
-
-        let mut relative_path = file
-            .path()
-            .strip_prefix(&opt.swf)
-            .unwrap_or_else(|_| file.path())
-            .to_path_buf();
+        let destination = |num_frames| {
+            let mut relative_path = file
+                .path()
+                .strip_prefix(&opt.swf)
+                .unwrap_or_else(|_| file.path())
+                .to_path_buf();
 
-        if frames.len() == 1 {
             let mut destination: PathBuf = (&output).into();
-            relative_path.set_extension("png");
+            relative_path.set_extension(opt.file_extension(num_frames).unwrap_or(""));
             destination.push(relative_path);
             if let Some(parent) = destination.parent() {
                 let _ = create_dir_all(parent);
             }
-            frames.get(0).unwrap().save(&destination)?;
-        } else {
-            let mut parent: PathBuf = (&output).into();
-            relative_path.set_extension("");
-            parent.push(&relative_path);
-            let _ = create_dir_all(&parent);
-            for (frame, image) in frames.iter().enumerate() {
-                let mut destination = parent.clone();
-                destination.push(format!("{}.png", frame));
-                image.save(&destination)?;
-            }
-        }
+            destination
+        };
+        let (new_renderer, _, _) = export_swf(renderer, file.path(), opt, &progress, destination)?;
+        renderer = new_renderer;
     }
 
-    let message = if opt.frames == 1 {
+    let message = if opt.range.frames == 1 {
         format!(
             "Saved first frame of {} files to {}",
             files.len(),
@@ -345,7 +629,7 @@ fn capture_multiple_swfs(mut descriptors: Descriptors, opt: &Opt) -> Result<(),
     } else {
         format!(
             "Saved first {} frames of {} files to {}",
-            opt.frames,
+            opt.range.frames,
             files.len(),
             output.to_string_lossy()
         )
@@ -360,6 +644,52 @@ fn capture_multiple_swfs(mut descriptors: Descriptors, opt: &Opt) -> Result<(),
     Ok(())
 }
 
+fn extract_swfs(opt: &Opt) -> Result<(), Box<dyn Error>> {
+    if opt.swf.is_file() {
+        let output = opt
+            .output_path
+            .clone()
+            .unwrap_or_else(|| PathBuf::from(opt.swf.file_stem().unwrap()));
+        let movie = Arc::new(SwfMovie::from_path(&opt.swf, None)?);
+        let count = extract::extract_assets(&movie, &output)?;
+        println!(
+            "Extracted {} assets of {} to {}",
+            count,
+            opt.swf.to_string_lossy(),
+            output.to_string_lossy()
+        );
+        return Ok(());
+    }
+
+    let output = opt
+        .output_path
+        .clone()
+        .ok_or("Output directory is required when extracting from multiple files.")?;
+    let files = find_files(&opt.swf, !opt.silent);
+    let mut count = 0;
+    for file in &files {
+        let mut destination = output.join(
+            file.path()
+                .strip_prefix(&opt.swf)
+                .unwrap_or_else(|_| file.path()),
+        );
+        destination.set_extension("");
+
+        match SwfMovie::from_path(file.path(), None) {
+            Ok(movie) => count += extract::extract_assets(&Arc::new(movie), &destination)?,
+            Err(e) => eprintln!("Couldn't load {}: {}", file.path().to_string_lossy(), e),
+        }
+    }
+    println!(
+        "Extracted {} assets of {} files to {}",
+        count,
+        files.len(),
+        output.to_string_lossy()
+    );
+
+    Ok(())
+}
+
 #[cfg(feature = "render_trace")]
 fn trace_path(opt: &Opt) -> Option<&Path> {
     if let Some(path) = &opt.trace_path {
@@ -377,23 +707,138 @@ fn trace_path(_opt: &Opt) -> Option<&Path> {
 
 fn main() -> Result<(), Box<dyn Error>> {
     let opt: Opt = Opt::parse();
-    let instance = wgpu::Instance::new(opt.graphics.into());
-    let descriptors =
-        futures::executor::block_on(WgpuRenderBackend::<TextureTarget>::build_descriptors(
+    if opt.extract {
+        return extract_swfs(&opt);
+    }
+
+    let renderer = if opt.software {
+        Renderer::Software
+    } else {
+        let instance = wgpu::Instance::new(opt.graphics.into());
+        Renderer::Wgpu(futures::executor::block_on(WgpuRenderBackend::<
+            TextureTarget,
+        >::build_descriptors(
             opt.graphics.into(),
             instance,
             None,
             opt.power.into(),
             trace_path(&opt),
-        ))?;
+        ))?)
+    };
+
+    if opt.replay.is_some() && !opt.swf.is_file() {
+        return Err("A recording can only be replayed when exporting a single file.".into());
+    }
 
     if opt.swf.is_file() {
-        capture_single_swf(descriptors, &opt)?;
+        capture_single_swf(renderer, &opt)?;
     } else if opt.output_path.is_some() {
-        capture_multiple_swfs(descriptors, &opt)?;
+        capture_multiple_swfs(renderer, &opt)?;
     } else {
         return Err("Output directory is required when exporting multiple files.".into());
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::{remove_dir_all, File};
+
+    /// A movie of 800x800 pixels at 24 frames per second.
+    const SWF: &str = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../tests/tests/swfs/avm1/movieclip_hittest_shapeflag/test.swf"
+    );
+
+    /// An empty directory for the output of a test.
+    fn output_dir(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("ruffle_exporter_{}", name));
+        let _ = remove_dir_all(&path);
+        create_dir_all(&path).unwrap();
+        path
+    }
+
+    /// Export the movie at a quarter of its size, so 200x200 pixels.
+    fn export(output: &Path, args: &[&str]) {
+        let output = output.to_str().unwrap();
+        let opt = Opt::parse_from(
+            [
+                "exporter",
+                SWF,
+                output,
+                "--software",
+                "--silent",
+                "--scale",
+                "0.25",
+            ]
+            .iter()
+            .chain(args),
+        );
+        capture_single_swf(Renderer::Software, &opt).unwrap();
+    }
+
+    #[test]
+    fn gif_has_a_frame_per_captured_frame() {
+        let dir = output_dir("gif");
+        let output = dir.join("movie.gif");
+        export(
+            &output,
+            &["--format", "gif", "--skipframes", "2", "--end-frame", "5"],
+        );
+
+        let mut decoder = gif::Decoder::new(File::open(&output).unwrap()).unwrap();
+        assert_eq!((decoder.width(), decoder.height()), (200, 200));
+        let mut delays = vec![];
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            assert_eq!((frame.width, frame.height), (200, 200));
+            delays.push(frame.delay);
+        }
+        // Frames 2 to 5, each shown for 1/24th of a second.
+        assert_eq!(delays, [4, 4, 4, 4]);
+
+        remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn apng_has_a_frame_per_captured_frame() {
+        let dir = output_dir("apng");
+        let output = dir.join("movie.png");
+        export(&output, &["--format", "apng", "--frames", "3"]);
+
+        let reader = png::Decoder::new(File::open(&output).unwrap())
+            .read_info()
+            .unwrap();
+        let info = reader.info();
+        assert_eq!((info.width, info.height), (200, 200));
+        assert_eq!(info.color_type, png::ColorType::Rgba);
+        assert_eq!(info.animation_control.unwrap().num_frames, 3);
+
+        remove_dir_all(dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn encoder_is_given_raw_rgba_frames() {
+        let dir = output_dir("encoder");
+        let output = dir.join("movie.rgba");
+        export(
+            &output,
+            &[
+                "--encoder",
+                "dd of={output} status=none",
+                "--duration",
+                "0.25",
+            ],
+        );
+
+        // A quarter of a second at 24 frames per second is 6 frames.
+        let len = std::fs::metadata(&output).unwrap().len();
+        assert_eq!(len, 200 * 200 * 4 * 6);
+        // The command doesn't read the audio, so none is captured.
+        assert!(!output.with_extension("wav").exists());
+
+        remove_dir_all(dir).unwrap();
+    }
+}
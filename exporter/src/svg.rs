@@ -0,0 +1,243 @@
+//! Conversion of SWF shapes to SVG
+
+use ruffle_core::shape_utils::{DistilledShape, DrawCommand, DrawPath};
+use ruffle_core::swf::{
+    self, CharacterId, Color, FillStyle, Gradient, GradientInterpolation, GradientSpread,
+    LineCapStyle, LineJoinStyle, LineStyle, Twips,
+};
+use std::fmt::Write;
+
+/// The extent of the gradient square, in pixels.
+///
+/// Gradients are defined from -16384 to 16384 twips, and are mapped onto the
+/// shape by the gradient matrix.
+const GRADIENT_EXTENT: f64 = 819.2;
+
+/// A bitmap that bitmap fills may refer to.
+pub struct BitmapRef {
+    pub href: String,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Convert a shape into an SVG document.
+///
+/// Bitmap fills are drawn with the bitmap returned by `bitmap` for their
+/// character ID, or not at all if it returns `None`.
+pub fn shape_to_svg(
+    shape: &swf::Shape,
+    bitmap: impl Fn(CharacterId) -> Option<BitmapRef>,
+) -> String {
+    let bounds = &shape.shape_bounds;
+    let x = bounds.x_min.to_pixels();
+    let y = bounds.y_min.to_pixels();
+    let width = (bounds.x_max - bounds.x_min).to_pixels();
+    let height = (bounds.y_max - bounds.y_min).to_pixels();
+
+    let mut defs = String::new();
+    let mut body = String::new();
+    let mut paint_count = 0;
+    let mut paint = |style: &FillStyle, defs: &mut String| -> (String, f32) {
+        paint_count += 1;
+        let id = format!("paint{}", paint_count);
+        match style {
+            FillStyle::Color(color) => (rgb(color), alpha(color)),
+            FillStyle::LinearGradient(gradient) => {
+                write_gradient(defs, &id, gradient, "linearGradient", "");
+                (format!("url(#{})", id), 1.0)
+            }
+            FillStyle::RadialGradient(gradient) => {
+                write_gradient(defs, &id, gradient, "radialGradient", "");
+                (format!("url(#{})", id), 1.0)
+            }
+            FillStyle::FocalGradient {
+                gradient,
+                focal_point,
+            } => {
+                let focus = format!(
+                    " fx=\"{}\" fy=\"0\"",
+                    focal_point.to_f64() * GRADIENT_EXTENT
+                );
+                write_gradient(defs, &id, gradient, "radialGradient", &focus);
+                (format!("url(#{})", id), 1.0)
+            }
+            FillStyle::Bitmap {
+                id: bitmap_id,
+                matrix,
+                is_repeating,
+                ..
+            } => match bitmap(*bitmap_id) {
+                Some(bitmap) => {
+                    // Bitmap matrices map bitmap pixels to twips. SVG has no
+                    // clamped patterns, so a bitmap that does not repeat is
+                    // drawn once in a tile larger than any shape.
+                    let _ = write!(
+                        defs,
+                        "<pattern id=\"{}\" patternUnits=\"userSpaceOnUse\" width=\"{}\" height=\"{}\" patternTransform=\"matrix({} {} {} {} {} {})\">\
+                         <image xlink:href=\"{}\" width=\"{}\" height=\"{}\"/></pattern>",
+                        id,
+                        if *is_repeating { bitmap.width } else { u32::MAX },
+                        if *is_repeating { bitmap.height } else { u32::MAX },
+                        matrix.a.to_f64() / 20.0,
+                        matrix.b.to_f64() / 20.0,
+                        matrix.c.to_f64() / 20.0,
+                        matrix.d.to_f64() / 20.0,
+                        matrix.tx.to_pixels(),
+                        matrix.ty.to_pixels(),
+                        escape(&bitmap.href),
+                        bitmap.width,
+                        bitmap.height
+                    );
+                    (format!("url(#{})", id), 1.0)
+                }
+                None => ("none".to_string(), 1.0),
+            },
+        }
+    };
+
+    for path in DistilledShape::from(shape).paths {
+        match path {
+            DrawPath::Fill { style, commands } => {
+                let (fill, opacity) = paint(style, &mut defs);
+                let _ = write!(
+                    body,
+                    "<path d=\"{}\" fill=\"{}\" fill-opacity=\"{}\" fill-rule=\"evenodd\"/>",
+                    path_data(&commands, false),
+                    fill,
+                    opacity
+                );
+            }
+            DrawPath::Stroke {
+                style,
+                is_closed,
+                commands,
+            } => {
+                let (stroke, opacity) = match &style.fill_style {
+                    Some(fill_style) => paint(fill_style, &mut defs),
+                    None => (rgb(&style.color), alpha(&style.color)),
+                };
+                let _ = write!(
+                    body,
+                    "<path d=\"{}\" fill=\"none\" stroke=\"{}\" stroke-opacity=\"{}\" {}/>",
+                    path_data(&commands, is_closed),
+                    stroke,
+                    opacity,
+                    stroke_attributes(style)
+                );
+            }
+        }
+    }
+
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" xmlns:xlink=\"http://www.w3.org/1999/xlink\" \
+         width=\"{w}\" height=\"{h}\" viewBox=\"{x} {y} {w} {h}\"><defs>{defs}</defs>{body}</svg>\n",
+        x = x,
+        y = y,
+        w = width,
+        h = height,
+        defs = defs,
+        body = body
+    )
+}
+
+fn write_gradient(defs: &mut String, id: &str, gradient: &Gradient, element: &str, extra: &str) {
+    let geometry = if element == "linearGradient" {
+        format!(
+            "x1=\"{}\" y1=\"0\" x2=\"{}\" y2=\"0\"",
+            -GRADIENT_EXTENT, GRADIENT_EXTENT
+        )
+    } else {
+        format!("cx=\"0\" cy=\"0\" r=\"{}\"", GRADIENT_EXTENT)
+    };
+    let spread = match gradient.spread {
+        GradientSpread::Pad => "pad",
+        GradientSpread::Reflect => "reflect",
+        GradientSpread::Repeat => "repeat",
+    };
+    let interpolation = match gradient.interpolation {
+        GradientInterpolation::Rgb => "sRGB",
+        GradientInterpolation::LinearRgb => "linearRGB",
+    };
+    let matrix = &gradient.matrix;
+
+    let _ = write!(
+        defs,
+        "<{element} id=\"{id}\" gradientUnits=\"userSpaceOnUse\" {geometry}{extra} spreadMethod=\"{spread}\" \
+         color-interpolation=\"{interpolation}\" gradientTransform=\"matrix({} {} {} {} {} {})\">",
+        matrix.a.to_f64(),
+        matrix.b.to_f64(),
+        matrix.c.to_f64(),
+        matrix.d.to_f64(),
+        matrix.tx.to_pixels(),
+        matrix.ty.to_pixels(),
+        element = element,
+        id = id,
+        geometry = geometry,
+        extra = extra,
+        spread = spread,
+        interpolation = interpolation
+    );
+    for record in &gradient.records {
+        let _ = write!(
+            defs,
+            "<stop offset=\"{}\" stop-color=\"{}\" stop-opacity=\"{}\"/>",
+            f32::from(record.ratio) / 255.0,
+            rgb(&record.color),
+            alpha(&record.color)
+        );
+    }
+    let _ = write!(defs, "</{}>", element);
+}
+
+fn stroke_attributes(style: &LineStyle) -> String {
+    // Flash draws strokes at least a pixel wide.
+    let width = style.width.to_pixels().max(1.0);
+    let cap = match style.start_cap {
+        LineCapStyle::Round => "round",
+        LineCapStyle::None => "butt",
+        LineCapStyle::Square => "square",
+    };
+    let mut attributes = format!("stroke-width=\"{}\" stroke-linecap=\"{}\"", width, cap);
+    let _ = match style.join_style {
+        LineJoinStyle::Round => write!(attributes, " stroke-linejoin=\"round\""),
+        LineJoinStyle::Bevel => write!(attributes, " stroke-linejoin=\"bevel\""),
+        LineJoinStyle::Miter(limit) => write!(
+            attributes,
+            " stroke-linejoin=\"miter\" stroke-miterlimit=\"{}\"",
+            limit.to_f64()
+        ),
+    };
+    attributes
+}
+
+fn path_data(commands: &[DrawCommand], is_closed: bool) -> String {
+    let mut data = String::new();
+    let px = |twips: Twips| twips.to_pixels();
+    for command in commands {
+        let _ = match command {
+            DrawCommand::MoveTo { x, y } => write!(data, "M{} {}", px(*x), px(*y)),
+            DrawCommand::LineTo { x, y } => write!(data, "L{} {}", px(*x), px(*y)),
+            DrawCommand::CurveTo { x1, y1, x2, y2 } => {
+                write!(data, "Q{} {} {} {}", px(*x1), px(*y1), px(*x2), px(*y2))
+            }
+        };
+    }
+    if is_closed {
+        data.push('Z');
+    }
+    data
+}
+
+fn rgb(color: &Color) -> String {
+    format!("#{:02x}{:02x}{:02x}", color.r, color.g, color.b)
+}
+
+fn alpha(color: &Color) -> f32 {
+    f32::from(color.a) / 255.0
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('<', "&lt;")
+}
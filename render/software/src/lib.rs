@@ -0,0 +1,1185 @@
+//! A render backend that draws on the CPU.
+//!
+//! Shapes are tessellated like the GPU backends do, and the triangles are
+//! rasterized with 16 samples per pixel. Drawing needs no graphics adapter,
+//! and the same frame always renders to the same pixels, which makes this
+//! backend suitable for headless capturing and image tests.
+
+mod raster;
+
+use raster::{Coverage, Point};
+use ruffle_core::backend::render::{
+    Bitmap, BitmapFormat, BitmapHandle, BitmapInfo, BitmapSource, Color, CommandList,
+    NullBitmapSource, RenderBackend, ShapeHandle, Transform,
+};
+use ruffle_core::color_transform::ColorTransform;
+use ruffle_core::matrix::Matrix;
+use ruffle_core::shape_utils::DistilledShape;
+use ruffle_core::swf;
+use ruffle_render_common_tess::{Draw, DrawType, Gradient, GradientType, ShapeTessellator};
+
+type Error = Box<dyn std::error::Error>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MaskState {
+    NoMask,
+    DrawMaskStencil,
+    DrawMaskedContent,
+    ClearMaskStencil,
+}
+
+pub struct SoftwareRenderBackend {
+    canvas: Canvas,
+    shape_tessellator: ShapeTessellator,
+    meshes: Vec<Vec<Draw>>,
+    textures: Vec<Texture>,
+}
+
+impl SoftwareRenderBackend {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            canvas: Canvas::new(width, height),
+            shape_tessellator: ShapeTessellator::new(),
+            meshes: Vec::new(),
+            textures: Vec::new(),
+        }
+    }
+
+    /// Returns the pixels of the last frame drawn, with premultiplied alpha.
+    pub fn capture_frame(&self) -> Bitmap {
+        Bitmap {
+            width: self.canvas.width,
+            height: self.canvas.height,
+            data: BitmapFormat::Rgba(self.canvas.pixels.clone()),
+        }
+    }
+
+    fn register_bitmap(&mut self, bitmap: Bitmap) -> Result<BitmapInfo, Error> {
+        let handle = BitmapHandle(self.textures.len());
+        let width = bitmap.width;
+        let height = bitmap.height;
+        self.textures.push(Texture::from(bitmap));
+
+        Ok(BitmapInfo {
+            handle,
+            width: width as u16,
+            height: height as u16,
+        })
+    }
+}
+
+impl RenderBackend for SoftwareRenderBackend {
+    fn set_viewport_dimensions(&mut self, width: u32, height: u32) {
+        self.canvas = Canvas::new(width, height);
+    }
+
+    fn register_shape(
+        &mut self,
+        shape: DistilledShape,
+        bitmap_source: &dyn BitmapSource,
+    ) -> ShapeHandle {
+        let handle = ShapeHandle(self.meshes.len());
+        let mesh = self
+            .shape_tessellator
+            .tessellate_shape(shape, bitmap_source);
+        self.meshes.push(mesh);
+        handle
+    }
+
+    fn replace_shape(
+        &mut self,
+        shape: DistilledShape,
+        bitmap_source: &dyn BitmapSource,
+        handle: ShapeHandle,
+    ) {
+        let mesh = self
+            .shape_tessellator
+            .tessellate_shape(shape, bitmap_source);
+        self.meshes[handle.0] = mesh;
+    }
+
+    fn register_glyph_shape(&mut self, glyph: &swf::Glyph) -> ShapeHandle {
+        let shape = ruffle_core::shape_utils::swf_glyph_to_shape(glyph);
+        self.register_shape((&shape).into(), &NullBitmapSource)
+    }
+
+    fn register_bitmap_jpeg(
+        &mut self,
+        data: &[u8],
+        jpeg_tables: Option<&[u8]>,
+    ) -> Result<BitmapInfo, Error> {
+        let data = ruffle_core::backend::render::glue_tables_to_jpeg(data, jpeg_tables);
+        self.register_bitmap_jpeg_2(&data[..])
+    }
+
+    fn register_bitmap_jpeg_2(&mut self, data: &[u8]) -> Result<BitmapInfo, Error> {
+        let bitmap = ruffle_core::backend::render::decode_define_bits_jpeg(data, None)?;
+        self.register_bitmap(bitmap)
+    }
+
+    fn register_bitmap_jpeg_3_or_4(
+        &mut self,
+        jpeg_data: &[u8],
+        alpha_data: &[u8],
+    ) -> Result<BitmapInfo, Error> {
+        let bitmap =
+            ruffle_core::backend::render::decode_define_bits_jpeg(jpeg_data, Some(alpha_data))?;
+        self.register_bitmap(bitmap)
+    }
+
+    fn register_bitmap_png(
+        &mut self,
+        swf_tag: &swf::DefineBitsLossless,
+    ) -> Result<BitmapInfo, Error> {
+        let bitmap = ruffle_core::backend::render::decode_define_bits_lossless(swf_tag)?;
+        self.register_bitmap(bitmap)
+    }
+
+    fn begin_frame(&mut self, clear: Color) {
+        self.canvas.clear(clear);
+    }
+
+    fn render_bitmap(&mut self, bitmap: BitmapHandle, transform: &Transform, smoothing: bool) {
+        if let Some(texture) = self.textures.get(bitmap.0) {
+            let width = texture.width as f32;
+            let height = texture.height as f32;
+            let to_uv = Affine::scale(1.0 / width, 1.0 / height);
+            let paint = match Paint::bitmap(to_uv, &transform.matrix, texture, smoothing, false) {
+                Some(paint) => paint,
+                None => return,
+            };
+            self.canvas.fill_quad(
+                &transform.matrix,
+                width,
+                height,
+                &paint,
+                &transform.color_transform,
+            );
+        }
+    }
+
+    fn render_shape(&mut self, shape: ShapeHandle, transform: &Transform) {
+        let canvas = &mut self.canvas;
+        let matrix = &transform.matrix;
+        let color_transform = &transform.color_transform;
+
+        for draw in &self.meshes[shape.0] {
+            let points: Vec<Point> = draw
+                .vertices
+                .iter()
+                .map(|vertex| transform_point(matrix, vertex.x, vertex.y))
+                .collect();
+
+            match &draw.draw_type {
+                DrawType::Color => {
+                    // Solid fills are batched together, so each run of
+                    // triangles of the same color is a separate fill.
+                    let mut current_color: Option<&swf::Color> = None;
+                    for triangle in draw.indices.chunks_exact(3) {
+                        let color = &draw.vertices[triangle[0] as usize].color;
+                        if let Some(current) = current_color.filter(|current| *current != color) {
+                            canvas.fill(&Paint::color(current), color_transform);
+                        }
+                        current_color = Some(color);
+                        canvas.add_triangle(triangle, &points);
+                    }
+                    if let Some(color) = current_color {
+                        canvas.fill(&Paint::color(color), color_transform);
+                    }
+                }
+                DrawType::Gradient(gradient) => {
+                    if let Some(paint) = Paint::gradient(gradient, matrix) {
+                        add_triangles(canvas, draw, &points);
+                        canvas.fill(&paint, color_transform);
+                    }
+                }
+                DrawType::Bitmap(bitmap) => {
+                    let texture = match self.textures.get(bitmap.bitmap.0) {
+                        Some(texture) => texture,
+                        // Bitmap not registered
+                        None => continue,
+                    };
+                    let to_uv = Affine::from_uv_matrix(&bitmap.matrix);
+                    if let Some(paint) = Paint::bitmap(
+                        to_uv,
+                        matrix,
+                        texture,
+                        bitmap.is_smoothed,
+                        bitmap.is_repeating,
+                    ) {
+                        add_triangles(canvas, draw, &points);
+                        canvas.fill(&paint, color_transform);
+                    }
+                }
+            }
+        }
+    }
+
+    fn draw_rect(&mut self, color: Color, matrix: &Matrix) {
+        self.canvas.fill_quad(
+            matrix,
+            1.0,
+            1.0,
+            &Paint::color(&color),
+            &ColorTransform::default(),
+        );
+    }
+
+    fn end_frame(&mut self) {}
+
+    fn push_mask(&mut self) {
+        self.canvas.push_mask();
+    }
+
+    fn activate_mask(&mut self) {
+        self.canvas.activate_mask();
+    }
+
+    fn deactivate_mask(&mut self) {
+        self.canvas.deactivate_mask();
+    }
+
+    fn pop_mask(&mut self) {
+        self.canvas.pop_mask();
+    }
+
+    fn get_bitmap_pixels(&mut self, bitmap: BitmapHandle) -> Option<Bitmap> {
+        let texture = self.textures.get(bitmap.0)?;
+        Some(Bitmap {
+            width: texture.width,
+            height: texture.height,
+            data: BitmapFormat::Rgba(texture.rgba.clone()),
+        })
+    }
+
+    fn register_bitmap_raw(
+        &mut self,
+        width: u32,
+        height: u32,
+        rgba: Vec<u8>,
+    ) -> Result<BitmapHandle, Error> {
+        Ok(self
+            .register_bitmap(Bitmap {
+                data: BitmapFormat::Rgba(rgba),
+                width,
+                height,
+            })?
+            .handle)
+    }
+
+    fn update_texture(
+        &mut self,
+        handle: BitmapHandle,
+        width: u32,
+        height: u32,
+        rgba: Vec<u8>,
+    ) -> Result<BitmapHandle, Error> {
+        let texture = self
+            .textures
+            .get_mut(handle.0)
+            .ok_or("update_texture: Bitmap is not registered")?;
+        *texture = Texture {
+            width,
+            height,
+            rgba,
+        };
+        Ok(handle)
+    }
+
+    fn render_offscreen(
+        &mut self,
+        commands: &CommandList,
+        width: u32,
+        height: u32,
+        clear: Color,
+    ) -> Option<Bitmap> {
+        let width = std::cmp::max(width, 1);
+        let height = std::cmp::max(height, 1);
+        let canvas = std::mem::replace(&mut self.canvas, Canvas::new(width, height));
+
+        self.begin_frame(clear);
+        commands.execute(self);
+        let bitmap = self.capture_frame();
+
+        self.canvas = canvas;
+        Some(bitmap)
+    }
+}
+
+/// Add every triangle of a draw to the coverage of the next fill.
+fn add_triangles(canvas: &mut Canvas, draw: &Draw, points: &[Point]) {
+    for triangle in draw.indices.chunks_exact(3) {
+        canvas.add_triangle(triangle, points);
+    }
+}
+
+fn transform_point(matrix: &Matrix, x: f32, y: f32) -> Point {
+    Point {
+        x: matrix.a * x + matrix.c * y + matrix.tx.to_pixels() as f32,
+        y: matrix.b * x + matrix.d * y + matrix.ty.to_pixels() as f32,
+    }
+}
+
+/// The pixels being drawn to, along with the masks that apply to them.
+struct Canvas {
+    width: u32,
+    height: u32,
+
+    /// RGBA pixels, with premultiplied alpha.
+    pixels: Vec<u8>,
+
+    coverage: Coverage,
+
+    /// The area each active mask lets through, from 0 to 255 per pixel.
+    /// Each mask only covers the area let through by the masks below it.
+    masks: Vec<Vec<u8>>,
+    mask_state: MaskState,
+}
+
+impl Canvas {
+    fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![0; width as usize * height as usize * 4],
+            coverage: Coverage::new(width, height),
+            masks: Vec::new(),
+            mask_state: MaskState::NoMask,
+        }
+    }
+
+    fn clear(&mut self, color: Color) {
+        let alpha = f32::from(color.a) / 255.0;
+        let clear = [
+            (f32::from(color.r) * alpha).round() as u8,
+            (f32::from(color.g) * alpha).round() as u8,
+            (f32::from(color.b) * alpha).round() as u8,
+            color.a,
+        ];
+        for pixel in self.pixels.chunks_exact_mut(4) {
+            pixel.copy_from_slice(&clear);
+        }
+        self.masks.clear();
+        self.mask_state = MaskState::NoMask;
+    }
+
+    fn add_triangle(&mut self, triangle: &[u32], points: &[Point]) {
+        self.coverage.add_triangle([
+            points[triangle[0] as usize],
+            points[triangle[1] as usize],
+            points[triangle[2] as usize],
+        ]);
+    }
+
+    /// Fill a `width` by `height` rectangle, transformed by `matrix`.
+    fn fill_quad(
+        &mut self,
+        matrix: &Matrix,
+        width: f32,
+        height: f32,
+        paint: &Paint,
+        color_transform: &ColorTransform,
+    ) {
+        let top_left = transform_point(matrix, 0.0, 0.0);
+        let top_right = transform_point(matrix, width, 0.0);
+        let bottom_right = transform_point(matrix, width, height);
+        let bottom_left = transform_point(matrix, 0.0, height);
+        self.coverage
+            .add_triangle([top_left, top_right, bottom_right]);
+        self.coverage
+            .add_triangle([top_left, bottom_right, bottom_left]);
+        self.fill(paint, color_transform);
+    }
+
+    /// Draw the accumulated coverage with the given paint.
+    fn fill(&mut self, paint: &Paint, color_transform: &ColorTransform) {
+        let Self {
+            width,
+            pixels,
+            coverage,
+            masks,
+            mask_state,
+            ..
+        } = self;
+        let width = *width;
+
+        match *mask_state {
+            MaskState::NoMask | MaskState::DrawMaskedContent => {
+                let clip = if *mask_state == MaskState::DrawMaskedContent {
+                    masks.last()
+                } else {
+                    None
+                };
+                let mult = color_transform.mult_rgba_normalized();
+                let add = color_transform.add_rgba_normalized();
+
+                coverage.drain(|x, y, coverage| {
+                    let index = (y * width + x) as usize;
+                    let coverage = match clip {
+                        Some(clip) => coverage * f32::from(clip[index]) / 255.0,
+                        None => coverage,
+                    };
+                    if coverage > 0.0 {
+                        let color = paint.sample(x as f32 + 0.5, y as f32 + 0.5, mult, add);
+                        blend(&mut pixels[index * 4..index * 4 + 4], color, coverage);
+                    }
+                });
+            }
+            MaskState::DrawMaskStencil => {
+                let (mask, parents) = masks.split_last_mut().unwrap();
+                let parent = parents.last();
+                coverage.drain(|x, y, coverage| {
+                    let index = (y * width + x) as usize;
+                    let coverage = match parent {
+                        Some(parent) => coverage * f32::from(parent[index]) / 255.0,
+                        None => coverage,
+                    };
+                    mask[index] = mask[index].max((coverage * 255.0).round() as u8);
+                });
+            }
+            // Masks are discarded as a whole when popped, so there is
+            // nothing to clear.
+            MaskState::ClearMaskStencil => coverage.drain(|_, _, _| {}),
+        }
+    }
+
+    fn push_mask(&mut self) {
+        debug_assert!(
+            self.mask_state == MaskState::NoMask || self.mask_state == MaskState::DrawMaskedContent
+        );
+        self.masks
+            .push(vec![0; self.width as usize * self.height as usize]);
+        self.mask_state = MaskState::DrawMaskStencil;
+    }
+
+    fn activate_mask(&mut self) {
+        debug_assert!(!self.masks.is_empty() && self.mask_state == MaskState::DrawMaskStencil);
+        self.mask_state = MaskState::DrawMaskedContent;
+    }
+
+    fn deactivate_mask(&mut self) {
+        debug_assert!(!self.masks.is_empty() && self.mask_state == MaskState::DrawMaskedContent);
+        self.mask_state = MaskState::ClearMaskStencil;
+    }
+
+    fn pop_mask(&mut self) {
+        debug_assert!(!self.masks.is_empty() && self.mask_state == MaskState::ClearMaskStencil);
+        self.masks.pop();
+        self.mask_state = if self.masks.is_empty() {
+            MaskState::NoMask
+        } else {
+            MaskState::DrawMaskedContent
+        };
+    }
+}
+
+/// Blend a premultiplied color over a pixel, scaled by the pixel's coverage.
+fn blend(pixel: &mut [u8], color: [f32; 4], coverage: f32) {
+    let inverse_alpha = 1.0 - color[3] * coverage;
+    for (channel, value) in pixel.iter_mut().zip(color) {
+        let result = value * coverage * 255.0 + f32::from(*channel) * inverse_alpha;
+        *channel = result.round().clamp(0.0, 255.0) as u8;
+    }
+}
+
+/// An affine transform between two 2D spaces.
+#[derive(Copy, Clone, Debug)]
+struct Affine {
+    a: f32,
+    b: f32,
+    c: f32,
+    d: f32,
+    tx: f32,
+    ty: f32,
+}
+
+impl Affine {
+    fn scale(x: f32, y: f32) -> Self {
+        Self {
+            a: x,
+            b: 0.0,
+            c: 0.0,
+            d: y,
+            tx: 0.0,
+            ty: 0.0,
+        }
+    }
+
+    /// Convert the column-major texture matrices built by the tessellator.
+    fn from_uv_matrix(matrix: &[[f32; 3]; 3]) -> Self {
+        Self {
+            a: matrix[0][0],
+            b: matrix[0][1],
+            c: matrix[1][0],
+            d: matrix[1][1],
+            tx: matrix[2][0],
+            ty: matrix[2][1],
+        }
+    }
+
+    /// The transform from screen space back to the local space of a display
+    /// object, or `None` if the object is collapsed to a line or point.
+    fn inverse_of(matrix: &Matrix) -> Option<Self> {
+        let det = matrix.a * matrix.d - matrix.b * matrix.c;
+        if det == 0.0 || !det.is_finite() {
+            return None;
+        }
+        let tx = matrix.tx.to_pixels() as f32;
+        let ty = matrix.ty.to_pixels() as f32;
+        Some(Self {
+            a: matrix.d / det,
+            b: -matrix.b / det,
+            c: -matrix.c / det,
+            d: matrix.a / det,
+            tx: (matrix.c * ty - matrix.d * tx) / det,
+            ty: (matrix.b * tx - matrix.a * ty) / det,
+        })
+    }
+
+    /// Apply `other` first, then this transform.
+    fn after(&self, other: &Self) -> Self {
+        Self {
+            a: self.a * other.a + self.c * other.b,
+            b: self.b * other.a + self.d * other.b,
+            c: self.a * other.c + self.c * other.d,
+            d: self.b * other.c + self.d * other.d,
+            tx: self.a * other.tx + self.c * other.ty + self.tx,
+            ty: self.b * other.tx + self.d * other.ty + self.ty,
+        }
+    }
+
+    fn apply(&self, x: f32, y: f32) -> (f32, f32) {
+        (
+            self.a * x + self.c * y + self.tx,
+            self.b * x + self.d * y + self.ty,
+        )
+    }
+}
+
+/// What a fill is painted with.
+enum Paint<'a> {
+    Color([f32; 4]),
+    Gradient {
+        gradient: &'a Gradient,
+        screen_to_uv: Affine,
+    },
+    Bitmap {
+        texture: &'a Texture,
+        screen_to_uv: Affine,
+        is_smoothed: bool,
+        is_repeating: bool,
+    },
+}
+
+impl<'a> Paint<'a> {
+    fn color(color: &swf::Color) -> Self {
+        Self::Color([
+            f32::from(color.r) / 255.0,
+            f32::from(color.g) / 255.0,
+            f32::from(color.b) / 255.0,
+            f32::from(color.a) / 255.0,
+        ])
+    }
+
+    fn gradient(gradient: &'a Gradient, matrix: &Matrix) -> Option<Self> {
+        let to_uv = Affine::from_uv_matrix(&gradient.matrix);
+        Some(Self::Gradient {
+            gradient,
+            screen_to_uv: to_uv.after(&Affine::inverse_of(matrix)?),
+        })
+    }
+
+    fn bitmap(
+        to_uv: Affine,
+        matrix: &Matrix,
+        texture: &'a Texture,
+        is_smoothed: bool,
+        is_repeating: bool,
+    ) -> Option<Self> {
+        Some(Self::Bitmap {
+            texture,
+            screen_to_uv: to_uv.after(&Affine::inverse_of(matrix)?),
+            is_smoothed,
+            is_repeating,
+        })
+    }
+
+    /// The premultiplied color at a point on the screen, after the color
+    /// transform given by `mult` and `add`.
+    fn sample(&self, x: f32, y: f32, mult: [f32; 4], add: [f32; 4]) -> [f32; 4] {
+        match self {
+            Paint::Color(color) => premultiply(transform_color(*color, mult, add)),
+            Paint::Gradient {
+                gradient,
+                screen_to_uv,
+            } => {
+                let (u, v) = screen_to_uv.apply(x, y);
+                let color = sample_gradient(gradient, u, v);
+                premultiply(transform_color(color, mult, add))
+            }
+            Paint::Bitmap {
+                texture,
+                screen_to_uv,
+                is_smoothed,
+                is_repeating,
+            } => {
+                let (u, v) = screen_to_uv.apply(x, y);
+                let color = texture.sample(u, v, *is_smoothed, *is_repeating);
+                // Unmultiply alpha before applying the color transform.
+                if color[3] > 0.0 {
+                    let straight = [
+                        color[0] / color[3],
+                        color[1] / color[3],
+                        color[2] / color[3],
+                        color[3],
+                    ];
+                    premultiply(transform_color(straight, mult, add))
+                } else {
+                    color
+                }
+            }
+        }
+    }
+}
+
+fn transform_color(color: [f32; 4], mult: [f32; 4], add: [f32; 4]) -> [f32; 4] {
+    let mut result = color;
+    for ((value, mult), add) in result.iter_mut().zip(mult).zip(add) {
+        *value = (*value * mult + add).clamp(0.0, 1.0);
+    }
+    result
+}
+
+fn premultiply(color: [f32; 4]) -> [f32; 4] {
+    [
+        color[0] * color[3],
+        color[1] * color[3],
+        color[2] * color[3],
+        color[3],
+    ]
+}
+
+/// The straight color of a gradient at a point in its UV space, which spans
+/// from 0 to 1 across the gradient square.
+fn sample_gradient(gradient: &Gradient, u: f32, v: f32) -> [f32; 4] {
+    let t = match gradient.gradient_type {
+        GradientType::Linear => u,
+        GradientType::Radial => ((u * 2.0 - 1.0).powi(2) + (v * 2.0 - 1.0).powi(2)).sqrt(),
+        GradientType::Focal => {
+            let focal_point = gradient.focal_point.to_f32();
+            let dx = focal_point - (u * 2.0 - 1.0);
+            let dy = -(v * 2.0 - 1.0);
+            let length = (dx * dx + dy * dy).sqrt();
+            if length == 0.0 {
+                0.0
+            } else {
+                let (dx, dy) = (dx / length, dy / length);
+                length / ((1.0 - focal_point * focal_point * dy * dy).sqrt() + focal_point * dx)
+            }
+        }
+    };
+
+    let t = match gradient.repeat_mode {
+        swf::GradientSpread::Pad => t.clamp(0.0, 1.0),
+        swf::GradientSpread::Repeat => t.rem_euclid(1.0),
+        swf::GradientSpread::Reflect => {
+            let t = t.abs().rem_euclid(2.0);
+            if t > 1.0 {
+                2.0 - t
+            } else {
+                t
+            }
+        }
+    };
+
+    let ratios = &gradient.ratios[..gradient.num_colors];
+    let colors = &gradient.colors[..gradient.num_colors];
+    let color = match ratios.iter().position(|&ratio| t <= ratio) {
+        None => *colors.last().unwrap_or(&[0.0; 4]),
+        Some(0) => colors[0],
+        Some(i) => {
+            let range = ratios[i] - ratios[i - 1];
+            let a = if range > 0.0 {
+                (t - ratios[i - 1]) / range
+            } else {
+                1.0
+            };
+            let mut color = [0.0; 4];
+            for (channel, value) in color.iter_mut().enumerate() {
+                *value = colors[i - 1][channel] + (colors[i][channel] - colors[i - 1][channel]) * a;
+            }
+            color
+        }
+    };
+
+    if gradient.interpolation == swf::GradientInterpolation::LinearRgb {
+        linear_to_srgb(color)
+    } else {
+        color
+    }
+}
+
+fn linear_to_srgb(color: [f32; 4]) -> [f32; 4] {
+    fn to_srgb_channel(n: f32) -> f32 {
+        if n <= 0.0031308 {
+            n * 12.92
+        } else {
+            1.055 * n.powf(1.0 / 2.4) - 0.055
+        }
+    }
+    [
+        to_srgb_channel(color[0]),
+        to_srgb_channel(color[1]),
+        to_srgb_channel(color[2]),
+        color[3],
+    ]
+}
+
+/// A registered bitmap, as RGBA with premultiplied alpha.
+struct Texture {
+    width: u32,
+    height: u32,
+    rgba: Vec<u8>,
+}
+
+impl From<Bitmap> for Texture {
+    fn from(bitmap: Bitmap) -> Self {
+        let rgba = match bitmap.data {
+            BitmapFormat::Rgba(rgba) => rgba,
+            BitmapFormat::Rgb(rgb) => rgb
+                .chunks_exact(3)
+                .flat_map(|rgb| [rgb[0], rgb[1], rgb[2], 255])
+                .collect(),
+        };
+        Self {
+            width: bitmap.width,
+            height: bitmap.height,
+            rgba,
+        }
+    }
+}
+
+impl Texture {
+    /// The premultiplied color at a point in UV space, which spans from 0 to 1
+    /// across the bitmap.
+    fn sample(&self, u: f32, v: f32, is_smoothed: bool, is_repeating: bool) -> [f32; 4] {
+        if self.width == 0 || self.height == 0 {
+            return [0.0; 4];
+        }
+        let x = u * self.width as f32;
+        let y = v * self.height as f32;
+
+        if !is_smoothed {
+            return self.texel(x.floor() as i64, y.floor() as i64, is_repeating);
+        }
+
+        let x = x - 0.5;
+        let y = y - 0.5;
+        let (x0, y0) = (x.floor(), y.floor());
+        let (fx, fy) = (x - x0, y - y0);
+        let (x0, y0) = (x0 as i64, y0 as i64);
+
+        let top_left = self.texel(x0, y0, is_repeating);
+        let top_right = self.texel(x0 + 1, y0, is_repeating);
+        let bottom_left = self.texel(x0, y0 + 1, is_repeating);
+        let bottom_right = self.texel(x0 + 1, y0 + 1, is_repeating);
+
+        let mut color = [0.0; 4];
+        for (i, value) in color.iter_mut().enumerate() {
+            let top = top_left[i] + (top_right[i] - top_left[i]) * fx;
+            let bottom = bottom_left[i] + (bottom_right[i] - bottom_left[i]) * fx;
+            *value = top + (bottom - top) * fy;
+        }
+        color
+    }
+
+    fn texel(&self, x: i64, y: i64, is_repeating: bool) -> [f32; 4] {
+        let (width, height) = (i64::from(self.width), i64::from(self.height));
+        let (x, y) = if is_repeating {
+            (x.rem_euclid(width), y.rem_euclid(height))
+        } else {
+            (x.clamp(0, width - 1), y.clamp(0, height - 1))
+        };
+
+        let index = ((y * width + x) * 4) as usize;
+        match self.rgba.get(index..index + 4) {
+            Some(texel) => [
+                f32::from(texel[0]) / 255.0,
+                f32::from(texel[1]) / 255.0,
+                f32::from(texel[2]) / 255.0,
+                f32::from(texel[3]) / 255.0,
+            ],
+            None => [0.0; 4],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ruffle_core::swf::{Fixed16, Fixed8, Twips};
+
+    const WHITE: Color = Color {
+        r: 255,
+        g: 255,
+        b: 255,
+        a: 255,
+    };
+
+    const RED: Color = Color {
+        r: 255,
+        g: 0,
+        b: 0,
+        a: 255,
+    };
+
+    const BLUE: Color = Color {
+        r: 0,
+        g: 0,
+        b: 255,
+        a: 255,
+    };
+
+    struct TestBitmapSource(BitmapInfo);
+
+    impl BitmapSource for TestBitmapSource {
+        fn bitmap(&self, _id: swf::CharacterId) -> Option<BitmapInfo> {
+            Some(self.0)
+        }
+    }
+
+    /// A `width` by `height` pixel rectangle at the origin with a single fill.
+    fn rectangle(width: f64, height: f64, fill_style: swf::FillStyle) -> swf::Shape {
+        let (width, height) = (Twips::from_pixels(width), Twips::from_pixels(height));
+        let bounds = swf::Rectangle {
+            x_min: Twips::ZERO,
+            x_max: width,
+            y_min: Twips::ZERO,
+            y_max: height,
+        };
+        swf::Shape {
+            version: 3,
+            id: 1,
+            shape_bounds: bounds.clone(),
+            edge_bounds: bounds,
+            has_fill_winding_rule: false,
+            has_non_scaling_strokes: false,
+            has_scaling_strokes: false,
+            styles: swf::ShapeStyles {
+                fill_styles: vec![fill_style],
+                line_styles: vec![],
+            },
+            shape: vec![
+                swf::ShapeRecord::StyleChange(Box::new(swf::StyleChangeData {
+                    move_to: Some((Twips::ZERO, Twips::ZERO)),
+                    fill_style_0: None,
+                    fill_style_1: Some(1),
+                    line_style: None,
+                    new_styles: None,
+                })),
+                swf::ShapeRecord::StraightEdge {
+                    delta_x: width,
+                    delta_y: Twips::ZERO,
+                },
+                swf::ShapeRecord::StraightEdge {
+                    delta_x: Twips::ZERO,
+                    delta_y: height,
+                },
+                swf::ShapeRecord::StraightEdge {
+                    delta_x: Twips::ZERO - width,
+                    delta_y: Twips::ZERO,
+                },
+                swf::ShapeRecord::StraightEdge {
+                    delta_x: Twips::ZERO,
+                    delta_y: Twips::ZERO - height,
+                },
+            ],
+        }
+    }
+
+    fn draw(backend: &mut SoftwareRenderBackend, shape: ShapeHandle, x: f64, y: f64) {
+        backend.render_shape(
+            shape,
+            &Transform {
+                matrix: Matrix::translate(Twips::from_pixels(x), Twips::from_pixels(y)),
+                ..Default::default()
+            },
+        );
+    }
+
+    fn pixel(backend: &SoftwareRenderBackend, x: u32, y: u32) -> [u8; 4] {
+        let frame = backend.capture_frame();
+        let index = ((y * frame.width + x) * 4) as usize;
+        match frame.data {
+            BitmapFormat::Rgba(rgba) => rgba[index..index + 4].try_into().unwrap(),
+            BitmapFormat::Rgb(_) => unreachable!(),
+        }
+    }
+
+    fn assert_pixel_near(actual: [u8; 4], expected: [u8; 4]) {
+        let near = actual
+            .iter()
+            .zip(expected)
+            .all(|(&actual, expected)| (i16::from(actual) - i16::from(expected)).abs() <= 1);
+        assert!(near, "expected {:?}, got {:?}", expected, actual);
+    }
+
+    #[test]
+    fn solid_fill() {
+        let mut backend = SoftwareRenderBackend::new(20, 20);
+        let shape = rectangle(10.0, 10.0, swf::FillStyle::Color(RED));
+        let shape = backend.register_shape((&shape).into(), &NullBitmapSource);
+
+        backend.begin_frame(WHITE);
+        draw(&mut backend, shape, 5.0, 5.0);
+
+        assert_eq!(pixel(&backend, 4, 4), [255, 255, 255, 255]);
+        assert_eq!(pixel(&backend, 5, 5), [255, 0, 0, 255]);
+        assert_eq!(pixel(&backend, 14, 14), [255, 0, 0, 255]);
+        assert_eq!(pixel(&backend, 15, 15), [255, 255, 255, 255]);
+    }
+
+    #[test]
+    fn edges_are_anti_aliased() {
+        let mut backend = SoftwareRenderBackend::new(20, 20);
+        let shape = rectangle(10.0, 10.0, swf::FillStyle::Color(RED));
+        let shape = backend.register_shape((&shape).into(), &NullBitmapSource);
+
+        backend.begin_frame(WHITE);
+        draw(&mut backend, shape, 5.5, 5.0);
+
+        // Half of the samples of each pixel on the left and right edges are
+        // inside the shape.
+        assert_eq!(pixel(&backend, 5, 8), [255, 128, 128, 255]);
+        assert_eq!(pixel(&backend, 6, 8), [255, 0, 0, 255]);
+        assert_eq!(pixel(&backend, 15, 8), [255, 128, 128, 255]);
+    }
+
+    #[test]
+    fn mask_clips_content() {
+        let mut backend = SoftwareRenderBackend::new(20, 20);
+        let mask = rectangle(10.0, 20.0, swf::FillStyle::Color(BLUE));
+        let mask = backend.register_shape((&mask).into(), &NullBitmapSource);
+        let content = rectangle(20.0, 10.0, swf::FillStyle::Color(RED));
+        let content = backend.register_shape((&content).into(), &NullBitmapSource);
+
+        backend.begin_frame(WHITE);
+        backend.push_mask();
+        draw(&mut backend, mask, 0.0, 0.0);
+        backend.activate_mask();
+        draw(&mut backend, content, 0.0, 0.0);
+        backend.deactivate_mask();
+        draw(&mut backend, mask, 0.0, 0.0);
+        backend.pop_mask();
+
+        // Only the overlap of the mask and the content is drawn, and the mask
+        // itself is never visible.
+        assert_eq!(pixel(&backend, 5, 5), [255, 0, 0, 255]);
+        assert_eq!(pixel(&backend, 15, 5), [255, 255, 255, 255]);
+        assert_eq!(pixel(&backend, 5, 15), [255, 255, 255, 255]);
+
+        // Content drawn after the mask is popped is not clipped.
+        draw(&mut backend, content, 0.0, 10.0);
+        assert_eq!(pixel(&backend, 15, 15), [255, 0, 0, 255]);
+    }
+
+    #[test]
+    fn nested_masks_intersect() {
+        let mut backend = SoftwareRenderBackend::new(20, 20);
+        let left = rectangle(10.0, 20.0, swf::FillStyle::Color(BLUE));
+        let left = backend.register_shape((&left).into(), &NullBitmapSource);
+        let top = rectangle(20.0, 10.0, swf::FillStyle::Color(BLUE));
+        let top = backend.register_shape((&top).into(), &NullBitmapSource);
+        let content = rectangle(20.0, 20.0, swf::FillStyle::Color(RED));
+        let content = backend.register_shape((&content).into(), &NullBitmapSource);
+
+        backend.begin_frame(WHITE);
+        backend.push_mask();
+        draw(&mut backend, left, 0.0, 0.0);
+        backend.activate_mask();
+        backend.push_mask();
+        draw(&mut backend, top, 0.0, 0.0);
+        backend.activate_mask();
+        draw(&mut backend, content, 0.0, 0.0);
+        backend.deactivate_mask();
+        draw(&mut backend, top, 0.0, 0.0);
+        backend.pop_mask();
+        backend.deactivate_mask();
+        draw(&mut backend, left, 0.0, 0.0);
+        backend.pop_mask();
+
+        assert_eq!(pixel(&backend, 5, 5), [255, 0, 0, 255]);
+        assert_eq!(pixel(&backend, 15, 5), [255, 255, 255, 255]);
+        assert_eq!(pixel(&backend, 5, 15), [255, 255, 255, 255]);
+        assert_eq!(pixel(&backend, 15, 15), [255, 255, 255, 255]);
+    }
+
+    /// A gradient whose square spans the 100 pixel box at the origin.
+    fn gradient(spread: swf::GradientSpread) -> swf::Gradient {
+        // The gradient square is 32768 twips wide, and 100 pixels are 2000 twips.
+        let scale = Fixed16::from_f64(2000.0 / 32768.0);
+        swf::Gradient {
+            matrix: swf::Matrix {
+                a: scale,
+                d: scale,
+                tx: Twips::from_pixels(50.0),
+                ty: Twips::from_pixels(50.0),
+                ..swf::Matrix::IDENTITY
+            },
+            spread,
+            interpolation: swf::GradientInterpolation::Rgb,
+            records: vec![
+                swf::GradientRecord {
+                    ratio: 0,
+                    color: RED,
+                },
+                swf::GradientRecord {
+                    ratio: 255,
+                    color: BLUE,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn linear_gradient_fill() {
+        let mut backend = SoftwareRenderBackend::new(100, 10);
+        let shape = rectangle(
+            100.0,
+            10.0,
+            swf::FillStyle::LinearGradient(gradient(swf::GradientSpread::Pad)),
+        );
+        let shape = backend.register_shape((&shape).into(), &NullBitmapSource);
+
+        backend.begin_frame(WHITE);
+        draw(&mut backend, shape, 0.0, 0.0);
+
+        // Pixels are sampled at their centers, so pixel 49 is 49.5% of the
+        // way from red to blue.
+        assert_pixel_near(pixel(&backend, 0, 5), [254, 0, 1, 255]);
+        assert_pixel_near(pixel(&backend, 49, 5), [129, 0, 126, 255]);
+        assert_pixel_near(pixel(&backend, 99, 5), [1, 0, 254, 255]);
+    }
+
+    #[test]
+    fn radial_gradient_pads_outside_its_square() {
+        let mut backend = SoftwareRenderBackend::new(100, 100);
+        let shape = rectangle(
+            100.0,
+            100.0,
+            swf::FillStyle::RadialGradient(gradient(swf::GradientSpread::Pad)),
+        );
+        let shape = backend.register_shape((&shape).into(), &NullBitmapSource);
+
+        backend.begin_frame(WHITE);
+        draw(&mut backend, shape, 0.0, 0.0);
+
+        // Pixel 50 is half a pixel away from the center on both axes, and
+        // the corners are beyond the gradient's radius and are padded with
+        // the last color.
+        assert_pixel_near(pixel(&backend, 50, 50), [251, 0, 4, 255]);
+        assert_eq!(pixel(&backend, 0, 0), [0, 0, 255, 255]);
+        assert_eq!(pixel(&backend, 99, 99), [0, 0, 255, 255]);
+    }
+
+    #[test]
+    fn repeating_gradient_fill() {
+        let mut backend = SoftwareRenderBackend::new(200, 10);
+        let shape = rectangle(
+            200.0,
+            10.0,
+            swf::FillStyle::LinearGradient(gradient(swf::GradientSpread::Repeat)),
+        );
+        let shape = backend.register_shape((&shape).into(), &NullBitmapSource);
+
+        backend.begin_frame(WHITE);
+        draw(&mut backend, shape, 0.0, 0.0);
+
+        assert_pixel_near(pixel(&backend, 149, 5), pixel(&backend, 49, 5));
+        assert_pixel_near(pixel(&backend, 100, 5), [254, 0, 1, 255]);
+    }
+
+    /// Registers a 2x2 bitmap of red, green, blue and transparent texels.
+    fn checkerboard(backend: &mut SoftwareRenderBackend) -> BitmapInfo {
+        #[rustfmt::skip]
+        let rgba = vec![
+            255, 0, 0, 255,   0, 255, 0, 255,
+            0, 0, 255, 255,   0, 0, 0, 0,
+        ];
+        let handle = backend.register_bitmap_raw(2, 2, rgba).unwrap();
+        BitmapInfo {
+            handle,
+            width: 2,
+            height: 2,
+        }
+    }
+
+    fn bitmap_fill(is_repeating: bool) -> swf::FillStyle {
+        // Bitmap fills are measured in twips, so a scale of 200 makes each
+        // texel 10 pixels wide.
+        swf::FillStyle::Bitmap {
+            id: 1,
+            matrix: swf::Matrix::scale(Fixed16::from_f64(200.0), Fixed16::from_f64(200.0)),
+            is_smoothed: false,
+            is_repeating,
+        }
+    }
+
+    #[test]
+    fn repeating_bitmap_fill() {
+        let mut backend = SoftwareRenderBackend::new(40, 40);
+        let bitmap = checkerboard(&mut backend);
+        let shape = rectangle(40.0, 40.0, bitmap_fill(true));
+        let shape = backend.register_shape((&shape).into(), &TestBitmapSource(bitmap));
+
+        backend.begin_frame(WHITE);
+        draw(&mut backend, shape, 0.0, 0.0);
+
+        assert_eq!(pixel(&backend, 5, 5), [255, 0, 0, 255]);
+        assert_eq!(pixel(&backend, 15, 5), [0, 255, 0, 255]);
+        assert_eq!(pixel(&backend, 5, 15), [0, 0, 255, 255]);
+        // Transparent texels leave the background visible.
+        assert_eq!(pixel(&backend, 15, 15), [255, 255, 255, 255]);
+        assert_eq!(pixel(&backend, 25, 25), [255, 0, 0, 255]);
+        assert_eq!(pixel(&backend, 35, 5), [0, 255, 0, 255]);
+    }
+
+    #[test]
+    fn clipped_bitmap_fill_clamps_to_the_edge() {
+        let mut backend = SoftwareRenderBackend::new(40, 40);
+        let bitmap = checkerboard(&mut backend);
+        let shape = rectangle(40.0, 40.0, bitmap_fill(false));
+        let shape = backend.register_shape((&shape).into(), &TestBitmapSource(bitmap));
+
+        backend.begin_frame(WHITE);
+        draw(&mut backend, shape, 0.0, 0.0);
+
+        assert_eq!(pixel(&backend, 5, 5), [255, 0, 0, 255]);
+        assert_eq!(pixel(&backend, 35, 5), [0, 255, 0, 255]);
+        assert_eq!(pixel(&backend, 5, 35), [0, 0, 255, 255]);
+        assert_eq!(pixel(&backend, 35, 35), [255, 255, 255, 255]);
+    }
+
+    #[test]
+    fn bitmap_fill_applies_color_transform() {
+        let mut backend = SoftwareRenderBackend::new(40, 40);
+        let bitmap = checkerboard(&mut backend);
+        let shape = rectangle(20.0, 20.0, bitmap_fill(false));
+        let shape = backend.register_shape((&shape).into(), &TestBitmapSource(bitmap));
+
+        backend.begin_frame(WHITE);
+        backend.render_shape(
+            shape,
+            &Transform {
+                color_transform: ColorTransform {
+                    a_mult: Fixed8::from_f32(0.5),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        );
+
+        // Red at half alpha over white.
+        assert_eq!(pixel(&backend, 5, 5), [255, 128, 128, 255]);
+    }
+}
@@ -0,0 +1,139 @@
+//! Anti-aliased triangle rasterization.
+
+/// The number of samples taken along each axis of a pixel.
+const SAMPLES_PER_AXIS: u32 = 4;
+
+/// The number of samples taken per pixel. Each sample is a bit of a `u16` mask.
+const SAMPLES_PER_PIXEL: u32 = SAMPLES_PER_AXIS * SAMPLES_PER_AXIS;
+
+/// A point in screen space, in pixels.
+#[derive(Copy, Clone, Debug)]
+pub struct Point {
+    pub x: f32,
+    pub y: f32,
+}
+
+/// Which samples of each pixel are covered by a set of triangles.
+///
+/// Triangles of the same fill are accumulated together before being drawn,
+/// so that edges shared between them are not blended twice, which would
+/// otherwise show up as seams.
+pub struct Coverage {
+    width: u32,
+    height: u32,
+    masks: Vec<u16>,
+
+    /// The area that may contain covered samples, as `(x_min, y_min, x_max, y_max)`
+    /// with exclusive maximums.
+    bounds: Option<(u32, u32, u32, u32)>,
+}
+
+impl Coverage {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            masks: vec![0; width as usize * height as usize],
+            bounds: None,
+        }
+    }
+
+    /// Mark the samples inside a triangle as covered.
+    ///
+    /// Samples that lie exactly on an edge count as inside, so triangles
+    /// sharing an edge never leave gaps between them.
+    pub fn add_triangle(&mut self, points: [Point; 3]) {
+        if points.iter().any(|p| !p.x.is_finite() || !p.y.is_finite()) {
+            return;
+        }
+
+        let [p0, mut p1, mut p2] = points;
+        let area = (p1.x - p0.x) * (p2.y - p0.y) - (p1.y - p0.y) * (p2.x - p0.x);
+        if area == 0.0 {
+            return;
+        } else if area < 0.0 {
+            std::mem::swap(&mut p1, &mut p2);
+        }
+
+        let x_min = p0.x.min(p1.x).min(p2.x).floor().max(0.0) as u32;
+        let y_min = p0.y.min(p1.y).min(p2.y).floor().max(0.0) as u32;
+        let x_max = (p0.x.max(p1.x).max(p2.x).ceil().max(0.0) as u32).min(self.width);
+        let y_max = (p0.y.max(p1.y).max(p2.y).ceil().max(0.0) as u32).min(self.height);
+        if x_min >= x_max || y_min >= y_max {
+            return;
+        }
+
+        let edges = [Edge::new(p0, p1), Edge::new(p1, p2), Edge::new(p2, p0)];
+        let mut any_covered = false;
+        for y in y_min..y_max {
+            for x in x_min..x_max {
+                let mut mask = 0u16;
+                for sample in 0..SAMPLES_PER_PIXEL {
+                    let sample_x = x as f32 + sample_offset(sample % SAMPLES_PER_AXIS);
+                    let sample_y = y as f32 + sample_offset(sample / SAMPLES_PER_AXIS);
+                    if edges.iter().all(|edge| edge.contains(sample_x, sample_y)) {
+                        mask |= 1 << sample;
+                    }
+                }
+                if mask != 0 {
+                    self.masks[(y * self.width + x) as usize] |= mask;
+                    any_covered = true;
+                }
+            }
+        }
+
+        if any_covered {
+            self.bounds = Some(match self.bounds {
+                Some((x0, y0, x1, y1)) => {
+                    (x0.min(x_min), y0.min(y_min), x1.max(x_max), y1.max(y_max))
+                }
+                None => (x_min, y_min, x_max, y_max),
+            });
+        }
+    }
+
+    /// Call `f` with the position and coverage of every covered pixel, and
+    /// reset the coverage for the next set of triangles.
+    pub fn drain(&mut self, mut f: impl FnMut(u32, u32, f32)) {
+        if let Some((x_min, y_min, x_max, y_max)) = self.bounds.take() {
+            for y in y_min..y_max {
+                for x in x_min..x_max {
+                    let mask = &mut self.masks[(y * self.width + x) as usize];
+                    if *mask != 0 {
+                        f(x, y, mask.count_ones() as f32 / SAMPLES_PER_PIXEL as f32);
+                        *mask = 0;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The position of a sample along an axis, relative to the pixel's corner.
+fn sample_offset(index: u32) -> f32 {
+    (index as f32 + 0.5) / SAMPLES_PER_AXIS as f32
+}
+
+/// An edge of a counter-clockwise triangle, as the line equation
+/// `a * x + b * y + c`, which is positive on the inside of the triangle.
+struct Edge {
+    a: f32,
+    b: f32,
+    c: f32,
+}
+
+impl Edge {
+    fn new(from: Point, to: Point) -> Self {
+        let a = from.y - to.y;
+        let b = to.x - from.x;
+        Self {
+            a,
+            b,
+            c: -(a * from.x + b * from.y),
+        }
+    }
+
+    fn contains(&self, x: f32, y: f32) -> bool {
+        self.a * x + self.b * y + self.c >= 0.0
+    }
+}
@@ -126,7 +126,11 @@ pub struct WgpuRenderBackend<T: RenderTarget> {
 
 #[allow(dead_code)]
 struct Frame<'a, T: RenderTarget> {
-    frame_data: Box<(wgpu::CommandEncoder, T::Frame, wgpu::CommandEncoder)>,
+    frame_data: Box<(
+        wgpu::CommandEncoder,
+        FrameOutput<T::Frame>,
+        wgpu::CommandEncoder,
+    )>,
 
     // TODO: This is a self-reference to the above, so we
     // use some unsafe to cast the lifetime away. We know this
@@ -144,6 +148,27 @@ impl<'a, T: RenderTarget> Frame<'static, T> {
     }
 }
 
+/// The texture that a frame is being rendered to.
+#[derive(Debug)]
+enum FrameOutput<F: RenderTargetFrame> {
+    /// The backend's own render target.
+    Target(F),
+
+    /// An offscreen texture, used by `render_offscreen`.
+    #[cfg(not(target_family = "wasm"))]
+    Offscreen(target::TextureTargetFrame),
+}
+
+impl<F: RenderTargetFrame> FrameOutput<F> {
+    fn view(&self) -> &wgpu::TextureView {
+        match self {
+            FrameOutput::Target(frame) => frame.view(),
+            #[cfg(not(target_family = "wasm"))]
+            FrameOutput::Offscreen(frame) => frame.view(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Enum)]
 pub enum MaskState {
     NoMask,
@@ -358,36 +383,12 @@ impl WgpuRenderBackend<target::TextureTarget> {
 
 impl<T: RenderTarget> WgpuRenderBackend<T> {
     pub fn new(mut descriptors: Descriptors, target: T) -> Result<Self, Error> {
-        let extent = wgpu::Extent3d {
-            width: target.width(),
-            height: target.height(),
-            depth_or_array_layers: 1,
-        };
-
-        let frame_buffer_label = create_debug_label!("Framebuffer texture");
-        let frame_buffer = descriptors.device.create_texture(&wgpu::TextureDescriptor {
-            label: frame_buffer_label.as_deref(),
-            size: extent,
-            mip_level_count: 1,
-            sample_count: descriptors.msaa_sample_count,
-            dimension: wgpu::TextureDimension::D2,
-            format: target.format(),
-            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
-        });
-        let frame_buffer_view = frame_buffer.create_view(&Default::default());
-
-        let depth_label = create_debug_label!("Depth texture");
-        let depth_texture = descriptors.device.create_texture(&wgpu::TextureDescriptor {
-            label: depth_label.as_deref(),
-            size: extent,
-            mip_level_count: 1,
-            sample_count: descriptors.msaa_sample_count,
-            dimension: wgpu::TextureDimension::D2,
-            format: wgpu::TextureFormat::Depth24PlusStencil8,
-            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
-        });
-
-        let depth_texture_view = depth_texture.create_view(&Default::default());
+        let (frame_buffer_view, depth_texture_view) = create_frame_buffer_views(
+            &descriptors,
+            target.format(),
+            target.width(),
+            target.height(),
+        );
 
         let (quad_vbo, quad_ibo, quad_tex_transforms) = create_quad_buffers(&descriptors.device);
 
@@ -764,6 +765,90 @@ impl<T: RenderTarget> WgpuRenderBackend<T> {
     pub fn device(&self) -> &wgpu::Device {
         &self.descriptors.device
     }
+
+    /// Starts a new frame that renders into the given output.
+    fn begin_frame_with_output(&mut self, frame_output: FrameOutput<T::Frame>, clear: Color) {
+        self.mask_state = MaskState::NoMask;
+        self.num_masks = 0;
+        self.descriptors.uniform_buffers.reset();
+
+        let label = create_debug_label!("Draw encoder");
+        let draw_encoder =
+            self.descriptors
+                .device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                    label: label.as_deref(),
+                });
+        let uniform_encoder_label = create_debug_label!("Uniform upload command encoder");
+        let uniform_encoder =
+            self.descriptors
+                .device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                    label: uniform_encoder_label.as_deref(),
+                });
+        let mut frame_data = Box::new((draw_encoder, frame_output, uniform_encoder));
+
+        self.descriptors
+            .globals
+            .update_uniform(&self.descriptors.device, &mut frame_data.0);
+
+        let (color_view, resolve_target) = if self.descriptors.msaa_sample_count >= 2 {
+            (&self.frame_buffer_view, Some(frame_data.1.view()))
+        } else {
+            (frame_data.1.view(), None)
+        };
+
+        let render_pass = frame_data.0.begin_render_pass(&wgpu::RenderPassDescriptor {
+            color_attachments: &[wgpu::RenderPassColorAttachment {
+                view: color_view,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color {
+                        r: f64::from(clear.r) / 255.0,
+                        g: f64::from(clear.g) / 255.0,
+                        b: f64::from(clear.b) / 255.0,
+                        a: f64::from(clear.a) / 255.0,
+                    }),
+                    store: true,
+                },
+                resolve_target,
+            }],
+            depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                view: &self.depth_texture_view,
+                depth_ops: Some(wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(0.0),
+                    store: true,
+                }),
+                stencil_ops: Some(wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(0),
+                    store: true,
+                }),
+            }),
+            label: None,
+        });
+
+        // Since RenderPass holds a reference to the CommandEncoder, we cast the lifetime
+        // away to allow for the self-referencing struct. draw_encoder is boxed so its
+        // address should remain stable.
+        self.current_frame = Some(Frame {
+            render_pass: unsafe {
+                std::mem::transmute::<_, wgpu::RenderPass<'static>>(render_pass)
+            },
+            frame_data,
+        });
+    }
+
+    /// Finishes the current frame, returning its command buffers and output.
+    fn finish_frame(&mut self) -> Option<(Vec<wgpu::CommandBuffer>, FrameOutput<T::Frame>)> {
+        let frame = self.current_frame.take()?;
+        // Finalize render pass.
+        drop(frame.render_pass);
+        self.descriptors.uniform_buffers.finish();
+        let (draw_encoder, frame_output, uniform_encoder) = *frame.frame_data;
+        Some((
+            vec![uniform_encoder.finish(), draw_encoder.finish()],
+            frame_output,
+        ))
+    }
 }
 
 impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
@@ -774,43 +859,10 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
 
         self.target.resize(&self.descriptors.device, width, height);
 
-        let label = create_debug_label!("Framebuffer texture");
-        let frame_buffer = self
-            .descriptors
-            .device
-            .create_texture(&wgpu::TextureDescriptor {
-                label: label.as_deref(),
-                size: wgpu::Extent3d {
-                    width,
-                    height,
-                    depth_or_array_layers: 1,
-                },
-                mip_level_count: 1,
-                sample_count: self.descriptors.msaa_sample_count,
-                dimension: wgpu::TextureDimension::D2,
-                format: self.target.format(),
-                usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
-            });
-        self.frame_buffer_view = frame_buffer.create_view(&Default::default());
-
-        let label = create_debug_label!("Depth texture");
-        let depth_texture = self
-            .descriptors
-            .device
-            .create_texture(&wgpu::TextureDescriptor {
-                label: label.as_deref(),
-                size: wgpu::Extent3d {
-                    width,
-                    height,
-                    depth_or_array_layers: 1,
-                },
-                mip_level_count: 1,
-                sample_count: self.descriptors.msaa_sample_count,
-                dimension: wgpu::TextureDimension::D2,
-                format: wgpu::TextureFormat::Depth24PlusStencil8,
-                usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
-            });
-        self.depth_texture_view = depth_texture.create_view(&Default::default());
+        let (frame_buffer_view, depth_texture_view) =
+            create_frame_buffer_views(&self.descriptors, self.target.format(), width, height);
+        self.frame_buffer_view = frame_buffer_view;
+        self.depth_texture_view = depth_texture_view;
         self.descriptors.globals.set_resolution(width, height);
     }
 
@@ -879,10 +931,6 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
     }
 
     fn begin_frame(&mut self, clear: Color) {
-        self.mask_state = MaskState::NoMask;
-        self.num_masks = 0;
-        self.descriptors.uniform_buffers.reset();
-
         let frame_output = match self.target.get_next_texture() {
             Ok(frame) => frame,
             Err(e) => {
@@ -897,69 +945,7 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
             }
         };
 
-        let label = create_debug_label!("Draw encoder");
-        let draw_encoder =
-            self.descriptors
-                .device
-                .create_command_encoder(&wgpu::CommandEncoderDescriptor {
-                    label: label.as_deref(),
-                });
-        let uniform_encoder_label = create_debug_label!("Uniform upload command encoder");
-        let uniform_encoder =
-            self.descriptors
-                .device
-                .create_command_encoder(&wgpu::CommandEncoderDescriptor {
-                    label: uniform_encoder_label.as_deref(),
-                });
-        let mut frame_data = Box::new((draw_encoder, frame_output, uniform_encoder));
-
-        self.descriptors
-            .globals
-            .update_uniform(&self.descriptors.device, &mut frame_data.0);
-
-        let (color_view, resolve_target) = if self.descriptors.msaa_sample_count >= 2 {
-            (&self.frame_buffer_view, Some(frame_data.1.view()))
-        } else {
-            (frame_data.1.view(), None)
-        };
-
-        let render_pass = frame_data.0.begin_render_pass(&wgpu::RenderPassDescriptor {
-            color_attachments: &[wgpu::RenderPassColorAttachment {
-                view: color_view,
-                ops: wgpu::Operations {
-                    load: wgpu::LoadOp::Clear(wgpu::Color {
-                        r: f64::from(clear.r) / 255.0,
-                        g: f64::from(clear.g) / 255.0,
-                        b: f64::from(clear.b) / 255.0,
-                        a: f64::from(clear.a) / 255.0,
-                    }),
-                    store: true,
-                },
-                resolve_target,
-            }],
-            depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
-                view: &self.depth_texture_view,
-                depth_ops: Some(wgpu::Operations {
-                    load: wgpu::LoadOp::Clear(0.0),
-                    store: true,
-                }),
-                stencil_ops: Some(wgpu::Operations {
-                    load: wgpu::LoadOp::Clear(0),
-                    store: true,
-                }),
-            }),
-            label: None,
-        });
-
-        // Since RenderPass holds a reference to the CommandEncoder, we cast the lifetime
-        // away to allow for the self-referencing struct. draw_encoder is boxed so its
-        // address should remain stable.
-        self.current_frame = Some(Frame {
-            render_pass: unsafe {
-                std::mem::transmute::<_, wgpu::RenderPass<'static>>(render_pass)
-            },
-            frame_data,
-        });
+        self.begin_frame_with_output(FrameOutput::Target(frame_output), clear);
     }
 
     fn render_bitmap(&mut self, bitmap: BitmapHandle, transform: &Transform, smoothing: bool) {
@@ -1222,18 +1208,20 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
     }
 
     fn end_frame(&mut self) {
-        if let Some(frame) = self.current_frame.take() {
-            // Finalize render pass.
-            drop(frame.render_pass);
-            self.descriptors.uniform_buffers.finish();
-            let draw_encoder = frame.frame_data.0;
-            let uniform_encoder = frame.frame_data.2;
-            self.target.submit(
-                &self.descriptors.device,
-                &self.descriptors.queue,
-                vec![uniform_encoder.finish(), draw_encoder.finish()],
-                frame.frame_data.1,
-            );
+        match self.finish_frame() {
+            Some((command_buffers, FrameOutput::Target(frame))) => {
+                self.target.submit(
+                    &self.descriptors.device,
+                    &self.descriptors.queue,
+                    command_buffers,
+                    frame,
+                );
+            }
+            #[cfg(not(target_family = "wasm"))]
+            Some((command_buffers, FrameOutput::Offscreen(_))) => {
+                self.descriptors.queue.submit(command_buffers);
+            }
+            None => {}
         }
     }
 
@@ -1324,6 +1312,97 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
 
         Ok(handle)
     }
+
+    #[cfg(not(target_family = "wasm"))]
+    fn render_offscreen(
+        &mut self,
+        commands: &ruffle_core::backend::render::CommandList,
+        width: u32,
+        height: u32,
+        clear: Color,
+    ) -> Option<Bitmap> {
+        // The pipelines are built for the target's format, which must match the offscreen texture.
+        if self.current_frame.is_some() || self.target.format() != wgpu::TextureFormat::Bgra8Unorm {
+            return None;
+        }
+
+        let width = std::cmp::max(width, 1);
+        let height = std::cmp::max(height, 1);
+        let mut offscreen = target::TextureTarget::new(&self.descriptors.device, (width, height));
+        let frame_output = offscreen.get_next_texture().ok()?;
+
+        let (frame_buffer_view, depth_texture_view) =
+            create_frame_buffer_views(&self.descriptors, offscreen.format(), width, height);
+        let frame_buffer_view = std::mem::replace(&mut self.frame_buffer_view, frame_buffer_view);
+        let depth_texture_view =
+            std::mem::replace(&mut self.depth_texture_view, depth_texture_view);
+        self.descriptors.globals.set_resolution(width, height);
+
+        self.begin_frame_with_output(FrameOutput::Offscreen(frame_output), clear);
+        commands.execute(self);
+        if let Some((command_buffers, FrameOutput::Offscreen(frame))) = self.finish_frame() {
+            offscreen.submit(
+                &self.descriptors.device,
+                &self.descriptors.queue,
+                command_buffers,
+                frame,
+            );
+        }
+
+        self.frame_buffer_view = frame_buffer_view;
+        self.depth_texture_view = depth_texture_view;
+        self.descriptors
+            .globals
+            .set_resolution(self.target.width(), self.target.height());
+
+        let image = offscreen.capture(&self.descriptors.device)?;
+        Some(Bitmap {
+            width,
+            height,
+            data: BitmapFormat::Rgba(image.into_raw()),
+        })
+    }
+}
+
+/// Creates the multisampled framebuffer and depth textures for a frame of the given size.
+fn create_frame_buffer_views(
+    descriptors: &Descriptors,
+    format: wgpu::TextureFormat,
+    width: u32,
+    height: u32,
+) -> (wgpu::TextureView, wgpu::TextureView) {
+    let extent = wgpu::Extent3d {
+        width,
+        height,
+        depth_or_array_layers: 1,
+    };
+
+    let frame_buffer_label = create_debug_label!("Framebuffer texture");
+    let frame_buffer = descriptors.device.create_texture(&wgpu::TextureDescriptor {
+        label: frame_buffer_label.as_deref(),
+        size: extent,
+        mip_level_count: 1,
+        sample_count: descriptors.msaa_sample_count,
+        dimension: wgpu::TextureDimension::D2,
+        format,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+    });
+
+    let depth_label = create_debug_label!("Depth texture");
+    let depth_texture = descriptors.device.create_texture(&wgpu::TextureDescriptor {
+        label: depth_label.as_deref(),
+        size: extent,
+        mip_level_count: 1,
+        sample_count: descriptors.msaa_sample_count,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::Depth24PlusStencil8,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+    });
+
+    (
+        frame_buffer.create_view(&Default::default()),
+        depth_texture.create_view(&Default::default()),
+    )
 }
 
 fn create_quad_buffers(device: &wgpu::Device) -> (wgpu::Buffer, wgpu::Buffer, wgpu::Buffer) {
@@ -1,10 +1,38 @@
 //! Post-scan analysis
 
 use crate::cli_options::AnalyzeOpt;
-use crate::file_results::{FileResults, Step};
+use crate::coverage::IssueKind;
+use crate::file_results::{Counts, FileResults, Step};
 use std::cmp::max;
+use std::collections::HashMap;
 use std::fs::File;
 
+/// How many missing features to list in the ranking.
+const MISSING_FEATURES_SHOWN: usize = 25;
+
+/// How many entries of each census table to list.
+const CENSUS_ENTRIES_SHOWN: usize = 25;
+
+/// The census tables, with the heading of each.
+const CENSUS_TABLES: [&str; 8] = [
+    "Tags",
+    "AVM1 actions",
+    "AVM2 ops",
+    "AS3 references",
+    "Video codecs",
+    "Audio codecs",
+    "Filters",
+    "Blend modes",
+];
+
+/// How often a missing feature was hit, or a census entry was counted,
+/// across a scan.
+#[derive(Default)]
+struct Impact {
+    files: u32,
+    hits: u64,
+}
+
 /// Generate and print statistics related to a scan's results
 pub fn analyze(results: impl Iterator<Item = FileResults>) {
     let mut total = 0;
@@ -14,10 +42,45 @@ pub fn analyze(results: impl Iterator<Item = FileResults>) {
     let mut parse = 0;
     let mut execute = 0;
     let mut complete = 0;
+    let mut missing_features: HashMap<(IssueKind, String), Impact> = HashMap::new();
+    let mut census: [HashMap<String, Impact>; 8] = Default::default();
 
     for result in results {
         total += 1;
 
+        let issues: [(IssueKind, &Counts); 5] = [
+            (IssueKind::Unimplemented, &result.unimplemented),
+            (IssueKind::UnknownAvm1Action, &result.unknown_avm1_actions),
+            (IssueKind::UnknownAvm2Op, &result.unknown_avm2_ops),
+            (IssueKind::MissingClass, &result.missing_classes),
+            (IssueKind::UnsupportedTag, &result.unsupported_tags),
+        ];
+        for (kind, counts) in issues {
+            for (name, count) in counts {
+                let impact = missing_features.entry((kind, name.clone())).or_default();
+                impact.files += 1;
+                impact.hits += u64::from(*count);
+            }
+        }
+
+        let census_counts: [&Counts; 8] = [
+            &result.tags,
+            &result.avm1_actions,
+            &result.avm2_ops,
+            &result.as3_references,
+            &result.video_codecs,
+            &result.audio_codecs,
+            &result.filters,
+            &result.blend_modes,
+        ];
+        for (table, counts) in census.iter_mut().zip(census_counts) {
+            for (name, count) in counts {
+                let impact = table.entry(name.clone()).or_default();
+                impact.files += 1;
+                impact.hits += u64::from(*count);
+            }
+        }
+
         match result.progress {
             Step::Start => start += 1,
             Step::Read => read += 1,
@@ -79,6 +142,47 @@ pub fn analyze(results: impl Iterator<Item = FileResults>) {
         digits = digits
     );
     println!();
+
+    if !missing_features.is_empty() {
+        let mut missing_features: Vec<_> = missing_features.into_iter().collect();
+        missing_features.sort_by(|(a_feature, a), (b_feature, b)| {
+            b.files
+                .cmp(&a.files)
+                .then(b.hits.cmp(&a.hits))
+                .then(a_feature.cmp(b_feature))
+        });
+
+        println!("Most impactful missing features:");
+        println!("{:>7} {:>9}  Feature", "Files", "Hits");
+        for ((kind, name), impact) in missing_features.iter().take(MISSING_FEATURES_SHOWN) {
+            println!("{:>7} {:>9}  {}: {}", impact.files, impact.hits, kind, name);
+        }
+        println!();
+    }
+
+    for (heading, table) in CENSUS_TABLES.iter().zip(census) {
+        if table.is_empty() {
+            continue;
+        }
+
+        let mut table: Vec<_> = table.into_iter().collect();
+        table.sort_by(|(a_name, a), (b_name, b)| {
+            b.files
+                .cmp(&a.files)
+                .then(b.hits.cmp(&a.hits))
+                .then(a_name.cmp(b_name))
+        });
+
+        println!("{}:", heading);
+        println!("{:>7} {:>9}  Name", "Files", "Count");
+        for (name, impact) in table.iter().take(CENSUS_ENTRIES_SHOWN) {
+            println!("{:>7} {:>9}  {}", impact.files, impact.hits, name);
+        }
+        if table.len() > CENSUS_ENTRIES_SHOWN {
+            println!("{:>17}  ({} more)", "", table.len() - CENSUS_ENTRIES_SHOWN);
+        }
+        println!();
+    }
 }
 
 pub fn analyze_main(opt: AnalyzeOpt) -> Result<(), std::io::Error> {
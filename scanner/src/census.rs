@@ -0,0 +1,371 @@
+//! Static content census.
+//!
+//! The census counts what a movie is made of — its tags, bytecode, AS3
+//! references, codecs and display effects — by parsing it alone. Nothing in
+//! the movie is run, so it is safe to take on untrusted content.
+
+use crate::file_results::Counts;
+use swf::avm2::types::{AbcFile, Index, Multiname, Namespace};
+use swf::{BlendMode, Filter, SwfBuf, Tag, TagCode};
+
+/// The tag code of `DefineSprite`, whose body contains a nested tag list.
+const DEFINE_SPRITE: u16 = TagCode::DefineSprite as u16;
+
+/// What a single movie contains.
+#[derive(Debug, Clone, Default)]
+pub struct Census {
+    pub tags: Counts,
+    pub avm1_actions: Counts,
+    pub avm2_ops: Counts,
+    pub as3_references: Counts,
+    pub video_codecs: Counts,
+    pub audio_codecs: Counts,
+    pub filters: Counts,
+    pub blend_modes: Counts,
+}
+
+impl Census {
+    /// Take the census of a movie, given its decompressed data and the tags
+    /// parsed from it.
+    pub fn take(swf_buf: &SwfBuf, tags: &[Tag<'_>]) -> Self {
+        let version = swf_buf.header.version();
+        let mut census = Census::default();
+
+        census.count_tag_codes(&swf_buf.data, version);
+        census.count_tags(tags, version);
+
+        census
+    }
+
+    /// Count the tag codes in a tag list, including unknown ones.
+    fn count_tag_codes(&mut self, data: &[u8], version: u8) {
+        let mut reader = swf::read::Reader::new(data, version);
+        while let Ok((tag_code, length)) = reader.read_tag_code_and_length() {
+            if tag_code == 0 {
+                break;
+            }
+            increment(&mut self.tags, TagCode::format(tag_code));
+
+            let input = reader.get_mut();
+            let body = &input[..length.min(input.len())];
+            *input = &input[body.len()..];
+            if tag_code == DEFINE_SPRITE && body.len() >= 4 {
+                // Skip the sprite's ID and frame count.
+                self.count_tag_codes(&body[4..], version);
+            }
+        }
+    }
+
+    fn count_tags(&mut self, tags: &[Tag<'_>], version: u8) {
+        for tag in tags {
+            match tag {
+                Tag::DoAction(action_data) => self.count_avm1_actions(action_data, version),
+                Tag::DoInitAction { action_data, .. } => {
+                    self.count_avm1_actions(action_data, version)
+                }
+                Tag::DoAbc(do_abc) => self.count_abc(do_abc.data),
+                Tag::DefineSprite(sprite) => self.count_tags(&sprite.tags, version),
+                Tag::DefineButton(button) | Tag::DefineButton2(button) => {
+                    for record in &button.records {
+                        self.count_filters(&record.filters);
+                        self.count_blend_mode(record.blend_mode);
+                    }
+                    for action in &button.actions {
+                        self.count_avm1_actions(action.action_data, version);
+                    }
+                }
+                Tag::PlaceObject(place_object) => {
+                    if let Some(filters) = &place_object.filters {
+                        self.count_filters(filters);
+                    }
+                    if let Some(blend_mode) = place_object.blend_mode {
+                        self.count_blend_mode(blend_mode);
+                    }
+                    for clip_action in place_object.clip_actions.iter().flatten() {
+                        self.count_avm1_actions(clip_action.action_data, version);
+                    }
+                }
+                Tag::DefineVideoStream(video) => {
+                    increment(&mut self.video_codecs, format!("{:?}", video.codec))
+                }
+                Tag::DefineSound(sound) => increment(
+                    &mut self.audio_codecs,
+                    format!("{:?}", sound.format.compression),
+                ),
+                Tag::SoundStreamHead(head) | Tag::SoundStreamHead2(head) => increment(
+                    &mut self.audio_codecs,
+                    format!("{:?}", head.stream_format.compression),
+                ),
+                _ => {}
+            }
+        }
+    }
+
+    /// Count the actions in a block of AVM1 bytecode.
+    ///
+    /// Function bodies follow their `DefineFunction` action inline, so they
+    /// are counted along with the rest of the block.
+    fn count_avm1_actions(&mut self, data: &[u8], version: u8) {
+        let mut reader = swf::avm1::read::Reader::new(data, version);
+        while let Ok((opcode, length)) = reader.read_opcode_and_length() {
+            if opcode != 0 {
+                increment(
+                    &mut self.avm1_actions,
+                    swf::avm1::opcode::OpCode::format(opcode),
+                );
+            }
+
+            let input = reader.get_mut();
+            *input = &input[length.min(input.len())..];
+        }
+    }
+
+    /// Count the ops and references of an ABC file.
+    ///
+    /// Anything after a part of the file that cannot be read is left out.
+    fn count_abc(&mut self, data: &[u8]) {
+        let abc = match swf::avm2::read::Reader::new(data).read() {
+            Ok(abc) => abc,
+            Err(_) => return,
+        };
+
+        for body in &abc.method_bodies {
+            let mut reader = swf::avm2::read::Reader::new(&body.code);
+            while let Ok(op) = reader.read_op() {
+                // Only the variant name of the op is of interest.
+                let op = format!("{:?}", op);
+                let name = op.split(|c| c == ' ' || c == '{' || c == '(').next();
+                increment(&mut self.avm2_ops, name.unwrap_or_default().to_string());
+            }
+        }
+
+        for multiname in &abc.constant_pool.multinames {
+            if let Some(name) = multiname_name(&abc, multiname) {
+                increment(&mut self.as3_references, name);
+            }
+        }
+    }
+
+    fn count_filters(&mut self, filters: &[Filter]) {
+        for filter in filters {
+            let name = match filter {
+                Filter::DropShadowFilter(_) => "DropShadowFilter",
+                Filter::BlurFilter(_) => "BlurFilter",
+                Filter::GlowFilter(_) => "GlowFilter",
+                Filter::BevelFilter(_) => "BevelFilter",
+                Filter::GradientGlowFilter(_) => "GradientGlowFilter",
+                Filter::ConvolutionFilter(_) => "ConvolutionFilter",
+                Filter::ColorMatrixFilter(_) => "ColorMatrixFilter",
+                Filter::GradientBevelFilter(_) => "GradientBevelFilter",
+            };
+            increment(&mut self.filters, name.to_string());
+        }
+    }
+
+    fn count_blend_mode(&mut self, blend_mode: BlendMode) {
+        if blend_mode != BlendMode::Normal {
+            increment(&mut self.blend_modes, format!("{:?}", blend_mode));
+        }
+    }
+}
+
+fn increment(counts: &mut Counts, name: String) {
+    *counts.entry(name).or_insert(0) += 1;
+}
+
+/// Get the name a multiname refers to.
+///
+/// Names in a package namespace are qualified with the package, so that
+/// references to player classes such as `flash.display.MovieClip` can be told
+/// apart. Runtime and late-bound names have no static name, and names in
+/// private or protected namespaces only refer to the movie's own code, so
+/// neither are counted.
+fn multiname_name(abc: &AbcFile, multiname: &Multiname) -> Option<String> {
+    let pool = &abc.constant_pool;
+    match multiname {
+        Multiname::QName { namespace, name } | Multiname::QNameA { namespace, name } => {
+            let package = match pool.namespaces.get(namespace.0.checked_sub(1)? as usize)? {
+                Namespace::Package(package) | Namespace::PackageInternal(package) => {
+                    pool_string(abc, package)?
+                }
+                _ => return None,
+            };
+            let name = pool_string(abc, name).filter(|name| !name.is_empty())?;
+            if package.is_empty() {
+                Some(name.to_string())
+            } else {
+                Some(format!("{}.{}", package, name))
+            }
+        }
+        Multiname::Multiname { name, .. } | Multiname::MultinameA { name, .. } => {
+            pool_string(abc, name)
+                .filter(|name| !name.is_empty())
+                .map(|name| name.to_string())
+        }
+        _ => None,
+    }
+}
+
+/// Get a string from the constant pool. Index 0 is the empty string, which
+/// stands for any name.
+fn pool_string<'a>(abc: &'a AbcFile, index: &Index<String>) -> Option<&'a str> {
+    match index.0 {
+        0 => Some(""),
+        i => abc
+            .constant_pool
+            .strings
+            .get(i as usize - 1)
+            .map(|s| s.as_str()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use swf::avm2::types::{ConstantPool, Method, MethodBody};
+    use swf::{DoAbc, Header, Sprite, SwfStr};
+
+    /// Write a movie made of `tags`, then take its census.
+    fn census(tags: &[Tag<'_>]) -> Census {
+        let mut data = vec![];
+        swf::write_swf(&Header::default_with_swf_version(10), tags, &mut data).unwrap();
+        let swf_buf = swf::decompress_swf(&data[..]).unwrap();
+        let swf = swf::parse_swf(&swf_buf).unwrap();
+        Census::take(&swf_buf, &swf.tags)
+    }
+
+    fn counts(counts: &[(&str, u32)]) -> Counts {
+        counts
+            .iter()
+            .map(|(name, count)| (name.to_string(), *count))
+            .collect()
+    }
+
+    #[test]
+    fn count_tags_and_actions_in_sprites() {
+        let census = census(&[
+            // Stop, Play, Stop
+            Tag::DoAction(&[0x07, 0x06, 0x07, 0x00]),
+            Tag::DefineSprite(Sprite {
+                id: 1,
+                num_frames: 1,
+                tags: vec![Tag::DoAction(&[0x06, 0x00]), Tag::ShowFrame],
+            }),
+            Tag::Unknown {
+                tag_code: 255,
+                data: &[1, 2, 3],
+            },
+            Tag::ShowFrame,
+        ]);
+
+        assert_eq!(
+            census.tags,
+            counts(&[
+                ("DefineSprite", 1),
+                ("DoAction", 2),
+                ("ShowFrame", 2),
+                ("Unknown(255)", 1),
+            ])
+        );
+        assert_eq!(census.avm1_actions, counts(&[("Play", 2), ("Stop", 2)]));
+        assert!(census.avm2_ops.is_empty());
+    }
+
+    #[test]
+    fn count_abc_ops_and_references() {
+        let abc = AbcFile {
+            major_version: 46,
+            minor_version: 16,
+            constant_pool: ConstantPool {
+                ints: vec![],
+                uints: vec![],
+                doubles: vec![],
+                strings: vec![
+                    "flash.display".to_string(),
+                    "MovieClip".to_string(),
+                    "trace".to_string(),
+                    "secret".to_string(),
+                ],
+                namespaces: vec![
+                    Namespace::Package(Index::new(1)),
+                    Namespace::Package(Index::new(0)),
+                    Namespace::Private(Index::new(0)),
+                ],
+                namespace_sets: vec![],
+                multinames: vec![
+                    Multiname::QName {
+                        namespace: Index::new(1),
+                        name: Index::new(2),
+                    },
+                    Multiname::QName {
+                        namespace: Index::new(2),
+                        name: Index::new(3),
+                    },
+                    Multiname::QName {
+                        namespace: Index::new(3),
+                        name: Index::new(4),
+                    },
+                    Multiname::MultinameL {
+                        namespace_set: Index::new(0),
+                    },
+                ],
+            },
+            methods: vec![Method {
+                name: Index::new(0),
+                params: vec![],
+                return_type: Index::new(0),
+                needs_arguments_object: false,
+                needs_activation: false,
+                needs_rest: false,
+                needs_dxns: false,
+            }],
+            metadata: vec![],
+            instances: vec![],
+            classes: vec![],
+            scripts: vec![],
+            method_bodies: vec![MethodBody {
+                method: Index::new(0),
+                max_stack: 1,
+                num_locals: 1,
+                init_scope_depth: 0,
+                max_scope_depth: 1,
+                // getlocal0, pushscope, getlocal0, pushscope, returnvoid
+                code: vec![0xd0, 0x30, 0xd0, 0x30, 0x47],
+                exceptions: vec![],
+                traits: vec![],
+            }],
+        };
+        let mut data = vec![];
+        swf::avm2::write::Writer::new(&mut data).write(abc).unwrap();
+
+        let census = census(&[
+            Tag::DoAbc(DoAbc {
+                name: SwfStr::from_utf8_str(""),
+                is_lazy_initialize: false,
+                data: &data,
+            }),
+            Tag::ShowFrame,
+        ]);
+
+        assert_eq!(
+            census.avm2_ops,
+            counts(&[("GetLocal", 2), ("PushScope", 2), ("ReturnVoid", 1)])
+        );
+        assert_eq!(
+            census.as3_references,
+            counts(&[("flash.display.MovieClip", 1), ("trace", 1)])
+        );
+    }
+
+    #[test]
+    fn normal_blend_mode_is_not_counted() {
+        let mut census = Census::default();
+        census.count_blend_mode(BlendMode::Normal);
+        census.count_blend_mode(BlendMode::Multiply);
+        census.count_blend_mode(BlendMode::Multiply);
+        census.count_filters(&[]);
+
+        assert_eq!(census.blend_modes, counts(&[("Multiply", 2)]));
+        assert!(census.filters.is_empty());
+    }
+}
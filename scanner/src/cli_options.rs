@@ -18,6 +18,9 @@ pub enum Mode {
     /// Analyze a previously executed scan and compile statistics on it
     Analyze(AnalyzeOpt),
 
+    /// Compare two previously executed scans and report what changed
+    Compare(CompareOpt),
+
     /// Execute a single SWF file and generate a machine-readable report
     ExecuteReport(ExecuteReportOpt),
 }
@@ -35,6 +38,9 @@ pub struct ScanOpt {
     /// Filenames to ignore
     #[clap(short = 'i', long = "ignore")]
     pub ignore: Vec<String>,
+
+    #[clap(flatten)]
+    pub execute: ExecuteOpt,
 }
 
 #[derive(Parser, Debug)]
@@ -44,9 +50,67 @@ pub struct AnalyzeOpt {
     pub input_path: PathBuf,
 }
 
+#[derive(Parser, Debug)]
+pub struct CompareOpt {
+    /// The CSV file of the earlier scan
+    #[clap(name = "before", parse(from_os_str))]
+    pub before_path: PathBuf,
+
+    /// The CSV file of the later scan
+    #[clap(name = "after", parse(from_os_str))]
+    pub after_path: PathBuf,
+
+    /// The file to store every change in CSV format
+    #[clap(short = 'o', long = "output", parse(from_os_str))]
+    pub output_path: Option<PathBuf>,
+
+    /// How much, in percent, a file's test duration must change to be reported
+    #[clap(long = "duration-threshold", default_value = "50")]
+    pub duration_threshold: f64,
+
+    /// How much, in milliseconds, a file's test duration must change to be
+    /// reported
+    #[clap(long = "min-duration-change", default_value = "1000")]
+    pub min_duration_change: u128,
+}
+
 #[derive(Parser, Debug)]
 pub struct ExecuteReportOpt {
     /// The single SWF file to parse and run
     #[clap(name = "file", parse(from_os_str))]
     pub input_path: PathBuf,
+
+    #[clap(flatten)]
+    pub execute: ExecuteOpt,
+}
+
+/// Options controlling how each SWF file is run
+#[derive(Parser, Debug, Clone)]
+pub struct ExecuteOpt {
+    /// How many frames of each movie to run
+    #[clap(short = 'f', long = "frames", default_value = "1")]
+    pub frames: u32,
+
+    /// Send synthetic mouse and keyboard input to each movie while it runs
+    #[clap(long = "synthetic-input")]
+    pub synthetic_input: bool,
+
+    /// Only take a census of the tags, bytecode and assets in each movie,
+    /// without running it
+    #[clap(long = "static")]
+    pub static_census: bool,
+}
+
+impl ExecuteOpt {
+    /// The arguments that pass these options on to `execute-report`.
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = vec!["--frames".to_string(), self.frames.to_string()];
+        if self.synthetic_input {
+            args.push("--synthetic-input".to_string());
+        }
+        if self.static_census {
+            args.push("--static".to_string());
+        }
+        args
+    }
 }
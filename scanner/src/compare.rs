@@ -0,0 +1,393 @@
+//! Comparison of two scans
+
+use crate::cli_options::CompareOpt;
+use crate::file_results::{into_hex, FileResults};
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap};
+use std::fs::File;
+use std::io::{Error, ErrorKind};
+use std::path::Path;
+
+/// A kind of difference between the results of a file in two scans.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+    /// The file only appears in the later scan.
+    Added,
+
+    /// The file only appears in the earlier scan.
+    Removed,
+
+    /// The file failed at an earlier step than before.
+    ProgressRegressed,
+
+    /// The file got further through the scan than before.
+    ProgressImproved,
+
+    /// An error message that was not reported before.
+    NewError,
+
+    /// An error message that is no longer reported.
+    FixedError,
+
+    /// The file took longer to test, beyond the duration threshold.
+    Slower,
+
+    /// The file took less time to test, beyond the duration threshold.
+    Faster,
+}
+
+/// A single difference between two scans.
+#[derive(Serialize, Debug, Clone)]
+pub struct Change {
+    /// The file name, as of the later scan if it is in both.
+    #[serde(rename = "Filename")]
+    pub name: String,
+
+    /// The SHA256 hash of the SWF file.
+    #[serde(rename = "SHA256 Hash", serialize_with = "into_hex")]
+    pub hash: Vec<u8>,
+
+    #[serde(rename = "Change")]
+    pub kind: ChangeKind,
+
+    /// What the changed value was in the earlier scan.
+    #[serde(rename = "Before")]
+    pub before: Option<String>,
+
+    /// What the changed value is in the later scan.
+    #[serde(rename = "After")]
+    pub after: Option<String>,
+}
+
+/// Read all results of a scan, keyed by the hash of each file.
+///
+/// Files that could not be read have no hash, so they cannot be matched up
+/// and are left out; the number left out is returned along with the results.
+fn read_results(path: &Path) -> Result<(HashMap<Vec<u8>, FileResults>, usize), Error> {
+    let file = File::open(path)?;
+    let reader = csv::Reader::from_reader(file);
+    let mut results = HashMap::new();
+    let mut unhashed = 0;
+
+    for result in reader.into_deserialize::<FileResults>() {
+        let result = result.map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
+        if result.hash.is_empty() {
+            unhashed += 1;
+        } else {
+            results.insert(result.hash.clone(), result);
+        }
+    }
+
+    Ok((results, unhashed))
+}
+
+/// The distinct error messages of a result.
+fn error_messages(result: &FileResults) -> BTreeSet<&str> {
+    result
+        .error
+        .iter()
+        .flat_map(|error| error.lines())
+        .filter(|line| !line.is_empty())
+        .collect()
+}
+
+/// List the differences between the results of one file in two scans.
+fn compare_file(before: &FileResults, after: &FileResults, opt: &CompareOpt) -> Vec<Change> {
+    let mut changes = vec![];
+    let change = |kind, old_value: Option<String>, new_value: Option<String>| Change {
+        name: after.name.clone(),
+        hash: after.hash.clone(),
+        kind,
+        before: old_value,
+        after: new_value,
+    };
+
+    if after.progress != before.progress {
+        let kind = if after.progress < before.progress {
+            ChangeKind::ProgressRegressed
+        } else {
+            ChangeKind::ProgressImproved
+        };
+        changes.push(change(
+            kind,
+            Some(format!("{:?}", before.progress)),
+            Some(format!("{:?}", after.progress)),
+        ));
+    }
+
+    let before_errors = error_messages(before);
+    let after_errors = error_messages(after);
+    for error in after_errors.difference(&before_errors) {
+        changes.push(change(ChangeKind::NewError, None, Some(error.to_string())));
+    }
+    for error in before_errors.difference(&after_errors) {
+        changes.push(change(
+            ChangeKind::FixedError,
+            Some(error.to_string()),
+            None,
+        ));
+    }
+
+    let difference =
+        after.testing_time.max(before.testing_time) - after.testing_time.min(before.testing_time);
+    let relative = difference as f64 / before.testing_time.max(1) as f64 * 100.0;
+    if difference >= opt.min_duration_change && relative >= opt.duration_threshold {
+        let kind = if after.testing_time > before.testing_time {
+            ChangeKind::Slower
+        } else {
+            ChangeKind::Faster
+        };
+        changes.push(change(
+            kind,
+            Some(before.testing_time.to_string()),
+            Some(after.testing_time.to_string()),
+        ));
+    }
+
+    changes
+}
+
+/// List the differences between two scans, sorted by file name.
+fn compare_scans(
+    before: &HashMap<Vec<u8>, FileResults>,
+    after: &HashMap<Vec<u8>, FileResults>,
+    opt: &CompareOpt,
+) -> Vec<Change> {
+    let mut changes = vec![];
+    for (hash, after_result) in after {
+        match before.get(hash) {
+            Some(before_result) => changes.extend(compare_file(before_result, after_result, opt)),
+            None => changes.push(Change {
+                name: after_result.name.clone(),
+                hash: hash.clone(),
+                kind: ChangeKind::Added,
+                before: None,
+                after: Some(format!("{:?}", after_result.progress)),
+            }),
+        }
+    }
+    for (hash, before_result) in before {
+        if !after.contains_key(hash) {
+            changes.push(Change {
+                name: before_result.name.clone(),
+                hash: hash.clone(),
+                kind: ChangeKind::Removed,
+                before: Some(format!("{:?}", before_result.progress)),
+                after: None,
+            });
+        }
+    }
+    changes.sort_by(|a, b| a.name.cmp(&b.name));
+
+    changes
+}
+
+/// Compare two scans, print a summary of what changed and optionally write
+/// every change to a CSV file.
+///
+/// Returns an error if any file regressed, so that the exit status of the
+/// scanner can gate an upgrade.
+pub fn compare_main(opt: CompareOpt) -> Result<(), Error> {
+    let (before, before_unhashed) = read_results(&opt.before_path)?;
+    let (after, after_unhashed) = read_results(&opt.after_path)?;
+    let changes = compare_scans(&before, &after, &opt);
+
+    if let Some(output_path) = &opt.output_path {
+        let mut writer = csv::Writer::from_path(output_path)?;
+        for change in &changes {
+            writer.serialize(change)?;
+        }
+        writer.flush()?;
+    }
+
+    let count = |kind| changes.iter().filter(|change| change.kind == kind).count();
+    let files_with = |kind| {
+        changes
+            .iter()
+            .filter(|change| change.kind == kind)
+            .map(|change| &change.hash)
+            .collect::<BTreeSet<_>>()
+            .len()
+    };
+    let regressed = count(ChangeKind::ProgressRegressed);
+    let new_errors = files_with(ChangeKind::NewError);
+
+    println!(
+        "Compared {} swf files before and {} after.",
+        before.len(),
+        after.len()
+    );
+    if before_unhashed + after_unhashed > 0 {
+        println!(
+            "{} results without a hash could not be compared.",
+            before_unhashed + after_unhashed
+        );
+    }
+    println!();
+    println!("{:>7}  files regressed to an earlier step", regressed);
+    println!(
+        "{:>7}  files progressed to a later step",
+        count(ChangeKind::ProgressImproved)
+    );
+    println!("{:>7}  files reported new errors", new_errors);
+    println!(
+        "{:>7}  files no longer reported some errors",
+        files_with(ChangeKind::FixedError)
+    );
+    println!("{:>7}  files got slower", count(ChangeKind::Slower));
+    println!("{:>7}  files got faster", count(ChangeKind::Faster));
+    println!("{:>7}  files were added", count(ChangeKind::Added));
+    println!("{:>7}  files were removed", count(ChangeKind::Removed));
+    println!();
+
+    let regressions: Vec<_> = changes
+        .iter()
+        .filter(|change| {
+            matches!(
+                change.kind,
+                ChangeKind::ProgressRegressed | ChangeKind::NewError
+            )
+        })
+        .collect();
+    if !regressions.is_empty() {
+        println!("Regressions:");
+        for change in &regressions {
+            match change.kind {
+                ChangeKind::ProgressRegressed => println!(
+                    "  {}: {} -> {}",
+                    change.name,
+                    change.before.as_deref().unwrap_or_default(),
+                    change.after.as_deref().unwrap_or_default()
+                ),
+                _ => println!(
+                    "  {}: {}",
+                    change.name,
+                    change.after.as_deref().unwrap_or_default()
+                ),
+            }
+        }
+        println!();
+    }
+
+    if regressed + new_errors > 0 {
+        return Err(Error::new(
+            ErrorKind::Other,
+            format!(
+                "{} files regressed and {} files reported new errors",
+                regressed, new_errors
+            ),
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_results::Step;
+    use std::path::PathBuf;
+
+    fn opt() -> CompareOpt {
+        CompareOpt {
+            before_path: PathBuf::new(),
+            after_path: PathBuf::new(),
+            output_path: None,
+            duration_threshold: 50.0,
+            min_duration_change: 1000,
+        }
+    }
+
+    fn result(hash: u8, progress: Step, testing_time: u128, error: Option<&str>) -> FileResults {
+        let mut result = FileResults::new(&format!("{}.swf", hash));
+        result.hash = vec![hash];
+        result.progress = progress;
+        result.testing_time = testing_time;
+        result.error = error.map(|error| error.to_string());
+        result
+    }
+
+    fn kinds(changes: &[Change]) -> Vec<ChangeKind> {
+        changes.iter().map(|change| change.kind).collect()
+    }
+
+    #[test]
+    fn unchanged_file_has_no_changes() {
+        let before = result(1, Step::Complete, 2000, None);
+        let after = result(1, Step::Complete, 2500, None);
+        assert!(compare_file(&before, &after, &opt()).is_empty());
+    }
+
+    #[test]
+    fn progress_changes() {
+        let complete = result(1, Step::Complete, 100, None);
+        let execute = result(1, Step::Execute, 100, Some("PANIC: oops"));
+
+        let changes = compare_file(&complete, &execute, &opt());
+        assert_eq!(
+            kinds(&changes),
+            [ChangeKind::ProgressRegressed, ChangeKind::NewError]
+        );
+        assert_eq!(changes[0].before.as_deref(), Some("Complete"));
+        assert_eq!(changes[0].after.as_deref(), Some("Execute"));
+        assert_eq!(changes[1].after.as_deref(), Some("PANIC: oops"));
+
+        let changes = compare_file(&execute, &complete, &opt());
+        assert_eq!(
+            kinds(&changes),
+            [ChangeKind::ProgressImproved, ChangeKind::FixedError]
+        );
+    }
+
+    #[test]
+    fn errors_are_compared_line_by_line() {
+        let before = result(1, Step::Execute, 100, Some("first\nsecond"));
+        let after = result(1, Step::Execute, 100, Some("second\nthird\n"));
+
+        let changes = compare_file(&before, &after, &opt());
+        assert_eq!(
+            kinds(&changes),
+            [ChangeKind::NewError, ChangeKind::FixedError]
+        );
+        assert_eq!(changes[0].after.as_deref(), Some("third"));
+        assert_eq!(changes[1].before.as_deref(), Some("first"));
+    }
+
+    #[test]
+    fn duration_changes_need_both_thresholds() {
+        let opt = opt();
+        let compare = |before, after| {
+            kinds(&compare_file(
+                &result(1, Step::Complete, before, None),
+                &result(1, Step::Complete, after, None),
+                &opt,
+            ))
+        };
+
+        assert_eq!(compare(2000, 4000), [ChangeKind::Slower]);
+        assert_eq!(compare(4000, 2000), [ChangeKind::Faster]);
+        // Over the relative threshold, but under the minimum change.
+        assert!(compare(100, 900).is_empty());
+        // Over the minimum change, but under the relative threshold.
+        assert!(compare(10000, 12000).is_empty());
+    }
+
+    #[test]
+    fn added_and_removed_files() {
+        let before = HashMap::from([
+            (vec![1], result(1, Step::Complete, 100, None)),
+            (vec![2], result(2, Step::Complete, 100, None)),
+        ]);
+        let after = HashMap::from([
+            (vec![2], result(2, Step::Complete, 100, None)),
+            (vec![3], result(3, Step::Parse, 100, None)),
+        ]);
+
+        let changes = compare_scans(&before, &after, &opt());
+        assert_eq!(kinds(&changes), [ChangeKind::Removed, ChangeKind::Added]);
+        assert_eq!(changes[0].name, "1.swf");
+        assert_eq!(changes[0].before.as_deref(), Some("Complete"));
+        assert_eq!(changes[1].name, "3.swf");
+        assert_eq!(changes[1].after.as_deref(), Some("Parse"));
+    }
+}
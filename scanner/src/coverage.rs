@@ -0,0 +1,181 @@
+//! Missing feature coverage.
+//!
+//! While a movie runs, Ruffle logs a message whenever it hits something it
+//! does not support. This module sorts those messages into the kinds of
+//! missing features we track, so that they can be counted per file and
+//! ranked across a whole corpus.
+
+use crate::file_results::Counts;
+use std::fmt;
+
+/// A kind of missing feature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum IssueKind {
+    /// An API that is unimplemented or only a stub.
+    Unimplemented,
+
+    /// An AVM1 action we could not decode.
+    UnknownAvm1Action,
+
+    /// An AVM2 opcode we could not decode or run.
+    UnknownAvm2Op,
+
+    /// An AS3 class or global that was not defined.
+    MissingClass,
+
+    /// A SWF tag we do not recognize.
+    UnsupportedTag,
+}
+
+impl fmt::Display for IssueKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            IssueKind::Unimplemented => "Unimplemented API",
+            IssueKind::UnknownAvm1Action => "Unknown AVM1 action",
+            IssueKind::UnknownAvm2Op => "Unknown AVM2 op",
+            IssueKind::MissingClass => "Missing class",
+            IssueKind::UnsupportedTag => "Unsupported tag",
+        })
+    }
+}
+
+/// Determine which missing feature, if any, a log message reports.
+///
+/// Returns the kind of feature and the name it should be counted under.
+pub fn classify(message: &str) -> Option<(IssueKind, String)> {
+    const MISSING_VARIABLE: &str = "Error #1065: Variable ";
+
+    if let Some(start) = message.find(MISSING_VARIABLE) {
+        let name = &message[start + MISSING_VARIABLE.len()..];
+        let name = name.split(' ').next().unwrap_or_default();
+        return Some((IssueKind::MissingClass, name.to_string()));
+    }
+
+    let kind = if message.contains("Unknown AVM1 opcode") {
+        IssueKind::UnknownAvm1Action
+    } else if message.contains("Unknown AVM2 opcode")
+        || message.contains("Unknown ABC opcode")
+        || message.contains("illegal opcode")
+    {
+        IssueKind::UnknownAvm2Op
+    } else if message.contains("Unknown tag code") {
+        IssueKind::UnsupportedTag
+    } else {
+        let lowercase = message.to_lowercase();
+        if lowercase.contains("unimplemented")
+            || lowercase.contains("not implemented")
+            || lowercase.contains("is a stub")
+        {
+            IssueKind::Unimplemented
+        } else {
+            return None;
+        }
+    };
+
+    Some((kind, message.to_string()))
+}
+
+/// Issues hit while running a single movie, counted per distinct issue of
+/// each kind.
+#[derive(Debug, Clone, Default)]
+pub struct Coverage {
+    pub unimplemented: Counts,
+    pub unknown_avm1_actions: Counts,
+    pub unknown_avm2_ops: Counts,
+    pub missing_classes: Counts,
+    pub unsupported_tags: Counts,
+}
+
+impl Coverage {
+    /// Count a log message, if it reports a missing feature.
+    pub fn record(&mut self, message: &str) {
+        if let Some((kind, name)) = classify(message) {
+            *self.counts_mut(kind).entry(name).or_insert(0) += 1;
+        }
+    }
+
+    fn counts_mut(&mut self, kind: IssueKind) -> &mut Counts {
+        match kind {
+            IssueKind::Unimplemented => &mut self.unimplemented,
+            IssueKind::UnknownAvm1Action => &mut self.unknown_avm1_actions,
+            IssueKind::UnknownAvm2Op => &mut self.unknown_avm2_ops,
+            IssueKind::MissingClass => &mut self.missing_classes,
+            IssueKind::UnsupportedTag => &mut self.unsupported_tags,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classify_missing_features() {
+        assert_eq!(
+            classify("Unknown AVM1 opcode: 0xF0"),
+            Some((
+                IssueKind::UnknownAvm1Action,
+                "Unknown AVM1 opcode: 0xF0".to_string()
+            ))
+        );
+        assert_eq!(
+            classify("VerifyError: Error #1011: Unknown AVM2 opcode 0x42"),
+            Some((
+                IssueKind::UnknownAvm2Op,
+                "VerifyError: Error #1011: Unknown AVM2 opcode 0x42".to_string()
+            ))
+        );
+        assert_eq!(
+            classify("Unknown tag code: 999"),
+            Some((
+                IssueKind::UnsupportedTag,
+                "Unknown tag code: 999".to_string()
+            ))
+        );
+        assert_eq!(
+            classify("BitmapData.draw: Not Implemented"),
+            Some((
+                IssueKind::Unimplemented,
+                "BitmapData.draw: Not Implemented".to_string()
+            ))
+        );
+        assert_eq!(
+            classify("Sound.computeSpectrum is a stub"),
+            Some((
+                IssueKind::Unimplemented,
+                "Sound.computeSpectrum is a stub".to_string()
+            ))
+        );
+        assert_eq!(classify("Couldn't load font"), None);
+    }
+
+    #[test]
+    fn classify_missing_class_by_name() {
+        assert_eq!(
+            classify("ReferenceError: Error #1065: Variable flash.net::Socket is not defined."),
+            Some((IssueKind::MissingClass, "flash.net::Socket".to_string()))
+        );
+    }
+
+    #[test]
+    fn record_counts_each_issue() {
+        let mut coverage = Coverage::default();
+        coverage.record("Unknown tag code: 999");
+        coverage.record("Unknown tag code: 999");
+        coverage.record("Error #1065: Variable Foo is not defined.");
+        coverage.record("Error #1065: Variable Bar is not defined.");
+        coverage.record("Frame rendered");
+
+        assert_eq!(
+            coverage.unsupported_tags,
+            Counts::from([("Unknown tag code: 999".to_string(), 2)])
+        );
+        assert_eq!(
+            coverage.missing_classes,
+            Counts::from([("Bar".to_string(), 1), ("Foo".to_string(), 1)])
+        );
+        assert!(coverage.unimplemented.is_empty());
+        assert!(coverage.unknown_avm1_actions.is_empty());
+        assert!(coverage.unknown_avm2_ops.is_empty());
+    }
+}
@@ -1,18 +1,23 @@
 //! Child/executor process impls
 
-use crate::cli_options::ExecuteReportOpt;
+use crate::census::Census;
+use crate::cli_options::{ExecuteOpt, ExecuteReportOpt};
+use crate::coverage::Coverage;
 use crate::file_results::{AvmType, FileResults, Step};
-use crate::logging::{ScanLogBackend, ThreadLocalScanLogger, LOCAL_LOGGER};
+use crate::logging::{ScanLogBackend, ThreadLocalScanLogger, LOCAL_COVERAGE, LOCAL_LOGGER};
 use ruffle_core::backend::audio::NullAudioBackend;
+use ruffle_core::backend::font::NullFontBackend;
 use ruffle_core::backend::locale::NullLocaleBackend;
 use ruffle_core::backend::navigator::{NullExecutor, NullNavigatorBackend};
+use ruffle_core::backend::print::NullPrintBackend;
 use ruffle_core::backend::render::NullRenderer;
 use ruffle_core::backend::storage::MemoryStorageBackend;
 use ruffle_core::backend::ui::NullUiBackend;
 use ruffle_core::backend::video::NullVideoBackend;
+use ruffle_core::events::{KeyCode, MouseButton};
 use ruffle_core::swf::{decompress_swf, parse_swf};
 use ruffle_core::tag_utils::SwfMovie;
-use ruffle_core::Player;
+use ruffle_core::{Player, PlayerEvent};
 use sha2::{Digest, Sha256};
 
 use std::path::Path;
@@ -20,14 +25,54 @@ use std::path::Path;
 use std::panic::catch_unwind;
 
 use std::io::{stdout, Write};
+use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
-fn execute_swf(file: &Path) {
+/// The keys pressed in turn by synthetic input.
+const SYNTHETIC_KEYS: [(KeyCode, Option<char>); 6] = [
+    (KeyCode::Space, Some(' ')),
+    (KeyCode::Return, Some('\r')),
+    (KeyCode::Left, None),
+    (KeyCode::Right, None),
+    (KeyCode::Up, None),
+    (KeyCode::Down, None),
+];
+
+/// Generate the synthetic input for a frame.
+///
+/// The mouse sweeps the stage in an 8x8 grid, clicking once per frame, and a
+/// few commonly used keys are pressed in turn.
+fn synthetic_input(frame: u32, width: f64, height: f64) -> Vec<PlayerEvent> {
+    let cell = frame % 64;
+    let x = (f64::from(cell % 8) + 0.5) * width / 8.0;
+    let y = (f64::from(cell / 8) + 0.5) * height / 8.0;
+    let (key_code, key_char) = SYNTHETIC_KEYS[frame as usize % SYNTHETIC_KEYS.len()];
+
+    vec![
+        PlayerEvent::MouseMove { x, y },
+        PlayerEvent::MouseDown {
+            x,
+            y,
+            button: MouseButton::Left,
+        },
+        PlayerEvent::MouseUp {
+            x,
+            y,
+            button: MouseButton::Left,
+        },
+        PlayerEvent::KeyDown { key_code, key_char },
+        PlayerEvent::KeyUp { key_code, key_char },
+    ]
+}
+
+fn execute_swf(file: &Path, opt: &ExecuteOpt, frames_executed: &AtomicU32) {
     let base_path = file.parent().unwrap();
     let (_executor, channel) = NullExecutor::new();
     let movie = SwfMovie::from_path(file, None).unwrap();
     let frame_time = 1000.0 / movie.frame_rate().to_f64();
+    let width = movie.width().to_pixels();
+    let height = movie.height().to_pixels();
     let player = Player::new(
         Box::new(NullRenderer::new()),
         Box::new(NullAudioBackend::new()),
@@ -37,6 +82,8 @@ fn execute_swf(file: &Path) {
         Box::new(NullVideoBackend::new()),
         Box::new(ScanLogBackend::new()),
         Box::new(NullUiBackend::new()),
+        Box::new(NullPrintBackend::new()),
+        Box::new(NullFontBackend::new()),
     )
     .unwrap();
 
@@ -45,9 +92,23 @@ fn execute_swf(file: &Path) {
         .lock()
         .unwrap()
         .set_max_execution_duration(Duration::from_secs(300));
-    player.lock().unwrap().run_frame();
-    player.lock().unwrap().update_timers(frame_time);
-    //executor.poll_all().unwrap();
+    player
+        .lock()
+        .unwrap()
+        .set_viewport_dimensions(width as u32, height as u32, 1.0);
+
+    for frame in 0..opt.frames {
+        if opt.synthetic_input {
+            for event in synthetic_input(frame, width, height) {
+                player.lock().unwrap().handle_event(event);
+            }
+        }
+        player.lock().unwrap().run_frame();
+        player.lock().unwrap().update_timers(frame_time);
+        //executor.poll_all().unwrap();
+
+        frames_executed.store(frame + 1, Ordering::Relaxed);
+    }
 }
 
 fn checkpoint<W: Write>(
@@ -84,6 +145,9 @@ pub fn execute_report_main(execute_report_opt: ExecuteReportOpt) -> Result<(), s
     LOCAL_LOGGER.with(|log_buffer| {
         log_buffer.borrow_mut().truncate(0);
     });
+    LOCAL_COVERAGE.with(|coverage| {
+        *coverage.borrow_mut() = Coverage::default();
+    });
 
     let mut file_result = FileResults::new(&name);
 
@@ -146,6 +210,18 @@ pub fn execute_report_main(execute_report_opt: ExecuteReportOpt) -> Result<(), s
                     true => AvmType::Avm2,
                     false => AvmType::Avm1,
                 });
+
+                if execute_report_opt.execute.static_census {
+                    let census = Census::take(&swf_buf, &swf.tags);
+                    file_result.tags = census.tags;
+                    file_result.avm1_actions = census.avm1_actions;
+                    file_result.avm2_ops = census.avm2_ops;
+                    file_result.as3_references = census.as3_references;
+                    file_result.video_codecs = census.video_codecs;
+                    file_result.audio_codecs = census.audio_codecs;
+                    file_result.filters = census.filters;
+                    file_result.blend_modes = census.blend_modes;
+                }
             }
             Err(e) => {
                 file_result.error = Some(format!("Parse error: {}", e));
@@ -165,10 +241,31 @@ pub fn execute_report_main(execute_report_opt: ExecuteReportOpt) -> Result<(), s
     };
 
     checkpoint(&mut file_result, &start, &mut writer)?;
+
+    //A static census ends here, without running anything in the movie.
+    if execute_report_opt.execute.static_census {
+        file_result.progress = Step::Complete;
+        checkpoint(&mut file_result, &start, &mut writer)?;
+
+        return Ok(());
+    }
+
     file_result.progress = Step::Execute;
 
-    //Run one frame of the movie in Ruffle.
-    if let Err(e) = catch_unwind(|| execute_swf(&file_path)) {
+    //Run the requested number of frames of the movie in Ruffle.
+    let frames_executed = AtomicU32::new(0);
+    let execute_opt = execute_report_opt.execute;
+    let result = catch_unwind(|| execute_swf(&file_path, &execute_opt, &frames_executed));
+
+    file_result.frames_executed = Some(frames_executed.load(Ordering::Relaxed));
+    let coverage = LOCAL_COVERAGE.with(|coverage| coverage.borrow().clone());
+    file_result.unimplemented = coverage.unimplemented;
+    file_result.unknown_avm1_actions = coverage.unknown_avm1_actions;
+    file_result.unknown_avm2_ops = coverage.unknown_avm2_ops;
+    file_result.missing_classes = coverage.missing_classes;
+    file_result.unsupported_tags = coverage.unsupported_tags;
+
+    if let Err(e) = result {
         match e.downcast::<String>() {
             Ok(e) => {
                 file_result.error = Some(format!("PANIC: {}", e));
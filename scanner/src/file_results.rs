@@ -5,6 +5,7 @@
 use serde::de::{Error as DesError, Unexpected, Visitor};
 use serde::ser::Error as SerError;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::BTreeMap;
 use std::fmt;
 use std::fmt::Write;
 
@@ -31,8 +32,14 @@ impl From<swf::Compression> for Compression {
     }
 }
 
+/// How many times each of a set of named things occurred.
+pub type Counts = BTreeMap<String, u32>;
+
 /// A particular step in the scanner process.
-#[derive(Serialize, Deserialize, Debug, Clone)]
+///
+/// Steps are ordered by how far through the process they are, so a result
+/// that reached a later step made more progress.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Step {
     /// Nothing has been done yet.
     ///
@@ -126,6 +133,127 @@ pub struct FileResults {
     /// The AVM type of the movie.
     #[serde(rename = "AVM Version")]
     pub vm_type: Option<AvmType>,
+
+    /// How many frames of the movie were run.
+    #[serde(rename = "Frames Executed")]
+    pub frames_executed: Option<u32>,
+
+    /// Unimplemented or stubbed APIs hit while running the movie.
+    #[serde(
+        rename = "Unimplemented APIs",
+        default,
+        serialize_with = "counts_to_lines",
+        deserialize_with = "counts_from_lines"
+    )]
+    pub unimplemented: Counts,
+
+    /// AVM1 actions that could not be decoded.
+    #[serde(
+        rename = "Unknown AVM1 Actions",
+        default,
+        serialize_with = "counts_to_lines",
+        deserialize_with = "counts_from_lines"
+    )]
+    pub unknown_avm1_actions: Counts,
+
+    /// AVM2 opcodes that could not be decoded or run.
+    #[serde(
+        rename = "Unknown AVM2 Ops",
+        default,
+        serialize_with = "counts_to_lines",
+        deserialize_with = "counts_from_lines"
+    )]
+    pub unknown_avm2_ops: Counts,
+
+    /// AS3 classes and globals that were not defined.
+    #[serde(
+        rename = "Missing Classes",
+        default,
+        serialize_with = "counts_to_lines",
+        deserialize_with = "counts_from_lines"
+    )]
+    pub missing_classes: Counts,
+
+    /// SWF tags that were not recognized.
+    #[serde(
+        rename = "Unsupported Tags",
+        default,
+        serialize_with = "counts_to_lines",
+        deserialize_with = "counts_from_lines"
+    )]
+    pub unsupported_tags: Counts,
+
+    /// The SWF tags in the movie, including those in sprites.
+    #[serde(
+        rename = "Tags",
+        default,
+        serialize_with = "counts_to_lines",
+        deserialize_with = "counts_from_lines"
+    )]
+    pub tags: Counts,
+
+    /// The AVM1 actions in the movie's bytecode.
+    #[serde(
+        rename = "AVM1 Actions",
+        default,
+        serialize_with = "counts_to_lines",
+        deserialize_with = "counts_from_lines"
+    )]
+    pub avm1_actions: Counts,
+
+    /// The AVM2 ops in the movie's bytecode.
+    #[serde(
+        rename = "AVM2 Ops",
+        default,
+        serialize_with = "counts_to_lines",
+        deserialize_with = "counts_from_lines"
+    )]
+    pub avm2_ops: Counts,
+
+    /// The AS3 classes and names referenced by the movie's bytecode.
+    #[serde(
+        rename = "AS3 References",
+        default,
+        serialize_with = "counts_to_lines",
+        deserialize_with = "counts_from_lines"
+    )]
+    pub as3_references: Counts,
+
+    /// The codecs of the movie's video streams.
+    #[serde(
+        rename = "Video Codecs",
+        default,
+        serialize_with = "counts_to_lines",
+        deserialize_with = "counts_from_lines"
+    )]
+    pub video_codecs: Counts,
+
+    /// The codecs of the movie's sounds and sound streams.
+    #[serde(
+        rename = "Audio Codecs",
+        default,
+        serialize_with = "counts_to_lines",
+        deserialize_with = "counts_from_lines"
+    )]
+    pub audio_codecs: Counts,
+
+    /// The filters applied to placed objects and buttons.
+    #[serde(
+        rename = "Filters",
+        default,
+        serialize_with = "counts_to_lines",
+        deserialize_with = "counts_from_lines"
+    )]
+    pub filters: Counts,
+
+    /// The blend modes, other than normal, applied to placed objects and buttons.
+    #[serde(
+        rename = "Blend Modes",
+        default,
+        serialize_with = "counts_to_lines",
+        deserialize_with = "counts_from_lines"
+    )]
+    pub blend_modes: Counts,
 }
 
 impl Default for FileResults {
@@ -153,12 +281,26 @@ impl FileResults {
             use_gpu: None,
             use_network_sandbox: None,
             vm_type: None,
+            frames_executed: None,
+            unimplemented: Counts::new(),
+            unknown_avm1_actions: Counts::new(),
+            unknown_avm2_ops: Counts::new(),
+            missing_classes: Counts::new(),
+            unsupported_tags: Counts::new(),
+            tags: Counts::new(),
+            avm1_actions: Counts::new(),
+            avm2_ops: Counts::new(),
+            as3_references: Counts::new(),
+            video_codecs: Counts::new(),
+            audio_codecs: Counts::new(),
+            filters: Counts::new(),
+            blend_modes: Counts::new(),
         }
     }
 }
 
 /// Formats data as capital hex
-fn into_hex<S>(hash: &[u8], s: S) -> Result<S::Ok, S::Error>
+pub fn into_hex<S>(hash: &[u8], s: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
 {
@@ -207,3 +349,104 @@ where
 
     d.deserialize_str(HexVisitor())
 }
+
+/// Formats counts as one `count name` line per name.
+fn counts_to_lines<S>(counts: &Counts, s: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    let mut out = String::new();
+    for (name, count) in counts {
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        // Writing to a `String` cannot fail.
+        let _ = write!(out, "{} {}", count, name.replace('\n', " "));
+    }
+
+    s.serialize_str(&out)
+}
+
+/// Parses counts formatted by `counts_to_lines`.
+fn counts_from_lines<'de, D>(d: D) -> Result<Counts, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    struct LinesVisitor();
+
+    impl Visitor<'_> for LinesVisitor {
+        type Value = Counts;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "Lines of counts followed by names")
+        }
+
+        fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+        where
+            E: DesError,
+        {
+            let mut counts = Counts::new();
+
+            for line in v.lines().filter(|line| !line.is_empty()) {
+                let (count, name) = line
+                    .split_once(' ')
+                    .ok_or_else(|| DesError::custom(format!("Invalid count: {}", line)))?;
+                let count = count
+                    .parse()
+                    .map_err(|_| DesError::custom(format!("Invalid count: {}", line)))?;
+                counts.insert(name.to_string(), count);
+            }
+
+            Ok(counts)
+        }
+    }
+
+    d.deserialize_str(LinesVisitor())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Write a result to CSV and read it back.
+    fn round_trip(result: &FileResults) -> FileResults {
+        let mut writer = csv::Writer::from_writer(vec![]);
+        writer.serialize(result).unwrap();
+        let data = writer.into_inner().unwrap();
+
+        let mut reader = csv::Reader::from_reader(&data[..]);
+        reader.deserialize().next().unwrap().unwrap()
+    }
+
+    #[test]
+    fn counts_round_trip() {
+        let mut result = FileResults::new("test.swf");
+        result.hash = vec![0xde, 0xad, 0xbe, 0xef];
+        result.tags = Counts::from([("DoAbc".to_string(), 1), ("ShowFrame".to_string(), 12)]);
+        result.unimplemented = Counts::from([("Stage.quality is a stub".to_string(), 3)]);
+
+        let result = round_trip(&result);
+        assert_eq!(result.hash, [0xde, 0xad, 0xbe, 0xef]);
+        assert_eq!(
+            result.tags,
+            Counts::from([("DoAbc".to_string(), 1), ("ShowFrame".to_string(), 12)])
+        );
+        assert_eq!(
+            result.unimplemented,
+            Counts::from([("Stage.quality is a stub".to_string(), 3)])
+        );
+        assert!(result.avm2_ops.is_empty());
+    }
+
+    #[test]
+    fn counts_keep_one_name_per_line() {
+        let mut result = FileResults::new("test.swf");
+        result.unimplemented = Counts::from([("first\nsecond".to_string(), 2)]);
+
+        let result = round_trip(&result);
+        assert_eq!(
+            result.unimplemented,
+            Counts::from([("first second".to_string(), 2)])
+        );
+    }
+}
@@ -1,5 +1,6 @@
 //! Utilities and mock objects needed for log output capture
 
+use crate::coverage::Coverage;
 use log::{Level, LevelFilter, Log, Metadata, Record};
 use ruffle_core::backend::log::LogBackend;
 use std::cell::RefCell;
@@ -21,10 +22,15 @@ impl LogBackend for ScanLogBackend {
 thread_local! {
     /// Thread local log buffer.
     pub static LOCAL_LOGGER: Rc<RefCell<Vec<String>>> = Rc::new(RefCell::new(Vec::new()));
+
+    /// Thread local count of missing features reported in the log.
+    pub static LOCAL_COVERAGE: Rc<RefCell<Coverage>> = Rc::new(RefCell::new(Coverage::default()));
 }
 
 /// `log` backend (not to be confused with Ruffle's notion of a log backend)
 /// that only logs errors to a thread-local area.
+///
+/// Warnings are not kept, but any missing features they report are counted.
 pub struct ThreadLocalScanLogger();
 
 static GLOBAL_LOGGER: ThreadLocalScanLogger = ThreadLocalScanLogger();
@@ -39,14 +45,20 @@ impl ThreadLocalScanLogger {
 
 impl Log for ThreadLocalScanLogger {
     fn enabled(&self, metadata: &Metadata) -> bool {
-        metadata.level() == Level::Error
+        metadata.level() <= Level::Warn
     }
 
     fn log(&self, record: &Record) {
         if self.enabled(record.metadata()) {
-            LOCAL_LOGGER.with(|log_buffer| {
-                log_buffer.borrow_mut().push(format!("{}", record.args()));
-            })
+            let message = format!("{}", record.args());
+
+            LOCAL_COVERAGE.with(|coverage| coverage.borrow_mut().record(&message));
+
+            if record.level() == Level::Error {
+                LOCAL_LOGGER.with(|log_buffer| {
+                    log_buffer.borrow_mut().push(message);
+                })
+            }
         }
     }
 
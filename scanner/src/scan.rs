@@ -1,7 +1,7 @@
 //! Main/scanner process impls
 
 use crate::analyze::analyze;
-use crate::cli_options::ScanOpt;
+use crate::cli_options::{ExecuteOpt, ScanOpt};
 use crate::file_results::FileResults;
 use crate::ser_bridge::SerBridge;
 use indicatif::{ProgressBar, ProgressStyle};
@@ -38,12 +38,18 @@ pub fn find_files(root: &Path, ignore: &[String]) -> Vec<DirEntry> {
     results
 }
 
-pub fn scan_file<P: AsRef<OsStr>>(exec_path: P, file: DirEntry, name: String) -> FileResults {
+pub fn scan_file<P: AsRef<OsStr>>(
+    exec_path: P,
+    file: DirEntry,
+    name: String,
+    execute_opt: &ExecuteOpt,
+) -> FileResults {
     let start = Instant::now();
     let mut file_results = FileResults::new(&name);
 
     let subproc = Command::new(exec_path)
         .args(&["execute-report", &file.path().to_string_lossy()])
+        .args(execute_opt.to_args())
         .output();
     match subproc {
         Ok(output) => {
@@ -68,6 +74,20 @@ pub fn scan_file<P: AsRef<OsStr>>(exec_path: P, file: DirEntry, name: String) ->
                             use_gpu,
                             use_network_sandbox,
                             vm_type,
+                            frames_executed,
+                            unimplemented,
+                            unknown_avm1_actions,
+                            unknown_avm2_ops,
+                            missing_classes,
+                            unsupported_tags,
+                            tags,
+                            avm1_actions,
+                            avm2_ops,
+                            as3_references,
+                            video_codecs,
+                            audio_codecs,
+                            filters,
+                            blend_modes,
                         } = child_results;
 
                         file_results.hash = hash;
@@ -85,6 +105,20 @@ pub fn scan_file<P: AsRef<OsStr>>(exec_path: P, file: DirEntry, name: String) ->
                         file_results.use_gpu = use_gpu;
                         file_results.use_network_sandbox = use_network_sandbox;
                         file_results.vm_type = vm_type;
+                        file_results.frames_executed = frames_executed;
+                        file_results.unimplemented = unimplemented;
+                        file_results.unknown_avm1_actions = unknown_avm1_actions;
+                        file_results.unknown_avm2_ops = unknown_avm2_ops;
+                        file_results.missing_classes = missing_classes;
+                        file_results.unsupported_tags = unsupported_tags;
+                        file_results.tags = tags;
+                        file_results.avm1_actions = avm1_actions;
+                        file_results.avm2_ops = avm2_ops;
+                        file_results.as3_references = as3_references;
+                        file_results.video_codecs = video_codecs;
+                        file_results.audio_codecs = audio_codecs;
+                        file_results.filters = filters;
+                        file_results.blend_modes = blend_modes;
                     }
                     Err(e) => {
                         file_results.error = Some(e.to_string());
@@ -129,6 +163,7 @@ pub fn scan_main(opt: ScanOpt) -> Result<(), std::io::Error> {
     );
 
     let input_path = opt.input_path;
+    let execute_opt = opt.execute;
     let closure_progress = progress;
 
     let result_iter = to_scan
@@ -139,7 +174,7 @@ pub fn scan_main(opt: ScanOpt) -> Result<(), std::io::Error> {
                 .strip_prefix(&input_path)
                 .unwrap_or_else(|_| file.path())
                 .to_slash_lossy();
-            let result = scan_file(&binary_path, file, name.clone());
+            let result = scan_file(&binary_path, file, name.clone(), &execute_opt);
 
             closure_progress.inc(1);
             closure_progress.set_message(name);
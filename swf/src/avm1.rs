@@ -1,4 +1,4 @@
-pub(crate) mod opcode;
+pub mod opcode;
 pub mod read;
 pub mod types;
 pub mod write;
@@ -429,29 +429,11 @@ impl<'a> Reader<'a> {
                 Tag::EnableTelemetry { password_hash }
             }
             TagCode::ImportAssets => {
-                let url = tag_reader.read_str()?;
-                let num_imports = tag_reader.read_u16()?;
-                let mut imports = Vec::with_capacity(num_imports as usize);
-                for _ in 0..num_imports {
-                    imports.push(ExportedAsset {
-                        id: tag_reader.read_u16()?,
-                        name: tag_reader.read_str()?,
-                    });
-                }
+                let (url, imports) = tag_reader.read_import_assets()?;
                 Tag::ImportAssets { url, imports }
             }
             TagCode::ImportAssets2 => {
-                let url = tag_reader.read_str()?;
-                tag_reader.read_u8()?; // Reserved; must be 1
-                tag_reader.read_u8()?; // Reserved; must be 0
-                let num_imports = tag_reader.read_u16()?;
-                let mut imports = Vec::with_capacity(num_imports as usize);
-                for _ in 0..num_imports {
-                    imports.push(ExportedAsset {
-                        id: tag_reader.read_u16()?,
-                        name: tag_reader.read_str()?,
-                    });
-                }
+                let (url, imports) = tag_reader.read_import_assets_2()?;
                 Tag::ImportAssets { url, imports }
             }
 
@@ -1166,7 +1148,7 @@ impl<'a> Reader<'a> {
         })
     }
 
-    fn read_define_font_align_zones(&mut self) -> Result<Tag<'a>> {
+    pub fn read_define_font_align_zones(&mut self) -> Result<Tag<'a>> {
         let id = self.read_character_id()?;
         let thickness = FontThickness::from_u8(self.read_u8()? >> 6)
             .ok_or_else(|| Error::invalid_data("Invalid font thickness type."))?;
@@ -1868,6 +1850,22 @@ impl<'a> Reader<'a> {
         Ok(exports)
     }
 
+    /// Reads the URL and imported characters of an `ImportAssets` tag.
+    pub fn read_import_assets(&mut self) -> Result<(&'a SwfStr, Vec<ExportedAsset<'a>>)> {
+        let url = self.read_str()?;
+        let imports = self.read_export_assets()?;
+        Ok((url, imports))
+    }
+
+    /// Reads the URL and imported characters of an `ImportAssets2` tag.
+    pub fn read_import_assets_2(&mut self) -> Result<(&'a SwfStr, Vec<ExportedAsset<'a>>)> {
+        let url = self.read_str()?;
+        self.read_u8()?; // Reserved; must be 1
+        self.read_u8()?; // Reserved; must be 0
+        let imports = self.read_export_assets()?;
+        Ok((url, imports))
+    }
+
     pub fn read_place_object(&mut self, tag_length: usize) -> Result<PlaceObject<'a>> {
         // TODO: What's a best way to know if the tag has a color transform?
         // You only know if there is still data remaining after the matrix.
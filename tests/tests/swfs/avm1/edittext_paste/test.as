@@ -0,0 +1,23 @@
+// Frame 1
+this.createTextField("field", 1, 0, 0, 200, 20);
+field.type = "input";
+field.onChanged = function(tf) {
+	trace("// onChanged: " + tf.text);
+};
+Selection.setFocus(field);
+System.setClipboard("pasted");
+Selection.setSelection(0, 0);
+
+// Frame 2
+Selection.setSelection(0, 3);
+
+// Frame 3
+Selection.setSelection(3, 3);
+
+// Frame 4
+System.setClipboard("one\rtwo");
+Selection.setSelection(0, 0);
+
+// Frame 5
+trace("// field.text: " + field.text);
+stop();
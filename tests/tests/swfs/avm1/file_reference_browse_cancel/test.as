@@ -0,0 +1,19 @@
+// There is no file_dialog/ directory, so the test runner cancels the open dialog.
+
+// Frame 1
+var listener = {};
+listener.onSelect = function(file) {
+	trace("onSelect: " + file.name + ", " + file.size + " bytes, type " + file.type);
+};
+listener.onCancel = function(file) {
+	trace("onCancel: name " + file.name);
+};
+
+var fr = new flash.net.FileReference();
+fr.addListener(listener);
+trace("// fr.browse()");
+trace(fr.browse([{description: "Text files", extension: "*.txt"}]));
+trace("// end of frame 1");
+
+// Frame 2
+stop();
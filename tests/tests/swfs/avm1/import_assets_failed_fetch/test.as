@@ -0,0 +1,11 @@
+// ImportAssets from "http://example.com/missing.swf": character 1 as "Thing".
+// The movie doesn't play until the shared library has loaded or failed to.
+
+// Frame 1
+trace("// frame 1");
+
+// Frame 2
+trace("// frame 2");
+_root.attachMovie("Thing", "thing", 1);
+trace(thing._name);
+stop();
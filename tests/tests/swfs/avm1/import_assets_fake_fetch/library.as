@@ -0,0 +1 @@
+// library.swf: an empty one-frame sprite (character 1), exported as "Thing".
@@ -0,0 +1,19 @@
+stop();
+
+var presses = 0;
+this.createEmptyMovieClip("square", 1);
+square.beginFill(0xFF0000);
+square.moveTo(0, 0);
+square.lineTo(100, 0);
+square.lineTo(100, 100);
+square.lineTo(0, 100);
+square.lineTo(0, 0);
+square.endFill();
+
+square.onPress = function() {
+	presses = presses + 1;
+	trace("// onPress " + presses + " at " + _root._xmouse + "," + _root._ymouse);
+};
+square.onRelease = function() {
+	trace("// onRelease " + presses);
+};
@@ -0,0 +1,9 @@
+stop();
+
+this.createTextField("field", 1, 0, 0, 200, 20);
+field.type = "input";
+field.onChanged = function(tf) {
+	trace("// onChanged: " + tf.text);
+};
+Selection.setFocus(field);
+Selection.setSelection(0, 0);
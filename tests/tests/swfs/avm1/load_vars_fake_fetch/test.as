@@ -0,0 +1,17 @@
+stop();
+
+var lv = new LoadVars();
+lv.onLoad = function(success) {
+	trace("// lv.onLoad(" + success + ")");
+	trace(lv.a);
+	trace(lv.b);
+
+	missing = new LoadVars();
+	missing.onLoad = function(success) {
+		trace("// missing.onLoad(" + success + ")");
+	};
+	trace("// missing.load(\"http://example.com/missing.txt\")");
+	missing.load("http://example.com/missing.txt");
+};
+trace("// lv.load(\"http://example.com/data.txt\")");
+lv.load("http://example.com/data.txt");
@@ -0,0 +1,32 @@
+// Each page is rendered when it is added, so content that changes
+// afterwards only shows up on later pages.
+var page = _root.createEmptyMovieClip("page", 1);
+page.beginFill(0xFF0000);
+page.moveTo(0, 0);
+page.lineTo(100, 0);
+page.lineTo(100, 100);
+page.lineTo(0, 100);
+page.lineTo(0, 0);
+page.endFill();
+
+var job = new PrintJob();
+trace("// job.start()");
+trace(job.start());
+trace("// job.addPage(page)");
+trace(job.addPage(page));
+
+var child = page.createEmptyMovieClip("child", 1);
+child.beginFill(0x0000FF);
+child.moveTo(50, 50);
+child.lineTo(150, 50);
+child.lineTo(150, 150);
+child.lineTo(50, 150);
+child.lineTo(50, 50);
+child.endFill();
+trace("// job.addPage(page) with a child");
+trace(job.addPage(page));
+
+trace("// job.send()");
+job.send();
+trace("// job.addPage(page) after send");
+trace(job.addPage(page));
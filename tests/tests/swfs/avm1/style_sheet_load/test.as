@@ -0,0 +1,18 @@
+stop();
+
+var css = new TextField.StyleSheet();
+css.onLoad = function(success) {
+	trace("// onLoad(" + success + ")");
+	trace(css.getStyle("p").fontSize);
+	trace(css.getStyle(".title").color);
+	trace(css.getStyle(".title").fontWeight);
+
+	missing = new TextField.StyleSheet();
+	missing.onLoad = function(success) {
+		trace("// missing onLoad(" + success + ")");
+	};
+	trace("// missing.load(\"missing.css\")");
+	trace(missing.load("missing.css"));
+};
+trace("// css.load(\"style.css\")");
+trace(css.load("style.css"));
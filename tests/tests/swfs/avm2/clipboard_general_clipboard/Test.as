@@ -0,0 +1,37 @@
+package {
+	public class Test {}
+}
+
+import flash.desktop.Clipboard;
+
+var cb:Clipboard = Clipboard.generalClipboard;
+trace("// cb === Clipboard.generalClipboard");
+trace(cb === Clipboard.generalClipboard);
+
+function checkEmpty() {
+	trace("// cb.formats.length");
+	trace(cb.formats.length);
+	trace("// cb.hasFormat(\"air:text\")");
+	trace(cb.hasFormat("air:text"));
+	trace("// cb.getData(\"air:text\")");
+	trace(cb.getData("air:text"));
+}
+
+checkEmpty();
+trace("// cb.setData(\"air:text\", \"Hello\")");
+trace(cb.setData("air:text", "Hello"));
+trace("// cb.formats");
+trace(cb.formats);
+trace("// cb.hasFormat(\"air:text\")");
+trace(cb.hasFormat("air:text"));
+trace("// cb.getData(\"air:text\")");
+trace(cb.getData("air:text"));
+
+trace("// cb.clear()");
+cb.clear();
+checkEmpty();
+
+cb.setData("air:text", "Hello again");
+trace("// cb.clearData(\"air:text\")");
+cb.clearData("air:text");
+checkEmpty();
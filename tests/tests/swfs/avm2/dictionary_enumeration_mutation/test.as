@@ -0,0 +1,47 @@
+// Keys that are still in the dictionary are enumerated exactly once, however
+// many keys are added or deleted during the enumeration.
+import flash.utils.Dictionary;
+
+var dict = new Dictionary();
+var key0 = {}, key1 = {}, key2 = {}, key3 = {}, key4 = {}, key5 = {}, key6 = {}, key7 = {}, key8;
+dict[key0] = 0;
+dict[key1] = 1;
+dict[key2] = 2;
+dict[key3] = 3;
+dict[key4] = 4;
+dict[key5] = 5;
+dict[key6] = 6;
+dict[key7] = 7;
+
+trace("// for each, deleting the first five keys and adding one at 5");
+for each (var value in dict) {
+	trace(value);
+	if (value == 5) {
+		delete dict[key0];
+		delete dict[key1];
+		delete dict[key2];
+		delete dict[key3];
+		delete dict[key4];
+		key8 = {};
+		dict[key8] = 8;
+	}
+}
+
+trace("// for each, deleting every key at 6");
+for each (value in dict) {
+	trace(value);
+	if (value == 6) {
+		delete dict[key5];
+		delete dict[key6];
+		delete dict[key7];
+		delete dict[key8];
+	}
+}
+
+trace("// adding a key after deleting every key");
+dict[key0] = 9;
+
+trace("// for each, after mutating");
+for each (value in dict) {
+	trace(value);
+}
@@ -0,0 +1,49 @@
+package {
+	public class Test {}
+}
+
+import flash.text.TextField;
+import flash.text.TextLineMetrics;
+
+// Lines are laid out in the device font, 12px Noto Sans, 16.3px apart.
+var tf:TextField = new TextField();
+tf.width = 200;
+tf.height = 40;
+tf.multiline = true;
+tf.text = "First line\rSecond line\rThird line\rFourth line";
+
+trace("// tf.numLines");
+trace(tf.numLines);
+trace("// tf.maxScrollV");
+trace(tf.maxScrollV);
+
+for (var line:int = 0; line < 4; line++) {
+	trace("// tf.getLineMetrics(" + line + ")");
+	var metrics:TextLineMetrics = tf.getLineMetrics(line);
+	trace(metrics.x);
+	trace(metrics.width);
+	trace(metrics.height);
+	trace(metrics.ascent);
+	trace(metrics.descent);
+	trace(metrics.leading);
+}
+
+// Points are in the field's coordinate space, so scrolling moves the text
+// under them.
+function hitTests() {
+	trace("// tf.scrollV");
+	trace(tf.scrollV);
+	trace("// tf.bottomScrollV");
+	trace(tf.bottomScrollV);
+	for each (var point:Array in [[4, 10], [4, 28], [150, 10], [300, 10]]) {
+		trace("// tf.getLineIndexAtPoint(" + point[0] + ", " + point[1] + ")");
+		trace(tf.getLineIndexAtPoint(point[0], point[1]));
+		trace("// tf.getCharIndexAtPoint(" + point[0] + ", " + point[1] + ")");
+		trace(tf.getCharIndexAtPoint(point[0], point[1]));
+	}
+}
+
+hitTests();
+trace("// tf.scrollV = 2");
+tf.scrollV = 2;
+hitTests();
@@ -0,0 +1,36 @@
+package {
+	public class Test {}
+}
+
+// The test runner picks the files in file_dialog/ in the open dialog, and
+// traces what would be saved instead of showing a save dialog.
+import flash.events.Event;
+import flash.net.FileReference;
+
+var fr:FileReference = new FileReference();
+fr.addEventListener("select", function(e:Event) {
+	trace("select: " + e.target.name + ", " + e.target.size + " bytes, type " + e.target.type);
+	e.target.load();
+});
+fr.addEventListener("complete", function(e:Event) {
+	trace("complete: " + e.target.name + ", data " + e.target.data);
+});
+fr.addEventListener("cancel", function(e:Event) {
+	trace("cancel");
+});
+trace("// fr.browse()");
+trace(fr.browse());
+
+var saver:FileReference = new FileReference();
+saver.addEventListener("select", function(e:Event) {
+	trace("save select: " + e.target.name);
+});
+saver.addEventListener("complete", function(e:Event) {
+	trace("save complete: " + e.target.name + ", " + e.target.size + " bytes");
+});
+saver.addEventListener("cancel", function(e:Event) {
+	trace("cancel");
+});
+trace("// saver.save(\"Hello from save\", \"saved.txt\")");
+saver.save("Hello from save", "saved.txt");
+trace("// end of frame 1");
@@ -0,0 +1,24 @@
+package {
+	public class Test {}
+}
+
+import flash.utils.Proxy;
+import flash.utils.flash_proxy;
+
+dynamic class TestProxy extends Proxy {
+	flash_proxy override function getDescendants(name:*):* {
+		trace("///attempted to get descendants: " + name.localName);
+		trace(name is QName);
+		trace(flash_proxy::isAttribute(name));
+
+		return "descendants of " + name.localName;
+	}
+}
+
+var p = new TestProxy();
+
+trace("///p..foo");
+trace(p..foo);
+
+trace("///p..@bar");
+trace(p..@bar);
@@ -0,0 +1,47 @@
+package {
+	public class Test {}
+}
+
+import flash.utils.Proxy;
+import flash.utils.flash_proxy;
+
+dynamic class TestProxy extends Proxy {
+	flash_proxy override function getProperty(name:*):* {
+		trace("///attempted to get property: " + name.localName);
+		trace(flash_proxy::isAttribute(name));
+
+		return "value of " + name.localName;
+	}
+
+	flash_proxy override function setProperty(name:*, value:*):void {
+		trace("///attempted to set property: " + name.localName + " to " + value);
+		trace(flash_proxy::isAttribute(name));
+	}
+
+	flash_proxy override function callProperty(name:*, ... rest):* {
+		trace("///attempted to call property: " + name.localName + " with " + rest);
+		trace(flash_proxy::isAttribute(name));
+
+		return rest.length;
+	}
+}
+
+var p = new TestProxy();
+
+trace("///p.foo");
+trace(p.foo);
+
+trace("///p.@foo");
+trace(p.@foo);
+
+trace("///p.bar = 1");
+p.bar = 1;
+
+trace("///p.@bar = 2");
+p.@bar = 2;
+
+trace("///p.baz(1, 2)");
+trace(p.baz(1, 2));
+
+trace("///p.flash_proxy::isAttribute(\"@foo\")");
+trace(p.flash_proxy::isAttribute("@foo"));
@@ -0,0 +1,44 @@
+package {
+	public class Test {}
+}
+
+import flash.utils.Proxy;
+import flash.utils.flash_proxy;
+
+dynamic class TestProxy extends Proxy {
+	flash_proxy override function nextNameIndex(index:int):int {
+		trace("///attempted to get next name index for index: " + index);
+
+		// Skip every other index, so that enumeration must follow the
+		// indices returned here instead of counting up.
+		if (index < 6) {
+			return index + 2;
+		}
+
+		return 0;
+	}
+
+	flash_proxy override function nextName(index:int):String {
+		trace("///attempted to get name for index: " + index);
+
+		return "key " + index;
+	}
+
+	flash_proxy override function nextValue(index:int):* {
+		trace("///attempted to get value for index: " + index);
+
+		return index * 10;
+	}
+}
+
+var p = new TestProxy();
+
+trace("///for (var k in p)...");
+for (var k in p) {
+	trace(k);
+}
+
+trace("///for each (var v in p)...");
+for each (var v in p) {
+	trace(v);
+}
@@ -0,0 +1,143 @@
+//! Input events that a test sends to the player.
+
+use crate::util::Error;
+use ruffle_core::events::{KeyCode, MouseButton as RuffleMouseButton, MouseWheelDelta};
+use ruffle_core::PlayerEvent;
+use serde::Deserialize;
+
+/// An input event, sent once `frame` frames have run.
+///
+/// Events for frame 0 are sent before the first frame runs. Mouse positions
+/// are in viewport pixels.
+#[derive(Deserialize)]
+pub struct ScheduledEvent {
+    pub frame: u32,
+
+    #[serde(flatten)]
+    pub event: InputEvent,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum InputEvent {
+    KeyDown {
+        key_code: u8,
+        key_char: Option<char>,
+    },
+    KeyUp {
+        key_code: u8,
+        key_char: Option<char>,
+    },
+    MouseMove {
+        x: f64,
+        y: f64,
+    },
+    MouseDown {
+        x: f64,
+        y: f64,
+        #[serde(default)]
+        button: MouseButton,
+    },
+    MouseUp {
+        x: f64,
+        y: f64,
+        #[serde(default)]
+        button: MouseButton,
+    },
+    /// A move to the given position, followed by a press and release.
+    Click {
+        x: f64,
+        y: f64,
+        #[serde(default)]
+        button: MouseButton,
+    },
+    MouseLeave,
+    MouseWheel {
+        lines: f64,
+    },
+    /// Each character of `text`, typed in turn.
+    TextInput {
+        text: String,
+    },
+}
+
+impl InputEvent {
+    pub fn to_player_events(&self) -> Result<Vec<PlayerEvent>, Error> {
+        let key_code = |key_code: u8| {
+            KeyCode::from_u8(key_code).ok_or(format!("Unknown key code {}", key_code))
+        };
+
+        Ok(match self {
+            Self::KeyDown {
+                key_code: code,
+                key_char,
+            } => vec![PlayerEvent::KeyDown {
+                key_code: key_code(*code)?,
+                key_char: *key_char,
+            }],
+            Self::KeyUp {
+                key_code: code,
+                key_char,
+            } => vec![PlayerEvent::KeyUp {
+                key_code: key_code(*code)?,
+                key_char: *key_char,
+            }],
+            Self::MouseMove { x, y } => vec![PlayerEvent::MouseMove { x: *x, y: *y }],
+            Self::MouseDown { x, y, button } => vec![PlayerEvent::MouseDown {
+                x: *x,
+                y: *y,
+                button: (*button).into(),
+            }],
+            Self::MouseUp { x, y, button } => vec![PlayerEvent::MouseUp {
+                x: *x,
+                y: *y,
+                button: (*button).into(),
+            }],
+            Self::Click { x, y, button } => vec![
+                PlayerEvent::MouseMove { x: *x, y: *y },
+                PlayerEvent::MouseDown {
+                    x: *x,
+                    y: *y,
+                    button: (*button).into(),
+                },
+                PlayerEvent::MouseUp {
+                    x: *x,
+                    y: *y,
+                    button: (*button).into(),
+                },
+            ],
+            Self::MouseLeave => vec![PlayerEvent::MouseLeave],
+            Self::MouseWheel { lines } => vec![PlayerEvent::MouseWheel {
+                delta: MouseWheelDelta::Lines(*lines),
+            }],
+            Self::TextInput { text } => text
+                .chars()
+                .map(|codepoint| PlayerEvent::TextInput { codepoint })
+                .collect(),
+        })
+    }
+}
+
+#[derive(Copy, Clone, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MouseButton {
+    Left,
+    Right,
+    Middle,
+}
+
+impl Default for MouseButton {
+    fn default() -> Self {
+        Self::Left
+    }
+}
+
+impl From<MouseButton> for RuffleMouseButton {
+    fn from(button: MouseButton) -> Self {
+        match button {
+            MouseButton::Left => Self::Left,
+            MouseButton::Right => Self::Right,
+            MouseButton::Middle => Self::Middle,
+        }
+    }
+}
@@ -0,0 +1,7 @@
+pub mod input;
+pub mod navigator;
+pub mod options;
+pub mod runner;
+pub mod test;
+
+pub type Error = Box<dyn std::error::Error>;
@@ -0,0 +1,84 @@
+//! A navigator backend that answers fetches with fake responses.
+
+use crate::util::options::FakeResponse;
+use ruffle_core::backend::navigator::{
+    NavigationMethod, NavigatorBackend, NullNavigatorBackend, OwnedFuture, RequestOptions,
+};
+use ruffle_core::indexmap::IndexMap;
+use ruffle_core::loader::Error;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::mpsc::Sender;
+use std::time::Duration;
+use url::Url;
+
+/// A navigator backend that responds to the URLs listed in a test's options
+/// with their fake responses, and reads any other URL from the test directory.
+pub struct TestNavigatorBackend {
+    inner: NullNavigatorBackend,
+
+    /// The fake response to each URL, or `None` if fetching it fails.
+    responses: HashMap<String, Option<Vec<u8>>>,
+}
+
+impl TestNavigatorBackend {
+    pub fn new(
+        base_path: &Path,
+        channel: Sender<OwnedFuture<(), Error>>,
+        responses: &[FakeResponse],
+    ) -> Result<Self, crate::util::Error> {
+        let mut fake_responses = HashMap::new();
+        for response in responses {
+            let body = match (&response.body, &response.file) {
+                (Some(body), _) => Some(body.clone().into_bytes()),
+                (None, Some(file)) => Some(std::fs::read(base_path.join(file))?),
+                (None, None) => None,
+            };
+            fake_responses.insert(response.url.clone(), body);
+        }
+
+        Ok(Self {
+            inner: NullNavigatorBackend::with_base_path(base_path, channel),
+            responses: fake_responses,
+        })
+    }
+}
+
+impl NavigatorBackend for TestNavigatorBackend {
+    fn navigate_to_url(
+        &self,
+        url: String,
+        window: Option<String>,
+        vars_method: Option<(NavigationMethod, IndexMap<String, String>)>,
+    ) {
+        self.inner.navigate_to_url(url, window, vars_method)
+    }
+
+    fn fetch(&self, url: &str, options: RequestOptions) -> OwnedFuture<Vec<u8>, Error> {
+        match self.responses.get(url) {
+            Some(response) => {
+                let response = response.clone();
+                let url = url.to_string();
+                Box::pin(async move { response.ok_or(Error::FetchError(url)) })
+            }
+            None => self.inner.fetch(url, options),
+        }
+    }
+
+    fn time_since_launch(&mut self) -> Duration {
+        self.inner.time_since_launch()
+    }
+
+    fn spawn_future(&mut self, future: OwnedFuture<(), Error>) {
+        self.inner.spawn_future(future)
+    }
+
+    fn resolve_relative_url<'a>(&self, url: &'a str) -> Cow<'a, str> {
+        self.inner.resolve_relative_url(url)
+    }
+
+    fn pre_process_url(&self, url: Url) -> Url {
+        self.inner.pre_process_url(url)
+    }
+}
@@ -0,0 +1,184 @@
+//! Options for a regression test, read from the `test.toml` next to its SWF.
+//!
+//! Only `num_frames` is required:
+//!
+//! ```toml
+//! num_frames = 2
+//!
+//! [approximations]
+//! epsilon = 0.051
+//!
+//! [player_options]
+//! viewport_dimensions = { width = 900, height = 900 }
+//!
+//! [[input]]
+//! frame = 1
+//! type = "mouse_down"
+//! x = 100.0
+//! y = 50.0
+//!
+//! [[fetch]]
+//! url = "http://example.com/data.txt"
+//! body = "a=1&b=2"
+//! ```
+
+use crate::util::input::ScheduledEvent;
+use crate::util::Error;
+use approx::assert_relative_eq;
+use ruffle_core::Player;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TestOptions {
+    /// The number of frames to run the SWF for.
+    pub num_frames: u32,
+
+    /// The file holding the expected trace output, relative to the test directory.
+    #[serde(default = "default_output_path")]
+    pub output_path: PathBuf,
+
+    /// Whether the test is skipped unless ignored tests are requested.
+    #[serde(default)]
+    pub ignore: bool,
+
+    /// Whether the final frame is rendered and compared against
+    /// `<test name>-<os>-<adapter>.png`, or `<test name>-software.png` when
+    /// there is no wgpu adapter. Only done with the `imgtests` feature.
+    #[serde(default)]
+    pub image: bool,
+
+    /// If set, numbers in the output are compared approximately.
+    #[serde(default)]
+    pub approximations: Option<Approximations>,
+
+    #[serde(default)]
+    pub player_options: PlayerOptions,
+
+    /// Input events to send to the player.
+    #[serde(default)]
+    pub input: Vec<ScheduledEvent>,
+
+    /// Fake responses to URLs fetched by the SWF.
+    #[serde(default)]
+    pub fetch: Vec<FakeResponse>,
+}
+
+impl Default for TestOptions {
+    fn default() -> Self {
+        Self {
+            num_frames: 1,
+            output_path: default_output_path(),
+            ignore: false,
+            image: false,
+            approximations: None,
+            player_options: PlayerOptions::default(),
+            input: Vec::new(),
+            fetch: Vec::new(),
+        }
+    }
+}
+
+impl TestOptions {
+    pub fn read(path: &Path) -> Result<Self, Error> {
+        Ok(toml::from_str(&std::fs::read_to_string(path)?)?)
+    }
+}
+
+fn default_output_path() -> PathBuf {
+    PathBuf::from("output.txt")
+}
+
+/// How close numbers in the output must be to the expected ones.
+///
+/// Unset values default to `f64::EPSILON`, as in `assert_relative_eq!`.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Approximations {
+    epsilon: Option<f64>,
+    max_relative: Option<f64>,
+}
+
+impl Approximations {
+    pub fn compare(&self, actual: f64, expected: f64) {
+        assert_relative_eq!(
+            actual,
+            expected,
+            epsilon = self.epsilon.unwrap_or(f64::EPSILON),
+            max_relative = self.max_relative.unwrap_or(f64::EPSILON)
+        );
+    }
+}
+
+/// Settings applied to the player after the SWF is loaded.
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PlayerOptions {
+    /// How long in seconds ActionScript may run before timing out.
+    /// Defaults to 300 seconds.
+    max_execution_duration: Option<f64>,
+
+    viewport_dimensions: Option<ViewportDimensions>,
+
+    /// The stage scale mode, as given to `Stage.scaleMode`.
+    scale_mode: Option<String>,
+
+    /// The stage alignment, as given to `Stage.align`.
+    align: Option<String>,
+
+    /// The stage quality, as given to `Stage.quality`.
+    quality: Option<String>,
+}
+
+impl PlayerOptions {
+    pub fn setup(&self, player: &mut Player) {
+        player.set_max_execution_duration(Duration::from_secs_f64(
+            self.max_execution_duration.unwrap_or(300.0),
+        ));
+        if let Some(viewport) = &self.viewport_dimensions {
+            player.set_viewport_dimensions(viewport.width, viewport.height, viewport.scale_factor);
+        }
+        if let Some(scale_mode) = &self.scale_mode {
+            player.set_scale_mode(scale_mode);
+        }
+        if let Some(align) = &self.align {
+            player.set_stage_align(align);
+        }
+        if let Some(quality) = &self.quality {
+            player.set_quality(quality);
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ViewportDimensions {
+    width: u32,
+    height: u32,
+    #[serde(default = "default_scale_factor")]
+    scale_factor: f64,
+}
+
+fn default_scale_factor() -> f64 {
+    1.0
+}
+
+/// A response to a fetch of `url`.
+///
+/// The fetch fails if neither `body` nor `file` is set. URLs without a fake
+/// response are read from the test directory.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FakeResponse {
+    /// The URL, exactly as the SWF requests it.
+    pub url: String,
+
+    #[serde(default)]
+    pub body: Option<String>,
+
+    /// A file to respond with, relative to the test directory.
+    #[serde(default)]
+    pub file: Option<PathBuf>,
+}
@@ -0,0 +1,364 @@
+//! Running a test's SWF in a headless player.
+
+use crate::util::navigator::TestNavigatorBackend;
+use crate::util::test::Test;
+use crate::util::Error;
+use ruffle_core::backend::render::{BitmapFormat, RenderBackend};
+use ruffle_core::backend::video::SoftwareVideoBackend;
+use ruffle_core::backend::video::VideoBackend;
+use ruffle_core::backend::{
+    audio::NullAudioBackend,
+    font::NullFontBackend,
+    locale::NullLocaleBackend,
+    log::LogBackend,
+    navigator::NullExecutor,
+    print::{PrintBackend, PrintJobSettings, PrintPage},
+    render::NullRenderer,
+    storage::MemoryStorageBackend,
+    ui::{FileDialogResult, FileFilter, MouseCursor, UiBackend},
+    video::NullVideoBackend,
+};
+use ruffle_core::tag_utils::SwfMovie;
+use ruffle_core::Player;
+use ruffle_render_software::SoftwareRenderBackend;
+use ruffle_render_wgpu::target::TextureTarget;
+use ruffle_render_wgpu::wgpu;
+use ruffle_render_wgpu::WgpuRenderBackend;
+use std::cell::RefCell;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+
+const RUN_IMG_TESTS: bool = cfg!(feature = "imgtests");
+
+fn get_img_platform_suffix(info: &wgpu::AdapterInfo) -> String {
+    format!("{}-{}", std::env::consts::OS, info.name)
+}
+
+/// Renders with wgpu when an adapter is available, otherwise with the
+/// software renderer.
+/// Returns the backend and the suffix of the reference images it compares against.
+fn img_render_backend(width: u32, height: u32) -> Result<(Box<dyn RenderBackend>, String), Error> {
+    let backend_bit = wgpu::Backends::PRIMARY;
+    let instance = wgpu::Instance::new(backend_bit);
+    let descriptors =
+        futures::executor::block_on(WgpuRenderBackend::<TextureTarget>::build_descriptors(
+            backend_bit,
+            instance,
+            None,
+            Default::default(),
+            None,
+        ));
+
+    match descriptors {
+        Ok(descriptors) => {
+            let platform_id = get_img_platform_suffix(&descriptors.info);
+            let target = TextureTarget::new(&descriptors.device, (width, height));
+            let render_backend = Box::new(WgpuRenderBackend::new(descriptors, target)?);
+            Ok((render_backend, platform_id))
+        }
+        Err(_) => {
+            let render_backend = Box::new(SoftwareRenderBackend::new(width, height));
+            Ok((render_backend, "software".to_string()))
+        }
+    }
+}
+
+/// Captures the last rendered frame from whichever backend `img_render_backend` chose.
+fn capture_frame(renderer: &mut dyn RenderBackend) -> Option<image::RgbaImage> {
+    if let Some(renderer) = renderer.downcast_mut::<WgpuRenderBackend<TextureTarget>>() {
+        return renderer.target().capture(renderer.device());
+    }
+
+    let frame = renderer
+        .downcast_mut::<SoftwareRenderBackend>()
+        .unwrap()
+        .capture_frame();
+    match frame.data {
+        BitmapFormat::Rgba(rgba) => image::RgbaImage::from_raw(frame.width, frame.height, rgba),
+        BitmapFormat::Rgb(_) => None,
+    }
+}
+
+/// Loads a test's SWF and runs it through the Ruffle core for its number of
+/// frames, sending its input events along the way.
+/// Returns the trace output.
+pub fn run_swf(
+    test: &Test,
+    before_start: impl FnOnce(Arc<Mutex<Player>>) -> Result<(), Error>,
+    before_end: impl FnOnce(Arc<Mutex<Player>>) -> Result<(), Error>,
+) -> Result<String, Error> {
+    let check_img = test.options.image && RUN_IMG_TESTS;
+
+    let base_path = test.swf_path.parent().unwrap();
+    let (mut executor, channel) = NullExecutor::new();
+    let movie = SwfMovie::from_path(&test.swf_path, None)?;
+    let frame_time = 1000.0 / movie.frame_rate().to_f64();
+    let trace_output = Rc::new(RefCell::new(Vec::new()));
+
+    let mut platform_id = None;
+
+    let (render_backend, video_backend): (Box<dyn RenderBackend>, Box<dyn VideoBackend>) =
+        if check_img {
+            let (render_backend, suffix) = img_render_backend(
+                movie.width().to_pixels() as u32,
+                movie.height().to_pixels() as u32,
+            )?;
+            platform_id = Some(suffix);
+            let video_backend = Box::new(SoftwareVideoBackend::new());
+            (render_backend, video_backend)
+        } else {
+            (Box::new(NullRenderer), Box::new(NullVideoBackend::new()))
+        };
+
+    let player = Player::new(
+        render_backend,
+        Box::new(NullAudioBackend::new()),
+        Box::new(TestNavigatorBackend::new(
+            base_path,
+            channel,
+            &test.options.fetch,
+        )?),
+        Box::new(MemoryStorageBackend::default()),
+        Box::new(NullLocaleBackend::new()),
+        video_backend,
+        Box::new(TestLogBackend::new(trace_output.clone())),
+        Box::new(TestUiBackend::new(trace_output.clone(), base_path)),
+        Box::new(TestPrintBackend::new(trace_output.clone())),
+        Box::new(NullFontBackend::new()),
+    )?;
+    player.lock().unwrap().set_root_movie(Arc::new(movie));
+    test.options
+        .player_options
+        .setup(&mut player.lock().unwrap());
+
+    before_start(player.clone())?;
+
+    send_input(test, &player, 0)?;
+    for frame in 1..=test.options.num_frames {
+        player.lock().unwrap().run_frame();
+        player.lock().unwrap().update_timers(frame_time);
+        executor.poll_all().unwrap();
+        send_input(test, &player, frame)?;
+    }
+
+    // Render the image to disk
+    // FIXME: Determine how we want to compare against on on-disk image
+    if check_img {
+        player.lock().unwrap().render();
+        let mut player_lock = player.lock().unwrap();
+        let image = capture_frame(player_lock.renderer_mut()).expect("Failed to capture image");
+
+        // The swf path ends in '<swf_name>/test.swf' - extract `swf_name`
+        let mut swf_path_buf = test.swf_path.clone();
+        swf_path_buf.pop();
+
+        let swf_name = swf_path_buf.file_name().unwrap().to_string_lossy();
+        let img_name = format!("{}-{}.png", swf_name, platform_id.unwrap());
+
+        let mut img_path = swf_path_buf.clone();
+        img_path.push(&img_name);
+
+        let result = match image::open(&img_path) {
+            Ok(existing_img) => {
+                if existing_img
+                    .as_rgba8()
+                    .expect("Expected 8-bit RGBA image")
+                    .as_raw()
+                    == image.as_raw()
+                {
+                    Ok(())
+                } else {
+                    Err(format!(
+                        "Test output does not match existing image `{:?}`",
+                        img_path
+                    ))
+                }
+            }
+            Err(err) => Err(format!(
+                "Error occured when trying to read existing image `{:?}`: {}",
+                img_path, err
+            )),
+        };
+
+        if let Err(err) = result {
+            let new_img_path = img_path.with_file_name(img_name + ".updated");
+            image.save_with_format(&new_img_path, image::ImageFormat::Png)?;
+            panic!(
+                "Image test failed - saved new image to `{:?}`\n{}",
+                new_img_path, err
+            );
+        }
+    }
+
+    before_end(player)?;
+
+    executor.block_all().unwrap();
+
+    let trace = trace_output.borrow().join("\n");
+    Ok(trace)
+}
+
+/// Send the input events scheduled for after `frame` frames have run.
+fn send_input(test: &Test, player: &Arc<Mutex<Player>>, frame: u32) -> Result<(), Error> {
+    for scheduled in test.options.input.iter().filter(|e| e.frame == frame) {
+        for event in scheduled.event.to_player_events()? {
+            player.lock().unwrap().handle_event(event);
+        }
+    }
+    Ok(())
+}
+
+struct TestLogBackend {
+    trace_output: Rc<RefCell<Vec<String>>>,
+}
+
+impl TestLogBackend {
+    pub fn new(trace_output: Rc<RefCell<Vec<String>>>) -> Self {
+        Self { trace_output }
+    }
+}
+
+impl LogBackend for TestLogBackend {
+    fn avm_trace(&self, message: &str) {
+        self.trace_output.borrow_mut().push(message.to_string());
+    }
+}
+
+/// A print backend that accepts every print job, and traces a summary of each page.
+struct TestPrintBackend {
+    trace_output: Rc<RefCell<Vec<String>>>,
+    num_pages: usize,
+}
+
+impl TestPrintBackend {
+    pub fn new(trace_output: Rc<RefCell<Vec<String>>>) -> Self {
+        Self {
+            trace_output,
+            num_pages: 0,
+        }
+    }
+}
+
+impl PrintBackend for TestPrintBackend {
+    fn start_job(&mut self) -> Option<PrintJobSettings> {
+        self.num_pages = 0;
+        Some(PrintJobSettings::default())
+    }
+
+    fn add_page(
+        &mut self,
+        _renderer: &mut dyn RenderBackend,
+        page: PrintPage,
+    ) -> Result<(), ruffle_core::backend::print::Error> {
+        self.num_pages += 1;
+        self.trace_output.borrow_mut().push(format!(
+            "[PrintJob] page {}: {}x{}, {} commands, printAsBitmap: {}",
+            self.num_pages,
+            page.width,
+            page.height,
+            page.commands.len(),
+            page.print_as_bitmap
+        ));
+        Ok(())
+    }
+
+    fn send(&mut self) -> Result<(), ruffle_core::backend::print::Error> {
+        self.trace_output
+            .borrow_mut()
+            .push(format!("[PrintJob] sent {} page(s)", self.num_pages));
+        Ok(())
+    }
+}
+
+/// A UI backend with an in-memory clipboard and scripted file dialogs.
+///
+/// The open dialog picks the files in the `file_dialog` directory next to the
+/// test's SWF, in alphabetical order, or is cancelled if that directory doesn't
+/// exist. The save dialog always accepts the default file name, and traces a
+/// summary of the saved data instead of writing it to disk.
+struct TestUiBackend {
+    trace_output: Rc<RefCell<Vec<String>>>,
+    clipboard: Option<String>,
+    file_dialog_path: PathBuf,
+}
+
+impl TestUiBackend {
+    pub fn new(trace_output: Rc<RefCell<Vec<String>>>, base_path: &Path) -> Self {
+        Self {
+            trace_output,
+            clipboard: None,
+            file_dialog_path: base_path.join("file_dialog"),
+        }
+    }
+}
+
+impl UiBackend for TestUiBackend {
+    fn mouse_visible(&self) -> bool {
+        true
+    }
+
+    fn set_mouse_visible(&mut self, _visible: bool) {}
+
+    fn set_mouse_cursor(&mut self, _cursor: MouseCursor) {}
+
+    fn set_clipboard_content(&mut self, content: String) {
+        self.clipboard = Some(content);
+    }
+
+    fn clipboard_content(&mut self) -> Option<String> {
+        self.clipboard.clone()
+    }
+
+    fn set_fullscreen(&mut self, _is_full: bool) -> Result<(), ruffle_core::backend::ui::Error> {
+        Ok(())
+    }
+
+    fn display_unsupported_message(&self) {}
+
+    fn display_root_movie_download_failed_message(&self) {}
+
+    fn message(&self, _message: &str) {}
+
+    fn display_file_open_dialog(
+        &mut self,
+        _filters: &[FileFilter],
+        multiple: bool,
+    ) -> Option<Vec<FileDialogResult>> {
+        let mut paths: Vec<PathBuf> = std::fs::read_dir(&self.file_dialog_path)
+            .ok()?
+            .filter_map(|entry| Some(entry.ok()?.path()))
+            .filter(|path| path.is_file())
+            .collect();
+        paths.sort();
+        if !multiple {
+            paths.truncate(1);
+        }
+
+        let files: Vec<FileDialogResult> = paths
+            .into_iter()
+            .map(|path| FileDialogResult {
+                file_name: path.file_name().unwrap().to_string_lossy().into_owned(),
+                contents: std::fs::read(&path).expect("Failed to read file for file dialog"),
+            })
+            .collect();
+        if files.is_empty() {
+            None
+        } else {
+            Some(files)
+        }
+    }
+
+    fn display_file_save_dialog(
+        &mut self,
+        default_file_name: &str,
+        data: &[u8],
+    ) -> Result<Option<String>, ruffle_core::backend::ui::Error> {
+        self.trace_output.borrow_mut().push(format!(
+            "[FileDialog] save {}: {} bytes",
+            default_file_name,
+            data.len()
+        ));
+        Ok(Some(default_file_name.to_string()))
+    }
+}
@@ -0,0 +1,107 @@
+use crate::util::options::TestOptions;
+use crate::util::runner::run_swf;
+use crate::util::Error;
+use ruffle_core::Player;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// Wrapper around string slice that makes debug output `{:?}` to print string same way as `{}`.
+/// Used in different `assert*!` macros in combination with `pretty_assertions` crate to make
+/// test failures to show nice diffs.
+/// Courtesy of https://github.com/colin-kiegel/rust-pretty-assertions/issues/24
+#[derive(PartialEq, Eq)]
+#[doc(hidden)]
+pub struct PrettyString<'a>(pub &'a str);
+
+/// Make diff to display string as multi-line string
+impl<'a> std::fmt::Debug for PrettyString<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+macro_rules! assert_eq {
+    ($left:expr, $right:expr) => {
+        pretty_assertions::assert_eq!(PrettyString($left.as_ref()), PrettyString($right.as_ref()));
+    };
+    ($left:expr, $right:expr, $message:expr) => {
+        pretty_assertions::assert_eq!(
+            PrettyString($left.as_ref()),
+            PrettyString($right.as_ref()),
+            $message
+        );
+    };
+}
+
+/// A SWF to run, and the trace output it should produce.
+pub struct Test {
+    pub name: String,
+    pub options: TestOptions,
+    pub swf_path: PathBuf,
+    pub output_path: PathBuf,
+}
+
+impl Test {
+    /// Create a test for the `test.swf` in `test_dir`.
+    pub fn from_options(options: TestOptions, test_dir: &Path, name: String) -> Self {
+        Self {
+            swf_path: test_dir.join("test.swf"),
+            output_path: test_dir.join(&options.output_path),
+            name,
+            options,
+        }
+    }
+
+    /// Create a test from a `test.toml` file, for the `test.swf` next to it.
+    pub fn from_options_file(options_path: &Path, name: String) -> Result<Self, Error> {
+        Ok(Self::from_options(
+            TestOptions::read(options_path)?,
+            options_path.parent().unwrap(),
+            name,
+        ))
+    }
+
+    pub fn run(
+        &self,
+        before_start: impl FnOnce(Arc<Mutex<Player>>) -> Result<(), Error>,
+        before_end: impl FnOnce(Arc<Mutex<Player>>) -> Result<(), Error>,
+    ) -> Result<(), Error> {
+        let trace_log = run_swf(self, before_start, before_end)?;
+        let mut expected_output = std::fs::read_to_string(&self.output_path)?.replace("\r\n", "\n");
+
+        // Strip a trailing newline if it has one.
+        if expected_output.ends_with('\n') {
+            expected_output = expected_output[0..expected_output.len() - "\n".len()].to_string();
+        }
+
+        if let Some(approximations) = &self.options.approximations {
+            std::assert_eq!(
+                trace_log.lines().count(),
+                expected_output.lines().count(),
+                "# of lines of output didn't match"
+            );
+
+            for (actual, expected) in trace_log.lines().zip(expected_output.lines()) {
+                // If these are numbers, compare using approx_eq.
+                if let (Ok(actual), Ok(expected)) = (actual.parse::<f64>(), expected.parse::<f64>())
+                {
+                    // NaNs should be able to pass in an approx test.
+                    if actual.is_nan() && expected.is_nan() {
+                        continue;
+                    }
+
+                    approximations.compare(actual, expected);
+                } else {
+                    assert_eq!(actual, expected);
+                }
+            }
+        } else {
+            assert_eq!(
+                trace_log, expected_output,
+                "ruffle output != flash player output"
+            );
+        }
+
+        Ok(())
+    }
+}
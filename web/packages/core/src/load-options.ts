@@ -187,6 +187,14 @@ export interface BaseLoadOptions {
         nanos: number;
     };
 
+    /**
+     * URLs of TrueType or OpenType font files to use as device fonts.
+     * Text using device fonts is rendered with a built-in font if no matching font is found.
+     *
+     * @default []
+     */
+    fonts?: string[];
+
     /**
      * Specifies the base directory or URL used to resolve all relative path statements in the SWF file.
      * null means the current directory.
@@ -10,6 +10,9 @@ use generational_arena::{Arena, Index};
 use js_sys::{Array, Function, Object, Promise, Uint8Array};
 use ruffle_core::backend::{
     audio::{AudioBackend, NullAudioBackend},
+    font::MemoryFontBackend,
+    navigator::{NavigatorBackend, RequestOptions},
+    print::NullPrintBackend,
     render::RenderBackend,
     storage::{MemoryStorageBackend, StorageBackend},
     video::SoftwareVideoBackend,
@@ -154,6 +157,8 @@ pub struct Config {
 
     #[serde(rename = "maxExecutionDuration")]
     max_execution_duration: Duration,
+
+    fonts: Vec<String>,
 }
 
 impl Default for Config {
@@ -171,6 +176,7 @@ impl Default for Config {
             warn_on_unsupported_content: true,
             log_level: log::Level::Error,
             max_execution_duration: Duration::from_secs(15),
+            fonts: vec![],
         }
     }
 }
@@ -481,11 +487,23 @@ impl Ruffle {
             log::error!("Unable to create audio backend. No audio will be played.");
             Box::new(NullAudioBackend::new())
         };
-        let navigator = Box::new(navigator::WebNavigatorBackend::new(
+        let navigator = navigator::WebNavigatorBackend::new(
             allow_script_access,
             config.upgrade_to_https,
             config.base_url,
-        ));
+        );
+        let mut font = MemoryFontBackend::new();
+        for url in &config.fonts {
+            match navigator.fetch(url, RequestOptions::get()).await {
+                Ok(data) => {
+                    if let Err(e) = font.add_font(data) {
+                        log::warn!("Unable to read font {}: {}", url, e);
+                    }
+                }
+                Err(e) => log::warn!("Unable to load font {}: {}", url, e),
+            }
+        }
+        let navigator = Box::new(navigator);
         let storage = match window.local_storage() {
             Ok(Some(s)) => {
                 Box::new(storage::LocalStorageBackend::new(s)) as Box<dyn StorageBackend>
@@ -500,9 +518,12 @@ impl Ruffle {
         let video = Box::new(SoftwareVideoBackend::new());
         let log = Box::new(log_adapter::WebLogBackend::new(trace_observer.clone()));
         let ui = Box::new(ui::WebUiBackend::new(js_player.clone(), &canvas));
+        let print = Box::new(NullPrintBackend::new());
+        let font = Box::new(font);
 
-        let core =
-            ruffle_core::Player::new(renderer, audio, navigator, storage, locale, video, log, ui)?;
+        let core = ruffle_core::Player::new(
+            renderer, audio, navigator, storage, locale, video, log, ui, print, font,
+        )?;
         if let Ok(mut core) = core.try_lock() {
             // Set config parameters.
             if let Some(color) = config.background_color.and_then(parse_html_color) {
@@ -1471,3 +1492,197 @@ fn web_key_to_codepoint(key: &str) -> Option<char> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ruffle_core::backend::{
+        font::FontBackend, locale::NullLocaleBackend, log::LogBackend,
+        navigator::NullNavigatorBackend, render::NullRenderer, ui::NullUiBackend,
+        video::NullVideoBackend,
+    };
+    use ruffle_core::swf::{self, Tag, Twips};
+    use std::rc::Rc;
+
+    fn be16(values: &[i32]) -> Vec<u8> {
+        values
+            .iter()
+            .flat_map(|&value| (value as u16).to_be_bytes())
+            .collect()
+    }
+
+    /// A TrueType font named "Test Font", with 1000 units per EM and a single
+    /// square glyph for 'A' that is 600 units wide.
+    fn test_font() -> Vec<u8> {
+        let mut head = vec![0; 54];
+        head[18..20].copy_from_slice(&1000u16.to_be_bytes());
+
+        let mut hhea = vec![0; 36];
+        hhea[4..10].copy_from_slice(&be16(&[800, -200, 100]));
+        hhea[34..36].copy_from_slice(&2u16.to_be_bytes());
+
+        let hmtx = be16(&[500, 0, 600, 0]);
+
+        // A format 4 subtable with a segment for 'A', and the final segment.
+        let mut cmap = be16(&[0, 1, 3, 1, 0, 12]);
+        cmap.extend(be16(&[4, 32, 0, 4, 4, 1, 0]));
+        cmap.extend(be16(&[0x41, 0xFFFF, 0]));
+        cmap.extend(be16(&[0x41, 0xFFFF]));
+        cmap.extend(be16(&[1 - 0x41, 1]));
+        cmap.extend(be16(&[0, 0]));
+
+        let mut square = be16(&[1, 0, 0, 100, 100, 3, 0]);
+        square.extend([0x01; 4]);
+        square.extend(be16(&[0, 0, 100, 0]));
+        square.extend(be16(&[0, 100, 0, -100]));
+        let loca = be16(&[0, 0, square.len() as i32 / 2]);
+
+        // A single Macintosh family name record.
+        let family = b"Test Font";
+        let mut name = be16(&[0, 1, 18, 1, 0, 0, 1, family.len() as i32, 0]);
+        name.extend(family);
+
+        let tables: [(&[u8; 4], Vec<u8>); 7] = [
+            (b"cmap", cmap),
+            (b"glyf", square),
+            (b"head", head),
+            (b"hhea", hhea),
+            (b"hmtx", hmtx),
+            (b"loca", loca),
+            (b"name", name),
+        ];
+        let mut font = vec![0, 1, 0, 0];
+        font.extend((tables.len() as u16).to_be_bytes());
+        font.extend([0; 6]);
+        let mut offset = 12 + tables.len() * 16;
+        for (tag, data) in &tables {
+            font.extend(*tag);
+            font.extend([0; 4]);
+            font.extend((offset as u32).to_be_bytes());
+            font.extend((data.len() as u32).to_be_bytes());
+            offset += data.len();
+        }
+        for (_, data) in &tables {
+            font.extend(data);
+        }
+        font
+    }
+
+    /// A movie with a device font text field showing "AA" in "Test Font" at
+    /// 20px, which traces the field's `textWidth`.
+    fn movie() -> Arc<SwfMovie> {
+        let edit_text = swf::EditText {
+            id: 1,
+            bounds: swf::Rectangle {
+                x_min: Twips::ZERO,
+                x_max: Twips::from_pixels(200.0),
+                y_min: Twips::ZERO,
+                y_max: Twips::from_pixels(40.0),
+            },
+            font_id: None,
+            font_class_name: None,
+            height: Some(Twips::from_pixels(20.0)),
+            color: None,
+            max_length: None,
+            layout: None,
+            variable_name: "".into(),
+            initial_text: Some("<font face=\"Test Font\" size=\"20\">AA</font>".into()),
+            is_word_wrap: false,
+            is_multiline: false,
+            is_password: false,
+            is_read_only: true,
+            is_auto_size: false,
+            is_selectable: false,
+            has_border: false,
+            was_static: false,
+            is_html: true,
+            is_device_font: true,
+        };
+        let place_object = swf::PlaceObject {
+            version: 2,
+            action: swf::PlaceObjectAction::Place(1),
+            depth: 1,
+            matrix: None,
+            color_transform: None,
+            ratio: None,
+            name: Some("field".into()),
+            clip_depth: None,
+            class_name: None,
+            filters: None,
+            background_color: None,
+            blend_mode: None,
+            clip_actions: None,
+            is_image: false,
+            is_bitmap_cached: None,
+            is_visible: None,
+            amf_data: None,
+        };
+        // Push "field", GetVariable, Push "textWidth", GetMember, Trace
+        let mut actions = vec![0x96, 0x07, 0x00, 0x00];
+        actions.extend(b"field\0");
+        actions.extend([0x1C, 0x96, 0x0B, 0x00, 0x00]);
+        actions.extend(b"textWidth\0");
+        actions.extend([0x4E, 0x26, 0x00]);
+
+        let header = swf::Header {
+            num_frames: 1,
+            ..swf::Header::default_with_swf_version(8)
+        };
+        let tags = [
+            Tag::DefineEditText(Box::new(edit_text)),
+            Tag::PlaceObject(Box::new(place_object)),
+            Tag::DoAction(&actions),
+            Tag::ShowFrame,
+        ];
+        let mut data = vec![];
+        swf::write_swf(&header, &tags, &mut data).unwrap();
+        Arc::new(SwfMovie::from_data(&data, None, None).unwrap())
+    }
+
+    struct TestLogBackend(Rc<RefCell<Vec<String>>>);
+
+    impl LogBackend for TestLogBackend {
+        fn avm_trace(&self, message: &str) {
+            self.0.borrow_mut().push(message.to_string());
+        }
+    }
+
+    /// Run the first frame of `movie()` with the given fonts, returning the
+    /// trace output.
+    fn run(font: MemoryFontBackend) -> Vec<String> {
+        let trace = Rc::new(RefCell::new(Vec::new()));
+        let player = Player::new(
+            Box::new(NullRenderer::new()),
+            Box::new(NullAudioBackend::new()),
+            Box::new(NullNavigatorBackend::new()),
+            Box::new(MemoryStorageBackend::default()),
+            Box::new(NullLocaleBackend::new()),
+            Box::new(NullVideoBackend::new()),
+            Box::new(TestLogBackend(trace.clone())),
+            Box::new(NullUiBackend::new()),
+            Box::new(NullPrintBackend::new()),
+            Box::new(font),
+        )
+        .unwrap();
+        let mut player = player.lock().unwrap();
+        player.set_root_movie(movie());
+        player.run_frame();
+        trace.take()
+    }
+
+    #[test]
+    fn device_font_from_fonts_option() {
+        let mut font = MemoryFontBackend::new();
+        font.add_font(test_font()).unwrap();
+        assert_eq!(font.font_names(), ["Test Font"]);
+
+        // Each 'A' is 0.6 EM, or 12px at 20px.
+        assert_eq!(run(font), ["24"]);
+    }
+
+    #[test]
+    fn device_font_fallback() {
+        // Without the font, the text falls back to the embedded Noto Sans.
+        assert_eq!(run(MemoryFontBackend::new()), ["25"]);
+    }
+}
@@ -1,5 +1,5 @@
 use super::JavascriptPlayer;
-use ruffle_core::backend::ui::{Error, MouseCursor, UiBackend};
+use ruffle_core::backend::ui::{Error, FileDialogResult, FileFilter, MouseCursor, UiBackend};
 use ruffle_web_common::JsResult;
 use web_sys::HtmlCanvasElement;
 
@@ -26,6 +26,13 @@ pub struct WebUiBackend {
     canvas: HtmlCanvasElement,
     cursor_visible: bool,
     cursor: MouseCursor,
+
+    /// The text most recently copied by the movie.
+    ///
+    /// Browsers only allow reading the system clipboard asynchronously, after
+    /// asking the user for permission, so the player keeps its own clipboard
+    /// instead. Text copied in other applications can't be pasted.
+    clipboard_content: String,
 }
 
 impl WebUiBackend {
@@ -35,6 +42,7 @@ impl WebUiBackend {
             canvas: canvas.clone(),
             cursor_visible: true,
             cursor: MouseCursor::Arrow,
+            clipboard_content: "".to_string(),
         }
     }
 
@@ -71,8 +79,12 @@ impl UiBackend for WebUiBackend {
         self.update_mouse_cursor();
     }
 
-    fn set_clipboard_content(&mut self, _content: String) {
-        log::warn!("set clipboard not implemented");
+    fn set_clipboard_content(&mut self, content: String) {
+        self.clipboard_content = content;
+    }
+
+    fn clipboard_content(&mut self) -> Option<String> {
+        Some(self.clipboard_content.clone())
     }
 
     fn set_fullscreen(&mut self, is_full: bool) -> Result<(), Error> {
@@ -97,4 +109,22 @@ impl UiBackend for WebUiBackend {
     fn message(&self, message: &str) {
         self.js_player.display_message(message);
     }
+
+    fn display_file_open_dialog(
+        &mut self,
+        _filters: &[FileFilter],
+        _multiple: bool,
+    ) -> Option<Vec<FileDialogResult>> {
+        log::warn!("File dialogs are not yet supported on web");
+        None
+    }
+
+    fn display_file_save_dialog(
+        &mut self,
+        _default_file_name: &str,
+        _data: &[u8],
+    ) -> Result<Option<String>, Error> {
+        log::warn!("File dialogs are not yet supported on web");
+        Ok(None)
+    }
 }